@@ -1,42 +1,72 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
 use cosmwasm_std::{
-    attr, entry_point, from_binary, to_binary, wasm_execute, Addr, Binary, CosmosMsg, Decimal,
-    Deps, DepsMut, Env, IbcQuery, ListChannelsResponse, MessageInfo, Order, QuerierWrapper,
-    QueryRequest, Response, StdResult, Uint128, Uint64, WasmMsg,
+    entry_point, from_binary, to_binary, to_vec, wasm_execute, Addr, BankMsg, Binary, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, IbcQuery, ListChannelsResponse, MessageInfo, Order,
+    QuerierWrapper, QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    SubMsgResult, Uint128, Uint64, WasmMsg,
 };
-use cw2::{get_contract_version, set_contract_version};
-use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg};
-use cw_storage_plus::Bound;
-use std::str::FromStr;
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, Map, Prefix, PrimaryKey};
+use std::collections::BTreeSet;
 
 use crate::astroport;
 use astroport_governance::assembly::{
-    helpers::validate_links, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, Proposal,
-    ProposalListResponse, ProposalStatus, ProposalVoteOption, ProposalVotesResponse, QueryMsg,
-    UpdateConfig,
+    helpers::validate_links, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    MultiChoiceTallyResponse, OptionTally, Proposal, ProposalBundle, ProposalContent,
+    ProposalCountsResponse, ProposalHookMsg, ProposalListResponse, ProposalOption, ProposalStatus,
+    ProposalTargetsResponse, ProposalVote, ProposalVoteOption, ProposalVotesResponse, QueryMsg,
+    RawStateEntry, StateSection, SudoMsg, TallyCheckResponse, TwoPhaseProposalResponse,
+    UpdateConfig, VoteBySigPayload, VoteListenerMsg, VoteOperatorGrant, VoteProofResponse,
+    VotingPowerPoint, EMERGENCY_PAUSE_PERIOD, MAX_BUNDLE_PROPOSALS, MAX_OPTION_LABEL_LENGTH,
+    MAX_PROPOSAL_OPTIONS, MAX_RANKED_OPTIONS, MAX_VOTE_LISTENERS, PROPOSAL_HOOK_GAS_LIMIT,
+    VOTE_LISTENER_GAS_LIMIT,
 };
 
 use crate::astroport::asset::addr_opt_validate;
 use astroport::xastro_token::QueryMsg as XAstroTokenQueryMsg;
-use astroport_governance::builder_unlock::msg::{
-    AllocationResponse, QueryMsg as BuilderUnlockQueryMsg, StateResponse,
+use astroport_governance::builder_unlock::msg::{QueryMsg as BuilderUnlockQueryMsg, StateResponse};
+use astroport_governance::events::{
+    cast_vote_attrs, end_proposal_attrs, proposal_attrs, submit_proposal_attrs, ACTION,
 };
+use astroport_governance::ibc_controller::{
+    ChannelParams, ExecuteMsg as ControllerExecuteMsg, QueryMsg as ControllerQueryMsg,
+};
+use astroport_governance::interchain::ForwardMetadata;
+use astroport_governance::migration::{load_migratable_version, migration_response};
+use astroport_governance::pagination::paginate_map;
 use astroport_governance::utils::WEEK;
 use astroport_governance::voting_escrow::{QueryMsg as VotingEscrowQueryMsg, VotingPowerResponse};
-use astroport_governance::voting_escrow_delegation::QueryMsg::AdjustedBalance;
+use astroport_governance::voting_escrow_delegation::get_adjusted_balance;
+use astroport_governance::{DEFAULT_LIMIT, MAX_LIMIT};
 
 use crate::error::ContractError;
-use crate::migration::{migrate_config_to_140, migrate_proposals_to_v140, MigrateMsg};
-use crate::state::{CONFIG, PROPOSALS, PROPOSAL_COUNT};
-
-use ibc_controller_package::ExecuteMsg as ControllerExecuteMsg;
+use crate::merkle::{hash_pair, vote_leaf, EMPTY_VOTE_ROOT};
+use crate::migration::{
+    migrate_config_to_140, migrate_config_to_160, migrate_proposals, ProposalsMigration,
+    PROPOSALS_MIGRATION,
+};
+use crate::signature::is_signed_by;
+use crate::state::{
+    BindingRound, VoteTree, ACTIVE_FORUM_THREADS, BINDING_ROUNDS, BUNDLES, CHAIN_PAUSED, CONFIG,
+    CONFIG_HISTORY, OPTION_TALLIES, PAUSED_UNTIL, PENDING_REMOVALS, PROPOSALS,
+    PROPOSALS_BY_END_BLOCK, PROPOSALS_BY_STATUS, PROPOSALS_BY_SUBMITTER, PROPOSAL_BUNDLES,
+    PROPOSAL_CONTENT, PROPOSAL_COUNT, PROPOSAL_HOOKS, PROPOSAL_IBC_FORWARD, PROPOSAL_OPTIONS,
+    PROPOSAL_STATUS_COUNTS, RANKED_BALLOTS, RANKED_PROPOSALS, REINDEX_CURSOR, SIGNALING_ROUNDS,
+    TWO_PHASE_PROPOSALS, VOTES, VOTE_LEAF_INDEXES, VOTE_LISTENERS, VOTE_NONCES, VOTE_OPERATORS,
+    VOTE_ROOTS, VOTE_TREES, VOTE_TREE_NODES, WINNING_OPTIONS,
+};
 
 // Contract name and version used for migration.
 const CONTRACT_NAME: &str = "astro-assembly";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply id of proposal hook notifications, which are only replied to on error
+const PROPOSAL_HOOK_REPLY_ID: u64 = 1;
+/// Reply id of vote notifications, which are only replied to on error
+const VOTE_LISTENER_REPLY_ID: u64 = 2;
+
 // Default pagination constants
-const DEFAULT_LIMIT: u32 = 10;
-const MAX_LIMIT: u32 = 30;
 const DEFAULT_VOTERS_LIMIT: u32 = 100;
 const MAX_VOTERS_LIMIT: u32 = 250;
 
@@ -44,7 +74,7 @@ const MAX_VOTERS_LIMIT: u32 = 250;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -69,14 +99,23 @@ pub fn instantiate(
         proposal_effective_delay: msg.proposal_effective_delay,
         proposal_expiration_period: msg.proposal_expiration_period,
         proposal_required_deposit: msg.proposal_required_deposit,
-        proposal_required_quorum: Decimal::from_str(&msg.proposal_required_quorum)?,
-        proposal_required_threshold: Decimal::from_str(&msg.proposal_required_threshold)?,
+        proposal_required_quorum: msg.proposal_required_quorum,
+        proposal_required_threshold: msg.proposal_required_threshold,
         whitelisted_links: msg.whitelisted_links,
+        proposal_max_messages: msg.proposal_max_messages,
+        proposal_max_messages_size: msg.proposal_max_messages_size,
+        links_curator: None,
+        excluded_voters: vec![],
+        builder_power_coefficient: Decimal::one(),
+        xastro_time_weighting: None,
+        signaling_required_deposit: msg.proposal_required_deposit,
+        emergency_committee: None,
     };
 
     config.validate()?;
 
     CONFIG.save(deps.storage, &config)?;
+    CONFIG_HISTORY.save(deps.storage, env.block.height, &config)?;
 
     PROPOSAL_COUNT.save(deps.storage, &Uint64::zero())?;
 
@@ -91,13 +130,49 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::CastVote { proposal_id, vote }** Cast a vote on a specific proposal.
 ///
+/// * **ExecuteMsg::CastVoteBySig { voter, proposal_id, vote, signature, nonce }** Cast a vote signed off-chain by the voter.
+///
+/// * **ExecuteMsg::GrantVoteOperator { operator, expires }** Allows an operator to vote on behalf of the sender.
+///
+/// * **ExecuteMsg::RevokeVoteOperator { operator }** Revokes a vote operator grant.
+///
+/// * **ExecuteMsg::CastVoteAsOperator { grantor, proposal_id, vote }** Cast a vote on behalf of a grantor.
+///
+/// * **ExecuteMsg::CastMultiChoiceVote { proposal_id, option }** Cast a vote for an option of a multi-choice proposal.
+///
+/// * **ExecuteMsg::CastRankedVote { proposal_id, ranking }** Cast a ranked ballot for a ranked-choice proposal.
+///
 /// * **ExecuteMsg::EndProposal { proposal_id }** Sets the status of an expired/finalized proposal.
 ///
 /// * **ExecuteMsg::ExecuteProposal { proposal_id }** Executes a successful proposal.
 ///
-/// * **ExecuteMsg::RemoveCompletedProposal { proposal_id }** Removes a finalized proposal from the proposal list.
+/// * **ExecuteMsg::ExecuteBundle { proposal_ids }** Executes all proposals of a bundle at once.
+///
+/// * **ExecuteMsg::RemoveCompletedProposal { proposal_id, limit }** Removes a finalized proposal and its votes in batches.
 ///
 /// * **ExecuteMsg::UpdateConfig(config)** Updates the contract configuration.
+///
+/// * **ExecuteMsg::UpdateLinkWhitelist { add, remove }** Updates the link whitelist.
+///
+/// * **ExecuteMsg::AddProposalHook { address }** Registers a contract notified about proposal status changes.
+///
+/// * **ExecuteMsg::RemoveProposalHook { address }** Unregisters a proposal hook.
+///
+/// * **ExecuteMsg::AddVoteListener { address }** Registers a contract notified about every vote.
+///
+/// * **ExecuteMsg::RemoveVoteListener { address }** Unregisters a vote listener.
+///
+/// * **ExecuteMsg::Reindex { limit }** Backfills proposal indexes for proposals created before they existed.
+///
+/// * **ExecuteMsg::MigrateProposals { limit }** Migrates the proposals left after a contract migration in batches.
+///
+/// * **ExecuteMsg::CommitVotes { proposal_id, limit }** Builds the vote merkle tree of an ended proposal in batches.
+///
+/// * **ExecuteMsg::Pause {}** Stops accepting new proposals and votes for a limited period.
+///
+/// * **ExecuteMsg::Unpause {}** Resumes accepting new proposals and votes.
+///
+/// * **ExecuteMsg::VetoProposal { proposal_id }** Rejects a passed proposal before it is executed.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -105,21 +180,79 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if !matches!(msg, ExecuteMsg::MigrateProposals { .. })
+        && PROPOSALS_MIGRATION.may_load(deps.storage)?.is_some()
+    {
+        return Err(ContractError::ProposalsMigrationPending {});
+    }
+
     match msg {
         ExecuteMsg::Receive(cw20_msg) => receive_cw20(deps, env, info, cw20_msg),
-        ExecuteMsg::CastVote { proposal_id, vote } => cast_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::CastVote { proposal_id, vote } => {
+            cast_vote(deps, env, info.sender, proposal_id, vote)
+        }
+        ExecuteMsg::CastVoteBySig {
+            voter,
+            proposal_id,
+            vote,
+            signature,
+            nonce,
+        } => cast_vote_by_sig(deps, env, voter, proposal_id, vote, signature, nonce),
+        ExecuteMsg::GrantVoteOperator { operator, expires } => {
+            grant_vote_operator(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeVoteOperator { operator } => {
+            let operator = deps.api.addr_validate(&operator)?;
+            VOTE_OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+            Ok(Response::new()
+                .add_attribute(ACTION, "revoke_vote_operator")
+                .add_attribute("grantor", info.sender)
+                .add_attribute("operator", operator))
+        }
+        ExecuteMsg::CastVoteAsOperator {
+            grantor,
+            proposal_id,
+            vote,
+        } => cast_vote_as_operator(deps, env, info, grantor, proposal_id, vote),
+        ExecuteMsg::CastMultiChoiceVote {
+            proposal_id,
+            option,
+        } => cast_multi_choice_vote(deps, env, info.sender, proposal_id, vec![option]),
+        ExecuteMsg::CastRankedVote {
+            proposal_id,
+            ranking,
+        } => cast_multi_choice_vote(deps, env, info.sender, proposal_id, ranking),
         ExecuteMsg::EndProposal { proposal_id } => end_proposal(deps, env, proposal_id),
         ExecuteMsg::ExecuteProposal { proposal_id } => execute_proposal(deps, env, proposal_id),
+        ExecuteMsg::ExecuteBundle { proposal_ids } => execute_bundle(deps, env, proposal_ids),
         ExecuteMsg::CheckMessages { messages } => check_messages(env, messages),
         ExecuteMsg::CheckMessagesPassed {} => Err(ContractError::MessagesCheckPassed {}),
-        ExecuteMsg::RemoveCompletedProposal { proposal_id } => {
-            remove_completed_proposal(deps, env, proposal_id)
+        ExecuteMsg::RemoveCompletedProposal { proposal_id, limit } => {
+            remove_completed_proposal(deps, env, proposal_id, limit)
         }
         ExecuteMsg::UpdateConfig(config) => update_config(deps, env, info, config),
+        ExecuteMsg::UpdateLinkWhitelist { add, remove } => {
+            update_link_whitelist(deps, env, info, add, remove)
+        }
         ExecuteMsg::IBCProposalCompleted {
             proposal_id,
             status,
         } => update_ibc_proposal_status(deps, info, proposal_id, status),
+        ExecuteMsg::AddProposalHook { address } => add_proposal_hook(deps, env, info, address),
+        ExecuteMsg::RemoveProposalHook { address } => {
+            remove_proposal_hook(deps, env, info, address)
+        }
+        ExecuteMsg::AddVoteListener { address } => add_vote_listener(deps, env, info, address),
+        ExecuteMsg::RemoveVoteListener { address } => {
+            remove_vote_listener(deps, env, info, address)
+        }
+        ExecuteMsg::Reindex { limit } => reindex(deps, limit),
+        ExecuteMsg::MigrateProposals { limit } => execute_migrate_proposals(deps, limit),
+        ExecuteMsg::CommitVotes { proposal_id, limit } => commit_votes(deps, proposal_id, limit),
+        ExecuteMsg::Pause {} => pause(deps, env, info),
+        ExecuteMsg::Unpause {} => unpause(deps, env, info),
+        ExecuteMsg::VetoProposal { proposal_id } => veto_proposal(deps, env, info, proposal_id),
     }
 }
 
@@ -132,6 +265,8 @@ pub fn receive_cw20(
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &env)?;
+
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::SubmitProposal {
             title,
@@ -139,6 +274,9 @@ pub fn receive_cw20(
             link,
             messages,
             ibc_channel,
+            ibc_forward,
+            forum_thread_id,
+            bundle_id,
         } => submit_proposal(
             deps,
             env,
@@ -150,6 +288,56 @@ pub fn receive_cw20(
             link,
             messages,
             ibc_channel,
+            ibc_forward,
+            forum_thread_id,
+            bundle_id,
+        ),
+        Cw20HookMsg::SubmitMultiChoiceProposal {
+            title,
+            description,
+            link,
+            options,
+            ranked,
+            ibc_channel,
+            forum_thread_id,
+        } => submit_multi_choice_proposal(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+            title,
+            ProposalContent {
+                description,
+                link,
+                messages: None,
+            },
+            options,
+            ranked,
+            ibc_channel,
+            forum_thread_id,
+        ),
+        Cw20HookMsg::SubmitTwoPhaseProposal {
+            title,
+            description,
+            link,
+            messages,
+            ibc_channel,
+            forum_thread_id,
+        } => submit_two_phase_proposal(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+            title,
+            ProposalContent {
+                description,
+                link,
+                messages,
+            },
+            ibc_channel,
+            forum_thread_id,
         ),
     }
 }
@@ -167,6 +355,12 @@ pub fn receive_cw20(
 /// * **link** proposal link.
 ///
 /// * **messages** executable messages (actions to perform if the proposal passes).
+///
+/// * **ibc_forward** route through intermediate chains to the remote chain.
+///
+/// * **forum_thread_id** forum discussion thread the proposal is bound to.
+///
+/// * **bundle_id** bundle the proposal joins.
 #[allow(clippy::too_many_arguments)]
 pub fn submit_proposal(
     deps: DepsMut,
@@ -179,6 +373,9 @@ pub fn submit_proposal(
     link: Option<String>,
     messages: Option<Vec<CosmosMsg>>,
     ibc_channel: Option<String>,
+    ibc_forward: Option<ForwardMetadata>,
+    forum_thread_id: Option<String>,
+    bundle_id: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -190,23 +387,314 @@ pub fn submit_proposal(
         return Err(ContractError::InsufficientDeposit {});
     }
 
-    // Update the proposal count
-    let count = PROPOSAL_COUNT.update(deps.storage, |c| -> StdResult<_> {
-        Ok(c.checked_add(Uint64::new(1))?)
-    })?;
-
     // Check that controller exists and it supports this channel
     if let Some(ibc_channel) = &ibc_channel {
-        if let Some(ibc_controller) = &config.ibc_controller {
-            check_controller_supports_channel(deps.querier, ibc_controller, ibc_channel)?;
-        } else {
-            return Err(ContractError::MissingIBCController {});
+        // IBC execution completes on the remote chain, so it can't be part of an atomic bundle
+        if bundle_id.is_some() {
+            return Err(ContractError::BundledIbcProposal {});
+        }
+        let messages_count = messages.as_ref().map_or(0, Vec::len);
+        check_ibc_channel(deps.querier, &config, ibc_channel, messages_count)?;
+    }
+
+    if let Some(forward) = &ibc_forward {
+        if ibc_channel.is_none() {
+            return Err(ContractError::ForwardWithoutIbcChannel {});
+        }
+        forward.validate()?;
+    }
+
+    let content = ProposalContent {
+        description,
+        link,
+        messages,
+    };
+
+    let proposal = create_proposal(
+        deps.storage,
+        &env,
+        &config,
+        sender,
+        deposit_amount,
+        title,
+        content,
+        ibc_channel,
+        forum_thread_id,
+        true,
+    )?;
+
+    if let Some(forward) = &ibc_forward {
+        PROPOSAL_IBC_FORWARD.save(deps.storage, proposal.proposal_id.u64(), forward)?;
+    }
+
+    if let Some(bundle_id) = bundle_id {
+        join_bundle(deps.storage, bundle_id, &proposal)?;
+    }
+
+    Ok(Response::new()
+        .add_attributes(submit_proposal_attrs(
+            &proposal.submitter,
+            proposal.proposal_id.u64(),
+            proposal.end_block,
+        ))
+        .add_attributes(bundle_id.map(|bundle_id| ("bundle_id", bundle_id.to_string())))
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            proposal.proposal_id.u64(),
+            &proposal.status,
+        )?))
+}
+
+/// Adds a proposal to a bundle. The bundle is created by the first proposal that joins it
+/// and only accepts further proposals from the same submitter.
+fn join_bundle(
+    storage: &mut dyn Storage,
+    bundle_id: u64,
+    proposal: &Proposal,
+) -> Result<(), ContractError> {
+    let mut bundle = BUNDLES
+        .may_load(storage, bundle_id)?
+        .unwrap_or_else(|| ProposalBundle {
+            submitter: proposal.submitter.clone(),
+            proposal_ids: vec![],
+        });
+
+    if bundle.submitter != proposal.submitter {
+        return Err(ContractError::BundleOwnedByOther { bundle_id });
+    }
+
+    if bundle.proposal_ids.len() >= MAX_BUNDLE_PROPOSALS {
+        return Err(ContractError::BundleFull {});
+    }
+
+    bundle.proposal_ids.push(proposal.proposal_id.u64());
+    BUNDLES.save(storage, bundle_id, &bundle)?;
+    PROPOSAL_BUNDLES.save(storage, proposal.proposal_id.u64(), &bundle_id)?;
+
+    Ok(())
+}
+
+/// Submits the signaling round of a two-phase proposal. The binding round with the messages
+/// opens automatically in [`end_proposal`] once the signaling round passes.
+///
+/// * **binding_content** content of the binding round.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_two_phase_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    deposit_amount: Uint128,
+    title: String,
+    binding_content: ProposalContent,
+    ibc_channel: Option<String>,
+    forum_thread_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.xastro_token_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if deposit_amount < config.signaling_required_deposit {
+        return Err(ContractError::InsufficientDeposit {});
+    }
+
+    if let Some(ibc_channel) = &ibc_channel {
+        let messages_count = binding_content.messages.as_ref().map_or(0, Vec::len);
+        check_ibc_channel(deps.querier, &config, ibc_channel, messages_count)?;
+    }
+
+    let signaling_content = ProposalContent {
+        messages: None,
+        ..binding_content.clone()
+    };
+
+    let signaling = create_proposal(
+        deps.storage,
+        &env,
+        &config,
+        sender,
+        deposit_amount,
+        title,
+        signaling_content,
+        None,
+        forum_thread_id,
+        true,
+    )?;
+    let signaling_id = signaling.proposal_id.u64();
+
+    // The binding round is validated upfront so it can't fail to open later
+    signaling.validate(&binding_content, &config)?;
+
+    BINDING_ROUNDS.save(
+        deps.storage,
+        signaling_id,
+        &BindingRound {
+            content: binding_content,
+            ibc_channel,
+        },
+    )?;
+    TWO_PHASE_PROPOSALS.save(deps.storage, signaling_id, &None)?;
+
+    Ok(Response::new()
+        .add_attributes(submit_proposal_attrs(
+            &signaling.submitter,
+            signaling_id,
+            signaling.end_block,
+        ))
+        .add_attribute("round", "signaling")
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            signaling_id,
+            &signaling.status,
+        )?))
+}
+
+/// Submits a multi-choice proposal. The messages of the winning option are executed once it passes.
+///
+/// * **options** labeled options, each with its own messages.
+///
+/// * **ranked** whether the winner is decided by instant runoff over ranked ballots.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_multi_choice_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    deposit_amount: Uint128,
+    title: String,
+    content: ProposalContent,
+    options: Vec<ProposalOption>,
+    ranked: bool,
+    ibc_channel: Option<String>,
+    forum_thread_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.xastro_token_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if deposit_amount < config.proposal_required_deposit {
+        return Err(ContractError::InsufficientDeposit {});
+    }
+
+    if options.len() < 2
+        || options.len() > MAX_PROPOSAL_OPTIONS
+        || options
+            .iter()
+            .any(|option| option.label.is_empty() || option.label.len() > MAX_OPTION_LABEL_LENGTH)
+    {
+        return Err(ContractError::InvalidProposalOptions {});
+    }
+
+    if ranked && options.len() > MAX_RANKED_OPTIONS {
+        return Err(ContractError::TooManyRankedOptions {});
+    }
+
+    if let Some(ibc_channel) = &ibc_channel {
+        // Any option may win, so the largest one must fit into the channel
+        let messages_count = options
+            .iter()
+            .map(|option| option.messages.as_ref().map_or(0, Vec::len))
+            .max()
+            .unwrap_or_default();
+        check_ibc_channel(deps.querier, &config, ibc_channel, messages_count)?;
+    }
+
+    let proposal = create_proposal(
+        deps.storage,
+        &env,
+        &config,
+        sender,
+        deposit_amount,
+        title,
+        content.clone(),
+        ibc_channel,
+        forum_thread_id,
+        true,
+    )?;
+    let proposal_id = proposal.proposal_id.u64();
+
+    for option in &options {
+        proposal.validate(
+            &ProposalContent {
+                messages: option.messages.clone(),
+                ..content.clone()
+            },
+            &config,
+        )?;
+    }
+
+    OPTION_TALLIES.save(
+        deps.storage,
+        proposal_id,
+        &vec![Uint128::zero(); options.len()],
+    )?;
+    PROPOSAL_OPTIONS.save(deps.storage, proposal_id, &options)?;
+    if ranked {
+        RANKED_PROPOSALS.save(deps.storage, proposal_id, &())?;
+    }
+
+    Ok(Response::new()
+        .add_attributes(submit_proposal_attrs(
+            &proposal.submitter,
+            proposal_id,
+            proposal.end_block,
+        ))
+        .add_attribute("options", options.len().to_string())
+        .add_attribute("ranked", ranked.to_string())
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            proposal_id,
+            &proposal.status,
+        )?))
+}
+
+/// Checks that the IBC controller exists, supports the given channel and accepts the number of
+/// messages the proposal would send over it.
+///
+/// * **messages_count** number of messages the proposal sends to the remote chain.
+fn check_ibc_channel(
+    querier: QuerierWrapper,
+    config: &Config,
+    ibc_channel: &String,
+    messages_count: usize,
+) -> Result<(), ContractError> {
+    match &config.ibc_controller {
+        Some(ibc_controller) => {
+            check_controller_supports_channel(querier, ibc_controller, ibc_channel)?;
+            check_controller_max_messages(querier, ibc_controller, ibc_channel, messages_count)
         }
+        None => Err(ContractError::MissingIBCController {}),
     }
+}
+
+/// Saves a new active proposal together with its content and indexes.
+///
+/// * **validate** whether the proposal should be validated against the current config.
+#[allow(clippy::too_many_arguments)]
+fn create_proposal(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    submitter: Addr,
+    deposit_amount: Uint128,
+    title: String,
+    content: ProposalContent,
+    ibc_channel: Option<String>,
+    forum_thread_id: Option<String>,
+    validate: bool,
+) -> Result<Proposal, ContractError> {
+    // Update the proposal count
+    let count = PROPOSAL_COUNT.update(storage, |c| -> StdResult<_> {
+        Ok(c.checked_add(Uint64::new(1))?)
+    })?;
 
     let proposal = Proposal {
         proposal_id: count,
-        submitter: sender.clone(),
+        submitter,
         status: ProposalStatus::Active,
         for_power: Uint128::zero(),
         against_power: Uint128::zero(),
@@ -223,60 +711,65 @@ pub fn submit_proposal(
             + config.proposal_effective_delay
             + config.proposal_expiration_period,
         title,
-        description,
-        link,
-        messages,
         deposit_amount,
         ibc_channel,
+        forum_thread_id,
     };
 
-    proposal.validate(config.whitelisted_links)?;
+    if validate {
+        proposal.validate(&content, config)?;
+    }
+
+    if let Some(thread) = &proposal.forum_thread_id {
+        if let Some(proposal_id) = ACTIVE_FORUM_THREADS.may_load(storage, thread)? {
+            return Err(ContractError::ForumThreadInUse {
+                thread: thread.clone(),
+                proposal_id,
+            });
+        }
+        ACTIVE_FORUM_THREADS.save(storage, thread, &count.u64())?;
+    }
 
-    PROPOSALS.save(deps.storage, count.u64(), &proposal)?;
+    PROPOSALS.save(storage, count.u64(), &proposal)?;
+    PROPOSAL_CONTENT.save(storage, count.u64(), &content)?;
+    index_proposal(storage, &proposal)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "submit_proposal"),
-        attr("submitter", sender),
-        attr("proposal_id", count),
-        attr(
-            "proposal_end_height",
-            (env.block.height + config.proposal_voting_period).to_string(),
-        ),
-    ]))
+    Ok(proposal)
 }
 
 /// Cast a vote on a proposal.
 ///
+/// * **voter** the address the vote is cast for.
+///
 /// * **proposal_id** is the identifier of the proposal.
 ///
 /// * **vote_option** contains the vote option.
 pub fn cast_vote(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    voter: Addr,
     proposal_id: u64,
     vote_option: ProposalVoteOption,
 ) -> Result<Response, ContractError> {
     let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
 
-    if proposal.status != ProposalStatus::Active {
-        return Err(ContractError::ProposalNotActive {});
-    }
-
-    if proposal.submitter == info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    check_not_paused(deps.storage, &env)?;
+    check_voting_open(&proposal, &env, &voter)?;
 
-    if env.block.height > proposal.end_block {
-        return Err(ContractError::VotingPeriodEnded {});
+    if PROPOSAL_OPTIONS.has(deps.storage, proposal_id) {
+        return match vote_option {
+            ProposalVoteOption::Choice(option) => {
+                cast_multi_choice_vote(deps, env, voter, proposal_id, vec![option])
+            }
+            _ => Err(ContractError::MultiChoiceProposal {}),
+        };
     }
 
-    if proposal.for_voters.contains(&info.sender) || proposal.against_voters.contains(&info.sender)
-    {
+    if proposal.for_voters.contains(&voter) || proposal.against_voters.contains(&voter) {
         return Err(ContractError::UserAlreadyVoted {});
     }
 
-    let voting_power = calc_voting_power(deps.as_ref(), info.sender.to_string(), &proposal)?;
+    let voting_power = calc_voting_power(deps.as_ref(), voter.to_string(), &proposal)?;
 
     if voting_power.is_zero() {
         return Err(ContractError::NoVotingPower {});
@@ -285,46 +778,375 @@ pub fn cast_vote(
     match vote_option {
         ProposalVoteOption::For => {
             proposal.for_power = proposal.for_power.checked_add(voting_power)?;
-            proposal.for_voters.push(info.sender.clone());
+            proposal.for_voters.push(voter.clone());
         }
         ProposalVoteOption::Against => {
             proposal.against_power = proposal.against_power.checked_add(voting_power)?;
-            proposal.against_voters.push(info.sender.clone());
+            proposal.against_voters.push(voter.clone());
         }
+        ProposalVoteOption::Choice(_) => return Err(ContractError::NotMultiChoiceProposal {}),
     };
 
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    VOTES.save(
+        deps.storage,
+        (proposal_id, &voter),
+        &ProposalVote {
+            option: vote_option.clone(),
+            power: voting_power,
+        },
+    )?;
+
+    let listener_msgs = vote_listener_msgs(
+        deps.storage,
+        proposal_id,
+        &voter,
+        &vote_option,
+        voting_power,
+    )?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "cast_vote"),
-        attr("proposal_id", proposal_id.to_string()),
-        attr("voter", &info.sender),
-        attr("vote", vote_option.to_string()),
-        attr("voting_power", voting_power),
-    ]))
+    Ok(Response::new()
+        .add_attributes(cast_vote_attrs(
+            proposal_id,
+            &voter,
+            &vote_option,
+            voting_power,
+        ))
+        .add_submessages(listener_msgs))
 }
 
-/// Ends proposal voting period and sets the proposal status by id.
-pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Response, ContractError> {
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+/// Cast a vote signed off-chain by the voter. The relayer submitting it pays the fees.
+///
+/// * **voter** the address that signed the vote.
+///
+/// * **signature** secp256k1 signature over sha256 of the JSON encoded [`VoteBySigPayload`].
+///
+/// * **nonce** must equal the voter's next nonce. Every signed vote increments it.
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote_by_sig(
+    deps: DepsMut,
+    env: Env,
+    voter: String,
+    proposal_id: u64,
+    vote: ProposalVoteOption,
+    signature: Binary,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    let voter = deps.api.addr_validate(&voter)?;
 
-    if proposal.status != ProposalStatus::Active {
-        return Err(ContractError::ProposalNotActive {});
+    let expected = VOTE_NONCES
+        .may_load(deps.storage, &voter)?
+        .unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidVoteNonce { expected });
     }
 
-    if env.block.height <= proposal.end_block {
-        return Err(ContractError::VotingPeriodNotEnded {});
+    let payload = to_vec(&VoteBySigPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        voter: voter.to_string(),
+        proposal_id,
+        vote: vote.clone(),
+        nonce,
+    })?;
+    if !is_signed_by(deps.api, &voter, &payload, &signature) {
+        return Err(ContractError::InvalidVoteSignature {});
     }
 
-    let config = CONFIG.load(deps.storage)?;
+    VOTE_NONCES.save(deps.storage, &voter, &(nonce + 1))?;
 
-    let for_votes = proposal.for_power;
-    let against_votes = proposal.against_power;
-    let total_votes = for_votes + against_votes;
+    Ok(cast_vote(deps, env, voter, proposal_id, vote)?.add_attribute("nonce", nonce.to_string()))
+}
 
-    let total_voting_power = calc_total_voting_power_at(deps.as_ref(), &proposal)?;
+/// Allows an operator to cast votes on behalf of the sender.
+///
+/// * **operator** the address allowed to vote.
+///
+/// * **expires** timestamp (in seconds) after which the grant expires.
+pub fn grant_vote_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<u64>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
 
-    let mut proposal_quorum: Decimal = Decimal::zero();
+    if matches!(expires, Some(expires) if expires <= env.block.time.seconds()) {
+        return Err(ContractError::InvalidGrantExpiration {});
+    }
+
+    VOTE_OPERATORS.save(deps.storage, (&info.sender, &operator), &expires)?;
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "grant_vote_operator")
+        .add_attribute("grantor", info.sender)
+        .add_attribute("operator", operator)
+        .add_attributes(expires.map(|expires| ("expires", expires.to_string()))))
+}
+
+/// Cast a vote on behalf of a grantor. The vote is cast with the grantor's voting power.
+///
+/// * **grantor** the address that allowed the sender to vote for it.
+///
+/// * **proposal_id** is the identifier of the proposal.
+///
+/// * **vote** contains the vote option.
+pub fn cast_vote_as_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantor: String,
+    proposal_id: u64,
+    vote: ProposalVoteOption,
+) -> Result<Response, ContractError> {
+    let grantor = deps.api.addr_validate(&grantor)?;
+
+    let is_allowed = match VOTE_OPERATORS.may_load(deps.storage, (&grantor, &info.sender))? {
+        Some(Some(expires)) => env.block.time.seconds() < expires,
+        Some(None) => true,
+        None => false,
+    };
+    if !is_allowed {
+        return Err(ContractError::NoVoteOperatorGrant {
+            operator: info.sender.to_string(),
+            grantor: grantor.to_string(),
+        });
+    }
+
+    Ok(cast_vote(deps, env, grantor, proposal_id, vote)?.add_attribute("operator", info.sender))
+}
+
+/// Cast a vote for an option of a multi-choice proposal. Ranked-choice proposals also accept
+/// several options in the order of preference. The first preference is recorded among the
+/// other votes, so vote listeners, tally checks and vote proofs cover it as well.
+///
+/// * **voter** the address the vote is cast for.
+///
+/// * **proposal_id** is the identifier of the proposal.
+///
+/// * **ranking** indexes of the chosen options, most preferred first.
+pub fn cast_multi_choice_vote(
+    deps: DepsMut,
+    env: Env,
+    voter: Addr,
+    proposal_id: u64,
+    ranking: Vec<u32>,
+) -> Result<Response, ContractError> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    check_not_paused(deps.storage, &env)?;
+    check_voting_open(&proposal, &env, &voter)?;
+
+    let mut tallies = OPTION_TALLIES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotMultiChoiceProposal {})?;
+
+    if VOTES.has(deps.storage, (proposal_id, &voter)) {
+        return Err(ContractError::UserAlreadyVoted {});
+    }
+
+    let ranked = RANKED_PROPOSALS.has(deps.storage, proposal_id);
+    if ranking.len() > 1 && !ranked {
+        return Err(ContractError::NotRankedChoiceProposal {});
+    }
+
+    for (i, choice) in ranking.iter().enumerate() {
+        if *choice as usize >= tallies.len() {
+            return Err(ContractError::InvalidProposalOption(*choice));
+        }
+        if ranking[..i].contains(choice) {
+            return Err(ContractError::InvalidRanking {});
+        }
+    }
+    let option = *ranking.first().ok_or(ContractError::InvalidRanking {})?;
+
+    let voting_power = calc_voting_power(deps.as_ref(), voter.to_string(), &proposal)?;
+
+    if voting_power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    // The tallies hold the first preferences
+    let tally = &mut tallies[option as usize];
+    *tally = tally.checked_add(voting_power)?;
+
+    OPTION_TALLIES.save(deps.storage, proposal_id, &tallies)?;
+    let vote_option = ProposalVoteOption::Choice(option);
+    VOTES.save(
+        deps.storage,
+        (proposal_id, &voter),
+        &ProposalVote {
+            option: vote_option.clone(),
+            power: voting_power,
+        },
+    )?;
+
+    if ranked {
+        let key: Vec<u8> = ranking.iter().map(|choice| *choice as u8).collect();
+        RANKED_BALLOTS.update(deps.storage, (proposal_id, &key), |power| -> StdResult<_> {
+            Ok(power.unwrap_or_default().checked_add(voting_power)?)
+        })?;
+    }
+
+    let listener_msgs = vote_listener_msgs(
+        deps.storage,
+        proposal_id,
+        &voter,
+        &vote_option,
+        voting_power,
+    )?;
+
+    Ok(Response::new()
+        .add_attributes(proposal_attrs("cast_multi_choice_vote", proposal_id))
+        .add_attribute("voter", &voter)
+        .add_attribute("option", option.to_string())
+        .add_attribute(
+            "ranking",
+            ranking
+                .iter()
+                .map(|choice| choice.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .add_attribute("voting_power", voting_power)
+        .add_submessages(listener_msgs))
+}
+
+/// Checks that the sender can still vote on the proposal.
+fn check_voting_open(proposal: &Proposal, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+
+    if &proposal.submitter == sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.height > proposal.end_block {
+        return Err(ContractError::VotingPeriodEnded {});
+    }
+
+    Ok(())
+}
+
+/// Checks that new proposals and votes aren't paused by the chain or the emergency committee.
+fn check_not_paused(storage: &dyn Storage, env: &Env) -> Result<(), ContractError> {
+    if CHAIN_PAUSED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::PausedByChain {});
+    }
+
+    match PAUSED_UNTIL.may_load(storage)? {
+        Some(paused_until) if env.block.time.seconds() < paused_until => {
+            Err(ContractError::Paused { paused_until })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the index of the option with strictly more voting power than any other option.
+fn plurality_winner(tallies: &[Uint128]) -> Option<u32> {
+    let max = tallies.iter().max()?;
+    let mut winners = tallies
+        .iter()
+        .enumerate()
+        .filter(|(_, power)| *power == max);
+
+    match (winners.next(), winners.next()) {
+        (Some((index, _)), None) => Some(index as u32),
+        _ => None,
+    }
+}
+
+/// Runs an instant runoff over the ranked ballots of a proposal. The options with the least
+/// first preferences are eliminated until one option holds the majority of the remaining ballots.
+fn instant_runoff_winner(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    options: usize,
+) -> StdResult<Option<u32>> {
+    let ballots = RANKED_BALLOTS
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut eliminated = vec![false; options];
+    loop {
+        let mut counts = vec![Uint128::zero(); options];
+        for (ranking, power) in &ballots {
+            let preferred = ranking
+                .iter()
+                .map(|choice| *choice as usize)
+                .find(|choice| !eliminated[*choice]);
+            if let Some(choice) = preferred {
+                counts[choice] += *power;
+            }
+        }
+
+        let remaining: Vec<usize> = (0..options).filter(|option| !eliminated[*option]).collect();
+        let total = remaining
+            .iter()
+            .fold(Uint128::zero(), |total, option| total + counts[*option]);
+        if total.is_zero() {
+            return Ok(None);
+        }
+
+        if let Some(winner) = remaining
+            .iter()
+            .find(|option| counts[**option] + counts[**option] > total)
+        {
+            return Ok(Some(*winner as u32));
+        }
+
+        let min = remaining
+            .iter()
+            .map(|option| counts[*option])
+            .min()
+            .unwrap_or_default();
+        let losers: Vec<usize> = remaining
+            .into_iter()
+            .filter(|option| counts[*option] == min)
+            .collect();
+
+        // Every remaining option is tied
+        if losers.len() == eliminated.iter().filter(|eliminated| !**eliminated).count() {
+            return Ok(None);
+        }
+
+        for option in losers {
+            eliminated[option] = true;
+        }
+    }
+}
+
+/// Ends proposal voting period and sets the proposal status by id.
+pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+
+    if env.block.height <= proposal.end_block {
+        return Err(ContractError::VotingPeriodNotEnded {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let option_tallies = OPTION_TALLIES.may_load(deps.storage, proposal_id)?;
+
+    let for_votes = proposal.for_power;
+    let against_votes = proposal.against_power;
+    let total_votes = match &option_tallies {
+        Some(tallies) => tallies
+            .iter()
+            .try_fold(Uint128::zero(), |total, power| total.checked_add(*power))?,
+        None => for_votes + against_votes,
+    };
+
+    let total_voting_power = calc_total_voting_power_at(deps.as_ref(), &proposal)?;
+
+    let mut proposal_quorum: Decimal = Decimal::zero();
     let mut proposal_threshold: Decimal = Decimal::zero();
 
     if !total_voting_power.is_zero() {
@@ -335,31 +1157,99 @@ pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Respons
         proposal_threshold = Decimal::from_ratio(for_votes, total_votes);
     }
 
+    // A multi-choice proposal passes when one option has strictly more voting power than any other.
+    // Ranked-choice proposals pass when an option wins the instant runoff
+    let winning_option = match &option_tallies {
+        Some(tallies) if RANKED_PROPOSALS.has(deps.storage, proposal_id) => {
+            instant_runoff_winner(deps.storage, proposal_id, tallies.len())?
+        }
+        Some(tallies) => plurality_winner(tallies),
+        None => None,
+    }
+    .filter(|_| !total_votes.is_zero());
+    let threshold_reached = match &option_tallies {
+        Some(_) => winning_option.is_some(),
+        None => proposal_threshold > config.proposal_required_threshold,
+    };
+
     // Determine the proposal result
-    proposal.status = if proposal_quorum >= config.proposal_required_quorum
-        && proposal_threshold > config.proposal_required_threshold
-    {
+    proposal.status = if proposal_quorum >= config.proposal_required_quorum && threshold_reached {
         ProposalStatus::Passed
     } else {
         ProposalStatus::Rejected
     };
 
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    let winning_option = winning_option.filter(|_| proposal.status == ProposalStatus::Passed);
+    if let Some(winning_option) = winning_option {
+        // The winning option's messages become the proposal messages
+        let options = PROPOSAL_OPTIONS.load(deps.storage, proposal_id)?;
+        let mut content = PROPOSAL_CONTENT.load(deps.storage, proposal_id)?;
+        content.messages = options[winning_option as usize].messages.clone();
+        PROPOSAL_CONTENT.save(deps.storage, proposal_id, &content)?;
+        WINNING_OPTIONS.save(deps.storage, proposal_id, &winning_option)?;
+    }
 
-    let response = Response::new()
-        .add_attributes(vec![
-            attr("action", "end_proposal"),
-            attr("proposal_id", proposal_id.to_string()),
-            attr("proposal_result", proposal.status.to_string()),
-        ])
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.xastro_token_addr.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: proposal.submitter.to_string(),
-                amount: proposal.deposit_amount,
-            })?,
-            funds: vec![],
-        }));
+    // The final tally is committed to with commit_votes so that single votes can be proven
+    VOTE_TREES.save(deps.storage, proposal_id, &VoteTree::default())?;
+
+    save_proposal_with_status(deps.storage, &proposal, &ProposalStatus::Active)?;
+    PROPOSALS_BY_END_BLOCK.remove(deps.storage, (proposal.end_block, proposal_id));
+    if let Some(thread) = &proposal.forum_thread_id {
+        ACTIVE_FORUM_THREADS.remove(deps.storage, thread);
+    }
+
+    let mut response = Response::new()
+        .add_attributes(end_proposal_attrs(proposal_id, &proposal.status))
+        .add_attributes(winning_option.map(|option| ("winning_option", option.to_string())))
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            proposal_id,
+            &proposal.status,
+        )?);
+
+    let binding_round = BINDING_ROUNDS.may_load(deps.storage, proposal_id)?;
+    if binding_round.is_some() {
+        BINDING_ROUNDS.remove(deps.storage, proposal_id);
+    }
+
+    match binding_round {
+        // The signaling round passed, so the binding round opens and takes over the deposit
+        Some(BindingRound {
+            content,
+            ibc_channel,
+        }) if proposal.status == ProposalStatus::Passed => {
+            let binding = create_proposal(
+                deps.storage,
+                &env,
+                &config,
+                proposal.submitter.clone(),
+                proposal.deposit_amount,
+                proposal.title.clone(),
+                content,
+                ibc_channel,
+                proposal.forum_thread_id.clone(),
+                false,
+            )?;
+            let binding_id = binding.proposal_id.u64();
+
+            TWO_PHASE_PROPOSALS.save(deps.storage, proposal_id, &Some(binding_id))?;
+            SIGNALING_ROUNDS.save(deps.storage, binding_id, &proposal_id)?;
+
+            response = response
+                .add_attributes(submit_proposal_attrs(
+                    &binding.submitter,
+                    binding_id,
+                    binding.end_block,
+                ))
+                .add_attribute("round", "binding")
+                .add_submessages(proposal_hook_msgs(
+                    deps.storage,
+                    binding_id,
+                    &binding.status,
+                )?);
+        }
+        _ => response = response.add_message(config.deposit_asset().refund_msg(&proposal)?),
+    }
 
     Ok(response)
 }
@@ -370,7 +1260,72 @@ pub fn execute_proposal(
     env: Env,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    if let Some(bundle_id) = PROPOSAL_BUNDLES.may_load(deps.storage, proposal_id)? {
+        return Err(ContractError::BundledProposal { bundle_id });
+    }
+
+    let (proposal, messages) = start_execution(deps.storage, &env, proposal_id)?;
+
+    Ok(Response::new()
+        .add_attributes(proposal_attrs("execute_proposal", proposal_id))
+        .add_messages(messages)
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            proposal_id,
+            &proposal.status,
+        )?))
+}
+
+/// Executes all proposals of a bundle. The messages of every proposal are dispatched in the
+/// same transaction, so a single failing message reverts the whole bundle.
+///
+/// * **proposal_ids** identifiers of all proposals in the bundle.
+pub fn execute_bundle(
+    deps: DepsMut,
+    env: Env,
+    mut proposal_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    proposal_ids.sort_unstable();
+    proposal_ids.dedup();
+
+    let bundle_id = match proposal_ids.first() {
+        Some(proposal_id) => PROPOSAL_BUNDLES.may_load(deps.storage, *proposal_id)?,
+        None => None,
+    }
+    .ok_or(ContractError::IncompleteBundle {})?;
+
+    let bundle = BUNDLES.load(deps.storage, bundle_id)?;
+    if bundle.proposal_ids != proposal_ids {
+        return Err(ContractError::IncompleteBundle {});
+    }
+
+    let mut response = Response::new()
+        .add_attribute(ACTION, "execute_bundle")
+        .add_attribute("bundle_id", bundle_id.to_string());
+
+    for proposal_id in proposal_ids {
+        let (proposal, messages) = start_execution(deps.storage, &env, proposal_id)?;
+        response = response
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_messages(messages)
+            .add_submessages(proposal_hook_msgs(
+                deps.storage,
+                proposal_id,
+                &proposal.status,
+            )?);
+    }
+
+    Ok(response)
+}
+
+/// Checks that a passed proposal can be executed and updates its status.
+/// Returns the proposal together with the messages to dispatch.
+fn start_execution(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<(Proposal, Vec<CosmosMsg>), ContractError> {
+    let mut proposal = PROPOSALS.load(storage, proposal_id)?;
 
     if proposal.status != ProposalStatus::Passed {
         return Err(ContractError::ProposalNotPassed {});
@@ -384,11 +1339,13 @@ pub fn execute_proposal(
         return Err(ContractError::ExecuteProposalExpired {});
     }
 
+    let content = PROPOSAL_CONTENT.load(storage, proposal_id)?;
+
     let messages;
     if let Some(channel) = &proposal.ibc_channel {
-        let config = CONFIG.load(deps.storage)?;
+        let config = CONFIG.load(storage)?;
 
-        messages = match &proposal.messages {
+        messages = match &content.messages {
             Some(messages) => {
                 if !messages.is_empty() {
                     proposal.status = ProposalStatus::InProgress;
@@ -400,6 +1357,7 @@ pub fn execute_proposal(
                             channel_id: channel.to_string(),
                             proposal_id,
                             messages: messages.to_vec(),
+                            forward: PROPOSAL_IBC_FORWARD.may_load(storage, proposal_id)?,
                         },
                         vec![],
                     )?)]
@@ -414,18 +1372,15 @@ pub fn execute_proposal(
             }
         };
 
-        PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+        save_proposal_with_status(storage, &proposal, &ProposalStatus::Passed)?;
     } else {
         proposal.status = ProposalStatus::Executed;
-        PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+        save_proposal_with_status(storage, &proposal, &ProposalStatus::Passed)?;
 
-        messages = proposal.messages.unwrap_or_default()
+        messages = content.messages.unwrap_or_default()
     }
 
-    Ok(Response::new()
-        .add_attribute("action", "execute_proposal")
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_messages(messages))
+    Ok((proposal, messages))
 }
 
 /// Checks that proposal messages are correct.
@@ -437,19 +1392,47 @@ pub fn check_messages(env: Env, mut messages: Vec<CosmosMsg>) -> Result<Response
     }));
 
     Ok(Response::new()
-        .add_attribute("action", "check_messages")
+        .add_attribute(ACTION, "check_messages")
         .add_messages(messages))
 }
 
-/// Removes an expired or rejected proposal from the general proposal list.
+/// Removes an expired or rejected proposal. The proposal and everything stored once per proposal
+/// are removed by the first call, while its votes, ranked ballots and vote merkle tree are removed
+/// in batches. The removal is repeated until the `finished` attribute is `true`.
+///
+/// * **limit** amount of votes, ranked ballots and vote merkle tree entries to remove.
 pub fn remove_completed_proposal(
     deps: DepsMut,
     env: Env,
     proposal_id: u64,
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    if !PENDING_REMOVALS.has(deps.storage, proposal_id) {
+        remove_proposal(deps.storage, &env, proposal_id)?;
+        PENDING_REMOVALS.save(deps.storage, proposal_id, &())?;
+    }
 
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let finished = remove_proposal_votes(deps.storage, proposal_id, limit)?;
+    if finished {
+        PENDING_REMOVALS.remove(deps.storage, proposal_id);
+    }
+
+    Ok(Response::new()
+        .add_attributes(proposal_attrs("remove_completed_proposal", proposal_id))
+        .add_attribute("finished", finished.to_string()))
+}
+
+/// Removes a completed proposal together with its indexes and everything stored once per proposal.
+fn remove_proposal(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+
+    let mut proposal = PROPOSALS.load(storage, proposal_id)?;
+    let stored_status = proposal.status.clone();
 
     if env.block.height
         > (proposal.end_block + config.proposal_effective_delay + config.proposal_expiration_period)
@@ -461,11 +1444,89 @@ pub fn remove_completed_proposal(
         return Err(ContractError::ProposalNotCompleted {});
     }
 
-    PROPOSALS.remove(deps.storage, proposal_id);
+    PROPOSALS.remove(storage, proposal_id);
+    PROPOSAL_CONTENT.remove(storage, proposal_id);
+    PROPOSAL_IBC_FORWARD.remove(storage, proposal_id);
+    PROPOSAL_BUNDLES.remove(storage, proposal_id);
+    PROPOSAL_OPTIONS.remove(storage, proposal_id);
+    OPTION_TALLIES.remove(storage, proposal_id);
+    RANKED_PROPOSALS.remove(storage, proposal_id);
+    WINNING_OPTIONS.remove(storage, proposal_id);
+    VOTE_ROOTS.remove(storage, proposal_id);
+    VOTE_TREES.remove(storage, proposal_id);
+    BINDING_ROUNDS.remove(storage, proposal_id);
+    // Unlink the other round of a two-phase proposal
+    if let Some(Some(binding_id)) = TWO_PHASE_PROPOSALS.may_load(storage, proposal_id)? {
+        SIGNALING_ROUNDS.remove(storage, binding_id);
+    }
+    TWO_PHASE_PROPOSALS.remove(storage, proposal_id);
+    SIGNALING_ROUNDS.remove(storage, proposal_id);
+    remove_from_status_index(storage, &stored_status, proposal_id)?;
+    PROPOSALS_BY_SUBMITTER.remove(storage, (&proposal.submitter, proposal_id));
+    // An expired proposal may have never been ended
+    PROPOSALS_BY_END_BLOCK.remove(storage, (proposal.end_block, proposal_id));
+    if let Some(thread) = &proposal.forum_thread_id {
+        // The thread may already be referenced by a newer proposal
+        if ACTIVE_FORUM_THREADS.may_load(storage, thread)? == Some(proposal_id) {
+            ACTIVE_FORUM_THREADS.remove(storage, thread);
+        }
+    }
+
+    Ok(())
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "remove_completed_proposal")
-        .add_attribute("proposal_id", proposal_id.to_string()))
+/// Removes up to `limit` votes, ranked ballots and vote merkle tree entries of a removed proposal.
+/// Removed entries are gone from the proposal prefix, so every batch starts where the previous one
+/// stopped. Returns whether nothing is left.
+fn remove_proposal_votes(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    limit: usize,
+) -> StdResult<bool> {
+    let mut removed = 0;
+
+    // Multi-choice voters aren't listed in the proposal
+    let voters = VOTES
+        .prefix(proposal_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for voter in &voters {
+        VOTES.remove(storage, (proposal_id, voter));
+    }
+    removed += voters.len();
+
+    let voters = VOTE_LEAF_INDEXES
+        .prefix(proposal_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit - removed)
+        .collect::<StdResult<Vec<_>>>()?;
+    for voter in &voters {
+        VOTE_LEAF_INDEXES.remove(storage, (proposal_id, voter));
+    }
+    removed += voters.len();
+
+    let nodes = VOTE_TREE_NODES
+        .sub_prefix(proposal_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit - removed)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (level, index) in &nodes {
+        VOTE_TREE_NODES.remove(storage, (proposal_id, *level, *index));
+    }
+    removed += nodes.len();
+
+    let rankings = RANKED_BALLOTS
+        .prefix(proposal_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit - removed)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ranking in &rankings {
+        RANKED_BALLOTS.remove(storage, (proposal_id, ranking.as_slice()));
+    }
+    removed += rankings.len();
+
+    Ok(removed < limit)
 }
 
 /// Updates Assembly contract parameters.
@@ -477,13 +1538,22 @@ pub fn update_config(
     info: MessageInfo,
     updated_config: Box<UpdateConfig>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-
     // Only the Assembly is allowed to update its own parameters (through a successful proposal)
     if info.sender != env.contract.address {
         return Err(ContractError::Unauthorized {});
     }
 
+    apply_config_update(deps, &env, updated_config)
+}
+
+/// Validates and saves the updated Assembly parameters.
+fn apply_config_update(
+    deps: DepsMut,
+    env: &Env,
+    updated_config: Box<UpdateConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
     if let Some(xastro_token_addr) = updated_config.xastro_token_addr {
         config.xastro_token_addr = deps.api.addr_validate(&xastro_token_addr)?;
     }
@@ -511,89 +1581,730 @@ pub fn update_config(
         config.proposal_voting_period = proposal_voting_period;
     }
 
-    if let Some(proposal_effective_delay) = updated_config.proposal_effective_delay {
-        config.proposal_effective_delay = proposal_effective_delay;
+    if let Some(proposal_effective_delay) = updated_config.proposal_effective_delay {
+        config.proposal_effective_delay = proposal_effective_delay;
+    }
+
+    if let Some(proposal_expiration_period) = updated_config.proposal_expiration_period {
+        config.proposal_expiration_period = proposal_expiration_period;
+    }
+
+    if let Some(proposal_required_deposit) = updated_config.proposal_required_deposit {
+        config.proposal_required_deposit = proposal_required_deposit;
+    }
+
+    if let Some(proposal_required_quorum) = updated_config.proposal_required_quorum {
+        config.proposal_required_quorum = proposal_required_quorum;
+    }
+
+    if let Some(proposal_required_threshold) = updated_config.proposal_required_threshold {
+        config.proposal_required_threshold = proposal_required_threshold;
+    }
+
+    if let Some(builder_power_coefficient) = updated_config.builder_power_coefficient {
+        config.builder_power_coefficient = builder_power_coefficient;
+    }
+
+    if let Some(weighting) = updated_config.xastro_time_weighting {
+        config.xastro_time_weighting = Some(weighting);
+    }
+
+    if updated_config.disable_xastro_time_weighting == Some(true) {
+        config.xastro_time_weighting = None;
+    }
+
+    if let Some(signaling_required_deposit) = updated_config.signaling_required_deposit {
+        config.signaling_required_deposit = signaling_required_deposit;
+    }
+
+    if let Some(proposal_max_messages) = updated_config.proposal_max_messages {
+        config.proposal_max_messages = proposal_max_messages;
+    }
+
+    if let Some(proposal_max_messages_size) = updated_config.proposal_max_messages_size {
+        config.proposal_max_messages_size = proposal_max_messages_size;
+    }
+
+    if let Some(links_curator) = updated_config.links_curator {
+        config.links_curator = Some(deps.api.addr_validate(&links_curator)?);
+    }
+
+    if updated_config.remove_links_curator == Some(true) {
+        config.links_curator = None;
+    }
+
+    if let Some(emergency_committee) = updated_config.emergency_committee {
+        config.emergency_committee = Some(deps.api.addr_validate(&emergency_committee)?);
+    }
+
+    if updated_config.remove_emergency_committee == Some(true) {
+        config.emergency_committee = None;
+    }
+
+    if let Some(excluded_voters_add) = updated_config.excluded_voters_add {
+        for voter in excluded_voters_add {
+            let voter = deps.api.addr_validate(&voter)?;
+            if !config.excluded_voters.contains(&voter) {
+                config.excluded_voters.push(voter);
+            }
+        }
+    }
+
+    if let Some(excluded_voters_remove) = updated_config.excluded_voters_remove {
+        config
+            .excluded_voters
+            .retain(|voter| !excluded_voters_remove.contains(&voter.to_string()));
+    }
+
+    apply_whitelist_changes(
+        &mut config,
+        updated_config.whitelist_add,
+        updated_config.whitelist_remove,
+    )?;
+
+    config.validate()?;
+
+    CONFIG.save(deps.storage, &config)?;
+    CONFIG_HISTORY.save(deps.storage, env.block.height, &config)?;
+
+    Ok(Response::new().add_attribute(ACTION, "update_config"))
+}
+
+/// Adds and removes links from the link whitelist.
+/// Only the links curator or the Assembly itself can call this function.
+///
+/// * **add** links to add to the whitelist.
+///
+/// * **remove** links to remove from the whitelist.
+pub fn update_link_whitelist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != env.contract.address && Some(&info.sender) != config.links_curator.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    apply_whitelist_changes(&mut config, add, remove)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    CONFIG_HISTORY.save(deps.storage, env.block.height, &config)?;
+
+    Ok(Response::new().add_attribute(ACTION, "update_link_whitelist"))
+}
+
+/// Applies whitelist additions and removals to the config.
+fn apply_whitelist_changes(
+    config: &mut Config,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<(), ContractError> {
+    if let Some(whitelist_add) = add {
+        validate_links(&whitelist_add)?;
+
+        config.whitelisted_links.append(
+            &mut whitelist_add
+                .into_iter()
+                .filter(|link| !config.whitelisted_links.contains(link))
+                .collect(),
+        );
+    }
+
+    if let Some(whitelist_remove) = remove {
+        config
+            .whitelisted_links
+            .retain(|link| !whitelist_remove.contains(link));
+
+        if config.whitelisted_links.is_empty() {
+            return Err(ContractError::WhitelistEmpty {});
+        }
+    }
+
+    Ok(())
+}
+
+/// Updates proposal status InProgress -> Executed or Failed. Intended to be called in the end of
+/// the ibc execution cycle via ibc-controller. Only ibc controller is able to call this function.
+///
+/// * **id** proposal's id,
+///
+/// * **status** a new proposal status reported by ibc controller.
+fn update_ibc_proposal_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    new_status: ProposalStatus,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if Some(info.sender) == config.ibc_controller {
+        let mut proposal = PROPOSALS.load(deps.storage, id)?;
+
+        if proposal.status != ProposalStatus::InProgress {
+            return Err(ContractError::WrongIbcProposalStatus(
+                proposal.status.to_string(),
+            ));
+        }
+
+        match new_status {
+            ProposalStatus::Executed {} | ProposalStatus::Failed {} => {
+                proposal.status = new_status;
+                save_proposal_with_status(deps.storage, &proposal, &ProposalStatus::InProgress)?;
+                Ok(Response::new()
+                    .add_attribute(ACTION, "ibc_proposal_completed")
+                    .add_submessages(proposal_hook_msgs(deps.storage, id, &proposal.status)?))
+            }
+            _ => Err(ContractError::InvalidRemoteIbcProposalStatus(
+                new_status.to_string(),
+            )),
+        }
+    } else {
+        Err(ContractError::InvalidIBCController {})
+    }
+}
+
+/// Pauses new proposals and votes for [`EMERGENCY_PAUSE_PERIOD`]. Only the emergency committee
+/// can pause, and only once the same period has passed since the previous pause ended.
+fn pause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if Some(&info.sender) != config.emergency_committee.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+    if let Some(paused_until) = PAUSED_UNTIL.may_load(deps.storage)? {
+        let available_at = paused_until + EMERGENCY_PAUSE_PERIOD;
+        if now < available_at {
+            return Err(ContractError::PauseCooldown { available_at });
+        }
+    }
+
+    let paused_until = now + EMERGENCY_PAUSE_PERIOD;
+    PAUSED_UNTIL.save(deps.storage, &paused_until)?;
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "pause")
+        .add_attribute("paused_until", paused_until.to_string()))
+}
+
+/// Lifts the current pause. The emergency committee or the Assembly itself can unpause.
+fn unpause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != env.contract.address
+        && Some(&info.sender) != config.emergency_committee.as_ref()
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+    match PAUSED_UNTIL.may_load(deps.storage)? {
+        Some(paused_until) if now < paused_until => PAUSED_UNTIL.save(deps.storage, &now)?,
+        _ => return Err(ContractError::NotPaused {}),
+    }
+
+    Ok(Response::new().add_attribute(ACTION, "unpause"))
+}
+
+/// Rejects a passed proposal before it is executed. Only the emergency committee can veto.
+/// A veto is only possible until the execution delay of the proposal ends, and proposals which
+/// change the committee can't be vetoed, so the committee can't block its own replacement.
+///
+/// * **proposal_id** proposal to reject.
+fn veto_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let committee = match &config.emergency_committee {
+        Some(committee) if committee == &info.sender => committee,
+        _ => return Err(ContractError::Unauthorized {}),
+    };
+
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(ContractError::ProposalNotPassed {});
+    }
+
+    if env.block.height >= proposal.delayed_end_block {
+        return Err(ContractError::VetoPeriodEnded {});
+    }
+
+    let content = PROPOSAL_CONTENT.load(deps.storage, proposal_id)?;
+    if changes_emergency_committee(&env, committee, &content.messages.unwrap_or_default()) {
+        return Err(ContractError::CommitteeChangeVeto {});
+    }
+
+    proposal.status = ProposalStatus::Rejected;
+    save_proposal_with_status(deps.storage, &proposal, &ProposalStatus::Passed)?;
+
+    Ok(Response::new()
+        .add_attributes(proposal_attrs("veto_proposal", proposal_id))
+        .add_submessages(proposal_hook_msgs(
+            deps.storage,
+            proposal_id,
+            &proposal.status,
+        )?))
+}
+
+/// Checks whether any of the messages calls, migrates or changes the admin of the emergency
+/// committee contract, or replaces or removes the committee in the Assembly config.
+fn changes_emergency_committee(env: &Env, committee: &Addr, messages: &[CosmosMsg]) -> bool {
+    messages.iter().any(|message| match message {
+        CosmosMsg::Wasm(
+            WasmMsg::Execute { contract_addr, .. }
+            | WasmMsg::Migrate { contract_addr, .. }
+            | WasmMsg::UpdateAdmin { contract_addr, .. }
+            | WasmMsg::ClearAdmin { contract_addr, .. },
+        ) if contract_addr == committee.as_str() => true,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) if contract_addr == env.contract.address.as_str() => matches!(
+            from_binary::<ExecuteMsg>(msg),
+            Ok(ExecuteMsg::UpdateConfig(config))
+                if config.emergency_committee.is_some()
+                    || config.remove_emergency_committee == Some(true)
+        ),
+        _ => false,
+    })
+}
+
+/// Registers a contract notified about proposal status changes.
+///
+/// * **address** contract to notify.
+fn add_proposal_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Only the Assembly is allowed to manage hooks (through a successful proposal)
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps.api.addr_validate(&address)?;
+    if PROPOSAL_HOOKS.has(deps.storage, &hook) {
+        return Err(ContractError::HookAlreadyRegistered(address));
+    }
+    PROPOSAL_HOOKS.save(deps.storage, &hook, &())?;
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "add_proposal_hook")
+        .add_attribute("hook", hook))
+}
+
+/// Unregisters a proposal hook.
+///
+/// * **address** contract to stop notifying.
+fn remove_proposal_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Only the Assembly is allowed to manage hooks (through a successful proposal)
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps.api.addr_validate(&address)?;
+    if !PROPOSAL_HOOKS.has(deps.storage, &hook) {
+        return Err(ContractError::HookNotRegistered(address));
+    }
+    PROPOSAL_HOOKS.remove(deps.storage, &hook);
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "remove_proposal_hook")
+        .add_attribute("hook", hook))
+}
+
+/// Builds the notifications of a proposal status change for every registered hook.
+/// Every notification is limited to [`PROPOSAL_HOOK_GAS_LIMIT`] gas and a failing or
+/// out of gas hook can't block the proposal, its error is handled in [`reply`].
+fn proposal_hook_msgs(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    status: &ProposalStatus,
+) -> StdResult<Vec<SubMsg>> {
+    PROPOSAL_HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|hook| {
+            let msg = ProposalHookMsg::ProposalStatusChanged {
+                proposal_id,
+                status: status.clone(),
+            }
+            .into_cosmos_msg(hook?)?;
+
+            Ok(SubMsg::reply_on_error(msg, PROPOSAL_HOOK_REPLY_ID)
+                .with_gas_limit(PROPOSAL_HOOK_GAS_LIMIT))
+        })
+        .collect()
+}
+
+/// Registers a contract notified about every vote.
+///
+/// * **address** contract to notify.
+fn add_vote_listener(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Only the Assembly is allowed to manage listeners (through a successful proposal)
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let listener = deps.api.addr_validate(&address)?;
+    if VOTE_LISTENERS.has(deps.storage, &listener) {
+        return Err(ContractError::ListenerAlreadyRegistered(address));
+    }
+
+    let listeners_count = VOTE_LISTENERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if listeners_count >= MAX_VOTE_LISTENERS {
+        return Err(ContractError::TooManyVoteListeners {});
+    }
+
+    VOTE_LISTENERS.save(deps.storage, &listener, &())?;
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "add_vote_listener")
+        .add_attribute("listener", listener))
+}
+
+/// Unregisters a vote listener.
+///
+/// * **address** contract to stop notifying.
+fn remove_vote_listener(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Only the Assembly is allowed to manage listeners (through a successful proposal)
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let listener = deps.api.addr_validate(&address)?;
+    if !VOTE_LISTENERS.has(deps.storage, &listener) {
+        return Err(ContractError::ListenerNotRegistered(address));
+    }
+    VOTE_LISTENERS.remove(deps.storage, &listener);
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "remove_vote_listener")
+        .add_attribute("listener", listener))
+}
+
+/// Builds the notifications of a vote for every registered listener.
+/// Every notification is limited to [`VOTE_LISTENER_GAS_LIMIT`] gas and a failing or
+/// out of gas listener can't block the vote, its error is handled in [`reply`].
+fn vote_listener_msgs(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    voter: &Addr,
+    vote: &ProposalVoteOption,
+    voting_power: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    VOTE_LISTENERS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|listener| {
+            let msg = VoteListenerMsg::VoteCast {
+                proposal_id,
+                voter: voter.clone(),
+                vote: vote.clone(),
+                voting_power,
+            }
+            .into_cosmos_msg(listener?)?;
+
+            Ok(SubMsg::reply_on_error(msg, VOTE_LISTENER_REPLY_ID)
+                .with_gas_limit(VOTE_LISTENER_GAS_LIMIT))
+        })
+        .collect()
+}
+
+/// Exposes the privileged messages the chain can send to the Assembly.
+///
+/// ## Sudo messages
+/// * **SudoMsg::Pause {}** Stops accepting new proposals and votes until the chain unpauses.
+///
+/// * **SudoMsg::Unpause {}** Lifts the pause set by the chain.
+///
+/// * **SudoMsg::ForceEndProposal { proposal_id }** Ends the voting period of a proposal and
+/// tallies its votes.
+///
+/// * **SudoMsg::ForceConfig(config)** Updates contract parameters bypassing the governance process.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::Pause {} => {
+            CHAIN_PAUSED.save(deps.storage, &true)?;
+
+            Ok(Response::new().add_attribute(ACTION, "sudo_pause"))
+        }
+        SudoMsg::Unpause {} => {
+            if !CHAIN_PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+                return Err(ContractError::NotPaused {});
+            }
+            CHAIN_PAUSED.remove(deps.storage);
+
+            Ok(Response::new().add_attribute(ACTION, "sudo_unpause"))
+        }
+        SudoMsg::ForceEndProposal { proposal_id } => force_end_proposal(deps, env, proposal_id),
+        SudoMsg::ForceConfig(updated_config) => apply_config_update(deps, &env, updated_config)
+            .map(|response| response.add_attribute("sudo", "true")),
+    }
+}
+
+/// Closes the voting period of an active proposal at the previous block and ends the proposal.
+/// The execution delay and expiration are counted from the new end block.
+fn force_end_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+
+    if env.block.height <= proposal.end_block {
+        let config = CONFIG.load(deps.storage)?;
+
+        PROPOSALS_BY_END_BLOCK.remove(deps.storage, (proposal.end_block, proposal_id));
+        proposal.end_block = env.block.height - 1;
+        proposal.delayed_end_block = proposal.end_block + config.proposal_effective_delay;
+        proposal.expiration_block = proposal.delayed_end_block + config.proposal_expiration_period;
+        PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    }
+
+    end_proposal(deps, env, proposal_id).map(|response| response.add_attribute("sudo", "true"))
+}
+
+/// Handles failed proposal hook and vote listener notifications. Their state changes are
+/// reverted while the action which triggered them is kept.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (PROPOSAL_HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute(ACTION, "proposal_hook_failed")
+            .add_attribute("error", err)),
+        (VOTE_LISTENER_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute(ACTION, "vote_listener_failed")
+            .add_attribute("error", err)),
+        _ => Err(StdError::generic_err("Unknown reply ID").into()),
+    }
+}
+
+/// Backfills the status, submitter and end block indexes in batches, continuing after the last processed proposal.
+///
+/// * **limit** amount of proposals to process.
+fn reindex(deps: DepsMut, limit: Option<u32>) -> Result<Response, ContractError> {
+    let cursor = REINDEX_CURSOR.may_load(deps.storage)?;
+
+    let proposals = paginate_map(&PROPOSALS, deps.storage, cursor, limit, Order::Ascending)?;
+
+    for (_, proposal) in &proposals {
+        index_proposal(deps.storage, proposal)?;
+    }
+
+    let mut response = Response::new()
+        .add_attribute(ACTION, "reindex")
+        .add_attribute("reindexed", proposals.len().to_string());
+
+    if let Some((last_id, _)) = proposals.last() {
+        REINDEX_CURSOR.save(deps.storage, last_id)?;
+        response = response.add_attribute("last_proposal_id", last_id.to_string());
+    }
+
+    Ok(response)
+}
+
+/// Migrates the proposals left after a contract migration in batches, continuing after the last migrated proposal.
+///
+/// * **limit** amount of proposals to migrate.
+fn execute_migrate_proposals(deps: DepsMut, limit: Option<u32>) -> Result<Response, ContractError> {
+    if PROPOSALS_MIGRATION.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::ProposalsAlreadyMigrated {});
+    }
+
+    let finished = migrate_proposals(deps.storage, limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT))?;
+
+    Ok(Response::new()
+        .add_attribute(ACTION, "migrate_proposals")
+        .add_attribute("finished", finished.to_string()))
+}
+
+/// Builds the vote merkle tree of an ended proposal in batches. The leaves are stored first in the
+/// order of voter addresses, then every level is hashed into the next one until a single root is left.
+/// Multi-choice votes are leaves with their chosen option, and a proposal without votes gets
+/// [`EMPTY_VOTE_ROOT`].
+///
+/// * **limit** amount of leaves or nodes to store.
+fn commit_votes(
+    deps: DepsMut,
+    proposal_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Votes of removed proposals are gone, so their tree can't be completed
+    PROPOSALS.load(deps.storage, proposal_id)?;
+
+    let mut tree = VOTE_TREES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoVoteTree(proposal_id))?;
+    if tree.leaves_done && tree.level_size <= 1 {
+        return Err(ContractError::VoteTreeBuilt(proposal_id));
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut stored = 0;
+
+    if !tree.leaves_done {
+        let votes = VOTES
+            .prefix(proposal_id)
+            .range(
+                deps.storage,
+                tree.last_voter.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        tree.leaves_done = votes.len() < limit;
+        for (voter, vote) in votes {
+            let leaf = vote_leaf(&voter, &vote);
+            VOTE_TREE_NODES.save(
+                deps.storage,
+                (proposal_id, 0, tree.level_size),
+                &Binary::from(leaf.to_vec()),
+            )?;
+            VOTE_LEAF_INDEXES.save(deps.storage, (proposal_id, &voter), &tree.level_size)?;
+            tree.level_size += 1;
+            tree.last_voter = Some(voter);
+            stored += 1;
+        }
+    } else {
+        while stored < limit && tree.level_size > 1 {
+            let left_index = tree.next_index * 2;
+            let left = VOTE_TREE_NODES.load(deps.storage, (proposal_id, tree.level, left_index))?;
+            // An unpaired last node is carried to the next level as is
+            let node = match VOTE_TREE_NODES
+                .may_load(deps.storage, (proposal_id, tree.level, left_index + 1))?
+            {
+                Some(right) => Binary::from(hash_pair(&left, &right).to_vec()),
+                None => left,
+            };
+            VOTE_TREE_NODES.save(
+                deps.storage,
+                (proposal_id, tree.level + 1, tree.next_index),
+                &node,
+            )?;
+            tree.next_index += 1;
+            stored += 1;
+
+            if tree.next_index * 2 >= tree.level_size {
+                tree.level += 1;
+                tree.level_size = tree.next_index;
+                tree.next_index = 0;
+            }
+        }
     }
 
-    if let Some(proposal_expiration_period) = updated_config.proposal_expiration_period {
-        config.proposal_expiration_period = proposal_expiration_period;
-    }
+    let mut response = Response::new()
+        .add_attribute(ACTION, "commit_votes")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("stored", stored.to_string());
 
-    if let Some(proposal_required_deposit) = updated_config.proposal_required_deposit {
-        config.proposal_required_deposit = Uint128::from(proposal_required_deposit);
+    if tree.leaves_done && tree.level_size <= 1 {
+        // A proposal without votes commits to an explicit empty root
+        let root = match tree.level_size {
+            0 => Binary::from(EMPTY_VOTE_ROOT.to_vec()),
+            _ => VOTE_TREE_NODES.load(deps.storage, (proposal_id, tree.level, 0))?,
+        };
+        VOTE_ROOTS.save(deps.storage, proposal_id, &root)?;
+        response = response.add_attribute("vote_root", root.to_base64());
     }
+    VOTE_TREES.save(deps.storage, proposal_id, &tree)?;
 
-    if let Some(proposal_required_quorum) = updated_config.proposal_required_quorum {
-        config.proposal_required_quorum = Decimal::from_str(&proposal_required_quorum)?;
-    }
+    Ok(response)
+}
 
-    if let Some(proposal_required_threshold) = updated_config.proposal_required_threshold {
-        config.proposal_required_threshold = Decimal::from_str(&proposal_required_threshold)?;
-    }
+/// Adds a proposal to the status and submitter indexes, and to the end block index while it's active.
+fn index_proposal(storage: &mut dyn Storage, proposal: &Proposal) -> StdResult<()> {
+    let proposal_id = proposal.proposal_id.u64();
 
-    if let Some(whitelist_add) = updated_config.whitelist_add {
-        validate_links(&whitelist_add)?;
+    add_to_status_index(storage, &proposal.status, proposal_id)?;
+    PROPOSALS_BY_SUBMITTER.save(storage, (&proposal.submitter, proposal_id), &())?;
 
-        config.whitelisted_links.append(
-            &mut whitelist_add
-                .into_iter()
-                .filter(|link| !config.whitelisted_links.contains(link))
-                .collect(),
-        );
+    if proposal.status == ProposalStatus::Active {
+        PROPOSALS_BY_END_BLOCK.save(storage, (proposal.end_block, proposal_id), &())?;
     }
 
-    if let Some(whitelist_remove) = updated_config.whitelist_remove {
-        config
-            .whitelisted_links
-            .retain(|link| !whitelist_remove.contains(link));
-
-        if config.whitelisted_links.is_empty() {
-            return Err(ContractError::WhitelistEmpty {});
-        }
-    }
+    Ok(())
+}
 
-    config.validate()?;
+/// Saves a proposal and moves it from the index of its previous status to the index of its current one.
+fn save_proposal_with_status(
+    storage: &mut dyn Storage,
+    proposal: &Proposal,
+    prev_status: &ProposalStatus,
+) -> StdResult<()> {
+    let proposal_id = proposal.proposal_id.u64();
 
-    CONFIG.save(deps.storage, &config)?;
+    remove_from_status_index(storage, prev_status, proposal_id)?;
+    add_to_status_index(storage, &proposal.status, proposal_id)?;
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    PROPOSALS.save(storage, proposal_id, proposal)
 }
 
-/// Updates proposal status InProgress -> Executed or Failed. Intended to be called in the end of
-/// the ibc execution cycle via ibc-controller. Only ibc controller is able to call this function.
-///
-/// * **id** proposal's id,
-///
-/// * **status** a new proposal status reported by ibc controller.
-fn update_ibc_proposal_status(
-    deps: DepsMut,
-    info: MessageInfo,
-    id: u64,
-    new_status: ProposalStatus,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if Some(info.sender) == config.ibc_controller {
-        let mut proposal = PROPOSALS.load(deps.storage, id)?;
+/// Adds a proposal to the index of a status and increments the status counter if it wasn't indexed yet.
+fn add_to_status_index(
+    storage: &mut dyn Storage,
+    status: &ProposalStatus,
+    proposal_id: u64,
+) -> StdResult<()> {
+    let status = status.to_string();
+
+    if !PROPOSALS_BY_STATUS.has(storage, (&status, proposal_id)) {
+        PROPOSALS_BY_STATUS.save(storage, (&status, proposal_id), &())?;
+        PROPOSAL_STATUS_COUNTS.update(storage, &status, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+    }
 
-        if proposal.status != ProposalStatus::InProgress {
-            return Err(ContractError::WrongIbcProposalStatus(
-                proposal.status.to_string(),
-            ));
-        }
+    Ok(())
+}
 
-        match new_status {
-            ProposalStatus::Executed {} | ProposalStatus::Failed {} => {
-                proposal.status = new_status;
-                PROPOSALS.save(deps.storage, id, &proposal)?;
-                Ok(Response::new().add_attribute("action", "ibc_proposal_completed"))
-            }
-            _ => Err(ContractError::InvalidRemoteIbcProposalStatus(
-                new_status.to_string(),
-            )),
-        }
-    } else {
-        Err(ContractError::InvalidIBCController {})
+/// Removes a proposal from the index of a status and decrements the status counter if it was indexed.
+/// Proposals created before the indexes existed are skipped until they are reindexed.
+fn remove_from_status_index(
+    storage: &mut dyn Storage,
+    status: &ProposalStatus,
+    proposal_id: u64,
+) -> StdResult<()> {
+    let status = status.to_string();
+
+    if PROPOSALS_BY_STATUS.has(storage, (&status, proposal_id)) {
+        PROPOSALS_BY_STATUS.remove(storage, (&status, proposal_id));
+        PROPOSAL_STATUS_COUNTS.update(storage, &status, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default().saturating_sub(1))
+        })?;
     }
+
+    Ok(())
 }
 
 /// Expose available contract queries.
@@ -601,78 +2312,503 @@ fn update_ibc_proposal_status(
 /// ## Queries
 /// * **QueryMsg::Config {}** Returns core contract settings stored in the [`Config`] structure.
 ///
-/// * **QueryMsg::Proposals { start, limit }** Returns a [`ProposalListResponse`] according to the specified input parameters.
+/// * **QueryMsg::Proposals { start, start_after, limit }** Returns a [`ProposalListResponse`] according to the specified input parameters.
+///
+/// * **QueryMsg::LatestProposals { limit }** Returns a [`ProposalListResponse`] with the most recent proposals, newest first.
 ///
 /// * **QueryMsg::Proposal { proposal_id }** Returns a [`Proposal`] according to the specified `proposal_id`.
 ///
+/// * **QueryMsg::ProposalContent { proposal_id }** Returns the [`ProposalContent`] of the specified proposal.
+///
 /// * **QueryMsg::ProposalVotes { proposal_id }** Returns proposal vote counts that are stored in the [`ProposalVotesResponse`] structure.
 ///
-/// * **QueryMsg::UserVotingPower { user, proposal_id }** Returns user voting power for a specific proposal.
+/// * **QueryMsg::UserVotingPower { user, proposal_id, at }** Returns user voting power for a specific proposal or point in time.
+///
+/// * **QueryMsg::TotalVotingPower { proposal_id, at }** Returns total voting power for a specific proposal or point in time.
+///
+/// * **QueryMsg::FinalizableProposals { limit }** Returns ids of active proposals whose voting period has ended.
+///
+/// * **QueryMsg::UserVote { proposal_id, user }** Returns the vote recorded for a user on a specific proposal.
+///
+/// * **QueryMsg::CheckTally { proposal_id }** Checks that the proposal tallies equal the sum of the recorded votes.
+///
+/// * **QueryMsg::VoteProof { proposal_id, voter }** Returns a merkle proof of a vote of an ended proposal.
 ///
-/// * **QueryMsg::TotalVotingPower { proposal_id }** Returns total voting power for a specific proposal.
+/// * **QueryMsg::ProposalsByStatus { status, start_after, limit }** Returns proposals with the specified status.
+///
+/// * **QueryMsg::ProposalsBySubmitter { submitter, start_after, limit }** Returns proposals created by the specified submitter.
+///
+/// * **QueryMsg::ProposalHooks {}** Returns the contracts notified about proposal status changes.
+///
+/// * **QueryMsg::VoteListeners {}** Returns the contracts notified about every vote.
+///
+/// * **QueryMsg::VoteNonce { voter }** Returns the nonce the next signed vote of the voter must use.
+///
+/// * **QueryMsg::VoteOperators { grantor }** Returns the operators allowed to vote on behalf of the grantor.
+///
+/// * **QueryMsg::ProposalCounts {}** Returns the number of stored proposals with every status.
+///
+/// * **QueryMsg::MultiChoiceTally { proposal_id }** Returns the tally of a multi-choice proposal.
+///
+/// * **QueryMsg::TwoPhaseProposal { proposal_id }** Returns both rounds of a two-phase proposal.
+///
+/// * **QueryMsg::ProposalBundle { bundle_id }** Returns the proposals of a bundle.
+///
+/// * **QueryMsg::ProposalTargets { proposal_id }** Returns the contracts and bank recipients a proposal's messages touch.
+///
+/// * **QueryMsg::ExportState { section, start_after, limit }** Returns a page of raw storage entries from the specified state section.
+///
+/// * **QueryMsg::PausedUntil {}** Returns the timestamp until which new proposals and votes are paused.
 ///
 /// * **QueryMsg::ProposalVoters {
 ///             proposal_id,
 ///             vote_option,
 ///             start,
+///             start_after,
 ///             limit,
 ///         }** Returns a vector of proposal voters according to the specified input parameters.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
-        QueryMsg::Proposals { start, limit } => to_binary(&query_proposals(deps, start, limit)?),
+        QueryMsg::Proposals {
+            start,
+            start_after,
+            limit,
+        } => to_binary(&query_proposals(deps, start, start_after, limit)?),
+        QueryMsg::LatestProposals { limit } => to_binary(&query_latest_proposals(deps, limit)?),
         QueryMsg::Proposal { proposal_id } => {
             to_binary(&PROPOSALS.load(deps.storage, proposal_id)?)
         }
+        QueryMsg::ProposalContent { proposal_id } => {
+            to_binary(&PROPOSAL_CONTENT.load(deps.storage, proposal_id)?)
+        }
         QueryMsg::ProposalVotes { proposal_id } => {
             to_binary(&query_proposal_votes(deps, proposal_id)?)
         }
-        QueryMsg::UserVotingPower { user, proposal_id } => {
-            let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+        QueryMsg::UserVotingPower {
+            user,
+            proposal_id,
+            at,
+        } => {
+            let at = voting_power_point(deps, proposal_id, at)?;
 
             deps.api.addr_validate(&user)?;
 
-            to_binary(&calc_voting_power(deps, user, &proposal)?)
+            to_binary(&calc_voting_power_at_point(deps, user, &at)?)
         }
-        QueryMsg::TotalVotingPower { proposal_id } => {
-            let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
-            to_binary(&calc_total_voting_power_at(deps, &proposal)?)
+        QueryMsg::TotalVotingPower { proposal_id, at } => {
+            let at = voting_power_point(deps, proposal_id, at)?;
+            to_binary(&calc_total_voting_power_at_point(deps, &at)?)
         }
+        QueryMsg::FinalizableProposals { limit } => {
+            to_binary(&query_finalizable_proposals(deps, env, limit)?)
+        }
+        QueryMsg::UserVote { proposal_id, user } => {
+            let user = deps.api.addr_validate(&user)?;
+            to_binary(&VOTES.may_load(deps.storage, (proposal_id, &user))?)
+        }
+        QueryMsg::CheckTally { proposal_id } => to_binary(&query_check_tally(deps, proposal_id)?),
+        QueryMsg::VoteProof { proposal_id, voter } => {
+            to_binary(&query_vote_proof(deps, proposal_id, voter)?)
+        }
+        QueryMsg::ProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => {
+            let status = status.to_string();
+            to_binary(&query_indexed_proposals(
+                deps,
+                PROPOSALS_BY_STATUS.prefix(&status),
+                start_after,
+                limit,
+            )?)
+        }
+        QueryMsg::ProposalsBySubmitter {
+            submitter,
+            start_after,
+            limit,
+        } => {
+            let submitter = deps.api.addr_validate(&submitter)?;
+            to_binary(&query_indexed_proposals(
+                deps,
+                PROPOSALS_BY_SUBMITTER.prefix(&submitter),
+                start_after,
+                limit,
+            )?)
+        }
+        QueryMsg::ProposalHooks {} => to_binary(
+            &PROPOSAL_HOOKS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::MultiChoiceTally { proposal_id } => {
+            to_binary(&query_multi_choice_tally(deps, proposal_id)?)
+        }
+        QueryMsg::TwoPhaseProposal { proposal_id } => {
+            to_binary(&query_two_phase_proposal(deps, proposal_id)?)
+        }
+        QueryMsg::ProposalBundle { bundle_id } => {
+            to_binary(&BUNDLES.load(deps.storage, bundle_id)?)
+        }
+        QueryMsg::ProposalTargets { proposal_id } => {
+            to_binary(&query_proposal_targets(deps, proposal_id)?)
+        }
+        QueryMsg::VoteOperators { grantor } => {
+            let grantor = deps.api.addr_validate(&grantor)?;
+            to_binary(
+                &VOTE_OPERATORS
+                    .prefix(&grantor)
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|grant| {
+                        let (operator, expires) = grant?;
+                        Ok(VoteOperatorGrant { operator, expires })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?,
+            )
+        }
+        QueryMsg::VoteNonce { voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_binary(
+                &VOTE_NONCES
+                    .may_load(deps.storage, &voter)?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::VoteListeners {} => to_binary(
+            &VOTE_LISTENERS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::ProposalCounts {} => to_binary(&query_proposal_counts(deps)?),
+        QueryMsg::ExportState {
+            section,
+            start_after,
+            limit,
+        } => {
+            let entries = match section {
+                StateSection::Proposals => export_map(deps, &PROPOSALS, start_after, limit),
+                StateSection::ProposalContent => {
+                    export_map(deps, &PROPOSAL_CONTENT, start_after, limit)
+                }
+                StateSection::Votes => export_map(deps, &VOTES, start_after, limit),
+                StateSection::ConfigHistory => {
+                    export_map(deps, &CONFIG_HISTORY, start_after, limit)
+                }
+            }?;
+            to_binary(&entries)
+        }
+        QueryMsg::PausedUntil {} => to_binary(&PAUSED_UNTIL.may_load(deps.storage)?),
         QueryMsg::ProposalVoters {
             proposal_id,
             vote_option,
             start,
+            start_after,
             limit,
         } => to_binary(&query_proposal_voters(
             deps,
             proposal_id,
             vote_option,
             start,
+            start_after,
             limit,
         )?),
     }
 }
 
+/// Returns both rounds of the two-phase proposal the specified proposal belongs to.
+///
+/// * **proposal_id** id of either round.
+pub fn query_two_phase_proposal(
+    deps: Deps,
+    proposal_id: u64,
+) -> StdResult<TwoPhaseProposalResponse> {
+    let parent_id = SIGNALING_ROUNDS
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or(proposal_id);
+
+    let binding_id = TWO_PHASE_PROPOSALS
+        .may_load(deps.storage, parent_id)?
+        .ok_or_else(|| StdError::generic_err("Not a two-phase proposal"))?;
+
+    Ok(TwoPhaseProposalResponse {
+        parent_id,
+        signaling: PROPOSALS.load(deps.storage, parent_id)?,
+        // The binding round may already be removed
+        binding: binding_id
+            .map(|binding_id| PROPOSALS.may_load(deps.storage, binding_id))
+            .transpose()?
+            .flatten(),
+    })
+}
+
+/// Returns the distinct contracts and bank recipients the messages of a proposal touch.
+///
+/// * **proposal_id** proposal for which the targets are returned.
+pub fn query_proposal_targets(deps: Deps, proposal_id: u64) -> StdResult<ProposalTargetsResponse> {
+    let content = PROPOSAL_CONTENT.load(deps.storage, proposal_id)?;
+
+    let mut messages = content.messages.unwrap_or_default();
+    if let Some(options) = PROPOSAL_OPTIONS.may_load(deps.storage, proposal_id)? {
+        messages.extend(
+            options
+                .into_iter()
+                .flat_map(|option| option.messages.unwrap_or_default()),
+        );
+    }
+    if let Some(binding_round) = BINDING_ROUNDS.may_load(deps.storage, proposal_id)? {
+        messages.extend(binding_round.content.messages.unwrap_or_default());
+    }
+
+    let mut contracts = BTreeSet::new();
+    let mut bank_recipients = BTreeSet::new();
+    for message in messages {
+        match message {
+            CosmosMsg::Wasm(
+                WasmMsg::Execute { contract_addr, .. }
+                | WasmMsg::Migrate { contract_addr, .. }
+                | WasmMsg::UpdateAdmin { contract_addr, .. }
+                | WasmMsg::ClearAdmin { contract_addr, .. },
+            ) => {
+                contracts.insert(contract_addr);
+            }
+            CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => {
+                bank_recipients.insert(to_address);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ProposalTargetsResponse {
+        contracts: contracts.into_iter().collect(),
+        bank_recipients: bank_recipients.into_iter().collect(),
+    })
+}
+
 /// Returns the current proposal list.
+///
+/// * **start** deprecated inclusive start, only used when `start_after` is not set.
+///
+/// * **start_after** id after which to start reading.
 pub fn query_proposals(
     deps: Deps,
     start: Option<u64>,
+    start_after: Option<u64>,
     limit: Option<u32>,
 ) -> StdResult<ProposalListResponse> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
 
+    // `start` is inclusive, so the page starts right after the previous proposal
+    let start_after = start_after.or_else(|| start.and_then(|start| start.checked_sub(1)));
+
+    let proposal_list = paginate_map(
+        &PROPOSALS,
+        deps.storage,
+        start_after,
+        limit,
+        Order::Ascending,
+    )?
+    .into_iter()
+    .map(|(_, proposal)| proposal)
+    .collect();
+
+    Ok(ProposalListResponse {
+        proposal_count,
+        proposal_list,
+    })
+}
+
+/// Returns the voting power of every option of a multi-choice proposal.
+pub fn query_multi_choice_tally(
+    deps: Deps,
+    proposal_id: u64,
+) -> StdResult<MultiChoiceTallyResponse> {
+    let options = PROPOSAL_OPTIONS.load(deps.storage, proposal_id)?;
+    let tallies = OPTION_TALLIES.load(deps.storage, proposal_id)?;
+
+    Ok(MultiChoiceTallyResponse {
+        options: options
+            .into_iter()
+            .zip(tallies)
+            .map(|(option, power)| OptionTally {
+                label: option.label,
+                power,
+            })
+            .collect(),
+        winning_option: WINNING_OPTIONS.may_load(deps.storage, proposal_id)?,
+    })
+}
+
+/// Returns ids of active proposals which can be ended at the current block, oldest first.
+pub fn query_finalizable_proposals(
+    deps: Deps,
+    env: Env,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // A proposal can be ended once the current height is strictly greater than its end block
+    PROPOSALS_BY_END_BLOCK
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((env.block.height, 0))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| key.map(|(_, proposal_id)| proposal_id))
+        .collect()
+}
+
+/// Sums the recorded votes of a proposal and compares them with the proposal tallies.
+/// Votes cast before V1.6.0 weren't recorded, so older proposals are reported as inconsistent.
+pub fn query_check_tally(deps: Deps, proposal_id: u64) -> StdResult<TallyCheckResponse> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let option_powers = OPTION_TALLIES
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default();
+
+    let mut recorded_for_power = Uint128::zero();
+    let mut recorded_against_power = Uint128::zero();
+    let mut recorded_option_powers = vec![Uint128::zero(); option_powers.len()];
+
+    for vote in VOTES
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, vote) = vote?;
+        match vote.option {
+            ProposalVoteOption::For => recorded_for_power += vote.power,
+            ProposalVoteOption::Against => recorded_against_power += vote.power,
+            ProposalVoteOption::Choice(option) => {
+                if let Some(power) = recorded_option_powers.get_mut(option as usize) {
+                    *power += vote.power;
+                }
+            }
+        }
+    }
+
+    Ok(TallyCheckResponse {
+        proposal_id,
+        for_power: proposal.for_power,
+        against_power: proposal.against_power,
+        recorded_for_power,
+        recorded_against_power,
+        is_consistent: proposal.for_power == recorded_for_power
+            && proposal.against_power == recorded_against_power
+            && option_powers == recorded_option_powers,
+        option_powers,
+        recorded_option_powers,
+    })
+}
+
+/// Reads the merkle proof of a vote from the vote tree committed after the proposal ended.
+pub fn query_vote_proof(
+    deps: Deps,
+    proposal_id: u64,
+    voter: String,
+) -> StdResult<VoteProofResponse> {
+    let voter = deps.api.addr_validate(&voter)?;
+
+    let root = VOTE_ROOTS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or_else(|| StdError::generic_err("Proposal votes aren't committed!"))?;
+    let vote = VOTES.load(deps.storage, (proposal_id, &voter))?;
+    let tree = VOTE_TREES.load(deps.storage, proposal_id)?;
+    let mut index = VOTE_LEAF_INDEXES.load(deps.storage, (proposal_id, &voter))?;
+
+    let mut proof = vec![];
+    for level in 0..tree.level {
+        if let Some(sibling) =
+            VOTE_TREE_NODES.may_load(deps.storage, (proposal_id, level, index ^ 1))?
+        {
+            proof.push(sibling);
+        }
+        index /= 2;
+    }
+
+    Ok(VoteProofResponse { root, vote, proof })
+}
+
+/// Loads the proposals referenced by a page of a proposal index prefix.
+fn query_indexed_proposals(
+    deps: Deps,
+    index: Prefix<u64, (), u64>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Proposal>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start.map(Bound::inclusive);
 
-    let proposal_list = PROPOSALS
-        .range(deps.storage, start, None, Order::Ascending)
+    index
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
         .take(limit)
-        .map(|item| {
-            let (_, v) = item?;
-            Ok(v)
+        .map(|proposal_id| PROPOSALS.load(deps.storage, proposal_id?))
+        .collect()
+}
+
+/// Returns the number of stored proposals with every status from the maintained counters.
+pub fn query_proposal_counts(deps: Deps) -> StdResult<ProposalCountsResponse> {
+    let count = |status: ProposalStatus| -> StdResult<u64> {
+        Ok(PROPOSAL_STATUS_COUNTS
+            .may_load(deps.storage, &status.to_string())?
+            .unwrap_or_default())
+    };
+
+    Ok(ProposalCountsResponse {
+        active: count(ProposalStatus::Active)?,
+        passed: count(ProposalStatus::Passed)?,
+        rejected: count(ProposalStatus::Rejected)?,
+        in_progress: count(ProposalStatus::InProgress)?,
+        failed: count(ProposalStatus::Failed)?,
+        executed: count(ProposalStatus::Executed)?,
+        expired: count(ProposalStatus::Expired)?,
+    })
+}
+
+/// Returns a page of raw entries from a [`Map`] so indexers can sync state without replaying events.
+fn export_map<'a, K, T>(
+    deps: Deps,
+    map: &Map<'a, K, T>,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RawStateEntry>>
+where
+    K: PrimaryKey<'a>,
+    T: Serialize + DeserializeOwned,
+{
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    map.range_raw(
+        deps.storage,
+        start_after.map(|key| Bound::ExclusiveRaw(key.into())),
+        None,
+        Order::Ascending,
+    )
+    .take(limit)
+    .map(|item| {
+        let (key, value) = item?;
+        Ok(RawStateEntry {
+            key: key.into(),
+            value: to_binary(&value)?,
         })
-        .collect::<StdResult<Vec<_>>>()?;
+    })
+    .collect()
+}
+
+/// Returns the most recent proposals in descending order by id.
+pub fn query_latest_proposals(deps: Deps, limit: Option<u32>) -> StdResult<ProposalListResponse> {
+    let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
+
+    let proposal_list = paginate_map(&PROPOSALS, deps.storage, None, limit, Order::Descending)?
+        .into_iter()
+        .map(|(_, proposal)| proposal)
+        .collect();
 
     Ok(ProposalListResponse {
         proposal_count,
@@ -681,26 +2817,65 @@ pub fn query_proposals(
 }
 
 /// Returns proposal's voters.
+///
+/// * **start** deprecated position in the voter list, only used when `start_after` is not set.
+///
+/// * **start_after** voter after which to start reading. Nothing is returned if the address didn't vote with `vote_option`.
 pub fn query_proposal_voters(
     deps: Deps,
     proposal_id: u64,
     vote_option: ProposalVoteOption,
     start: Option<u64>,
+    start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Vec<Addr>> {
     let limit = limit.unwrap_or(DEFAULT_VOTERS_LIMIT).min(MAX_VOTERS_LIMIT);
-    let start = start.unwrap_or_default();
 
     let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
 
     let voters = match vote_option {
         ProposalVoteOption::For => proposal.for_voters,
         ProposalVoteOption::Against => proposal.against_voters,
+        // Multi-choice voters are only recorded among the votes, in the order of their addresses
+        ProposalVoteOption::Choice(_) => {
+            let skip = match start_after {
+                Some(_) => 0,
+                None => start.unwrap_or_default() as usize,
+            };
+            let start_after = start_after
+                .map(|voter| deps.api.addr_validate(&voter))
+                .transpose()?;
+
+            return VOTES
+                .prefix(proposal_id)
+                .range(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .filter(|vote| !matches!(vote, Ok((_, vote)) if vote.option != vote_option))
+                .skip(skip)
+                .take(limit as usize)
+                .map(|vote| vote.map(|(voter, _)| voter))
+                .collect();
+        }
+    };
+
+    let start = match start_after {
+        Some(start_after) => {
+            let start_after = deps.api.addr_validate(&start_after)?;
+            voters
+                .iter()
+                .position(|voter| *voter == start_after)
+                .map_or(voters.len(), |position| position + 1)
+        }
+        None => start.unwrap_or_default() as usize,
     };
 
     Ok(voters
         .iter()
-        .skip(start as usize)
+        .skip(start)
         .take(limit as usize)
         .cloned()
         .collect())
@@ -717,57 +2892,127 @@ pub fn query_proposal_votes(deps: Deps, proposal_id: u64) -> StdResult<ProposalV
     })
 }
 
+/// Resolves the point voting power is computed at: the override if set, otherwise the proposal start.
+fn voting_power_point(
+    deps: Deps,
+    proposal_id: Option<u64>,
+    at: Option<VotingPowerPoint>,
+) -> StdResult<VotingPowerPoint> {
+    match (at, proposal_id) {
+        (Some(at), _) => Ok(at),
+        (None, Some(proposal_id)) => Ok(PROPOSALS
+            .load(deps.storage, proposal_id)?
+            .voting_power_point()),
+        (None, None) => Err(StdError::generic_err(
+            "Either proposal_id or at must be specified!",
+        )),
+    }
+}
+
 /// Calculates an address' voting power at the specified block.
 ///
 /// * **sender** address whose voting power we calculate.
 ///
 /// * **proposal** proposal for which we want to compute the `sender` (voter) voting power.
 pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> StdResult<Uint128> {
+    calc_voting_power_at_point(deps, sender, &proposal.voting_power_point())
+}
+
+/// Calculates an address' voting power at an arbitrary point in time.
+///
+/// * **sender** address whose voting power we calculate.
+///
+/// * **at** block height and time at which the voting power is computed.
+pub fn calc_voting_power_at_point(
+    deps: Deps,
+    sender: String,
+    at: &VotingPowerPoint,
+) -> StdResult<Uint128> {
     let config = CONFIG.load(deps.storage)?;
 
-    // This is the address' xASTRO balance at the previous block (proposal.start_block - 1).
+    // Excluded addresses don't count towards the total voting power, so they can't vote either
+    if config
+        .excluded_voters
+        .iter()
+        .any(|voter| voter.as_str() == sender)
+    {
+        return Ok(Uint128::zero());
+    }
+
+    calc_address_voting_power(deps, &config, sender, at, true)
+}
+
+/// Calculates the voting power of an address at the specified point, ignoring voting exclusions.
+///
+/// * **time_weighted** whether xASTRO is scaled by the time it has been held for.
+fn calc_address_voting_power(
+    deps: Deps,
+    config: &Config,
+    sender: String,
+    at: &VotingPowerPoint,
+    time_weighted: bool,
+) -> StdResult<Uint128> {
+    // This is the address' xASTRO balance at the previous block (at.height - 1).
     // We use the previous block because it always has an up-to-date checkpoint.
     // BalanceAt will always return the balance information in the previous block,
-    // so we don't subtract one block from proposal.start_block.
+    // so we don't subtract one block from at.height.
     let xastro_amount: BalanceResponse = deps.querier.query_wasm_smart(
-        config.xastro_token_addr,
+        &config.xastro_token_addr,
         &XAstroTokenQueryMsg::BalanceAt {
             address: sender.clone(),
-            block: proposal.start_block,
+            block: at.height,
         },
     )?;
 
     let mut total = xastro_amount.balance;
 
-    let locked_amount: AllocationResponse = deps.querier.query_wasm_smart(
-        config.builder_unlock_addr,
-        &BuilderUnlockQueryMsg::Allocation {
+    // Long-term holders get more weight than recently acquired xASTRO
+    if let Some(weighting) = config
+        .xastro_time_weighting
+        .as_ref()
+        .filter(|_| time_weighted)
+    {
+        if !total.is_zero() {
+            let held_amount: BalanceResponse = deps.querier.query_wasm_smart(
+                &config.xastro_token_addr,
+                &XAstroTokenQueryMsg::BalanceAt {
+                    address: sender.clone(),
+                    block: at.height.saturating_sub(weighting.holding_blocks),
+                },
+            )?;
+            total = weighting.weighted_balance(total, held_amount.balance);
+        }
+    }
+
+    // Locked builder allocations at the specified block including the voting power delegated to the sender
+    let locked_amount: Uint128 = deps.querier.query_wasm_smart(
+        &config.builder_unlock_addr,
+        &BuilderUnlockQueryMsg::VotingPower {
             account: sender.clone(),
+            height: Some(at.height),
         },
     )?;
 
-    if !locked_amount.params.amount.is_zero() {
-        total = total
-            .checked_add(locked_amount.params.amount)?
-            .checked_sub(locked_amount.status.astro_withdrawn)?;
+    let locked_amount = locked_amount * config.builder_power_coefficient;
+    if !locked_amount.is_zero() {
+        total = total.checked_add(locked_amount)?;
     }
 
-    if let Some(vxastro_token_addr) = config.vxastro_token_addr {
+    if let Some(vxastro_token_addr) = &config.vxastro_token_addr {
         let vxastro_amount: Uint128 =
-            if let Some(voting_escrow_delegator_addr) = config.voting_escrow_delegator_addr {
-                deps.querier.query_wasm_smart(
-                    voting_escrow_delegator_addr,
-                    &AdjustedBalance {
-                        account: sender.clone(),
-                        timestamp: Some(proposal.start_time - WEEK),
-                    },
+            if let Some(voting_escrow_delegator_addr) = &config.voting_escrow_delegator_addr {
+                get_adjusted_balance(
+                    &deps.querier,
+                    voting_escrow_delegator_addr.to_string(),
+                    sender.clone(),
+                    Some(at.time.saturating_sub(WEEK)),
                 )?
             } else {
                 let res: VotingPowerResponse = deps.querier.query_wasm_smart(
-                    &vxastro_token_addr,
+                    vxastro_token_addr,
                     &VotingEscrowQueryMsg::UserVotingPowerAt {
                         user: sender.clone(),
-                        time: proposal.start_time - WEEK,
+                        time: at.time.saturating_sub(WEEK),
                     },
                 )?;
 
@@ -782,7 +3027,7 @@ pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> Std
             vxastro_token_addr,
             &VotingEscrowQueryMsg::UserDepositAtHeight {
                 user: sender,
-                height: proposal.start_block,
+                height: at.height,
             },
         )?;
 
@@ -796,32 +3041,41 @@ pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> Std
 ///
 /// * **proposal** proposal for which we calculate the total voting power.
 pub fn calc_total_voting_power_at(deps: Deps, proposal: &Proposal) -> StdResult<Uint128> {
+    calc_total_voting_power_at_point(deps, &proposal.voting_power_point())
+}
+
+/// Calculates the total voting power at an arbitrary point in time.
+///
+/// * **at** block height and time at which the total voting power is computed.
+pub fn calc_total_voting_power_at_point(deps: Deps, at: &VotingPowerPoint) -> StdResult<Uint128> {
     let config = CONFIG.load(deps.storage)?;
 
-    // This is the address' xASTRO balance at the previous block (proposal.start_block - 1).
+    // This is the xASTRO total supply at the previous block (at.height - 1).
     // We use the previous block because it always has an up-to-date checkpoint.
     let mut total: Uint128 = deps.querier.query_wasm_smart(
         &config.xastro_token_addr,
         &XAstroTokenQueryMsg::TotalSupplyAt {
-            block: proposal.start_block - 1,
+            block: at.height.saturating_sub(1),
         },
     )?;
 
     // Total amount of ASTRO locked in the initial builder's unlock schedule
-    let builder_state: StateResponse = deps
-        .querier
-        .query_wasm_smart(config.builder_unlock_addr, &BuilderUnlockQueryMsg::State {})?;
+    let builder_state: StateResponse = deps.querier.query_wasm_smart(
+        &config.builder_unlock_addr,
+        &BuilderUnlockQueryMsg::State {},
+    )?;
 
-    if !builder_state.remaining_astro_tokens.is_zero() {
-        total = total.checked_add(builder_state.remaining_astro_tokens)?;
+    let builder_power = builder_state.remaining_astro_tokens * config.builder_power_coefficient;
+    if !builder_power.is_zero() {
+        total = total.checked_add(builder_power)?;
     }
 
-    if let Some(vxastro_token_addr) = config.vxastro_token_addr {
+    if let Some(vxastro_token_addr) = &config.vxastro_token_addr {
         // Total vxASTRO voting power
         let vxastro: VotingPowerResponse = deps.querier.query_wasm_smart(
             vxastro_token_addr,
             &VotingEscrowQueryMsg::TotalVotingPowerAt {
-                time: proposal.start_time - WEEK,
+                time: at.time.saturating_sub(WEEK),
             },
         )?;
         if !vxastro.voting_power.is_zero() {
@@ -829,6 +3083,13 @@ pub fn calc_total_voting_power_at(deps: Deps, proposal: &Proposal) -> StdResult<
         }
     }
 
+    // Unvoteable supply would otherwise make the quorum harder to reach.
+    // The total isn't time-weighted, so the full balances are subtracted.
+    for voter in &config.excluded_voters {
+        let excluded = calc_address_voting_power(deps, &config, voter.to_string(), at, false)?;
+        total = total.saturating_sub(excluded);
+    }
+
     Ok(total)
 }
 
@@ -854,27 +3115,61 @@ pub fn check_controller_supports_channel(
         .ok_or_else(|| ContractError::InvalidChannel(given_channel.to_string()))
 }
 
+/// Checks that the number of proposal messages fits into the message limit the controller has
+/// for the channel. The controller checks the limit again when the proposal is executed,
+/// as the channel parameters may change while the proposal is being voted on.
+pub fn check_controller_max_messages(
+    querier: QuerierWrapper,
+    ibc_controller: &Addr,
+    channel_id: &String,
+    messages_count: usize,
+) -> Result<(), ContractError> {
+    let params: Option<ChannelParams> = querier.query_wasm_smart(
+        ibc_controller,
+        &ControllerQueryMsg::ChannelParams {
+            channel_id: channel_id.to_string(),
+        },
+    )?;
+
+    match params.and_then(|params| params.max_messages) {
+        Some(max) if messages_count > max as usize => Err(ContractError::TooManyIbcMessages {
+            channel_id: channel_id.to_string(),
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
+
 /// Manages contract migration.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    let contract_version = get_contract_version(deps.storage)?;
-
-    match contract_version.contract.as_ref() {
-        "astro-assembly" => match contract_version.version.as_ref() {
-            "1.3.0" => {
-                let cfg = migrate_config_to_140(deps.branch(), msg)?;
-                migrate_proposals_to_v140(deps.branch(), &cfg)?;
-            }
-            _ => return Err(ContractError::MigrationError {}),
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version =
+        load_migratable_version(deps.storage, CONTRACT_NAME, &["1.3.0", "1.5.0"])?
+            .ok_or(ContractError::MigrationError {})?;
+
+    let set_deadlines = contract_version.version == "1.3.0";
+    if set_deadlines {
+        migrate_config_to_140(deps.branch(), msg)?;
+    } else {
+        migrate_config_to_160(deps.branch())?;
+    }
+
+    // Proposals are migrated in batches. The ones left are migrated with ExecuteMsg::MigrateProposals
+    PROPOSALS_MIGRATION.save(
+        deps.storage,
+        &ProposalsMigration {
+            set_deadlines,
+            last_proposal_id: None,
         },
-        _ => return Err(ContractError::MigrationError {}),
-    };
+    )?;
+    let finished = migrate_proposals(deps.storage, MAX_LIMIT)?;
+
+    CONFIG_HISTORY.save(deps.storage, env.block.height, &CONFIG.load(deps.storage)?)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::new()
-        .add_attribute("previous_contract_name", &contract_version.contract)
-        .add_attribute("previous_contract_version", &contract_version.version)
-        .add_attribute("new_contract_name", CONTRACT_NAME)
-        .add_attribute("new_contract_version", CONTRACT_VERSION))
+    Ok(
+        migration_response(&contract_version, CONTRACT_NAME, CONTRACT_VERSION)
+            .add_attribute("proposals_migrated", finished.to_string()),
+    )
 }
@@ -1,4 +1,8 @@
-use astroport_governance::assembly::ProposalStatus;
+use astroport_governance::assembly::{
+    ProposalStatus, MAX_BUNDLE_PROPOSALS, MAX_OPTION_LABEL_LENGTH, MAX_PROPOSAL_OPTIONS,
+    MAX_RANKED_OPTIONS, MAX_VOTE_LISTENERS,
+};
+use astroport_governance::errors::CommonError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -8,6 +12,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -20,6 +27,27 @@ pub enum ContractError {
     #[error("User already voted!")]
     UserAlreadyVoted {},
 
+    #[error("Proposal isn't a multi-choice proposal!")]
+    NotMultiChoiceProposal {},
+
+    #[error("Multi-choice proposals can only be voted for one of their options!")]
+    MultiChoiceProposal {},
+
+    #[error("Option {0} doesn't exist!")]
+    InvalidProposalOption(u32),
+
+    #[error("Proposal doesn't accept ranked ballots!")]
+    NotRankedChoiceProposal {},
+
+    #[error("A ranking must list distinct options!")]
+    InvalidRanking {},
+
+    #[error("Ranked-choice proposals can't have more than {max} options!", max = MAX_RANKED_OPTIONS)]
+    TooManyRankedOptions {},
+
+    #[error("A multi-choice proposal must have between 2 and {max_options} options with labels of up to {max_label} characters!", max_options = MAX_PROPOSAL_OPTIONS, max_label = MAX_OPTION_LABEL_LENGTH)]
+    InvalidProposalOptions {},
+
     #[error("You don't have any voting power!")]
     NoVotingPower {},
 
@@ -32,12 +60,77 @@ pub enum ContractError {
     #[error("Insufficient token deposit!")]
     InsufficientDeposit {},
 
+    #[error("Forum thread {thread} is already referenced by active proposal {proposal_id}")]
+    ForumThreadInUse { thread: String, proposal_id: u64 },
+
+    #[error("Bundle {bundle_id} belongs to another submitter!")]
+    BundleOwnedByOther { bundle_id: u64 },
+
+    #[error("A bundle can't have more than {max} proposals!", max = MAX_BUNDLE_PROPOSALS)]
+    BundleFull {},
+
+    #[error("Bundled proposals can't be executed over IBC!")]
+    BundledIbcProposal {},
+
+    #[error("A forward route requires an IBC channel!")]
+    ForwardWithoutIbcChannel {},
+
+    #[error("Proposals are being migrated! Finish the migration with migrate_proposals first.")]
+    ProposalsMigrationPending {},
+
+    #[error("All proposals are already migrated!")]
+    ProposalsAlreadyMigrated {},
+
+    #[error("{operator} isn't allowed to vote on behalf of {grantor}!")]
+    NoVoteOperatorGrant { operator: String, grantor: String },
+
+    #[error("Grant expiration must be in the future!")]
+    InvalidGrantExpiration {},
+
+    #[error("Invalid vote signature!")]
+    InvalidVoteSignature {},
+
+    #[error("Invalid nonce, expected {expected}!")]
+    InvalidVoteNonce { expected: u64 },
+
+    #[error(
+        "Proposal belongs to bundle {bundle_id} and can only be executed with execute_bundle!"
+    )]
+    BundledProposal { bundle_id: u64 },
+
+    #[error("Proposals don't form a complete bundle!")]
+    IncompleteBundle {},
+
     #[error("Proposal not passed!")]
     ProposalNotPassed {},
 
+    #[error("Proposals can only be vetoed before their execution delay ends!")]
+    VetoPeriodEnded {},
+
+    #[error("Proposals that change the emergency committee can't be vetoed!")]
+    CommitteeChangeVeto {},
+
     #[error("Proposal not completed!")]
     ProposalNotCompleted {},
 
+    #[error("New proposals and votes are paused until {paused_until}!")]
+    Paused { paused_until: u64 },
+
+    #[error("New proposals and votes are paused by the chain!")]
+    PausedByChain {},
+
+    #[error("The Assembly isn't paused!")]
+    NotPaused {},
+
+    #[error("The Assembly can't be paused again before {available_at}!")]
+    PauseCooldown { available_at: u64 },
+
+    #[error("Proposal {0} has no vote tree to build!")]
+    NoVoteTree(u64),
+
+    #[error("Vote tree of proposal {0} is already built!")]
+    VoteTreeBuilt(u64),
+
     #[error("Proposal delay not ended!")]
     ProposalDelayNotEnded {},
 
@@ -56,6 +149,9 @@ pub enum ContractError {
     #[error("IBC controller is not set")]
     MissingIBCController {},
 
+    #[error("Channel {channel_id} accepts at most {max} messages per proposal")]
+    TooManyIbcMessages { channel_id: String, max: u32 },
+
     #[error(
         "Failed to process callback from IBC controller as proposal {0} is not in \"{}\" state",
         ProposalStatus::InProgress
@@ -67,6 +163,21 @@ pub enum ContractError {
 
     #[error("Sender is not an IBC controller installed in the assembly")]
     InvalidIBCController {},
+
+    #[error("Proposal hook {0} is already registered")]
+    HookAlreadyRegistered(String),
+
+    #[error("Proposal hook {0} is not registered")]
+    HookNotRegistered(String),
+
+    #[error("Vote listener {0} is already registered")]
+    ListenerAlreadyRegistered(String),
+
+    #[error("Vote listener {0} is not registered")]
+    ListenerNotRegistered(String),
+
+    #[error("Can't register more than {max} vote listeners", max = MAX_VOTE_LISTENERS)]
+    TooManyVoteListeners {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,8 +1,10 @@
 pub mod contract;
 pub mod error;
+pub mod merkle;
 pub mod state;
 
 mod migration;
+mod signature;
 
 // During development this import could be replaced with another astroport version.
 // However, in production, the astroport version should be the same for all contracts.
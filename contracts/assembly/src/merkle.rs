@@ -0,0 +1,52 @@
+use astroport_governance::assembly::{ProposalVote, ProposalVoteOption};
+use cosmwasm_std::{Addr, Binary};
+use sha2::{Digest, Sha256};
+
+/// Prefix of leaf hashes. Keeps a leaf from being passed off as an inner node
+const LEAF_PREFIX: u8 = 0;
+/// Prefix of inner node hashes
+const NODE_PREFIX: u8 = 1;
+
+/// Root committed for a proposal without votes
+pub const EMPTY_VOTE_ROOT: [u8; 32] = [0; 32];
+
+/// Hashes a single vote: sha256(0x00 || voter || option || power), where option is one byte
+/// (0 for `For`, 1 for `Against`) or 2 followed by the 4 byte big-endian index of a multi-choice
+/// option, and power is a 16 byte big-endian integer.
+pub fn vote_leaf(voter: &Addr, vote: &ProposalVote) -> [u8; 32] {
+    let option = match vote.option {
+        ProposalVoteOption::For => vec![0u8],
+        ProposalVoteOption::Against => vec![1u8],
+        ProposalVoteOption::Choice(index) => [&[2u8][..], &index.to_be_bytes()].concat(),
+    };
+
+    Sha256::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(voter.as_bytes())
+        .chain_update(option)
+        .chain_update(vote.power.u128().to_be_bytes())
+        .finalize()
+        .into()
+}
+
+/// Hashes a pair of nodes. The pair is sorted first, so proofs don't need to carry positions.
+pub fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    Sha256::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Checks a membership proof returned by
+/// [`QueryMsg::VoteProof`](astroport_governance::assembly::QueryMsg::VoteProof).
+pub fn verify_vote_proof(root: &[u8], voter: &Addr, vote: &ProposalVote, proof: &[Binary]) -> bool {
+    let computed = proof.iter().fold(vote_leaf(voter, vote), |node, sibling| {
+        hash_pair(&node, sibling.as_slice())
+    });
+
+    computed.as_slice() == root
+}
@@ -1,21 +1,18 @@
-use crate::state::{CONFIG, PROPOSALS};
+use crate::state::{CONFIG, PROPOSALS, PROPOSALS_BY_END_BLOCK, PROPOSAL_CONTENT};
 use astroport_governance::{
-    assembly::{Config, Proposal, ProposalStatus},
+    assembly::{
+        Config, MigrateMsg, Proposal, ProposalContent, ProposalStatus, MAX_PROPOSAL_MESSAGES,
+        MAX_PROPOSAL_MESSAGES_SIZE,
+    },
     astroport::asset::addr_opt_validate,
+    migration::migrate_map,
 };
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, CosmosMsg, Decimal, DepsMut, StdResult, Uint128, Uint64};
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, DepsMut, StdResult, Storage, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
 
-/// This structure describes a migration message.
-#[cw_serde]
-pub struct MigrateMsg {
-    voting_escrow_delegator_addr: Option<String>,
-    vxastro_token_addr: Option<String>,
-    ibc_controller: Option<String>,
-}
-
+/// Proposal layout used up to V1.5.0, with the content stored inline
 #[cw_serde]
 pub struct ProposalV130 {
     /// Unique proposal ID
@@ -25,8 +22,10 @@ pub struct ProposalV130 {
     /// Status of the proposal
     pub status: ProposalStatus,
     /// `For` power of proposal
+    #[serde(alias = "for_votes")]
     pub for_power: Uint128,
     /// `Against` power of proposal
+    #[serde(alias = "against_votes")]
     pub against_power: Uint128,
     /// `For` votes for the proposal
     pub for_voters: Vec<Addr>,
@@ -84,47 +83,131 @@ pub struct ConfigV130 {
 
 pub const CONFIG_V130: Item<ConfigV130> = Item::new("config");
 
-/// Migrate proposals to V1.4.0
-pub(crate) fn migrate_proposals_to_v140(deps: DepsMut, cfg: &Config) -> StdResult<()> {
-    let v130_proposals_interface: Map<u64, ProposalV130> = Map::new("proposals");
-    let proposals_v130 = v130_proposals_interface
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending {})
-        .collect::<StdResult<Vec<_>>>()?;
-
-    for (key, proposal) in proposals_v130 {
-        PROPOSALS.save(
-            deps.storage,
-            key,
-            &Proposal {
-                proposal_id: proposal.proposal_id,
-                submitter: proposal.submitter,
-                status: proposal.status,
-                for_power: proposal.for_power,
-                against_power: proposal.against_power,
-                for_voters: proposal.for_voters,
-                against_voters: proposal.against_voters,
-                start_block: proposal.start_block,
-                start_time: proposal.start_time,
-                end_block: proposal.end_block,
-                delayed_end_block: proposal.end_block + cfg.proposal_effective_delay,
-                expiration_block: proposal.end_block
-                    + cfg.proposal_effective_delay
-                    + cfg.proposal_expiration_period,
-                title: proposal.title,
-                description: proposal.description,
-                link: proposal.link,
-                messages: proposal.messages,
-                deposit_amount: proposal.deposit_amount,
-                ibc_channel: proposal.ibc_channel,
-            },
-        )?;
+#[cw_serde]
+pub struct ConfigV150 {
+    /// xASTRO token address
+    pub xastro_token_addr: Addr,
+    /// vxASTRO token address
+    pub vxastro_token_addr: Option<Addr>,
+    /// Voting Escrow delegator address
+    pub voting_escrow_delegator_addr: Option<Addr>,
+    /// Astroport IBC controller contract
+    pub ibc_controller: Option<Addr>,
+    /// Builder unlock contract address
+    pub builder_unlock_addr: Addr,
+    /// Proposal voting period
+    pub proposal_voting_period: u64,
+    /// Proposal effective delay
+    pub proposal_effective_delay: u64,
+    /// Proposal expiration period
+    pub proposal_expiration_period: u64,
+    /// Proposal required deposit
+    pub proposal_required_deposit: Uint128,
+    /// Proposal required quorum
+    pub proposal_required_quorum: Decimal,
+    /// Proposal required threshold
+    pub proposal_required_threshold: Decimal,
+    /// Whitelisted links
+    pub whitelisted_links: Vec<String>,
+}
+
+pub const CONFIG_V150: Item<ConfigV150> = Item::new("config");
+
+/// This structure describes the progress of migrating proposals to the current layout.
+#[cw_serde]
+pub struct ProposalsMigration {
+    /// Whether the delayed end and expiration blocks are set from the config, which is needed for
+    /// proposals created before V1.4.0
+    pub set_deadlines: bool,
+    /// The last migrated proposal
+    pub last_proposal_id: Option<u64>,
+}
+
+/// Progress of the proposals migration. Removed once every proposal is migrated
+pub const PROPOSALS_MIGRATION: Item<ProposalsMigration> = Item::new("proposals_migration");
+
+/// Migrates proposals to the current layout in batches, continuing after the last migrated proposal.
+/// Legacy and migrated proposals share the same namespace, so the migration must go in ID order.
+///
+/// * **limit** amount of proposals to migrate.
+///
+/// Returns whether every proposal is migrated.
+pub(crate) fn migrate_proposals(storage: &mut dyn Storage, limit: u32) -> StdResult<bool> {
+    let mut migration = PROPOSALS_MIGRATION.load(storage)?;
+    let cfg = CONFIG.load(storage)?;
+    let legacy_proposals: Map<u64, ProposalV130> = Map::new("proposals");
+
+    let migrated = migrate_map(
+        storage,
+        &legacy_proposals,
+        migration.last_proposal_id,
+        Some(limit as usize),
+        |storage, key, mut proposal| {
+            if migration.set_deadlines {
+                proposal.delayed_end_block = proposal.end_block + cfg.proposal_effective_delay;
+                proposal.expiration_block =
+                    proposal.delayed_end_block + cfg.proposal_expiration_period;
+            }
+            migration.last_proposal_id = Some(key);
+
+            save_split_proposal(storage, key, proposal)
+        },
+    )?;
+
+    if migrated < limit as usize {
+        PROPOSALS_MIGRATION.remove(storage);
+        Ok(true)
+    } else {
+        PROPOSALS_MIGRATION.save(storage, &migration)?;
+        Ok(false)
+    }
+}
+
+fn save_split_proposal(
+    storage: &mut dyn Storage,
+    key: u64,
+    proposal: ProposalV130,
+) -> StdResult<()> {
+    if proposal.status == ProposalStatus::Active {
+        PROPOSALS_BY_END_BLOCK.save(storage, (proposal.end_block, key), &())?;
     }
 
-    Ok(())
+    PROPOSAL_CONTENT.save(
+        storage,
+        key,
+        &ProposalContent {
+            description: proposal.description,
+            link: proposal.link,
+            messages: proposal.messages,
+        },
+    )?;
+
+    PROPOSALS.save(
+        storage,
+        key,
+        &Proposal {
+            proposal_id: proposal.proposal_id,
+            submitter: proposal.submitter,
+            status: proposal.status,
+            for_power: proposal.for_power,
+            against_power: proposal.against_power,
+            for_voters: proposal.for_voters,
+            against_voters: proposal.against_voters,
+            start_block: proposal.start_block,
+            start_time: proposal.start_time,
+            end_block: proposal.end_block,
+            delayed_end_block: proposal.delayed_end_block,
+            expiration_block: proposal.expiration_block,
+            title: proposal.title,
+            deposit_amount: proposal.deposit_amount,
+            ibc_channel: proposal.ibc_channel,
+            forum_thread_id: None,
+        },
+    )
 }
 
 /// Migrate contract config to V1.4.0
-pub(crate) fn migrate_config_to_140(deps: DepsMut, msg: MigrateMsg) -> StdResult<Config> {
+pub(crate) fn migrate_config_to_140(deps: DepsMut, msg: MigrateMsg) -> StdResult<()> {
     let cfg_v130 = CONFIG_V130.load(deps.storage)?;
 
     let cfg = Config {
@@ -143,9 +226,45 @@ pub(crate) fn migrate_config_to_140(deps: DepsMut, msg: MigrateMsg) -> StdResult
         proposal_required_quorum: cfg_v130.proposal_required_quorum,
         proposal_required_threshold: cfg_v130.proposal_required_threshold,
         whitelisted_links: cfg_v130.whitelisted_links,
+        proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+        proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+        links_curator: None,
+        excluded_voters: vec![],
+        builder_power_coefficient: Decimal::one(),
+        xastro_time_weighting: None,
+        signaling_required_deposit: cfg_v130.proposal_required_deposit,
+        emergency_committee: None,
     };
 
-    CONFIG.save(deps.storage, &cfg)?;
+    CONFIG.save(deps.storage, &cfg)
+}
+
+/// Migrate contract config to V1.6.0
+pub(crate) fn migrate_config_to_160(deps: DepsMut) -> StdResult<()> {
+    let cfg_v150 = CONFIG_V150.load(deps.storage)?;
+
+    let cfg = Config {
+        xastro_token_addr: cfg_v150.xastro_token_addr,
+        vxastro_token_addr: cfg_v150.vxastro_token_addr,
+        voting_escrow_delegator_addr: cfg_v150.voting_escrow_delegator_addr,
+        ibc_controller: cfg_v150.ibc_controller,
+        builder_unlock_addr: cfg_v150.builder_unlock_addr,
+        proposal_voting_period: cfg_v150.proposal_voting_period,
+        proposal_effective_delay: cfg_v150.proposal_effective_delay,
+        proposal_expiration_period: cfg_v150.proposal_expiration_period,
+        proposal_required_deposit: cfg_v150.proposal_required_deposit,
+        proposal_required_quorum: cfg_v150.proposal_required_quorum,
+        proposal_required_threshold: cfg_v150.proposal_required_threshold,
+        whitelisted_links: cfg_v150.whitelisted_links,
+        proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+        proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+        links_curator: None,
+        excluded_voters: vec![],
+        builder_power_coefficient: Decimal::one(),
+        xastro_time_weighting: None,
+        signaling_required_deposit: cfg_v150.proposal_required_deposit,
+        emergency_committee: None,
+    };
 
-    Ok(cfg)
+    CONFIG.save(deps.storage, &cfg)
 }
@@ -0,0 +1,52 @@
+use bech32::FromBase32;
+use cosmwasm_std::{Addr, Api};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+/// Length of a secp256k1 signature without the recovery id
+const SIGNATURE_LENGTH: usize = 64;
+
+/// Checks that `signature` signs sha256(`payload`) with the key behind the `voter` address.
+/// The signature is 64 bytes (r || s) followed by a one byte recovery id. The recovered key is
+/// matched against the address the same way Cosmos SDK derives it: ripemd160(sha256(compressed key)).
+pub fn is_signed_by(api: &dyn Api, voter: &Addr, payload: &[u8], signature: &[u8]) -> bool {
+    let (signature, recovery_id) = match signature {
+        [signature @ .., recovery_id] if signature.len() == SIGNATURE_LENGTH => {
+            (signature, *recovery_id)
+        }
+        _ => return false,
+    };
+
+    let hash = Sha256::digest(payload);
+    let pubkey = match api.secp256k1_recover_pubkey(&hash, signature, recovery_id) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return false,
+    };
+
+    let address_bytes = match bech32::decode(voter.as_str())
+        .ok()
+        .and_then(|(_, data, _)| Vec::<u8>::from_base32(&data).ok())
+    {
+        Some(bytes) => bytes,
+        None => return false,
+    };
+
+    match compress_pubkey(&pubkey) {
+        Some(compressed) => {
+            Ripemd160::digest(Sha256::digest(compressed)).as_slice() == address_bytes
+        }
+        None => false,
+    }
+}
+
+/// Converts an uncompressed secp256k1 public key (0x04 || x || y) to its compressed form.
+fn compress_pubkey(pubkey: &[u8]) -> Option<Vec<u8>> {
+    match pubkey {
+        [0x04, point @ ..] if point.len() == 64 => {
+            let (x, y) = point.split_at(32);
+            let prefix = if y[31] % 2 == 0 { 0x02 } else { 0x03 };
+            Some([&[prefix], x].concat())
+        }
+        _ => None,
+    }
+}
@@ -1,12 +1,135 @@
-use astroport_governance::assembly::{Config, Proposal};
-use cosmwasm_std::Uint64;
+use astroport_governance::assembly::{
+    Config, Proposal, ProposalBundle, ProposalContent, ProposalOption, ProposalVote,
+};
+use astroport_governance::interchain::ForwardMetadata;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
 
 /// Stores the config for the Assembly contract
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// Stores every version of the config by the block height at which it was set
+pub const CONFIG_HISTORY: Map<u64, Config> = Map::new("config_history");
+
 /// Stores the global state for the Assembly contract
 pub const PROPOSAL_COUNT: Item<Uint64> = Item::new("proposal_count");
 
 /// This is a map that contains information about all proposals
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+
+/// Stores the description, link and messages of every proposal
+pub const PROPOSAL_CONTENT: Map<u64, ProposalContent> = Map::new("proposal_content");
+
+/// Indexes active proposals by (end_block, proposal_id) so the ones that can be ended are found with a range scan
+pub const PROPOSALS_BY_END_BLOCK: Map<(u64, u64), ()> = Map::new("proposals_by_end_block");
+
+/// Options of multi-choice proposals
+pub const PROPOSAL_OPTIONS: Map<u64, Vec<ProposalOption>> = Map::new("proposal_options");
+
+/// Voting power cast for every option of a multi-choice proposal, in the option order
+pub const OPTION_TALLIES: Map<u64, Vec<Uint128>> = Map::new("option_tallies");
+
+/// Multi-choice proposals decided by instant runoff
+pub const RANKED_PROPOSALS: Map<u64, ()> = Map::new("ranked_proposals");
+
+/// Voting power of every distinct ranking cast for a ranked-choice proposal.
+/// The ranking is stored as option indexes, one byte each
+pub const RANKED_BALLOTS: Map<(u64, &[u8]), Uint128> = Map::new("ranked_ballots");
+
+/// Index of the winning option of every passed multi-choice proposal
+pub const WINNING_OPTIONS: Map<u64, u32> = Map::new("winning_options");
+
+/// Binding round content and IBC channel waiting for a signaling round to pass
+#[cw_serde]
+pub struct BindingRound {
+    pub content: ProposalContent,
+    pub ibc_channel: Option<String>,
+}
+
+/// Binding rounds waiting for their signaling round (by signaling proposal id)
+pub const BINDING_ROUNDS: Map<u64, BindingRound> = Map::new("binding_rounds");
+
+/// Maps the signaling round of a two-phase proposal to its binding round, once opened
+pub const TWO_PHASE_PROPOSALS: Map<u64, Option<u64>> = Map::new("two_phase_proposals");
+
+/// Maps binding rounds to their signaling round
+pub const SIGNALING_ROUNDS: Map<u64, u64> = Map::new("signaling_rounds");
+
+/// Maps forum thread ids to the active proposal referencing them
+pub const ACTIVE_FORUM_THREADS: Map<&str, u64> = Map::new("active_forum_threads");
+
+/// Indexes proposals by (status, proposal_id)
+pub const PROPOSALS_BY_STATUS: Map<(&str, u64), ()> = Map::new("proposals_by_status");
+
+/// Stores the number of stored proposals with every status
+pub const PROPOSAL_STATUS_COUNTS: Map<&str, u64> = Map::new("proposal_status_counts");
+
+/// Indexes proposals by (submitter, proposal_id)
+pub const PROPOSALS_BY_SUBMITTER: Map<(&Addr, u64), ()> = Map::new("proposals_by_submitter");
+
+/// Timestamp until which new proposals and votes are paused by the emergency committee
+pub const PAUSED_UNTIL: Item<u64> = Item::new("paused_until");
+
+/// Whether new proposals and votes are paused by the chain through [`SudoMsg::Pause`](astroport_governance::assembly::SudoMsg::Pause)
+pub const CHAIN_PAUSED: Item<bool> = Item::new("chain_paused");
+
+/// Stores the id of the last proposal processed by [`ExecuteMsg::Reindex`](astroport_governance::assembly::ExecuteMsg::Reindex)
+pub const REINDEX_CURSOR: Item<u64> = Item::new("reindex_cursor");
+
+/// Removed proposals whose votes and vote merkle tree are still being removed in batches with
+/// [`ExecuteMsg::RemoveCompletedProposal`](astroport_governance::assembly::ExecuteMsg::RemoveCompletedProposal)
+pub const PENDING_REMOVALS: Map<u64, ()> = Map::new("pending_removals");
+
+/// Stores the option and voting power of every vote by (proposal_id, voter)
+pub const VOTES: Map<(u64, &Addr), ProposalVote> = Map::new("votes");
+
+/// Contracts notified about proposal status changes
+pub const PROPOSAL_HOOKS: Map<&Addr, ()> = Map::new("proposal_hooks");
+
+/// Contracts notified about every vote
+pub const VOTE_LISTENERS: Map<&Addr, ()> = Map::new("vote_listeners");
+
+/// Proposal bundles keyed by bundle id
+pub const BUNDLES: Map<u64, ProposalBundle> = Map::new("bundles");
+
+/// Bundle id of every bundled proposal
+pub const PROPOSAL_BUNDLES: Map<u64, u64> = Map::new("proposal_bundles");
+
+/// Route through intermediate chains of IBC proposals whose remote chain has no direct channel to the hub
+pub const PROPOSAL_IBC_FORWARD: Map<u64, ForwardMetadata> = Map::new("proposal_ibc_forward");
+
+/// Merkle root over the votes of every ended proposal
+pub const VOTE_ROOTS: Map<u64, Binary> = Map::new("vote_roots");
+
+/// Progress of building the vote merkle tree of an ended proposal with
+/// [`ExecuteMsg::CommitVotes`](astroport_governance::assembly::ExecuteMsg::CommitVotes)
+#[cw_serde]
+#[derive(Default)]
+pub struct VoteTree {
+    /// The last voter whose leaf was stored
+    pub last_voter: Option<Addr>,
+    /// Whether the leaves of all votes are stored
+    pub leaves_done: bool,
+    /// The level being hashed into the next one. Once the tree is built this is the root level
+    pub level: u32,
+    /// The amount of nodes at `level`
+    pub level_size: u32,
+    /// The index of the next node to compute at `level + 1`
+    pub next_index: u32,
+}
+
+/// Vote merkle trees of ended proposals
+pub const VOTE_TREES: Map<u64, VoteTree> = Map::new("vote_trees");
+
+/// Vote merkle tree nodes by (proposal_id, level, index). Level 0 holds the leaves
+pub const VOTE_TREE_NODES: Map<(u64, u32, u32), Binary> = Map::new("vote_tree_nodes");
+
+/// Leaf index of every vote by (proposal_id, voter)
+pub const VOTE_LEAF_INDEXES: Map<(u64, &Addr), u32> = Map::new("vote_leaf_indexes");
+
+/// Nonce the next signed vote of every voter must use
+pub const VOTE_NONCES: Map<&Addr, u64> = Map::new("vote_nonces");
+
+/// Expiration of every vote operator grant by (grantor, operator)
+pub const VOTE_OPERATORS: Map<(&Addr, &Addr), Option<u64>> = Map::new("vote_operators");
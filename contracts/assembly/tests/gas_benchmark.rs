@@ -0,0 +1,318 @@
+//! Gas benchmarks for the Assembly hot paths.
+//!
+//! cw-multi-test doesn't meter gas, so the contract entry points are called directly on top of a
+//! storage that charges the Cosmos SDK KVStore gas schedule. Storage access dominates the cost of
+//! these endpoints, which makes the numbers suitable for comparing storage layouts.
+//!
+//! The benchmark is ignored by default. Run it with
+//! `cargo test -p astro-assembly --test gas_benchmark -- --ignored --nocapture` to print the results.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use astro_assembly::contract::{execute, instantiate};
+use astroport_governance::assembly::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, ProposalOption, ProposalVoteOption, DEPOSIT_INTERVAL,
+    MAX_PROPOSAL_MESSAGES, MAX_PROPOSAL_MESSAGES_SIZE, VOTING_PERIOD_INTERVAL,
+};
+use astroport_governance::mock_querier::{GovernanceMockQuerier, BUILDER_UNLOCK_ADDR, XASTRO_ADDR};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, to_binary, to_vec, Addr, BankMsg, CosmosMsg, Decimal, Env, Order, OwnedDeps, Record,
+    Storage, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+
+const PROPOSAL_VOTING_PERIOD: u64 = *VOTING_PERIOD_INTERVAL.start();
+const PROPOSAL_EFFECTIVE_DELAY: u64 = 12_342;
+const PROPOSAL_EXPIRATION_PERIOD: u64 = 86_399;
+const PROPOSAL_REQUIRED_DEPOSIT: u128 = *DEPOSIT_INTERVAL.start();
+
+// Default Cosmos SDK KVStore gas config
+const HAS_COST: u64 = 1000;
+const DELETE_COST: u64 = 1000;
+const READ_COST_FLAT: u64 = 1000;
+const READ_COST_PER_BYTE: u64 = 3;
+const WRITE_COST_FLAT: u64 = 2000;
+const WRITE_COST_PER_BYTE: u64 = 30;
+const ITER_NEXT_COST_FLAT: u64 = 30;
+
+/// Wraps [`MockStorage`] and accumulates the gas the SDK would charge for every access.
+#[derive(Default)]
+struct GasMeteredStorage {
+    storage: MockStorage,
+    gas_used: Cell<u64>,
+}
+
+impl GasMeteredStorage {
+    fn consume(&self, gas: u64) {
+        self.gas_used.set(self.gas_used.get() + gas);
+    }
+
+    /// Returns the gas used since the last call and resets the counter.
+    fn take_gas(&self) -> u64 {
+        self.gas_used.replace(0)
+    }
+}
+
+impl Storage for GasMeteredStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        match &value {
+            Some(value) => {
+                self.consume(READ_COST_FLAT + READ_COST_PER_BYTE * (key.len() + value.len()) as u64)
+            }
+            None => self.consume(HAS_COST),
+        }
+        value
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        Box::new(
+            self.storage
+                .range(start, end, order)
+                .map(move |(key, value)| {
+                    self.consume(
+                        ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * (key.len() + value.len()) as u64,
+                    );
+                    (key, value)
+                }),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.consume(WRITE_COST_FLAT + WRITE_COST_PER_BYTE * (key.len() + value.len()) as u64);
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.consume(DELETE_COST);
+        self.storage.remove(key)
+    }
+}
+
+type MeteredDeps = OwnedDeps<GasMeteredStorage, MockApi, GovernanceMockQuerier>;
+
+/// Gas used by each hot path for a single proposal.
+#[derive(Debug)]
+struct Measurement {
+    vote: ProposalVoteOption,
+    voters: usize,
+    messages: usize,
+    cast_vote: u64,
+    end_proposal: u64,
+    execute_proposal: u64,
+}
+
+fn setup() -> (MeteredDeps, Env) {
+    let mut deps = OwnedDeps {
+        storage: GasMeteredStorage::default(),
+        api: MockApi::default(),
+        querier: GovernanceMockQuerier::default(),
+        custom_query_type: PhantomData,
+    };
+    let env = mock_env();
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            xastro_token_addr: XASTRO_ADDR.to_string(),
+            vxastro_token_addr: None,
+            voting_escrow_delegator_addr: None,
+            ibc_controller: None,
+            builder_unlock_addr: BUILDER_UNLOCK_ADDR.to_string(),
+            proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+            proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+            proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+            proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+            proposal_required_quorum: Decimal::from_str("0.50").unwrap(),
+            proposal_required_threshold: Decimal::from_str("0.60").unwrap(),
+            whitelisted_links: vec!["https://some.link/".to_string()],
+            proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+            proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+        },
+    )
+    .unwrap();
+
+    (deps, env)
+}
+
+/// Returns the address of the i-th voter. Every address has the same length, so the gas
+/// differences only come from the amount of stored votes.
+fn voter(i: usize) -> String {
+    format!("voter{i:04}")
+}
+
+/// Measures the hot paths of a proposal with `messages` messages that receives `voters` votes.
+/// `cast_vote` is measured for the last vote, when the proposal already holds every other voter.
+/// A [`ProposalVoteOption::Choice`] vote is measured on a multi-choice proposal whose first option
+/// holds the messages.
+fn measure(voters: usize, messages: usize, vote: ProposalVoteOption) -> Measurement {
+    let (mut deps, mut env) = setup();
+
+    let voter_power = Uint128::new(1_000);
+    for i in 0..voters {
+        deps.querier.xastro_balances.insert(voter(i), voter_power);
+    }
+    deps.querier.xastro_total_supply = voter_power * Uint128::from(voters as u128);
+
+    let messages = (0..messages)
+        .map(|i| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: format!("receiver{i}"),
+                amount: coins(1, "uluna"),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XASTRO_ADDR, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "submitter".to_string(),
+            amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+            msg: to_binary(&match vote {
+                ProposalVoteOption::Choice(_) => Cw20HookMsg::SubmitMultiChoiceProposal {
+                    title: "Benchmark".to_string(),
+                    description: "Gas benchmark proposal".to_string(),
+                    link: None,
+                    options: vec![
+                        ProposalOption {
+                            label: "Messages".to_string(),
+                            messages: Some(messages.clone()),
+                        },
+                        ProposalOption {
+                            label: "Nothing".to_string(),
+                            messages: None,
+                        },
+                    ],
+                    ranked: false,
+                    ibc_channel: None,
+                    forum_thread_id: None,
+                },
+                _ => Cw20HookMsg::SubmitProposal {
+                    title: "Benchmark".to_string(),
+                    description: "Gas benchmark proposal".to_string(),
+                    link: None,
+                    messages: Some(messages.clone()),
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
+                },
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let mut cast_vote = 0;
+    for i in 0..voters {
+        deps.storage.take_gas();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&voter(i), &[]),
+            ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: vote.clone(),
+            },
+        )
+        .unwrap();
+        cast_vote = deps.storage.take_gas();
+    }
+
+    env.block.height += PROPOSAL_VOTING_PERIOD + 1;
+    deps.storage.take_gas();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::EndProposal { proposal_id: 1 },
+    )
+    .unwrap();
+    let end_proposal = deps.storage.take_gas();
+
+    env.block.height += PROPOSAL_EFFECTIVE_DELAY;
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+    )
+    .unwrap();
+    let execute_proposal = deps.storage.take_gas();
+
+    Measurement {
+        vote,
+        voters,
+        messages: messages.len(),
+        cast_vote,
+        end_proposal,
+        execute_proposal,
+    }
+}
+
+#[test]
+fn cast_vote_gas_per_voter() {
+    // Multi-choice votes are only stored by voter, so their cost doesn't depend on other votes.
+    // The tolerance covers the longer tally numbers
+    let few = measure(10, 1, ProposalVoteOption::Choice(0));
+    let many = measure(100, 1, ProposalVoteOption::Choice(0));
+    assert!(
+        many.cast_vote <= few.cast_vote + few.cast_vote / 100,
+        "{few:?} {many:?}"
+    );
+
+    // Binary proposals also list their voters, so every earlier voter may only add the cost of
+    // reading and rewriting one more address of that list
+    let few = measure(10, 1, ProposalVoteOption::For);
+    let many = measure(100, 1, ProposalVoteOption::For);
+    let voter_entry = to_vec(&Addr::unchecked(voter(0))).unwrap().len() as u64 + 1;
+    let per_voter = (READ_COST_PER_BYTE + WRITE_COST_PER_BYTE) * voter_entry;
+    assert!(
+        many.cast_vote <= few.cast_vote + few.cast_vote / 100 + 90 * per_voter,
+        "{few:?} {many:?}"
+    );
+}
+
+#[test]
+#[ignore = "benchmark, run with --ignored --nocapture to print the results"]
+fn benchmark_hot_paths() {
+    let mut measurements = vec![];
+
+    for vote in [ProposalVoteOption::For, ProposalVoteOption::Choice(0)] {
+        for voters in [1, 10, 100, 500] {
+            measurements.push(measure(voters, 1, vote.clone()));
+        }
+    }
+
+    for messages in [0, 4, MAX_PROPOSAL_MESSAGES] {
+        measurements.push(measure(10, messages, ProposalVoteOption::For));
+    }
+
+    println!(
+        "{:>9} {:>8} {:>9} {:>12} {:>14} {:>18}",
+        "vote", "voters", "messages", "cast_vote", "end_proposal", "execute_proposal"
+    );
+    for m in &measurements {
+        println!(
+            "{:>9} {:>8} {:>9} {:>12} {:>14} {:>18}",
+            m.vote.to_string(),
+            m.voters,
+            m.messages,
+            m.cast_vote,
+            m.end_proposal,
+            m.execute_proposal
+        );
+    }
+}
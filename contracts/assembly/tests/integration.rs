@@ -1,13 +1,36 @@
 use astro_assembly::astroport;
+use astro_assembly::contract::{
+    calc_total_voting_power_at, calc_voting_power, execute, migrate, query, reply,
+};
+use astro_assembly::merkle::{verify_vote_proof, EMPTY_VOTE_ROOT};
+use astro_assembly::state::{
+    BindingRound, VoteTree, BINDING_ROUNDS, CONFIG, OPTION_TALLIES, PENDING_REMOVALS, PROPOSALS,
+    PROPOSAL_BUNDLES, PROPOSAL_CONTENT, PROPOSAL_COUNT, PROPOSAL_IBC_FORWARD, PROPOSAL_OPTIONS,
+    RANKED_BALLOTS, RANKED_PROPOSALS, SIGNALING_ROUNDS, TWO_PHASE_PROPOSALS, VOTES,
+    VOTE_LEAF_INDEXES, VOTE_ROOTS, VOTE_TREES, VOTE_TREE_NODES, WINNING_OPTIONS,
+};
 use astroport::{
     token::InstantiateMsg as TokenInstantiateMsg, xastro_token::QueryMsg as XAstroQueryMsg,
 };
+use astroport_governance::assembly::helpers::build_submit_proposal_msg;
 use astroport_governance::assembly::{
-    Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, Proposal, ProposalListResponse,
-    ProposalStatus, ProposalVoteOption, ProposalVotesResponse, QueryMsg, UpdateConfig,
-    DEPOSIT_INTERVAL, VOTING_PERIOD_INTERVAL,
+    Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, MultiChoiceTallyResponse,
+    OptionTally, Proposal, ProposalBundle, ProposalContent, ProposalCountsResponse,
+    ProposalHookMsg, ProposalListResponse, ProposalOption, ProposalStatus, ProposalTargetsResponse,
+    ProposalVote, ProposalVoteOption, ProposalVotesResponse, QueryMsg, RawStateEntry,
+    StakeTimeWeighting, StateSection, TallyCheckResponse, TwoPhaseProposalResponse, UpdateConfig,
+    VoteBySigPayload, VoteListenerMsg, VoteOperatorGrant, VoteProofResponse, VotingPowerPoint,
+    DEPOSIT_INTERVAL, EMERGENCY_PAUSE_PERIOD, MAX_DESC_LENGTH, MAX_LINK_LENGTH,
+    MAX_PROPOSAL_MESSAGES, MAX_PROPOSAL_MESSAGES_SIZE, MAX_TITLE_LENGTH, MAX_VOTE_LISTENERS,
+    PROPOSAL_HOOK_GAS_LIMIT, VOTING_PERIOD_INTERVAL,
+};
+use astroport_governance::ibc_controller::ChannelParams;
+use astroport_governance::interchain::{ForwardMetadata, IBC_APP_VERSION};
+use astroport_governance::mock_querier::{
+    mock_dependencies, BUILDER_UNLOCK_ADDR, VXASTRO_ADDR, XASTRO_ADDR,
 };
 
+use cosmwasm_schema::cw_serde;
 use std::str::FromStr;
 
 use astroport_governance::voting_escrow::{
@@ -23,15 +46,26 @@ use astroport_governance::voting_escrow_delegation::{
     ExecuteMsg as DelegatorExecuteMsg, InstantiateMsg as DelegatorInstantiateMsg,
     QueryMsg as DelegatorQueryMsg,
 };
+use astroport_governance::MAX_LIMIT;
+use bech32::{ToBase32, Variant};
 use cosmwasm_std::{
-    testing::{mock_env, MockApi, MockStorage},
-    to_binary, Addr, Binary, CosmosMsg, Decimal, QueryRequest, StdResult, Timestamp, Uint128,
-    Uint64, WasmMsg, WasmQuery,
+    attr, coins, from_binary, from_slice,
+    testing::{mock_env, mock_ibc_channel, mock_info, MockApi, MockStorage},
+    to_binary, to_vec, Addr, BankMsg, Binary, ContractResult, CosmosMsg, Decimal, Deps, DepsMut,
+    Empty, Env, IbcOrder, MessageInfo, Order, QueryRequest, Reply, ReplyOn, Response, StdError,
+    StdResult, SubMsgResult, SystemError, SystemResult, Timestamp, Uint128, Uint64, WasmMsg,
+    WasmQuery,
 };
-use cw20::{BalanceResponse, Cw20ExecuteMsg, MinterResponse};
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use cw_multi_test::{
     next_block, App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor,
 };
+use cw_storage_plus::{Item, Map};
+use k256::ecdsa::{recoverable, signature::Signer, SigningKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 const PROPOSAL_VOTING_PERIOD: u64 = *VOTING_PERIOD_INTERVAL.start();
 const PROPOSAL_EFFECTIVE_DELAY: u64 = 12_342;
@@ -53,11 +87,15 @@ fn test_contract_instantiation() {
     let vxastro_token_addr = instantiate_vxastro_token(&mut app, &owner, &xastro_token_addr);
     let builder_unlock_addr = instantiate_builder_unlock_contract(&mut app, &owner, &token_addr);
 
-    let assembly_contract = Box::new(ContractWrapper::new_with_empty(
-        astro_assembly::contract::execute,
-        astro_assembly::contract::instantiate,
-        astro_assembly::contract::query,
-    ));
+    let assembly_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astro_assembly::contract::execute,
+            astro_assembly::contract::instantiate,
+            astro_assembly::contract::query,
+        )
+        .with_reply_empty(astro_assembly::contract::reply)
+        .with_sudo_empty(astro_assembly::contract::sudo),
+    );
 
     let assembly_code = app.store_code(assembly_contract);
 
@@ -71,9 +109,11 @@ fn test_contract_instantiation() {
         proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
         proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
         proposal_required_deposit: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
-        proposal_required_quorum: String::from(PROPOSAL_REQUIRED_QUORUM),
-        proposal_required_threshold: String::from(PROPOSAL_REQUIRED_THRESHOLD),
+        proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+        proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD).unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+        proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
     };
 
     // Try to instantiate assembly with wrong threshold
@@ -82,7 +122,7 @@ fn test_contract_instantiation() {
             assembly_code,
             owner.clone(),
             &InstantiateMsg {
-                proposal_required_threshold: "0.3".to_string(),
+                proposal_required_threshold: Decimal::from_str("0.3").unwrap(),
                 ..assembly_default_instantiate_msg.clone()
             },
             &[],
@@ -101,7 +141,7 @@ fn test_contract_instantiation() {
             assembly_code,
             owner.clone(),
             &InstantiateMsg {
-                proposal_required_threshold: "1.1".to_string(),
+                proposal_required_threshold: Decimal::from_str("1.1").unwrap(),
                 ..assembly_default_instantiate_msg.clone()
             },
             &[],
@@ -120,7 +160,7 @@ fn test_contract_instantiation() {
             assembly_code,
             owner.clone(),
             &InstantiateMsg {
-                proposal_required_quorum: "1.1".to_string(),
+                proposal_required_quorum: Decimal::from_str("1.1").unwrap(),
                 ..assembly_default_instantiate_msg.clone()
             },
             &[],
@@ -227,6 +267,7 @@ fn test_proposal_submitting() {
             assembly_addr.clone(),
             &QueryMsg::Proposals {
                 start: None,
+                start_after: None,
                 limit: None,
             },
         )
@@ -254,6 +295,9 @@ fn test_proposal_submitting() {
             link: Some(String::from("https://some.link")),
             messages: None,
             ibc_channel: None,
+            ibc_forward: None,
+            forum_thread_id: None,
+            bundle_id: None,
         })
         .unwrap(),
         amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT - 1),
@@ -278,6 +322,9 @@ fn test_proposal_submitting() {
                     link: Some(String::from("https://some.link/")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -298,11 +345,14 @@ fn test_proposal_submitting() {
             &Cw20ExecuteMsg::Send {
                 contract: assembly_addr.to_string(),
                 msg: to_binary(&Cw20HookMsg::SubmitProposal {
-                    title: String::from_utf8(vec![b'X'; 65]).unwrap(),
+                    title: String::from_utf8(vec![b'X'; MAX_TITLE_LENGTH + 1]).unwrap(),
                     description: String::from("Description"),
                     link: Some(String::from("https://some.link/")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -316,6 +366,76 @@ fn test_proposal_submitting() {
         "Generic error: Title too long!"
     );
 
+    // Try to create a proposal with too many messages
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: assembly_addr.to_string(),
+        msg: Binary::default(),
+        funds: vec![],
+    });
+    let err = app
+        .execute_contract(
+            user.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: String::from("Title"),
+                    description: String::from("Description"),
+                    link: Some(String::from("https://some.link/")),
+                    messages: Some(vec![msg; MAX_PROPOSAL_MESSAGES + 1]),
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Generic error: A proposal can't have more than {MAX_PROPOSAL_MESSAGES} messages!")
+    );
+
+    // Try to create a proposal with messages that are too large
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: assembly_addr.to_string(),
+        msg: Binary::from(vec![0u8; MAX_PROPOSAL_MESSAGES_SIZE as usize]),
+        funds: vec![],
+    });
+    let err = app
+        .execute_contract(
+            user.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: String::from("Title"),
+                    description: String::from("Description"),
+                    link: Some(String::from("https://some.link/")),
+                    messages: Some(vec![msg]),
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!(
+            "Generic error: Proposal messages can't be larger than {MAX_PROPOSAL_MESSAGES_SIZE} bytes!"
+        )
+    );
+
     // Try to create a proposal with wrong description
     let err = app
         .execute_contract(
@@ -329,6 +449,9 @@ fn test_proposal_submitting() {
                     link: Some(String::from("https://some.link/")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -350,10 +473,13 @@ fn test_proposal_submitting() {
                 contract: assembly_addr.to_string(),
                 msg: to_binary(&Cw20HookMsg::SubmitProposal {
                     title: String::from("Title"),
-                    description: String::from_utf8(vec![b'X'; 1025]).unwrap(),
+                    description: String::from_utf8(vec![b'X'; MAX_DESC_LENGTH + 1]).unwrap(),
                     link: Some(String::from("https://some.link/")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -380,6 +506,9 @@ fn test_proposal_submitting() {
                     link: Some(String::from("X")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -402,9 +531,12 @@ fn test_proposal_submitting() {
                 msg: to_binary(&Cw20HookMsg::SubmitProposal {
                     title: String::from("Title"),
                     description: String::from("Description"),
-                    link: Some(String::from_utf8(vec![b'X'; 129]).unwrap()),
+                    link: Some(String::from_utf8(vec![b'X'; MAX_LINK_LENGTH + 1]).unwrap()),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -430,6 +562,9 @@ fn test_proposal_submitting() {
                     link: Some(String::from("https://some1.link")),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -457,6 +592,9 @@ fn test_proposal_submitting() {
                     )),
                     messages: None,
                     ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
                 })
                 .unwrap(),
                 amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -470,6 +608,37 @@ fn test_proposal_submitting() {
         "Generic error: Link is not properly formatted or contains unsafe characters!"
     );
 
+    // A forward route is only valid for proposals executed over IBC
+    let err = app
+        .execute_contract(
+            user.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: String::from("Title"),
+                    description: String::from("Description"),
+                    link: Some(String::from("https://some.link/")),
+                    messages: None,
+                    ibc_channel: None,
+                    ibc_forward: Some(ForwardMetadata {
+                        hops: vec!["channel-1".to_string()],
+                    }),
+                    forum_thread_id: None,
+                    bundle_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        "A forward route requires an IBC channel!"
+    );
+
     // Valid proposal submission
     app.execute_contract(
         user.clone(),
@@ -496,11 +665,26 @@ fn test_proposal_submitting() {
                         proposal_required_threshold: None,
                         whitelist_add: None,
                         whitelist_remove: None,
+                        proposal_max_messages: None,
+                        proposal_max_messages_size: None,
+                        links_curator: None,
+                        remove_links_curator: None,
+                        excluded_voters_add: None,
+                        excluded_voters_remove: None,
+                        builder_power_coefficient: None,
+                        xastro_time_weighting: None,
+                        disable_xastro_time_weighting: None,
+                        signaling_required_deposit: None,
+                        emergency_committee: None,
+                        remove_emergency_committee: None,
                     })))
                     .unwrap(),
                     funds: vec![],
                 })]),
                 ibc_channel: None,
+                ibc_forward: None,
+                forum_thread_id: None,
+                bundle_id: None,
             })
             .unwrap(),
             amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
@@ -525,10 +709,22 @@ fn test_proposal_submitting() {
     assert_eq!(proposal.start_block, 12_345);
     assert_eq!(proposal.end_block, 12_345 + PROPOSAL_VOTING_PERIOD);
     assert_eq!(proposal.title, String::from("Title"));
-    assert_eq!(proposal.description, String::from("Description"));
-    assert_eq!(proposal.link, Some(String::from("https://some.link/q/")));
     assert_eq!(
-        proposal.messages,
+        proposal.deposit_amount,
+        Uint128::from(PROPOSAL_REQUIRED_DEPOSIT)
+    );
+
+    let content: ProposalContent = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalContent { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(content.description, String::from("Description"));
+    assert_eq!(content.link, Some(String::from("https://some.link/q/")));
+    assert_eq!(
+        content.messages,
         Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: assembly_addr.to_string(),
             msg: to_binary(&ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
@@ -545,15 +741,67 @@ fn test_proposal_submitting() {
                 proposal_required_threshold: None,
                 whitelist_add: None,
                 whitelist_remove: None,
+                proposal_max_messages: None,
+                proposal_max_messages_size: None,
+                links_curator: None,
+                remove_links_curator: None,
+                excluded_voters_add: None,
+                excluded_voters_remove: None,
+                builder_power_coefficient: None,
+                xastro_time_weighting: None,
+                disable_xastro_time_weighting: None,
+                signaling_required_deposit: None,
+                emergency_committee: None,
+                remove_emergency_committee: None,
             })))
             .unwrap(),
             funds: vec![],
         })])
     );
+
+    let entries: Vec<RawStateEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ExportState {
+                section: StateSection::Proposals,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, Binary::from(1u64.to_be_bytes()));
     assert_eq!(
-        proposal.deposit_amount,
-        Uint128::from(PROPOSAL_REQUIRED_DEPOSIT)
-    )
+        from_binary::<Proposal>(&entries[0].value).unwrap(),
+        proposal
+    );
+
+    let entries: Vec<RawStateEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ExportState {
+                section: StateSection::Proposals,
+                start_after: Some(entries[0].key.clone()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(entries.is_empty());
+
+    let entries: Vec<RawStateEntry> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ExportState {
+                section: StateSection::ConfigHistory,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(entries.len(), 1);
 }
 
 #[cfg(not(feature = "testnet"))]
@@ -596,6 +844,8 @@ fn test_successful_proposal() {
             start_time: 12_345,
             cliff: 5,
             duration: 500,
+            unlock_period: None,
+            percent_at_start: None,
         },
         proposed_receiver: None,
     };
@@ -687,6 +937,18 @@ fn test_successful_proposal() {
                     "https://some2.link/".to_string(),
                 ]),
                 whitelist_remove: Some(vec!["https://some.link/".to_string()]),
+                proposal_max_messages: None,
+                proposal_max_messages_size: None,
+                links_curator: None,
+                remove_links_curator: None,
+                excluded_voters_add: None,
+                excluded_voters_remove: None,
+                builder_power_coefficient: None,
+                xastro_time_weighting: None,
+                disable_xastro_time_weighting: None,
+                signaling_required_deposit: None,
+                emergency_committee: None,
+                remove_emergency_committee: None,
             })))
             .unwrap(),
             funds: vec![],
@@ -715,9 +977,38 @@ fn test_successful_proposal() {
 
         check_user_vp(&mut app, &assembly_addr, &sender, 1, expected_vp);
 
-        cast_vote(&mut app, assembly_addr.clone(), 1, sender, option).unwrap();
+        cast_vote(&mut app, assembly_addr.clone(), 1, sender, option.clone()).unwrap();
+
+        let vote: Option<ProposalVote> = app
+            .wrap()
+            .query_wasm_smart(
+                assembly_addr.clone(),
+                &QueryMsg::UserVote {
+                    proposal_id: 1,
+                    user: addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            vote,
+            Some(ProposalVote {
+                option,
+                power: Uint128::new(expected_vp),
+            })
+        );
     }
 
+    let tally: TallyCheckResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::CheckTally { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(tally.is_consistent);
+    assert_eq!(tally.recorded_for_power, tally.for_power);
+    assert_eq!(tally.recorded_against_power, Uint128::new(1150));
+
     let proposal: Proposal = app
         .wrap()
         .query_wasm_smart(
@@ -742,6 +1033,7 @@ fn test_successful_proposal() {
                 proposal_id: 1,
                 vote_option: ProposalVoteOption::For,
                 start: None,
+                start_after: None,
                 limit: None,
             },
         )
@@ -755,6 +1047,7 @@ fn test_successful_proposal() {
                 proposal_id: 1,
                 vote_option: ProposalVoteOption::Against,
                 start: None,
+                start_after: None,
                 limit: None,
             },
         )
@@ -790,6 +1083,55 @@ fn test_successful_proposal() {
         ]
     );
 
+    // `start_after` is exclusive and takes precedence over the deprecated `start`
+    let voters: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalVoters {
+                proposal_id: 1,
+                vote_option: ProposalVoteOption::Against,
+                start: Some(0),
+                start_after: Some("user9".to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(voters, vec![Addr::unchecked("user10")]);
+
+    let voters: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalVoters {
+                proposal_id: 1,
+                vote_option: ProposalVoteOption::Against,
+                start: None,
+                start_after: Some("user11".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(voters.is_empty());
+
+    let voters: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalVoters {
+                proposal_id: 1,
+                vote_option: ProposalVoteOption::Against,
+                start: Some(2),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        voters,
+        vec![Addr::unchecked("user10"), Addr::unchecked("user11")]
+    );
+
     // Skip voting period
     app.update_block(|bi| {
         bi.height += PROPOSAL_VOTING_PERIOD + 1;
@@ -916,7 +1258,10 @@ fn test_successful_proposal() {
         .execute_contract(
             Addr::unchecked("user0"),
             assembly_addr.clone(),
-            &ExecuteMsg::RemoveCompletedProposal { proposal_id: 1 },
+            &ExecuteMsg::RemoveCompletedProposal {
+                proposal_id: 1,
+                limit: None,
+            },
             &[],
         )
         .unwrap_err();
@@ -932,7 +1277,10 @@ fn test_successful_proposal() {
     app.execute_contract(
         Addr::unchecked("user0"),
         assembly_addr.clone(),
-        &ExecuteMsg::RemoveCompletedProposal { proposal_id: 1 },
+        &ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 1,
+            limit: None,
+        },
         &[],
     )
     .unwrap();
@@ -943,6 +1291,7 @@ fn test_successful_proposal() {
             assembly_addr.to_string(),
             &QueryMsg::Proposals {
                 start: None,
+                start_after: None,
                 limit: None,
             },
         )
@@ -1007,6 +1356,18 @@ fn test_voting_power_changes() {
                 proposal_required_threshold: None,
                 whitelist_add: None,
                 whitelist_remove: None,
+                proposal_max_messages: None,
+                proposal_max_messages_size: None,
+                links_curator: None,
+                remove_links_curator: None,
+                excluded_voters_add: None,
+                excluded_voters_remove: None,
+                builder_power_coefficient: None,
+                xastro_time_weighting: None,
+                disable_xastro_time_weighting: None,
+                signaling_required_deposit: None,
+                emergency_committee: None,
+                remove_emergency_committee: None,
             })))
             .unwrap(),
             funds: vec![],
@@ -1283,12 +1644,47 @@ fn test_unsuccessful_proposal() {
         .unwrap();
     }
 
+    // The proposal can't be ended while voting is still open
+    let finalizable: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap();
+    assert!(finalizable.is_empty());
+
     // Skip voting period
     app.update_block(|bi| {
         bi.height += PROPOSAL_VOTING_PERIOD + 1;
         bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
     });
 
+    let finalizable: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap();
+    assert_eq!(finalizable, vec![1]);
+
+    // Reindexing proposals which are already indexed doesn't change the counters
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        assembly_addr.clone(),
+        &ExecuteMsg::Reindex { limit: None },
+        &[],
+    )
+    .unwrap();
+
+    let counts: ProposalCountsResponse = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalCounts {})
+        .unwrap();
+    assert_eq!(counts.active, 1);
+    assert_eq!(counts.rejected, 0);
+
     // Check balance of submitter before and after proposal completion
     check_token_balance(&mut app, &xastro_addr, &Addr::unchecked("user0"), 0);
 
@@ -1307,6 +1703,15 @@ fn test_unsuccessful_proposal() {
         10000_000000,
     );
 
+    let finalizable: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap();
+    assert!(finalizable.is_empty());
+
     // Check proposal status
     let proposal: Proposal = app
         .wrap()
@@ -1318,6 +1723,13 @@ fn test_unsuccessful_proposal() {
 
     assert_eq!(proposal.status, ProposalStatus::Rejected);
 
+    let counts: ProposalCountsResponse = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalCounts {})
+        .unwrap();
+    assert_eq!(counts.active, 0);
+    assert_eq!(counts.rejected, 1);
+
     // Remove expired proposal
     app.update_block(|bi| {
         bi.height += PROPOSAL_EXPIRATION_PERIOD + PROPOSAL_EFFECTIVE_DELAY + 1;
@@ -1329,7 +1741,10 @@ fn test_unsuccessful_proposal() {
     app.execute_contract(
         Addr::unchecked("user0"),
         assembly_addr.clone(),
-        &ExecuteMsg::RemoveCompletedProposal { proposal_id: 1 },
+        &ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 1,
+            limit: None,
+        },
         &[],
     )
     .unwrap();
@@ -1340,6 +1755,7 @@ fn test_unsuccessful_proposal() {
             assembly_addr.to_string(),
             &QueryMsg::Proposals {
                 start: None,
+                start_after: None,
                 limit: None,
             },
         )
@@ -1348,6 +1764,75 @@ fn test_unsuccessful_proposal() {
     assert_eq!(res.proposal_list, vec![]);
     // proposal_count should not be changed after removing
     assert_eq!(res.proposal_count, Uint64::from(1u32));
+
+    let counts: ProposalCountsResponse = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalCounts {})
+        .unwrap();
+    assert_eq!(counts.rejected, 0);
+}
+
+#[test]
+fn test_remove_expired_active_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &Addr::unchecked("user0"),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        Addr::unchecked("user0"),
+        None,
+    );
+
+    // The proposal expires without being ended
+    let blocks = PROPOSAL_VOTING_PERIOD + PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD + 1;
+    app.update_block(|bi| {
+        bi.height += blocks;
+        bi.time = bi.time.plus_seconds(5 * blocks);
+    });
+
+    let finalizable: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap();
+    assert_eq!(finalizable, vec![1]);
+
+    app.execute_contract(
+        Addr::unchecked("user0"),
+        assembly_addr.clone(),
+        &ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 1,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The removed proposal isn't returned to keepers anymore
+    let finalizable: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap();
+    assert!(finalizable.is_empty());
 }
 
 #[test]
@@ -1384,7 +1869,11 @@ fn test_check_messages() {
     let vxastro_blacklist_msg = vec![(
         vxastro_addr.to_string(),
         to_binary(
-            &astroport_governance::voting_escrow::ExecuteMsg::UpdateConfig { new_guardian: None },
+            &astroport_governance::voting_escrow::ExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                checkpoint_hooks: None,
+                generator_controller: None,
+            },
         )
         .unwrap(),
     )];
@@ -1524,6 +2013,18 @@ fn test_delegated_vp() {
                 proposal_required_threshold: None,
                 whitelist_add: None,
                 whitelist_remove: None,
+                proposal_max_messages: None,
+                proposal_max_messages_size: None,
+                links_curator: None,
+                remove_links_curator: None,
+                excluded_voters_add: None,
+                excluded_voters_remove: None,
+                builder_power_coefficient: None,
+                xastro_time_weighting: None,
+                disable_xastro_time_weighting: None,
+                signaling_required_deposit: None,
+                emergency_committee: None,
+                remove_emergency_committee: None,
             })))
             .unwrap(),
             funds: vec![],
@@ -1586,7 +2087,3687 @@ fn test_delegated_vp() {
     assert_eq!(proposal.status, ProposalStatus::Passed);
 }
 
-fn mock_app() -> App {
+#[test]
+fn test_proposal_legacy_field_names() {
+    let proposal = Proposal {
+        proposal_id: Uint64::from(1u64),
+        submitter: Addr::unchecked("submitter"),
+        status: ProposalStatus::Active,
+        for_power: Uint128::new(100),
+        against_power: Uint128::new(50),
+        for_voters: vec![Addr::unchecked("user1")],
+        against_voters: vec![Addr::unchecked("user2")],
+        start_block: 1,
+        start_time: 2,
+        end_block: 3,
+        delayed_end_block: 4,
+        expiration_block: 5,
+        title: "Title".to_string(),
+        deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+        ibc_channel: None,
+        forum_thread_id: None,
+    };
+
+    // The current format round-trips and keeps the new field names
+    let current = to_binary(&proposal).unwrap();
+    let current_json = String::from_utf8(current.to_vec()).unwrap();
+    assert!(current_json.contains("\"for_power\":\"100\""));
+    assert!(current_json.contains("\"against_power\":\"50\""));
+    assert_eq!(from_binary::<Proposal>(&current).unwrap(), proposal);
+
+    // The legacy format is read into the same structure
+    let legacy = Binary::from(
+        current_json
+            .replace("for_power", "for_votes")
+            .replace("against_power", "against_votes")
+            .as_bytes(),
+    );
+    assert_eq!(from_binary::<Proposal>(&legacy).unwrap(), proposal);
+
+    let votes: ProposalVotesResponse = from_binary(&Binary::from(
+        br#"{"proposal_id":1,"for_votes":"100","against_votes":"50"}"#.as_slice(),
+    ))
+    .unwrap();
+    assert_eq!(
+        votes,
+        ProposalVotesResponse {
+            proposal_id: 1,
+            for_power: Uint128::new(100),
+            against_power: Uint128::new(50),
+        }
+    );
+}
+
+#[test]
+fn test_calc_voting_power_with_mock_querier() {
+    let mut deps = mock_dependencies();
+    deps.querier
+        .xastro_balances
+        .insert("user".to_string(), Uint128::new(100));
+    deps.querier.xastro_total_supply = Uint128::new(1_000);
+    deps.querier
+        .builder_voting_powers
+        .insert("user".to_string(), Uint128::new(200));
+    deps.querier.builder_state.remaining_astro_tokens = Uint128::new(2_000);
+    deps.querier
+        .vxastro_voting_powers
+        .insert("user".to_string(), Uint128::new(300));
+    deps.querier
+        .vxastro_deposits
+        .insert("user".to_string(), Uint128::new(400));
+    deps.querier.vxastro_total_voting_power = Uint128::new(3_000);
+
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                xastro_token_addr: Addr::unchecked(XASTRO_ADDR),
+                vxastro_token_addr: Some(Addr::unchecked(VXASTRO_ADDR)),
+                voting_escrow_delegator_addr: None,
+                ibc_controller: None,
+                builder_unlock_addr: Addr::unchecked(BUILDER_UNLOCK_ADDR),
+                proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+                proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+                proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+                proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+                proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD)
+                    .unwrap(),
+                whitelisted_links: vec!["https://some.link/".to_string()],
+                proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+                proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+                links_curator: None,
+                excluded_voters: vec![],
+                builder_power_coefficient: Decimal::one(),
+                xastro_time_weighting: None,
+                signaling_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                emergency_committee: None,
+            },
+        )
+        .unwrap();
+
+    let proposal = Proposal {
+        proposal_id: Uint64::from(1u64),
+        submitter: Addr::unchecked("submitter"),
+        status: ProposalStatus::Active,
+        for_power: Uint128::zero(),
+        against_power: Uint128::zero(),
+        for_voters: vec![],
+        against_voters: vec![],
+        start_block: 10,
+        start_time: EPOCH_START + WEEK,
+        end_block: 10 + PROPOSAL_VOTING_PERIOD,
+        delayed_end_block: 10 + PROPOSAL_VOTING_PERIOD + PROPOSAL_EFFECTIVE_DELAY,
+        expiration_block: 10
+            + PROPOSAL_VOTING_PERIOD
+            + PROPOSAL_EFFECTIVE_DELAY
+            + PROPOSAL_EXPIRATION_PERIOD,
+        title: "Title".to_string(),
+        deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+        ibc_channel: None,
+        forum_thread_id: None,
+    };
+
+    // xASTRO balance + builder allocation + vxASTRO voting power + xASTRO locked in vxASTRO
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "user".to_string(), &proposal).unwrap(),
+        Uint128::new(1_000)
+    );
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "random".to_string(), &proposal).unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        calc_total_voting_power_at(deps.as_ref(), &proposal).unwrap(),
+        Uint128::new(6_000)
+    );
+
+    // Excluded addresses can't vote and their voting power doesn't count towards the total
+    CONFIG
+        .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+            config.excluded_voters = vec![Addr::unchecked("user")];
+            Ok(config)
+        })
+        .unwrap();
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "user".to_string(), &proposal).unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        calc_total_voting_power_at(deps.as_ref(), &proposal).unwrap(),
+        Uint128::new(5_000)
+    );
+
+    // Only half of the locked builder allocations count as voting power
+    CONFIG
+        .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+            config.excluded_voters = vec![];
+            config.builder_power_coefficient = Decimal::percent(50);
+            Ok(config)
+        })
+        .unwrap();
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "user".to_string(), &proposal).unwrap(),
+        Uint128::new(900)
+    );
+    assert_eq!(
+        calc_total_voting_power_at(deps.as_ref(), &proposal).unwrap(),
+        Uint128::new(5_000)
+    );
+
+    // Half of the xASTRO was acquired within the holding period, so it counts with half of its weight
+    CONFIG
+        .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+            config.xastro_time_weighting = Some(StakeTimeWeighting {
+                min_weight: Decimal::percent(50),
+                holding_blocks: 5,
+            });
+            Ok(config)
+        })
+        .unwrap();
+    deps.querier.xastro_balances_at.insert(
+        ("user".to_string(), proposal.start_block - 5),
+        Uint128::new(50),
+    );
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "user".to_string(), &proposal).unwrap(),
+        Uint128::new(875)
+    );
+    // The total voting power isn't time-weighted
+    assert_eq!(
+        calc_total_voting_power_at(deps.as_ref(), &proposal).unwrap(),
+        Uint128::new(5_000)
+    );
+
+    deps.querier.xastro_balances_at.insert(
+        ("user".to_string(), proposal.start_block - 5),
+        Uint128::new(100),
+    );
+    assert_eq!(
+        calc_voting_power(deps.as_ref(), "user".to_string(), &proposal).unwrap(),
+        Uint128::new(900)
+    );
+}
+
+#[cw_serde]
+enum HookExecuteMsg {
+    ProposalHook(ProposalHookMsg),
+    VoteHook(VoteListenerMsg),
+}
+
+fn hook_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn hook_execute(_: DepsMut, _: Env, _: MessageInfo, msg: HookExecuteMsg) -> StdResult<Response> {
+    match msg {
+        HookExecuteMsg::ProposalHook(ProposalHookMsg::ProposalStatusChanged { status, .. }) => {
+            Ok(Response::new().add_attribute("hook_status", status.to_string()))
+        }
+        HookExecuteMsg::VoteHook(VoteListenerMsg::VoteCast {
+            voter,
+            vote,
+            voting_power,
+            ..
+        }) => Ok(Response::new()
+            .add_attribute("listener_voter", voter)
+            .add_attribute("listener_vote", vote.to_string())
+            .add_attribute("listener_power", voting_power)),
+    }
+}
+
+fn failing_hook_execute(
+    _: DepsMut,
+    _: Env,
+    _: MessageInfo,
+    _: HookExecuteMsg,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("Hook failed"))
+}
+
+fn hook_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_binary(&Empty {})
+}
+
+#[test]
+fn test_proposal_hooks_and_vote_listeners() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner.clone(), false);
+
+    let hook_code = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let failing_hook_code = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        failing_hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let hook = app
+        .instantiate_contract(hook_code, owner.clone(), &Empty {}, &[], "Hook", None)
+        .unwrap();
+    let failing_hook = app
+        .instantiate_contract(
+            failing_hook_code,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "Failing hook",
+            None,
+        )
+        .unwrap();
+
+    // Only the Assembly itself can manage hooks
+    let err = app
+        .execute_contract(
+            owner,
+            assembly_addr.clone(),
+            &ExecuteMsg::AddProposalHook {
+                address: hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    for address in [&hook, &failing_hook] {
+        app.execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddProposalHook {
+                address: address.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let err = app
+        .execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddProposalHook {
+                address: hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Proposal hook {hook} is already registered")
+    );
+
+    let hooks: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks.len(), 2);
+
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &Addr::unchecked("user0"),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &Addr::unchecked("user1"),
+        PROPOSAL_REQUIRED_DEPOSIT * 10,
+    );
+
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(WEEK);
+        block.height += WEEK / 5;
+    });
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        Addr::unchecked("user0"),
+        None,
+    );
+
+    for address in [&hook, &failing_hook] {
+        app.execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddVoteListener {
+                address: address.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The failing listener doesn't block the vote
+    let res = cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        Addr::unchecked("user1"),
+        ProposalVoteOption::For,
+    )
+    .unwrap();
+
+    let has_attribute = |res: &AppResponse, key: &str, value: &str| {
+        res.events.iter().any(|event| {
+            event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == key && attr.value == value)
+        })
+    };
+    assert!(has_attribute(&res, "listener_voter", "user1"));
+    assert!(has_attribute(
+        &res,
+        "listener_power",
+        &(PROPOSAL_REQUIRED_DEPOSIT * 10).to_string()
+    ));
+    assert!(has_attribute(&res, "action", "vote_listener_failed"));
+
+    // The amount of listeners is limited
+    for i in 0..MAX_VOTE_LISTENERS - 2 {
+        app.execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddVoteListener {
+                address: format!("listener{i}"),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let err = app
+        .execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddVoteListener {
+                address: "one_too_many".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Can't register more than {MAX_VOTE_LISTENERS} vote listeners")
+    );
+
+    app.execute_contract(
+        assembly_addr.clone(),
+        assembly_addr.clone(),
+        &ExecuteMsg::RemoveVoteListener {
+            address: failing_hook.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let listeners: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::VoteListeners {})
+        .unwrap();
+    assert_eq!(listeners.len(), MAX_VOTE_LISTENERS - 1);
+    assert!(!listeners.contains(&failing_hook));
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    // The failing hook doesn't block the proposal
+    let res = app
+        .execute_contract(
+            Addr::unchecked("user0"),
+            assembly_addr.clone(),
+            &ExecuteMsg::EndProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+    assert!(has_attribute(&res, "hook_status", "passed"));
+    assert!(has_attribute(&res, "action", "proposal_hook_failed"));
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+
+    app.execute_contract(
+        assembly_addr.clone(),
+        assembly_addr.clone(),
+        &ExecuteMsg::RemoveProposalHook {
+            address: failing_hook.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::RemoveProposalHook {
+                address: failing_hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Proposal hook {failing_hook} is not registered")
+    );
+
+    let hooks: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks, vec![hook]);
+}
+
+#[test]
+fn test_out_of_gas_proposal_hook() {
+    let mut deps = mock_dependencies();
+    deps.querier.xastro_total_supply = Uint128::new(1_000);
+    let env = mock_env();
+
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                xastro_token_addr: Addr::unchecked(XASTRO_ADDR),
+                vxastro_token_addr: None,
+                voting_escrow_delegator_addr: None,
+                ibc_controller: None,
+                builder_unlock_addr: Addr::unchecked(BUILDER_UNLOCK_ADDR),
+                proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+                proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+                proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+                proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+                proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD)
+                    .unwrap(),
+                whitelisted_links: vec!["https://some.link/".to_string()],
+                proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+                proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+                links_curator: None,
+                excluded_voters: vec![],
+                builder_power_coefficient: Decimal::one(),
+                xastro_time_weighting: None,
+                signaling_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                emergency_committee: None,
+            },
+        )
+        .unwrap();
+
+    let end_block = env.block.height - 1;
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &Proposal {
+                proposal_id: Uint64::from(1u64),
+                submitter: Addr::unchecked("submitter"),
+                status: ProposalStatus::Active,
+                for_power: Uint128::zero(),
+                against_power: Uint128::zero(),
+                for_voters: vec![],
+                against_voters: vec![],
+                start_block: end_block - PROPOSAL_VOTING_PERIOD,
+                start_time: env.block.time.seconds() - 5 * PROPOSAL_VOTING_PERIOD,
+                end_block,
+                delayed_end_block: end_block + PROPOSAL_EFFECTIVE_DELAY,
+                expiration_block: end_block + PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD,
+                title: "Title".to_string(),
+                deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                ibc_channel: None,
+                forum_thread_id: None,
+            },
+        )
+        .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(env.contract.address.as_str(), &[]),
+        ExecuteMsg::AddProposalHook {
+            address: "hook".to_string(),
+        },
+    )
+    .unwrap();
+
+    // The hook notification is gas limited, so a hook can't spend the gas of the whole transaction
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::EndProposal { proposal_id: 1 },
+    )
+    .unwrap();
+    let hook_msg = res
+        .messages
+        .iter()
+        .find(|msg| msg.reply_on == ReplyOn::Error)
+        .unwrap();
+    assert_eq!(hook_msg.gas_limit, Some(PROPOSAL_HOOK_GAS_LIMIT));
+
+    // The hook ran out of gas. Its error is handled while the proposal stays ended
+    let res = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: hook_msg.id,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "proposal_hook_failed");
+    let proposal = PROPOSALS.load(deps.as_ref().storage, 1).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Rejected);
+}
+
+#[test]
+fn test_ibc_proposal_max_messages() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    deps.querier.base.update_ibc(
+        "wasm.controller",
+        &[mock_ibc_channel(
+            "channel-1",
+            IbcOrder::Unordered,
+            IBC_APP_VERSION,
+        )],
+    );
+    deps.querier.base.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "controller" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&Some(ChannelParams {
+                    timeout: 60,
+                    max_messages: Some(1),
+                    relayer_fee: vec![],
+                }))
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Err(SystemError::NoSuchContract {
+            addr: "unknown".to_string(),
+        }),
+    });
+
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                xastro_token_addr: Addr::unchecked(XASTRO_ADDR),
+                vxastro_token_addr: None,
+                voting_escrow_delegator_addr: None,
+                ibc_controller: Some(Addr::unchecked("controller")),
+                builder_unlock_addr: Addr::unchecked(BUILDER_UNLOCK_ADDR),
+                proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+                proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+                proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+                proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+                proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD)
+                    .unwrap(),
+                whitelisted_links: vec!["https://some.link/".to_string()],
+                proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+                proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+                links_curator: None,
+                excluded_voters: vec![],
+                builder_power_coefficient: Decimal::one(),
+                xastro_time_weighting: None,
+                signaling_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                emergency_committee: None,
+            },
+        )
+        .unwrap();
+    PROPOSAL_COUNT
+        .save(deps.as_mut().storage, &Uint64::zero())
+        .unwrap();
+
+    let submit = |deps: DepsMut, messages_count: usize| {
+        let message: CosmosMsg = BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: coins(1, "uluna"),
+        }
+        .into();
+        execute(
+            deps,
+            env.clone(),
+            mock_info(XASTRO_ADDR, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "submitter".to_string(),
+                amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    messages: Some(vec![message; messages_count]),
+                    ibc_channel: Some("channel-1".to_string()),
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
+                })
+                .unwrap(),
+            }),
+        )
+    };
+
+    // The proposal would never be accepted by the controller, so it's rejected on submission
+    let err = submit(deps.as_mut(), 2).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Channel channel-1 accepts at most 1 messages per proposal"
+    );
+
+    submit(deps.as_mut(), 1).unwrap();
+    assert_eq!(
+        PROPOSAL_COUNT.load(deps.as_ref().storage).unwrap(),
+        Uint64::new(1)
+    );
+}
+
+#[test]
+fn test_remove_completed_proposal_in_batches() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                xastro_token_addr: Addr::unchecked(XASTRO_ADDR),
+                vxastro_token_addr: None,
+                voting_escrow_delegator_addr: None,
+                ibc_controller: None,
+                builder_unlock_addr: Addr::unchecked(BUILDER_UNLOCK_ADDR),
+                proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+                proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+                proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+                proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+                proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD)
+                    .unwrap(),
+                whitelisted_links: vec!["https://some.link/".to_string()],
+                proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+                proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
+                links_curator: None,
+                excluded_voters: vec![],
+                builder_power_coefficient: Decimal::one(),
+                xastro_time_weighting: None,
+                signaling_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                emergency_committee: None,
+            },
+        )
+        .unwrap();
+
+    // A rejected proposal with an entry in every map kept per proposal
+    let storage = deps.as_mut().storage;
+    let end_block = env.block.height - 1;
+    PROPOSALS
+        .save(
+            storage,
+            1,
+            &Proposal {
+                proposal_id: Uint64::from(1u64),
+                submitter: Addr::unchecked("submitter"),
+                status: ProposalStatus::Rejected,
+                for_power: Uint128::zero(),
+                against_power: Uint128::zero(),
+                for_voters: vec![],
+                against_voters: vec![],
+                start_block: end_block - PROPOSAL_VOTING_PERIOD,
+                start_time: env.block.time.seconds() - 5 * PROPOSAL_VOTING_PERIOD,
+                end_block,
+                delayed_end_block: end_block + PROPOSAL_EFFECTIVE_DELAY,
+                expiration_block: end_block + PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD,
+                title: "Title".to_string(),
+                deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                ibc_channel: None,
+                forum_thread_id: None,
+            },
+        )
+        .unwrap();
+    let content = ProposalContent {
+        description: "Description".to_string(),
+        link: None,
+        messages: None,
+    };
+    PROPOSAL_CONTENT.save(storage, 1, &content).unwrap();
+    PROPOSAL_OPTIONS
+        .save(
+            storage,
+            1,
+            &vec![ProposalOption {
+                label: "Yes".to_string(),
+                messages: None,
+            }],
+        )
+        .unwrap();
+    OPTION_TALLIES
+        .save(storage, 1, &vec![Uint128::new(1)])
+        .unwrap();
+    RANKED_PROPOSALS.save(storage, 1, &()).unwrap();
+    WINNING_OPTIONS.save(storage, 1, &0).unwrap();
+    PROPOSAL_BUNDLES.save(storage, 1, &1).unwrap();
+    PROPOSAL_IBC_FORWARD
+        .save(
+            storage,
+            1,
+            &ForwardMetadata {
+                hops: vec!["channel-5".to_string()],
+            },
+        )
+        .unwrap();
+    VOTE_ROOTS.save(storage, 1, &Binary::default()).unwrap();
+    VOTE_TREES.save(storage, 1, &VoteTree::default()).unwrap();
+    BINDING_ROUNDS
+        .save(
+            storage,
+            1,
+            &BindingRound {
+                content,
+                ibc_channel: None,
+            },
+        )
+        .unwrap();
+    TWO_PHASE_PROPOSALS.save(storage, 1, &None).unwrap();
+    SIGNALING_ROUNDS.save(storage, 1, &0).unwrap();
+    RANKED_BALLOTS
+        .save(storage, (1, &[0u8][..]), &Uint128::new(1))
+        .unwrap();
+    for i in 0..MAX_LIMIT + 5 {
+        let voter = Addr::unchecked(format!("voter{i}"));
+        VOTES
+            .save(
+                storage,
+                (1, &voter),
+                &ProposalVote {
+                    option: ProposalVoteOption::Against,
+                    power: Uint128::new(1),
+                },
+            )
+            .unwrap();
+        VOTE_LEAF_INDEXES.save(storage, (1, &voter), &i).unwrap();
+        VOTE_TREE_NODES
+            .save(storage, (1, 0, i), &Binary::default())
+            .unwrap();
+    }
+
+    let remove = |deps: DepsMut| {
+        execute(
+            deps,
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::RemoveCompletedProposal {
+                proposal_id: 1,
+                limit: Some(MAX_LIMIT),
+            },
+        )
+        .unwrap()
+    };
+
+    // The votes are removed in batches of up to MAX_LIMIT entries
+    let mut batches = 0;
+    loop {
+        let res = remove(deps.as_mut());
+        batches += 1;
+        assert!(!PROPOSALS.has(deps.as_ref().storage, 1));
+        if res.attributes.contains(&attr("finished", "true")) {
+            break;
+        }
+    }
+    assert_eq!(batches, 4);
+
+    let storage = deps.as_ref().storage;
+    assert!(!PROPOSAL_CONTENT.has(storage, 1));
+    assert!(!PROPOSAL_OPTIONS.has(storage, 1));
+    assert!(!OPTION_TALLIES.has(storage, 1));
+    assert!(!RANKED_PROPOSALS.has(storage, 1));
+    assert!(!WINNING_OPTIONS.has(storage, 1));
+    assert!(!PROPOSAL_BUNDLES.has(storage, 1));
+    assert!(!PROPOSAL_IBC_FORWARD.has(storage, 1));
+    assert!(!VOTE_ROOTS.has(storage, 1));
+    assert!(!VOTE_TREES.has(storage, 1));
+    assert!(!BINDING_ROUNDS.has(storage, 1));
+    assert!(!TWO_PHASE_PROPOSALS.has(storage, 1));
+    assert!(!SIGNALING_ROUNDS.has(storage, 1));
+    assert!(!PENDING_REMOVALS.has(storage, 1));
+    assert_eq!(
+        VOTES
+            .prefix(1)
+            .keys(storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+    assert_eq!(
+        VOTE_LEAF_INDEXES
+            .prefix(1)
+            .keys(storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+    assert_eq!(
+        VOTE_TREE_NODES
+            .sub_prefix(1)
+            .keys(storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+    assert_eq!(
+        RANKED_BALLOTS
+            .prefix(1)
+            .keys(storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+
+    // Nothing is left to remove
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper", &[]),
+        ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}
+
+#[test]
+fn test_links_curator() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let curator = Addr::unchecked("curator");
+
+    let (_, _, _, _, _, assembly_addr, _) = instantiate_contracts(&mut app, owner, false);
+
+    let update_whitelist = |app: &mut App, sender: &Addr, add: Vec<&str>, remove: Vec<&str>| {
+        app.execute_contract(
+            sender.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::UpdateLinkWhitelist {
+                add: Some(add.into_iter().map(String::from).collect()),
+                remove: Some(remove.into_iter().map(String::from).collect()),
+            },
+            &[],
+        )
+    };
+    let update_curator = |app: &mut App, links_curator: Option<String>, remove: Option<bool>| {
+        app.execute_contract(
+            assembly_addr.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                xastro_token_addr: None,
+                vxastro_token_addr: None,
+                voting_escrow_delegator_addr: None,
+                ibc_controller: None,
+                builder_unlock_addr: None,
+                proposal_voting_period: None,
+                proposal_effective_delay: None,
+                proposal_expiration_period: None,
+                proposal_required_deposit: None,
+                proposal_required_quorum: None,
+                proposal_required_threshold: None,
+                whitelist_add: None,
+                whitelist_remove: None,
+                proposal_max_messages: None,
+                proposal_max_messages_size: None,
+                links_curator,
+                remove_links_curator: remove,
+                excluded_voters_add: None,
+                excluded_voters_remove: None,
+                builder_power_coefficient: None,
+                xastro_time_weighting: None,
+                disable_xastro_time_weighting: None,
+                signaling_required_deposit: None,
+                emergency_committee: None,
+                remove_emergency_committee: None,
+            })),
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Nobody but the Assembly can update the whitelist while there is no curator
+    let err =
+        update_whitelist(&mut app, &curator, vec!["https://other.link/"], vec![]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    update_curator(&mut app, Some(curator.to_string()), None);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.links_curator, Some(curator.clone()));
+
+    update_whitelist(
+        &mut app,
+        &curator,
+        vec!["https://other.link/"],
+        vec!["https://some.link/"],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.whitelisted_links, vec!["https://other.link/"]);
+
+    // The curator can't empty the whitelist
+    let err =
+        update_whitelist(&mut app, &curator, vec![], vec!["https://other.link/"]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Whitelist cannot be empty!");
+
+    // The curator can't update the rest of the config
+    let err = app
+        .execute_contract(
+            curator.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::AddVoteListener {
+                address: curator.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Other addresses still can't update the whitelist
+    let err = update_whitelist(
+        &mut app,
+        &Addr::unchecked("random"),
+        vec!["https://random.link/"],
+        vec![],
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    update_curator(&mut app, None, Some(true));
+
+    let err = update_whitelist(&mut app, &curator, vec!["https://some.link/"], vec![]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+}
+
+#[test]
+fn test_forum_thread_binding() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 3,
+    );
+
+    let submit = |app: &mut App, forum_thread_id: &str| {
+        app.execute_contract(
+            submitter.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    messages: None,
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: Some(forum_thread_id.to_string()),
+                    bundle_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+    };
+
+    let err = submit(&mut app, "thread/42").unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Forum thread id is not properly formatted!"
+    );
+
+    submit(&mut app, "thread-42").unwrap();
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.forum_thread_id, Some("thread-42".to_string()));
+
+    // A competing proposal can't reference the same discussion
+    let err = submit(&mut app, "thread-42").unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Forum thread thread-42 is already referenced by active proposal 1"
+    );
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::EndProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // The thread is released once the proposal isn't active anymore
+    submit(&mut app, "thread-42").unwrap();
+
+    // Removing the ended proposal doesn't release the thread referenced by the new one
+    let blocks = PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD + 1;
+    app.update_block(|bi| {
+        bi.height += blocks;
+        bi.time = bi.time.plus_seconds(5 * blocks);
+    });
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 1,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err = submit(&mut app, "thread-42").unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Forum thread thread-42 is already referenced by active proposal 2"
+    );
+
+    // The thread is released once the proposal which expired without being ended is removed
+    let blocks = PROPOSAL_VOTING_PERIOD + PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD + 1;
+    app.update_block(|bi| {
+        bi.height += blocks;
+        bi.time = bi.time.plus_seconds(5 * blocks);
+    });
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::RemoveCompletedProposal {
+            proposal_id: 2,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+    submit(&mut app, "thread-42").unwrap();
+}
+
+#[test]
+fn test_two_phase_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let voter = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+    mint_tokens(&mut app, &staking_instance, &xastro_addr, &voter, 10_000);
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    app.execute_contract(
+        submitter.clone(),
+        xastro_addr.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: assembly_addr.to_string(),
+            msg: to_binary(&Cw20HookMsg::SubmitTwoPhaseProposal {
+                title: "Title".to_string(),
+                description: "Description".to_string(),
+                link: None,
+                messages: Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: assembly_addr.to_string(),
+                    msg: to_binary(&ExecuteMsg::AddProposalHook {
+                        address: "hook".to_string(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })]),
+                ibc_channel: None,
+                forum_thread_id: Some("thread-1".to_string()),
+            })
+            .unwrap(),
+            amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The signaling round doesn't carry any messages
+    let content: ProposalContent = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalContent { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(content.messages, None);
+
+    let res: TwoPhaseProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::TwoPhaseProposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(res.parent_id, 1);
+    assert_eq!(res.binding, None);
+
+    let end_round = |app: &mut App, proposal_id: u64| {
+        cast_vote(
+            app,
+            assembly_addr.clone(),
+            proposal_id,
+            voter.clone(),
+            ProposalVoteOption::For,
+        )
+        .unwrap();
+
+        app.update_block(|bi| {
+            bi.height += PROPOSAL_VOTING_PERIOD + 1;
+            bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+        });
+
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::EndProposal { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // The binding round opens once the signaling round passes and keeps the deposit
+    end_round(&mut app, 1);
+    check_token_balance(&mut app, &xastro_addr, &submitter, 0);
+
+    let res: TwoPhaseProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::TwoPhaseProposal { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(res.parent_id, 1);
+    assert_eq!(res.signaling.status, ProposalStatus::Passed);
+    let binding = res.binding.unwrap();
+    assert_eq!(binding.proposal_id, Uint64::new(2));
+    assert_eq!(binding.status, ProposalStatus::Active);
+    assert_eq!(binding.forum_thread_id, Some("thread-1".to_string()));
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    end_round(&mut app, 2);
+    check_token_balance(
+        &mut app,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_EFFECTIVE_DELAY + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_EFFECTIVE_DELAY + 1));
+    });
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::ExecuteProposal { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+
+    let hooks: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks, vec![Addr::unchecked("hook")]);
+}
+
+#[test]
+fn test_multi_choice_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner.clone(), false);
+
+    let hook_code = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let listener = app
+        .instantiate_contract(hook_code, owner, &Empty {}, &[], "Listener", None)
+        .unwrap();
+    app.execute_contract(
+        assembly_addr.clone(),
+        assembly_addr.clone(),
+        &ExecuteMsg::AddVoteListener {
+            address: listener.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    for (voter, amount) in [("user1", 6_000), ("user2", 4_000), ("user3", 3_000)] {
+        mint_tokens(
+            &mut app,
+            &staking_instance,
+            &xastro_addr,
+            &Addr::unchecked(voter),
+            amount,
+        );
+    }
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    let option = |label: &str, hook: Option<&str>| ProposalOption {
+        label: label.to_string(),
+        messages: hook.map(|hook| {
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: assembly_addr.to_string(),
+                msg: to_binary(&ExecuteMsg::AddProposalHook {
+                    address: hook.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        }),
+    };
+    let submit = |app: &mut App, options: Vec<ProposalOption>| {
+        app.execute_contract(
+            submitter.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitMultiChoiceProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    options,
+                    ranked: false,
+                    ibc_channel: None,
+                    forum_thread_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+    };
+
+    let err = submit(&mut app, vec![option("Only", None)]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "A multi-choice proposal must have between 2 and 10 options with labels of up to 64 characters!"
+    );
+
+    submit(
+        &mut app,
+        vec![
+            option("Hook A", Some("hook_a")),
+            option("Hook B", Some("hook_b")),
+            option("Nothing", None),
+        ],
+    )
+    .unwrap();
+
+    // Binary votes aren't accepted
+    let err = cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        Addr::unchecked("user1"),
+        ProposalVoteOption::For,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Multi-choice proposals can only be voted for one of their options!"
+    );
+
+    let vote = |app: &mut App, voter: &str, option: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastMultiChoiceVote {
+                proposal_id: 1,
+                option,
+            },
+            &[],
+        )
+    };
+
+    let err = vote(&mut app, "user1", 3).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Option 3 doesn't exist!");
+
+    // Listeners are notified of multi-choice votes
+    let res = vote(&mut app, "user1", 1).unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "listener_vote" && attr.value == "choice_1")));
+    vote(&mut app, "user2", 0).unwrap();
+    // Options can also be chosen through the shared vote path used by operators and signed votes
+    cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        Addr::unchecked("user3"),
+        ProposalVoteOption::Choice(2),
+    )
+    .unwrap();
+
+    let err = vote(&mut app, "user1", 0).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "User already voted!");
+    let err = cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        Addr::unchecked("user2"),
+        ProposalVoteOption::Choice(1),
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "User already voted!");
+
+    let user_vote: Option<ProposalVote> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::UserVote {
+                proposal_id: 1,
+                user: "user3".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user_vote,
+        Some(ProposalVote {
+            option: ProposalVoteOption::Choice(2),
+            power: Uint128::new(3_000),
+        })
+    );
+
+    let voters: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalVoters {
+                proposal_id: 1,
+                vote_option: ProposalVoteOption::Choice(0),
+                start: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(voters, vec![Addr::unchecked("user2")]);
+
+    let tally: TallyCheckResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::CheckTally { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(tally.is_consistent);
+    assert_eq!(
+        tally.recorded_option_powers,
+        vec![
+            Uint128::new(4_000),
+            Uint128::new(6_000),
+            Uint128::new(3_000)
+        ]
+    );
+    assert_eq!(tally.option_powers, tally.recorded_option_powers);
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::EndProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let tally: MultiChoiceTallyResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::MultiChoiceTally { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        tally.options,
+        vec![
+            OptionTally {
+                label: "Hook A".to_string(),
+                power: Uint128::new(4_000)
+            },
+            OptionTally {
+                label: "Hook B".to_string(),
+                power: Uint128::new(6_000)
+            },
+            OptionTally {
+                label: "Nothing".to_string(),
+                power: Uint128::new(3_000)
+            },
+        ]
+    );
+    assert_eq!(tally.winning_option, Some(1));
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_EFFECTIVE_DELAY + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_EFFECTIVE_DELAY + 1));
+    });
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Only the winning option's messages are executed
+    let hooks: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks, vec![Addr::unchecked("hook_b")]);
+}
+
+#[test]
+fn test_ranked_choice_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    for (voter, amount) in [
+        ("user1", 6_000),
+        ("user2", 4_000),
+        ("user3", 3_000),
+        ("user4", 2_500),
+    ] {
+        mint_tokens(
+            &mut app,
+            &staking_instance,
+            &xastro_addr,
+            &Addr::unchecked(voter),
+            amount,
+        );
+    }
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    let submit = |app: &mut App, labels: &[&str]| {
+        app.execute_contract(
+            submitter.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitMultiChoiceProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    options: labels
+                        .iter()
+                        .map(|label| ProposalOption {
+                            label: label.to_string(),
+                            messages: None,
+                        })
+                        .collect(),
+                    ranked: true,
+                    ibc_channel: None,
+                    forum_thread_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+    };
+
+    let err = submit(&mut app, &["A", "B", "C", "D", "E", "F"]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Ranked-choice proposals can't have more than 5 options!"
+    );
+
+    submit(&mut app, &["A", "B", "C", "D"]).unwrap();
+
+    let vote = |app: &mut App, voter: &str, ranking: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastRankedVote {
+                proposal_id: 1,
+                ranking,
+            },
+            &[],
+        )
+    };
+
+    let err = vote(&mut app, "user2", vec![1, 2, 1]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "A ranking must list distinct options!"
+    );
+    let err = vote(&mut app, "user2", vec![]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "A ranking must list distinct options!"
+    );
+    let err = vote(&mut app, "user2", vec![1, 4]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Option 4 doesn't exist!");
+
+    // A single choice ballot is a ranking of one option
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        assembly_addr.clone(),
+        &ExecuteMsg::CastMultiChoiceVote {
+            proposal_id: 1,
+            option: 0,
+        },
+        &[],
+    )
+    .unwrap();
+    vote(&mut app, "user2", vec![1, 2]).unwrap();
+    vote(&mut app, "user3", vec![2, 1]).unwrap();
+    vote(&mut app, "user4", vec![3, 1]).unwrap();
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::EndProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // "A" leads the first preferences, but "B" wins once "D" and then "C" are eliminated
+    let tally: MultiChoiceTallyResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::MultiChoiceTally { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        tally
+            .options
+            .iter()
+            .map(|option| option.power.u128())
+            .collect::<Vec<_>>(),
+        vec![6_000, 4_000, 3_000, 2_500]
+    );
+    assert_eq!(tally.winning_option, Some(1));
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+
+    // Ranked ballots are rejected by plain multi-choice proposals
+    app.execute_contract(
+        submitter.clone(),
+        xastro_addr.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: assembly_addr.to_string(),
+            msg: to_binary(&Cw20HookMsg::SubmitMultiChoiceProposal {
+                title: "Title".to_string(),
+                description: "Description".to_string(),
+                link: None,
+                options: vec![
+                    ProposalOption {
+                        label: "A".to_string(),
+                        messages: None,
+                    },
+                    ProposalOption {
+                        label: "B".to_string(),
+                        messages: None,
+                    },
+                ],
+                ranked: false,
+                ibc_channel: None,
+                forum_thread_id: None,
+            })
+            .unwrap(),
+            amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user1"),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastRankedVote {
+                proposal_id: 2,
+                ranking: vec![0, 1],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposal doesn't accept ranked ballots!"
+    );
+}
+
+#[test]
+fn test_cast_vote_by_sig() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let pubkey = signing_key.verifying_key().to_encoded_point(true);
+    let voter = bech32::encode(
+        "terra",
+        Ripemd160::digest(Sha256::digest(pubkey.as_bytes())).to_base32(),
+        Variant::Bech32,
+    )
+    .unwrap();
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &Addr::unchecked(&voter),
+        5_000,
+    );
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        submitter.clone(),
+        None,
+    );
+    create_proposal(&mut app, &xastro_addr, &assembly_addr, submitter, None);
+
+    let chain_id = app.block_info().chain_id;
+    let sign = |key: &SigningKey, proposal_id: u64, nonce: u64| {
+        let payload = VoteBySigPayload {
+            chain_id: chain_id.clone(),
+            contract: assembly_addr.to_string(),
+            voter: voter.clone(),
+            proposal_id,
+            vote: ProposalVoteOption::For,
+            nonce,
+        };
+        let signature: recoverable::Signature = key.sign(&to_vec(&payload).unwrap());
+        Binary::from(signature.as_ref().to_vec())
+    };
+    let relay = |app: &mut App, proposal_id: u64, signature: Binary, nonce: u64| {
+        app.execute_contract(
+            Addr::unchecked("relayer"),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastVoteBySig {
+                voter: voter.clone(),
+                proposal_id,
+                vote: ProposalVoteOption::For,
+                signature,
+                nonce,
+            },
+            &[],
+        )
+    };
+
+    // A vote signed by another key is rejected
+    let other_key = SigningKey::from_bytes(&[8u8; 32]).unwrap();
+    let err = relay(&mut app, 1, sign(&other_key, 1, 0), 0).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid vote signature!");
+
+    // The signature covers the proposal
+    let err = relay(&mut app, 2, sign(&signing_key, 1, 0), 0).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid vote signature!");
+
+    let signature = sign(&signing_key, 1, 0);
+    relay(&mut app, 1, signature.clone(), 0).unwrap();
+
+    let vote: Option<ProposalVote> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::UserVote {
+                proposal_id: 1,
+                user: voter.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote,
+        Some(ProposalVote {
+            option: ProposalVoteOption::For,
+            power: Uint128::new(5_000),
+        })
+    );
+
+    // The signature can't be replayed
+    let err = relay(&mut app, 1, signature, 0).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid nonce, expected 1!");
+
+    let nonce: u64 = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::VoteNonce {
+                voter: voter.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nonce, 1);
+
+    relay(&mut app, 2, sign(&signing_key, 2, 1), 1).unwrap();
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(proposal.for_power, Uint128::new(5_000));
+    assert_eq!(proposal.for_voters, vec![Addr::unchecked(&voter)]);
+}
+
+#[test]
+fn test_vote_operator() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let grantor = Addr::unchecked("user1");
+    let operator = Addr::unchecked("operator");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 3,
+    );
+    mint_tokens(&mut app, &staking_instance, &xastro_addr, &grantor, 5_000);
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    for _ in 0..3 {
+        create_proposal(
+            &mut app,
+            &xastro_addr,
+            &assembly_addr,
+            submitter.clone(),
+            None,
+        );
+    }
+
+    let grant = |app: &mut App, expires: Option<u64>| {
+        app.execute_contract(
+            grantor.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::GrantVoteOperator {
+                operator: operator.to_string(),
+                expires,
+            },
+            &[],
+        )
+    };
+    let vote_as = |app: &mut App, sender: &Addr, proposal_id: u64| {
+        app.execute_contract(
+            sender.clone(),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastVoteAsOperator {
+                grantor: grantor.to_string(),
+                proposal_id,
+                vote: ProposalVoteOption::Against,
+            },
+            &[],
+        )
+    };
+
+    let now = app.block_info().time.seconds();
+    let err = grant(&mut app, Some(now)).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Grant expiration must be in the future!"
+    );
+
+    grant(&mut app, None).unwrap();
+
+    let grants: Vec<VoteOperatorGrant> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::VoteOperators {
+                grantor: grantor.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        grants,
+        vec![VoteOperatorGrant {
+            operator: operator.clone(),
+            expires: None,
+        }]
+    );
+
+    let err = vote_as(&mut app, &Addr::unchecked("stranger"), 1).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "stranger isn't allowed to vote on behalf of user1!"
+    );
+
+    // The vote is cast with the grantor's power, which the grantor keeps
+    vote_as(&mut app, &operator, 1).unwrap();
+    let vote: Option<ProposalVote> = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::UserVote {
+                proposal_id: 1,
+                user: grantor.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote,
+        Some(ProposalVote {
+            option: ProposalVoteOption::Against,
+            power: Uint128::new(5_000),
+        })
+    );
+    check_user_vp(&mut app, &assembly_addr, &grantor, 2, 5_000);
+
+    let err = vote_as(&mut app, &operator, 1).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "User already voted!");
+
+    app.execute_contract(
+        grantor.clone(),
+        assembly_addr.clone(),
+        &ExecuteMsg::RevokeVoteOperator {
+            operator: operator.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = vote_as(&mut app, &operator, 2).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "operator isn't allowed to vote on behalf of user1!"
+    );
+
+    // Expired grants can't be used
+    grant(&mut app, Some(now + 10)).unwrap();
+    app.update_block(|bi| {
+        bi.height += 2;
+        bi.time = bi.time.plus_seconds(10);
+    });
+    let err = vote_as(&mut app, &operator, 2).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "operator isn't allowed to vote on behalf of user1!"
+    );
+
+    // The grantor can still vote directly
+    cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        2,
+        grantor.clone(),
+        ProposalVoteOption::For,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_voting_power_at_point() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let user = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+    mint_tokens(&mut app, &staking_instance, &xastro_addr, &user, 1_000);
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+    let first_point = VotingPowerPoint {
+        height: app.block_info().height,
+        time: app.block_info().time.seconds(),
+    };
+
+    mint_tokens(&mut app, &staking_instance, &xastro_addr, &user, 2_000);
+    create_proposal(&mut app, &xastro_addr, &assembly_addr, submitter, None);
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+    let second_point = VotingPowerPoint {
+        height: app.block_info().height,
+        time: app.block_info().time.seconds(),
+    };
+
+    let user_vp = |app: &App, proposal_id: Option<u64>, at: Option<VotingPowerPoint>| {
+        app.wrap().query_wasm_smart::<Uint128>(
+            assembly_addr.clone(),
+            &QueryMsg::UserVotingPower {
+                user: user.to_string(),
+                proposal_id,
+                at,
+            },
+        )
+    };
+    let total_vp = |app: &App, proposal_id: Option<u64>, at: Option<VotingPowerPoint>| {
+        app.wrap().query_wasm_smart::<Uint128>(
+            assembly_addr.clone(),
+            &QueryMsg::TotalVotingPower { proposal_id, at },
+        )
+    };
+
+    assert_eq!(
+        user_vp(&app, None, Some(first_point)).unwrap(),
+        Uint128::new(1_000)
+    );
+    assert_eq!(
+        user_vp(&app, None, Some(second_point.clone())).unwrap(),
+        Uint128::new(3_000)
+    );
+
+    // A point equal to the proposal start yields the proposal's voting power
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    let proposal_point = VotingPowerPoint {
+        height: proposal.start_block,
+        time: proposal.start_time,
+    };
+    assert_eq!(
+        user_vp(&app, Some(1), None).unwrap(),
+        user_vp(&app, None, Some(proposal_point.clone())).unwrap()
+    );
+    assert_eq!(
+        total_vp(&app, Some(1), None).unwrap(),
+        total_vp(&app, None, Some(proposal_point)).unwrap()
+    );
+
+    // The override takes precedence over the proposal
+    assert_eq!(
+        user_vp(&app, Some(1), Some(second_point)).unwrap(),
+        Uint128::new(3_000)
+    );
+
+    let err = total_vp(&app, None, None).unwrap_err();
+    assert_eq!(
+        StdError::generic_err(
+            "Querier contract error: Generic error: Either proposal_id or at must be specified!"
+        ),
+        err
+    );
+}
+
+#[test]
+fn test_proposal_targets() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+
+    let execute = |contract_addr: &str| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&ExecuteMsg::CheckMessagesPassed {}).unwrap(),
+            funds: vec![],
+        })
+    };
+    let send = |to_address: &str| {
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: to_address.to_string(),
+            amount: coins(100, "uluna"),
+        })
+    };
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        submitter,
+        Some(vec![
+            execute("contract2"),
+            send("recipient"),
+            execute("contract1"),
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: "contract3".to_string(),
+                new_code_id: 2,
+                msg: to_binary(&Empty {}).unwrap(),
+            }),
+            send("recipient"),
+            execute("contract2"),
+        ]),
+    );
+
+    let targets: ProposalTargetsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalTargets { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        targets,
+        ProposalTargetsResponse {
+            contracts: vec![
+                "contract1".to_string(),
+                "contract2".to_string(),
+                "contract3".to_string(),
+            ],
+            bank_recipients: vec!["recipient".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_vote_proof() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+    let voters = [
+        ("user1", 6_000, ProposalVoteOption::For),
+        ("user2", 4_000, ProposalVoteOption::Against),
+        ("user3", 3_000, ProposalVoteOption::For),
+    ];
+    for (voter, amount, _) in &voters {
+        mint_tokens(
+            &mut app,
+            &staking_instance,
+            &xastro_addr,
+            &Addr::unchecked(*voter),
+            *amount,
+        );
+    }
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    create_proposal(&mut app, &xastro_addr, &assembly_addr, submitter, None);
+
+    for (voter, _, option) in &voters {
+        cast_vote(
+            &mut app,
+            assembly_addr.clone(),
+            1,
+            Addr::unchecked(*voter),
+            option.clone(),
+        )
+        .unwrap();
+    }
+
+    let query_proof = |app: &App, voter: &str| {
+        app.wrap().query_wasm_smart::<VoteProofResponse>(
+            assembly_addr.clone(),
+            &QueryMsg::VoteProof {
+                proposal_id: 1,
+                voter: voter.to_string(),
+            },
+        )
+    };
+
+    let err = query_proof(&app, "user1").unwrap_err();
+    assert_eq!(
+        StdError::generic_err(
+            "Querier contract error: Generic error: Proposal votes aren't committed!"
+        ),
+        err
+    );
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    let commit_votes = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::CommitVotes {
+                proposal_id: 1,
+                limit: Some(1),
+            },
+            &[],
+        )
+    };
+
+    let err = commit_votes(&mut app).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposal 1 has no vote tree to build!"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        assembly_addr.clone(),
+        &ExecuteMsg::EndProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // The tree is built one node at a time: 3 leaves, a final empty leaf page and 3 inner nodes
+    for _ in 0..6 {
+        commit_votes(&mut app).unwrap();
+        query_proof(&app, "user1").unwrap_err();
+    }
+    commit_votes(&mut app).unwrap();
+
+    let err = commit_votes(&mut app).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Vote tree of proposal 1 is already built!"
+    );
+
+    for (voter, amount, option) in &voters {
+        let res = query_proof(&app, voter).unwrap();
+        let voter = Addr::unchecked(*voter);
+        assert_eq!(
+            res.vote,
+            ProposalVote {
+                option: option.clone(),
+                power: Uint128::new(*amount),
+            }
+        );
+        assert!(verify_vote_proof(&res.root, &voter, &res.vote, &res.proof));
+
+        // A tampered vote doesn't match the committed root
+        let tampered = ProposalVote {
+            option: option.clone(),
+            power: Uint128::new(amount + 1),
+        };
+        assert!(!verify_vote_proof(&res.root, &voter, &tampered, &res.proof));
+    }
+
+    query_proof(&app, "user4").unwrap_err();
+}
+
+#[test]
+fn test_multi_choice_vote_proof() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    let voters = [
+        ("user1", 6_000, 1),
+        ("user2", 4_000, 0),
+        ("user3", 3_000, 1),
+    ];
+    for (voter, amount, _) in &voters {
+        mint_tokens(
+            &mut app,
+            &staking_instance,
+            &xastro_addr,
+            &Addr::unchecked(*voter),
+            *amount,
+        );
+    }
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    app.execute_contract(
+        submitter.clone(),
+        xastro_addr.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: assembly_addr.to_string(),
+            msg: to_binary(&Cw20HookMsg::SubmitMultiChoiceProposal {
+                title: "Title".to_string(),
+                description: "Description".to_string(),
+                link: None,
+                options: ["A", "B"]
+                    .into_iter()
+                    .map(|label| ProposalOption {
+                        label: label.to_string(),
+                        messages: None,
+                    })
+                    .collect(),
+                ranked: false,
+                ibc_channel: None,
+                forum_thread_id: None,
+            })
+            .unwrap(),
+            amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+        },
+        &[],
+    )
+    .unwrap();
+    // Nobody votes on the second proposal
+    create_proposal(&mut app, &xastro_addr, &assembly_addr, submitter, None);
+
+    for (voter, _, option) in &voters {
+        app.execute_contract(
+            Addr::unchecked(*voter),
+            assembly_addr.clone(),
+            &ExecuteMsg::CastMultiChoiceVote {
+                proposal_id: 1,
+                option: *option,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    let end_proposal = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::EndProposal { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
+    let commit_votes = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::CommitVotes {
+                proposal_id,
+                limit: None,
+            },
+            &[],
+        )
+    };
+
+    // Leaves and inner nodes are stored by separate calls
+    end_proposal(&mut app, 1);
+    commit_votes(&mut app, 1).unwrap();
+    commit_votes(&mut app, 1).unwrap();
+    for (voter, amount, option) in &voters {
+        let res: VoteProofResponse = app
+            .wrap()
+            .query_wasm_smart(
+                assembly_addr.clone(),
+                &QueryMsg::VoteProof {
+                    proposal_id: 1,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap();
+        let voter = Addr::unchecked(*voter);
+        assert_eq!(
+            res.vote,
+            ProposalVote {
+                option: ProposalVoteOption::Choice(*option),
+                power: Uint128::new(*amount),
+            }
+        );
+        assert!(verify_vote_proof(&res.root, &voter, &res.vote, &res.proof));
+
+        // The chosen option is part of the leaf
+        let tampered = ProposalVote {
+            option: ProposalVoteOption::Choice(1 - option),
+            power: Uint128::new(*amount),
+        };
+        assert!(!verify_vote_proof(&res.root, &voter, &tampered, &res.proof));
+    }
+
+    // A proposal without votes commits to the empty root at once
+    end_proposal(&mut app, 2);
+    let res = commit_votes(&mut app, 2).unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "vote_root"
+            && attr.value == Binary::from(EMPTY_VOTE_ROOT.to_vec()).to_base64())));
+    let err = commit_votes(&mut app, 2).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Vote tree of proposal 2 is already built!"
+    );
+}
+
+#[test]
+fn test_emergency_committee() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let committee = Addr::unchecked("committee");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let voter = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &voter,
+        PROPOSAL_REQUIRED_DEPOSIT * 4,
+    );
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    app.execute_contract(
+        assembly_addr.clone(),
+        assembly_addr.clone(),
+        &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+            xastro_token_addr: None,
+            vxastro_token_addr: None,
+            voting_escrow_delegator_addr: None,
+            ibc_controller: None,
+            builder_unlock_addr: None,
+            proposal_voting_period: None,
+            proposal_effective_delay: None,
+            proposal_expiration_period: None,
+            proposal_required_deposit: None,
+            proposal_required_quorum: None,
+            proposal_required_threshold: None,
+            whitelist_add: None,
+            whitelist_remove: None,
+            proposal_max_messages: None,
+            proposal_max_messages_size: None,
+            links_curator: None,
+            remove_links_curator: None,
+            excluded_voters_add: None,
+            excluded_voters_remove: None,
+            builder_power_coefficient: None,
+            xastro_time_weighting: None,
+            disable_xastro_time_weighting: None,
+            signaling_required_deposit: None,
+            emergency_committee: Some(committee.to_string()),
+            remove_emergency_committee: None,
+        })),
+        &[],
+    )
+    .unwrap();
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        submitter.clone(),
+        None,
+    );
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| {
+        app.execute_contract(sender.clone(), assembly_addr.clone(), &msg, &[])
+    };
+
+    let err = execute(&mut app, &voter, ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    execute(&mut app, &committee, ExecuteMsg::Pause {}).unwrap();
+
+    let paused_until: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::PausedUntil {})
+        .unwrap();
+    let paused_until = paused_until.unwrap();
+    assert_eq!(
+        paused_until,
+        app.block_info().time.seconds() + EMERGENCY_PAUSE_PERIOD
+    );
+
+    // New proposals and votes are rejected while paused
+    let err = app
+        .execute_contract(
+            submitter.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    messages: None,
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: None,
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("New proposals and votes are paused until {paused_until}!")
+    );
+    let err = cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        voter.clone(),
+        ProposalVoteOption::For,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("New proposals and votes are paused until {paused_until}!")
+    );
+
+    let err = execute(&mut app, &committee, ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!(
+            "The Assembly can't be paused again before {}!",
+            paused_until + EMERGENCY_PAUSE_PERIOD
+        )
+    );
+
+    // The Assembly can lift the pause as well
+    execute(&mut app, &assembly_addr, ExecuteMsg::Unpause {}).unwrap();
+    let err = execute(&mut app, &committee, ExecuteMsg::Unpause {}).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The Assembly isn't paused!");
+
+    cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        voter.clone(),
+        ProposalVoteOption::For,
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    // Only passed proposals can be vetoed
+    let err = execute(
+        &mut app,
+        &committee,
+        ExecuteMsg::VetoProposal { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Proposal not passed!");
+
+    execute(&mut app, &voter, ExecuteMsg::EndProposal { proposal_id: 1 }).unwrap();
+
+    let err = execute(
+        &mut app,
+        &voter,
+        ExecuteMsg::VetoProposal { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    execute(
+        &mut app,
+        &committee,
+        ExecuteMsg::VetoProposal { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Rejected);
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_EFFECTIVE_DELAY + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_EFFECTIVE_DELAY + 1));
+    });
+    let err = execute(
+        &mut app,
+        &voter,
+        ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Proposal not passed!");
+}
+
+#[test]
+fn test_committee_cant_veto_its_removal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let committee = Addr::unchecked("committee");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let voter = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 3,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &voter,
+        PROPOSAL_REQUIRED_DEPOSIT * 6,
+    );
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    let committee_config = |emergency_committee: Option<String>, remove: Option<bool>| {
+        ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+            xastro_token_addr: None,
+            vxastro_token_addr: None,
+            voting_escrow_delegator_addr: None,
+            ibc_controller: None,
+            builder_unlock_addr: None,
+            proposal_voting_period: None,
+            proposal_effective_delay: None,
+            proposal_expiration_period: None,
+            proposal_required_deposit: None,
+            proposal_required_quorum: None,
+            proposal_required_threshold: None,
+            whitelist_add: None,
+            whitelist_remove: None,
+            proposal_max_messages: None,
+            proposal_max_messages_size: None,
+            links_curator: None,
+            remove_links_curator: None,
+            excluded_voters_add: None,
+            excluded_voters_remove: None,
+            builder_power_coefficient: None,
+            xastro_time_weighting: None,
+            disable_xastro_time_weighting: None,
+            signaling_required_deposit: None,
+            emergency_committee,
+            remove_emergency_committee: remove,
+        }))
+    };
+
+    app.execute_contract(
+        assembly_addr.clone(),
+        assembly_addr.clone(),
+        &committee_config(Some(committee.to_string()), None),
+        &[],
+    )
+    .unwrap();
+
+    // 1: removes the committee, 2: calls the committee contract, 3: changes nothing
+    let proposal_messages = [
+        Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: assembly_addr.to_string(),
+            msg: to_binary(&committee_config(None, Some(true))).unwrap(),
+            funds: vec![],
+        })]),
+        Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: committee.to_string(),
+            msg: to_binary(&Empty {}).unwrap(),
+            funds: vec![],
+        })]),
+        None,
+    ];
+    for messages in proposal_messages {
+        create_proposal(
+            &mut app,
+            &xastro_addr,
+            &assembly_addr,
+            submitter.clone(),
+            messages,
+        );
+    }
+    for proposal_id in 1..=3 {
+        cast_vote(
+            &mut app,
+            assembly_addr.clone(),
+            proposal_id,
+            voter.clone(),
+            ProposalVoteOption::For,
+        )
+        .unwrap();
+    }
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    let execute = |app: &mut App, sender: &Addr, msg: ExecuteMsg| {
+        app.execute_contract(sender.clone(), assembly_addr.clone(), &msg, &[])
+    };
+
+    for proposal_id in 1..=3 {
+        execute(&mut app, &voter, ExecuteMsg::EndProposal { proposal_id }).unwrap();
+    }
+
+    for proposal_id in 1..=2 {
+        let err = execute(
+            &mut app,
+            &committee,
+            ExecuteMsg::VetoProposal { proposal_id },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Proposals that change the emergency committee can't be vetoed!"
+        );
+    }
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_EFFECTIVE_DELAY + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_EFFECTIVE_DELAY + 1));
+    });
+
+    // Executable proposals can't be vetoed anymore
+    let err = execute(
+        &mut app,
+        &committee,
+        ExecuteMsg::VetoProposal { proposal_id: 3 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposals can only be vetoed before their execution delay ends!"
+    );
+
+    execute(
+        &mut app,
+        &voter,
+        ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.emergency_committee, None);
+}
+#[test]
+fn test_sudo() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let voter = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 2,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &voter,
+        PROPOSAL_REQUIRED_DEPOSIT * 4,
+    );
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    create_proposal(
+        &mut app,
+        &xastro_addr,
+        &assembly_addr,
+        submitter.clone(),
+        None,
+    );
+
+    let err = app
+        .wasm_sudo(assembly_addr.clone(), &SudoMsg::Unpause {})
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The Assembly isn't paused!");
+
+    app.wasm_sudo(assembly_addr.clone(), &SudoMsg::Pause {})
+        .unwrap();
+
+    // New proposals and votes are rejected until the chain unpauses
+    let submit_msg = build_submit_proposal_msg(
+        &xastro_addr,
+        &assembly_addr,
+        Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+        "Test title!",
+        "Test description!",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let err = app.execute(submitter.clone(), submit_msg).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "New proposals and votes are paused by the chain!"
+    );
+    let err = cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        voter.clone(),
+        ProposalVoteOption::For,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "New proposals and votes are paused by the chain!"
+    );
+
+    app.wasm_sudo(assembly_addr.clone(), &SudoMsg::Unpause {})
+        .unwrap();
+    cast_vote(
+        &mut app,
+        assembly_addr.clone(),
+        1,
+        voter.clone(),
+        ProposalVoteOption::For,
+    )
+    .unwrap();
+
+    // The chain ends the voting period right away
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+    app.wasm_sudo(
+        assembly_addr.clone(),
+        &SudoMsg::ForceEndProposal { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    let height = app.block_info().height;
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+    assert_eq!(proposal.end_block, height - 1);
+    assert_eq!(
+        proposal.delayed_end_block,
+        height - 1 + PROPOSAL_EFFECTIVE_DELAY
+    );
+    assert_eq!(
+        proposal.expiration_block,
+        height - 1 + PROPOSAL_EFFECTIVE_DELAY + PROPOSAL_EXPIRATION_PERIOD
+    );
+
+    let err = app
+        .wasm_sudo(
+            assembly_addr.clone(),
+            &SudoMsg::ForceEndProposal { proposal_id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Proposal not active!");
+
+    // The chain updates parameters bypassing the governance process
+    app.wasm_sudo(
+        assembly_addr.clone(),
+        &SudoMsg::ForceConfig(Box::new(UpdateConfig {
+            xastro_token_addr: None,
+            vxastro_token_addr: None,
+            voting_escrow_delegator_addr: None,
+            ibc_controller: None,
+            builder_unlock_addr: None,
+            proposal_voting_period: None,
+            proposal_effective_delay: None,
+            proposal_expiration_period: None,
+            proposal_required_deposit: None,
+            proposal_required_quorum: None,
+            proposal_required_threshold: None,
+            whitelist_add: Some(vec!["https://other.link/".to_string()]),
+            whitelist_remove: None,
+            proposal_max_messages: None,
+            proposal_max_messages_size: None,
+            links_curator: None,
+            remove_links_curator: None,
+            excluded_voters_add: None,
+            excluded_voters_remove: None,
+            builder_power_coefficient: None,
+            xastro_time_weighting: None,
+            disable_xastro_time_weighting: None,
+            signaling_required_deposit: None,
+            emergency_committee: None,
+            remove_emergency_committee: None,
+        })),
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(assembly_addr, &QueryMsg::Config {})
+        .unwrap();
+    assert!(config
+        .whitelisted_links
+        .contains(&"https://other.link/".to_string()));
+}
+
+#[test]
+fn test_proposal_bundle() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    let other = Addr::unchecked("user1");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 4,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &other,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    );
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &Addr::unchecked("user2"),
+        10_000,
+    );
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    let submit = |app: &mut App, sender: &Addr, hook: &str, bundle_id: u64| {
+        app.execute_contract(
+            sender.clone(),
+            xastro_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.to_string(),
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    link: None,
+                    messages: Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: assembly_addr.to_string(),
+                        msg: to_binary(&ExecuteMsg::AddProposalHook {
+                            address: hook.to_string(),
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    })]),
+                    ibc_channel: None,
+                    ibc_forward: None,
+                    forum_thread_id: None,
+                    bundle_id: Some(bundle_id),
+                })
+                .unwrap(),
+                amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+            },
+            &[],
+        )
+    };
+
+    submit(&mut app, &submitter, "hook_a", 7).unwrap();
+    submit(&mut app, &submitter, "hook_b", 7).unwrap();
+
+    // Only the bundle creator can add proposals to it
+    let err = submit(&mut app, &other, "hook_c", 7).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Bundle 7 belongs to another submitter!"
+    );
+
+    submit(&mut app, &submitter, "hook_c", 8).unwrap();
+    submit(&mut app, &submitter, "hook_d", 8).unwrap();
+
+    let bundle: ProposalBundle = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::ProposalBundle { bundle_id: 7 },
+        )
+        .unwrap();
+    assert_eq!(
+        bundle,
+        ProposalBundle {
+            submitter: submitter.clone(),
+            proposal_ids: vec![1, 2],
+        }
+    );
+
+    for (proposal_id, vote) in [
+        (1, ProposalVoteOption::For),
+        (2, ProposalVoteOption::For),
+        (3, ProposalVoteOption::For),
+        (4, ProposalVoteOption::Against),
+    ] {
+        cast_vote(
+            &mut app,
+            assembly_addr.clone(),
+            proposal_id,
+            Addr::unchecked("user2"),
+            vote,
+        )
+        .unwrap();
+    }
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_VOTING_PERIOD + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_VOTING_PERIOD + 1));
+    });
+
+    for proposal_id in 1..=4 {
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::EndProposal { proposal_id },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.update_block(|bi| {
+        bi.height += PROPOSAL_EFFECTIVE_DELAY + 1;
+        bi.time = bi.time.plus_seconds(5 * (PROPOSAL_EFFECTIVE_DELAY + 1));
+    });
+
+    let execute_bundle = |app: &mut App, proposal_ids: Vec<u64>| {
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::ExecuteBundle { proposal_ids },
+            &[],
+        )
+    };
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            assembly_addr.clone(),
+            &ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposal belongs to bundle 7 and can only be executed with execute_bundle!"
+    );
+
+    let err = execute_bundle(&mut app, vec![1]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposals don't form a complete bundle!"
+    );
+    let err = execute_bundle(&mut app, vec![2, 3]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Proposals don't form a complete bundle!"
+    );
+
+    // A rejected member blocks the whole bundle
+    let err = execute_bundle(&mut app, vec![3, 4]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Proposal not passed!");
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::Proposal { proposal_id: 3 },
+        )
+        .unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+
+    execute_bundle(&mut app, vec![2, 1]).unwrap();
+
+    for proposal_id in [1, 2] {
+        let proposal: Proposal = app
+            .wrap()
+            .query_wasm_smart(assembly_addr.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+    }
+
+    let hooks: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(assembly_addr.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(
+        hooks,
+        vec![Addr::unchecked("hook_a"), Addr::unchecked("hook_b")]
+    );
+
+    let err = execute_bundle(&mut app, vec![1, 2]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Proposal not passed!");
+}
+
+#[test]
+fn test_proposals_pagination() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let (_, staking_instance, xastro_addr, _, _, assembly_addr, _) =
+        instantiate_contracts(&mut app, owner, false);
+
+    let submitter = Addr::unchecked("user0");
+    mint_tokens(
+        &mut app,
+        &staking_instance,
+        &xastro_addr,
+        &submitter,
+        PROPOSAL_REQUIRED_DEPOSIT * 3,
+    );
+
+    for _ in 0..3 {
+        create_proposal(
+            &mut app,
+            &xastro_addr,
+            &assembly_addr,
+            submitter.clone(),
+            None,
+        );
+    }
+
+    let query_ids = |app: &App, start: Option<u64>, start_after: Option<u64>, limit| {
+        let res: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                assembly_addr.clone(),
+                &QueryMsg::Proposals {
+                    start,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        res.proposal_list
+            .iter()
+            .map(|proposal| proposal.proposal_id.u64())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(query_ids(&app, None, None, None), vec![1, 2, 3]);
+    // Deprecated `start` is inclusive
+    assert_eq!(query_ids(&app, Some(2), None, None), vec![2, 3]);
+    // `start_after` is exclusive
+    assert_eq!(query_ids(&app, None, Some(1), None), vec![2, 3]);
+    assert_eq!(query_ids(&app, None, Some(1), Some(1)), vec![2]);
+    assert_eq!(query_ids(&app, None, Some(3), None), Vec::<u64>::new());
+    assert_eq!(query_ids(&app, None, Some(0), None), vec![1, 2, 3]);
+    // `start_after` takes precedence over `start`
+    assert_eq!(query_ids(&app, Some(1), Some(2), None), vec![3]);
+
+    let res: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            assembly_addr.clone(),
+            &QueryMsg::LatestProposals { limit: Some(2) },
+        )
+        .unwrap();
+    assert_eq!(res.proposal_count, Uint64::new(3));
+    assert_eq!(
+        res.proposal_list
+            .iter()
+            .map(|proposal| proposal.proposal_id.u64())
+            .collect::<Vec<_>>(),
+        vec![3, 2]
+    );
+}
+
+#[test]
+fn test_reindex_legacy_proposals() {
+    let mut deps = mock_dependencies();
+
+    // Proposals saved without any index entries, as they were before the indexes existed
+    let legacy_proposals = vec![
+        (1u64, "alice", ProposalStatus::Active),
+        (2u64, "bob", ProposalStatus::Passed),
+        (3u64, "alice", ProposalStatus::Rejected),
+    ];
+
+    for (id, submitter, status) in legacy_proposals {
+        PROPOSALS
+            .save(
+                deps.as_mut().storage,
+                id,
+                &Proposal {
+                    proposal_id: Uint64::from(id),
+                    submitter: Addr::unchecked(submitter),
+                    status,
+                    for_power: Uint128::zero(),
+                    against_power: Uint128::zero(),
+                    for_voters: vec![],
+                    against_voters: vec![],
+                    start_block: 10,
+                    start_time: EPOCH_START + WEEK,
+                    end_block: 10 + PROPOSAL_VOTING_PERIOD,
+                    delayed_end_block: 10 + PROPOSAL_VOTING_PERIOD + PROPOSAL_EFFECTIVE_DELAY,
+                    expiration_block: 10
+                        + PROPOSAL_VOTING_PERIOD
+                        + PROPOSAL_EFFECTIVE_DELAY
+                        + PROPOSAL_EXPIRATION_PERIOD,
+                    title: "Title".to_string(),
+                    deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                    ibc_channel: None,
+                    forum_thread_id: None,
+                },
+            )
+            .unwrap();
+    }
+
+    let query_ids = |deps: Deps, msg: QueryMsg| -> Vec<u64> {
+        let proposals: Vec<Proposal> = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        proposals.iter().map(|p| p.proposal_id.u64()).collect()
+    };
+
+    let by_submitter = QueryMsg::ProposalsBySubmitter {
+        submitter: "alice".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    assert!(query_ids(deps.as_ref(), by_submitter.clone()).is_empty());
+
+    // Anyone can reindex in batches
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reindex { limit: Some(2) },
+    )
+    .unwrap();
+    assert_eq!(query_ids(deps.as_ref(), by_submitter.clone()), vec![1]);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reindex { limit: None },
+    )
+    .unwrap();
+    assert_eq!(query_ids(deps.as_ref(), by_submitter), vec![1, 3]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reindex { limit: None },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1].value, "0");
+
+    assert_eq!(
+        query_ids(
+            deps.as_ref(),
+            QueryMsg::ProposalsByStatus {
+                status: ProposalStatus::Passed,
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![2]
+    );
+    assert_eq!(
+        query_ids(
+            deps.as_ref(),
+            QueryMsg::ProposalsBySubmitter {
+                submitter: "alice".to_string(),
+                start_after: Some(1),
+                limit: None,
+            }
+        ),
+        vec![3]
+    );
+
+    let mut env = mock_env();
+    env.block.height = 11 + PROPOSAL_VOTING_PERIOD;
+    let finalizable: Vec<u64> = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::FinalizableProposals { limit: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(finalizable, vec![1]);
+}
+
+#[test]
+fn test_migrate_proposals_in_batches() {
+    /// Config layout of V1.5.0
+    #[cw_serde]
+    struct ConfigV150 {
+        xastro_token_addr: Addr,
+        vxastro_token_addr: Option<Addr>,
+        voting_escrow_delegator_addr: Option<Addr>,
+        ibc_controller: Option<Addr>,
+        builder_unlock_addr: Addr,
+        proposal_voting_period: u64,
+        proposal_effective_delay: u64,
+        proposal_expiration_period: u64,
+        proposal_required_deposit: Uint128,
+        proposal_required_quorum: Decimal,
+        proposal_required_threshold: Decimal,
+        whitelisted_links: Vec<String>,
+    }
+
+    /// Proposal layout of V1.5.0, with the content stored inline
+    #[cw_serde]
+    struct ProposalV150 {
+        proposal_id: Uint64,
+        submitter: Addr,
+        status: ProposalStatus,
+        for_power: Uint128,
+        against_power: Uint128,
+        for_voters: Vec<Addr>,
+        against_voters: Vec<Addr>,
+        start_block: u64,
+        start_time: u64,
+        end_block: u64,
+        delayed_end_block: u64,
+        expiration_block: u64,
+        title: String,
+        description: String,
+        link: Option<String>,
+        messages: Option<Vec<CosmosMsg>>,
+        deposit_amount: Uint128,
+        ibc_channel: Option<String>,
+    }
+
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, "astro-assembly", "1.5.0").unwrap();
+    Item::new("config")
+        .save(
+            deps.as_mut().storage,
+            &ConfigV150 {
+                xastro_token_addr: Addr::unchecked(XASTRO_ADDR),
+                vxastro_token_addr: None,
+                voting_escrow_delegator_addr: None,
+                ibc_controller: None,
+                builder_unlock_addr: Addr::unchecked(BUILDER_UNLOCK_ADDR),
+                proposal_voting_period: PROPOSAL_VOTING_PERIOD,
+                proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
+                proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
+                proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+                proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD)
+                    .unwrap(),
+                whitelisted_links: vec!["https://some.link/".to_string()],
+            },
+        )
+        .unwrap();
+
+    let legacy_proposals: Map<u64, ProposalV150> = Map::new("proposals");
+    let proposals_count = MAX_LIMIT as u64 + 5;
+    for id in 1..=proposals_count {
+        legacy_proposals
+            .save(
+                deps.as_mut().storage,
+                id,
+                &ProposalV150 {
+                    proposal_id: Uint64::from(id),
+                    submitter: Addr::unchecked("submitter"),
+                    status: ProposalStatus::Rejected,
+                    for_power: Uint128::zero(),
+                    against_power: Uint128::zero(),
+                    for_voters: vec![],
+                    against_voters: vec![],
+                    start_block: 10,
+                    start_time: EPOCH_START + WEEK,
+                    end_block: 10 + PROPOSAL_VOTING_PERIOD,
+                    delayed_end_block: 10 + PROPOSAL_VOTING_PERIOD + PROPOSAL_EFFECTIVE_DELAY,
+                    expiration_block: 10
+                        + PROPOSAL_VOTING_PERIOD
+                        + PROPOSAL_EFFECTIVE_DELAY
+                        + PROPOSAL_EXPIRATION_PERIOD,
+                    title: "Title".to_string(),
+                    description: format!("Description {id}"),
+                    link: None,
+                    messages: None,
+                    deposit_amount: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
+                    ibc_channel: None,
+                },
+            )
+            .unwrap();
+    }
+
+    // The migration only migrates the first batch of proposals
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            voting_escrow_delegator_addr: None,
+            vxastro_token_addr: None,
+            ibc_controller: None,
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("proposals_migrated", "false")));
+    assert!(PROPOSAL_CONTENT.has(deps.as_ref().storage, MAX_LIMIT as u64));
+    assert!(!PROPOSAL_CONTENT.has(deps.as_ref().storage, MAX_LIMIT as u64 + 1));
+
+    // Nothing else can be executed until every proposal is migrated
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reindex { limit: None },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Proposals are being migrated! Finish the migration with migrate_proposals first."
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::MigrateProposals { limit: None },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("finished", "true")));
+
+    for id in 1..=proposals_count {
+        assert_eq!(
+            PROPOSALS
+                .load(deps.as_ref().storage, id)
+                .unwrap()
+                .proposal_id
+                .u64(),
+            id
+        );
+        assert_eq!(
+            PROPOSAL_CONTENT
+                .load(deps.as_ref().storage, id)
+                .unwrap()
+                .description,
+            format!("Description {id}")
+        );
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::MigrateProposals { limit: None },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "All proposals are already migrated!");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reindex { limit: None },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_update_config_legacy_deposit_encoding() {
+    // UpdateConfig messages stored before the deposit became a Uint128 encode it as a number
+    let legacy: UpdateConfig = from_slice(br#"{"proposal_required_deposit":1000}"#).unwrap();
+    assert_eq!(legacy.proposal_required_deposit, Some(Uint128::new(1000)));
+
+    let current: UpdateConfig = from_slice(br#"{"proposal_required_deposit":"1000"}"#).unwrap();
+    assert_eq!(current.proposal_required_deposit, Some(Uint128::new(1000)));
+    assert_eq!(
+        from_slice::<UpdateConfig>(&to_vec(&current).unwrap()).unwrap(),
+        current
+    );
+
+    let empty: UpdateConfig = from_slice(br#"{"proposal_required_deposit":null}"#).unwrap();
+    assert_eq!(empty.proposal_required_deposit, None);
+    let empty: UpdateConfig = from_slice(b"{}").unwrap();
+    assert_eq!(empty.proposal_required_deposit, None);
+
+    from_slice::<UpdateConfig>(br#"{"proposal_required_deposit":"abc"}"#).unwrap_err();
+}
+
+#[test]
+fn test_proposal_status_and_vote_option_parsing() {
+    for status in [
+        ProposalStatus::Active,
+        ProposalStatus::Passed,
+        ProposalStatus::Rejected,
+        ProposalStatus::InProgress,
+        ProposalStatus::Failed,
+        ProposalStatus::Executed,
+        ProposalStatus::Expired,
+    ] {
+        assert_eq!(
+            ProposalStatus::from_str(&status.to_string()).unwrap(),
+            status
+        );
+        assert_eq!(
+            ProposalStatus::try_from(status.to_string().as_str()).unwrap(),
+            status
+        );
+    }
+
+    for option in [ProposalVoteOption::For, ProposalVoteOption::Against] {
+        assert_eq!(
+            ProposalVoteOption::from_str(&option.to_string()).unwrap(),
+            option
+        );
+        assert_eq!(
+            ProposalVoteOption::try_from(option.to_string().as_str()).unwrap(),
+            option
+        );
+    }
+
+    assert_eq!(
+        ProposalStatus::from_str("unknown").unwrap_err().to_string(),
+        "Error parsing into type ProposalStatus: Unknown proposal status: unknown"
+    );
+    assert_eq!(
+        ProposalVoteOption::try_from("abstain")
+            .unwrap_err()
+            .to_string(),
+        "Error parsing into type ProposalVoteOption: Unknown vote option: abstain"
+    );
+}
+
+fn mock_app() -> App {
     let mut env = mock_env();
     env.block.time = Timestamp::from_seconds(EPOCH_START);
     let api = MockApi::default();
@@ -1723,11 +5904,14 @@ fn instantiate_xastro_token(router: &mut App, owner: &Addr, astro_token: &Addr)
 }
 
 fn instantiate_vxastro_token(router: &mut App, owner: &Addr, xastro: &Addr) -> Addr {
-    let vxastro_token_contract = Box::new(ContractWrapper::new_with_empty(
-        voting_escrow::contract::execute,
-        voting_escrow::contract::instantiate,
-        voting_escrow::contract::query,
-    ));
+    let vxastro_token_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            voting_escrow::contract::execute,
+            voting_escrow::contract::instantiate,
+            voting_escrow::contract::query,
+        )
+        .with_reply_empty(voting_escrow::contract::reply),
+    );
 
     let vxastro_token_code_id = router.store_code(vxastro_token_contract);
 
@@ -1764,6 +5948,7 @@ fn instantiate_builder_unlock_contract(router: &mut App, owner: &Addr, astro_tok
         owner: owner.to_string(),
         astro_token: astro_token.to_string(),
         max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+        decrease_notice_period: None,
     };
 
     router
@@ -1786,11 +5971,15 @@ fn instantiate_assembly_contract(
     builder: &Addr,
     delegator: Option<String>,
 ) -> Addr {
-    let assembly_contract = Box::new(ContractWrapper::new_with_empty(
-        astro_assembly::contract::execute,
-        astro_assembly::contract::instantiate,
-        astro_assembly::contract::query,
-    ));
+    let assembly_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astro_assembly::contract::execute,
+            astro_assembly::contract::instantiate,
+            astro_assembly::contract::query,
+        )
+        .with_reply_empty(astro_assembly::contract::reply)
+        .with_sudo_empty(astro_assembly::contract::sudo),
+    );
 
     let assembly_code = router.store_code(assembly_contract);
 
@@ -1804,9 +5993,11 @@ fn instantiate_assembly_contract(
         proposal_effective_delay: PROPOSAL_EFFECTIVE_DELAY,
         proposal_expiration_period: PROPOSAL_EXPIRATION_PERIOD,
         proposal_required_deposit: Uint128::new(PROPOSAL_REQUIRED_DEPOSIT),
-        proposal_required_quorum: String::from(PROPOSAL_REQUIRED_QUORUM),
-        proposal_required_threshold: String::from(PROPOSAL_REQUIRED_THRESHOLD),
+        proposal_required_quorum: Decimal::from_str(PROPOSAL_REQUIRED_QUORUM).unwrap(),
+        proposal_required_threshold: Decimal::from_str(PROPOSAL_REQUIRED_THRESHOLD).unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        proposal_max_messages: MAX_PROPOSAL_MESSAGES as u32,
+        proposal_max_messages_size: MAX_PROPOSAL_MESSAGES_SIZE,
     };
 
     router
@@ -1945,25 +6136,22 @@ fn create_proposal(
     submitter: Addr,
     msgs: Option<Vec<CosmosMsg>>,
 ) {
-    let submit_proposal_msg = Cw20HookMsg::SubmitProposal {
-        title: "Test title!".to_string(),
-        description: "Test description!".to_string(),
-        link: None,
-        messages: msgs,
-        ibc_channel: None,
-    };
-
-    app.execute_contract(
-        submitter,
-        token.clone(),
-        &Cw20ExecuteMsg::Send {
-            contract: assembly.to_string(),
-            amount: Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
-            msg: to_binary(&submit_proposal_msg).unwrap(),
-        },
-        &[],
+    let submit_proposal_msg = build_submit_proposal_msg(
+        token,
+        assembly,
+        Uint128::from(PROPOSAL_REQUIRED_DEPOSIT),
+        "Test title!",
+        "Test description!",
+        None,
+        msgs,
+        None,
+        None,
+        None,
+        None,
     )
     .unwrap();
+
+    app.execute(submitter, submit_proposal_msg).unwrap();
 }
 
 fn check_token_balance(app: &mut App, token: &Addr, address: &Addr, expected: u128) {
@@ -1981,7 +6169,8 @@ fn check_user_vp(app: &mut App, assembly: &Addr, address: &Addr, proposal_id: u6
             assembly.to_string(),
             &QueryMsg::UserVotingPower {
                 user: address.to_string(),
-                proposal_id,
+                proposal_id: Some(proposal_id),
+                at: None,
             },
         )
         .unwrap();
@@ -1994,7 +6183,10 @@ fn check_total_vp(app: &mut App, assembly: &Addr, proposal_id: u64, expected: u1
         .wrap()
         .query_wasm_smart(
             assembly.to_string(),
-            &QueryMsg::TotalVotingPower { proposal_id },
+            &QueryMsg::TotalVotingPower {
+                proposal_id: Some(proposal_id),
+                at: None,
+            },
         )
         .unwrap();
 
@@ -0,0 +1,604 @@
+use cosmwasm_std::{
+    attr, coins, entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use astroport_governance::bribes::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, MAX_BRIBE_ASSETS,
+};
+use astroport_governance::generator_controller::{
+    ConfigResponse as ControllerConfigResponse, GaugeInfoResponse, QueryMsg as ControllerQueryMsg,
+    VotedPoolInfoResponse,
+};
+use astroport_governance::utils::get_period;
+
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+use crate::error::ContractError;
+use crate::state::{
+    Bribe, Config, ALLOWED_ASSETS, BRIBES, CLAIMED, CONFIG, DEPOSITS, OWNERSHIP_PROPOSAL,
+};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-bribes";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_validate(&msg.owner)?,
+            generator_controller: deps.api.addr_validate(&msg.generator_controller)?,
+        },
+    )?;
+    for asset_info in msg.allowed_assets {
+        asset_info.check(deps.api)?;
+        ALLOWED_ASSETS.save(deps.storage, asset_info.to_string(), &asset_info)?;
+    }
+
+    Ok(Response::new())
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::Receive(msg)** Parse incoming messages from CW20 reward tokens.
+///
+/// * **ExecuteMsg::DepositBribe { pool, period }** Attaches native reward tokens to a pool at a specific period.
+///
+/// * **ExecuteMsg::Claim { pool, period, assets }** Claims rewards attached to a pool at a specific period.
+///
+/// * **ExecuteMsg::Reclaim { pool, period }** Returns rewards attached to a pool nobody voted for at a specific period.
+///
+/// * **ExecuteMsg::UpdateAllowedAssets { add, remove }** Updates reward tokens that can be attached to pools.
+///
+/// * **ExecuteMsg::ProposeNewOwner { new_owner, expires_in }** Creates a request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::DepositBribe { pool, period } => {
+            let assets = info
+                .funds
+                .iter()
+                .map(|coin| Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: coin.denom.clone(),
+                    },
+                    amount: coin.amount,
+                })
+                .collect();
+            deposit_bribe(deps, env, info.sender, pool, period, assets)
+        }
+        ExecuteMsg::Claim {
+            pool,
+            period,
+            assets,
+        } => claim(deps, info, pool, period, assets),
+        ExecuteMsg::Reclaim { pool, period } => reclaim(deps, info, pool, period),
+        ExecuteMsg::UpdateAllowedAssets { add, remove } => update_allowed_assets(
+            deps,
+            info,
+            add.unwrap_or_default(),
+            remove.unwrap_or_default(),
+        ),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut config| {
+                        config.owner = new_owner;
+                        Ok(config)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        }
+    }
+}
+
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+///
+/// * **cw20_msg** CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::DepositBribe { pool, period } => {
+            let asset = Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            };
+            let depositor = deps.api.addr_validate(&cw20_msg.sender)?;
+            deposit_bribe(deps, env, depositor, pool, period, vec![asset])
+        }
+    }
+}
+
+/// Attaches reward tokens to a pool at a specific period.
+/// Rewards can only be attached to whitelisted pools and to periods which haven't started yet,
+/// so voters are able to take them into account before their votes are applied.
+/// Only allowed reward tokens can be attached, so the limited reward slots can't be filled with junk tokens.
+///
+/// * **depositor** address which can reclaim the rewards if nobody votes for the pool.
+///
+/// * **pool** LP token address of the pool voters should vote for.
+///
+/// * **period** period at which votes for the pool are rewarded.
+///
+/// * **assets** reward tokens to attach.
+fn deposit_bribe(
+    deps: DepsMut,
+    env: Env,
+    depositor: Addr,
+    pool: String,
+    period: u64,
+    assets: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool_addr = deps.api.addr_validate(&pool)?;
+
+    if assets.is_empty() || assets.iter().any(|asset| asset.amount.is_zero()) {
+        return Err(ContractError::ZeroDeposit {});
+    }
+
+    if let Some(asset) = assets
+        .iter()
+        .find(|asset| !ALLOWED_ASSETS.has(deps.storage, asset.info.to_string()))
+    {
+        return Err(ContractError::AssetNotAllowed(asset.info.to_string()));
+    }
+
+    if period <= get_period(env.block.time.seconds())? {
+        return Err(ContractError::InvalidPeriod {});
+    }
+
+    let controller_config: ControllerConfigResponse = deps
+        .querier
+        .query_wasm_smart(&config.generator_controller, &ControllerQueryMsg::Config {})?;
+    if !controller_config.whitelisted_pools.contains(&pool_addr) {
+        return Err(ContractError::PoolIsNotWhitelisted(pool));
+    }
+
+    let mut bribes = BRIBES
+        .may_load(deps.storage, (&pool_addr, period))?
+        .unwrap_or_default();
+    let mut deposits = DEPOSITS
+        .may_load(deps.storage, (&pool_addr, period, &depositor))?
+        .unwrap_or_default();
+
+    let mut attributes = vec![
+        attr("action", "deposit_bribe"),
+        attr("pool", &pool_addr),
+        attr("period", period.to_string()),
+    ];
+
+    for asset in assets {
+        attributes.push(attr("bribe", asset.to_string()));
+        match deposits
+            .iter_mut()
+            .find(|deposit| deposit.info == asset.info)
+        {
+            Some(deposit) => deposit.amount = deposit.amount.checked_add(asset.amount)?,
+            None => deposits.push(asset.clone()),
+        }
+        match bribes
+            .iter_mut()
+            .find(|bribe| bribe.asset_info == asset.info)
+        {
+            Some(bribe) => bribe.amount = bribe.amount.checked_add(asset.amount)?,
+            None => bribes.push(Bribe {
+                asset_info: asset.info,
+                amount: asset.amount,
+                claimed: Uint128::zero(),
+            }),
+        }
+    }
+
+    if bribes.len() > MAX_BRIBE_ASSETS {
+        return Err(ContractError::TooManyBribeAssets(MAX_BRIBE_ASSETS));
+    }
+
+    BRIBES.save(deps.storage, (&pool_addr, period), &bribes)?;
+    DEPOSITS.save(deps.storage, (&pool_addr, period, &depositor), &deposits)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// Sends rewards attached to a pool at a specific period to the sender
+/// pro-rata to their vote weight applied to the pool at that period.
+/// Every reward token is claimed separately, so a token which fails to transfer can be skipped.
+///
+/// * **pool** LP token address of the pool the sender voted for.
+///
+/// * **period** period for which rewards are claimed.
+///
+/// * **assets** reward tokens to claim. All unclaimed ones if not set.
+fn claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool: String,
+    period: u64,
+    assets: Option<Vec<AssetInfo>>,
+) -> Result<Response, ContractError> {
+    let pool_addr = deps.api.addr_validate(&pool)?;
+
+    let mut bribes = BRIBES
+        .may_load(deps.storage, (&pool_addr, period))?
+        .unwrap_or_default();
+    let requested = assets.unwrap_or_else(|| {
+        bribes
+            .iter()
+            .map(|bribe| bribe.asset_info.clone())
+            .collect()
+    });
+    if !requested.is_empty()
+        && requested.iter().all(|asset_info| {
+            CLAIMED.has(
+                deps.storage,
+                (&info.sender, &pool_addr, period, &asset_info.to_string()),
+            )
+        })
+    {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let rewards: Vec<_> = calc_claimable(deps.as_ref(), &info.sender, &pool_addr, period)?
+        .into_iter()
+        .filter(|reward| requested.contains(&reward.info))
+        .collect();
+    if rewards.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let mut messages = vec![];
+    let mut attributes = vec![
+        attr("action", "claim"),
+        attr("user", &info.sender),
+        attr("pool", &pool_addr),
+        attr("period", period.to_string()),
+    ];
+
+    for reward in rewards {
+        if let Some(bribe) = bribes
+            .iter_mut()
+            .find(|bribe| bribe.asset_info == reward.info)
+        {
+            bribe.claimed = bribe.claimed.checked_add(reward.amount)?;
+        }
+        CLAIMED.save(
+            deps.storage,
+            (&info.sender, &pool_addr, period, &reward.info.to_string()),
+            &(),
+        )?;
+        attributes.push(attr("reward", reward.to_string()));
+        messages.push(transfer_msg(&reward, &info.sender)?);
+    }
+
+    BRIBES.save(deps.storage, (&pool_addr, period), &bribes)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Returns rewards the sender attached to a pool at a specific period if nobody voted for the pool
+/// at that period, so they can't be claimed by anyone.
+///
+/// * **pool** LP token address of the pool rewards were attached to.
+///
+/// * **period** period for which rewards were attached.
+fn reclaim(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool: String,
+    period: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool_addr = deps.api.addr_validate(&pool)?;
+
+    assert_tuned(deps.as_ref(), &config, period)?;
+
+    let voted_pool = resolve_pool(deps.as_ref(), &config, &pool_addr)?;
+    let pool_votes: VotedPoolInfoResponse = deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::PoolInfoAtPeriod {
+            pool_addr: voted_pool.to_string(),
+            period,
+        },
+    )?;
+    if !pool_votes.vxastro_amount.is_zero() {
+        return Err(ContractError::PoolWasVotedFor {});
+    }
+
+    let deposits = DEPOSITS
+        .may_load(deps.storage, (&pool_addr, period, &info.sender))?
+        .unwrap_or_default();
+    if deposits.is_empty() {
+        return Err(ContractError::NothingToReclaim {});
+    }
+
+    let mut bribes = BRIBES.load(deps.storage, (&pool_addr, period))?;
+    let mut messages = vec![];
+    let mut attributes = vec![
+        attr("action", "reclaim"),
+        attr("depositor", &info.sender),
+        attr("pool", &pool_addr),
+        attr("period", period.to_string()),
+    ];
+
+    for deposit in deposits {
+        if let Some(bribe) = bribes
+            .iter_mut()
+            .find(|bribe| bribe.asset_info == deposit.info)
+        {
+            bribe.amount = bribe.amount.checked_sub(deposit.amount)?;
+        }
+        attributes.push(attr("reward", deposit.to_string()));
+        messages.push(transfer_msg(&deposit, &info.sender)?);
+    }
+
+    bribes.retain(|bribe| !bribe.amount.is_zero());
+    if bribes.is_empty() {
+        BRIBES.remove(deps.storage, (&pool_addr, period));
+    } else {
+        BRIBES.save(deps.storage, (&pool_addr, period), &bribes)?;
+    }
+    DEPOSITS.remove(deps.storage, (&pool_addr, period, &info.sender));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Adds or removes reward tokens that can be attached to pools. Only the owner can execute this.
+///
+/// * **add** reward tokens to allow.
+///
+/// * **remove** reward tokens to disallow. Rewards already attached with them can still be claimed.
+fn update_allowed_assets(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<AssetInfo>,
+    remove: Vec<AssetInfo>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attributes = vec![attr("action", "update_allowed_assets")];
+    for asset_info in add {
+        asset_info.check(deps.api)?;
+        attributes.push(attr("added_asset", asset_info.to_string()));
+        ALLOWED_ASSETS.save(deps.storage, asset_info.to_string(), &asset_info)?;
+    }
+    for asset_info in remove {
+        attributes.push(attr("removed_asset", asset_info.to_string()));
+        ALLOWED_ASSETS.remove(deps.storage, asset_info.to_string());
+    }
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// Returns the actual LP token address of a pool following LP token replacements in the generator controller.
+/// Votes for a replaced LP token are moved to the new one, so rewards attached to the replaced LP token
+/// are distributed according to votes for the new one.
+fn resolve_pool(deps: Deps, config: &Config, pool_addr: &Addr) -> StdResult<Addr> {
+    deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::ResolvedPool {
+            pool_addr: pool_addr.to_string(),
+        },
+    )
+}
+
+/// Checks that pools were tuned at or after the specified period,
+/// so votes applied at that period are final.
+fn assert_tuned(deps: Deps, config: &Config, period: u64) -> Result<(), ContractError> {
+    let tune_info: GaugeInfoResponse = deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::TuneInfo {},
+    )?;
+    if tune_info.tune_ts == 0 || get_period(tune_info.tune_ts)? < period {
+        return Err(ContractError::NotTunedYet {});
+    }
+
+    Ok(())
+}
+
+/// Calculates rewards a user can claim for a pool at a specific period.
+/// The user's share is verified against vote snapshots stored in the generator controller.
+/// Rewards can only be claimed once pools were tuned at or after the specified period.
+/// Reward tokens the user already claimed are skipped.
+fn calc_claimable(
+    deps: Deps,
+    user: &Addr,
+    pool_addr: &Addr,
+    period: u64,
+) -> Result<Vec<Asset>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    assert_tuned(deps, &config, period)?;
+
+    let bribes = BRIBES
+        .may_load(deps.storage, (pool_addr, period))?
+        .unwrap_or_default();
+    if bribes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let voted_pool = resolve_pool(deps, &config, pool_addr)?;
+    let user_vote: VotedPoolInfoResponse = deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::UserVoteAtPeriod {
+            user: user.to_string(),
+            pool_addr: voted_pool.to_string(),
+            period,
+        },
+    )?;
+    let pool_votes: VotedPoolInfoResponse = deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::PoolInfoAtPeriod {
+            pool_addr: voted_pool.to_string(),
+            period,
+        },
+    )?;
+
+    if user_vote.vxastro_amount.is_zero() || pool_votes.vxastro_amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let rewards = bribes
+        .into_iter()
+        .filter(|bribe| {
+            !CLAIMED.has(
+                deps.storage,
+                (user, pool_addr, period, &bribe.asset_info.to_string()),
+            )
+        })
+        .filter_map(|bribe| {
+            let share = bribe
+                .amount
+                .multiply_ratio(user_vote.vxastro_amount, pool_votes.vxastro_amount);
+            // Rounding errors must not allow voters to claim more than was deposited
+            let amount = share.min(bribe.amount - bribe.claimed);
+            if amount.is_zero() {
+                None
+            } else {
+                Some(Asset {
+                    info: bribe.asset_info,
+                    amount,
+                })
+            }
+        })
+        .collect();
+
+    Ok(rewards)
+}
+
+/// Builds a message which transfers the specified asset to the recipient.
+fn transfer_msg(asset: &Asset, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let msg = match &asset.info {
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }),
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(asset.amount.u128(), denom),
+        }),
+    };
+
+    Ok(msg)
+}
+
+/// Expose available contract queries.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Fetch contract config.
+///
+/// * **QueryMsg::AllowedAssets {}** Fetch reward tokens that can be attached to pools.
+///
+/// * **QueryMsg::Bribes { pool, period }** Fetch rewards attached to a pool at a specific period.
+///
+/// * **QueryMsg::Claimable { user, pool, period }** Fetch rewards a user can claim for a pool at a specific period.
+///
+/// * **QueryMsg::Deposits { depositor, pool, period }** Fetch rewards a depositor attached to a pool at a specific period.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::AllowedAssets {} => to_binary(
+            &ALLOWED_ASSETS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, asset_info)| asset_info))
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::Bribes { pool, period } => {
+            let pool_addr = deps.api.addr_validate(&pool)?;
+            to_binary(
+                &BRIBES
+                    .may_load(deps.storage, (&pool_addr, period))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::Claimable { user, pool, period } => {
+            let user = deps.api.addr_validate(&user)?;
+            let pool_addr = deps.api.addr_validate(&pool)?;
+            let rewards = calc_claimable(deps, &user, &pool_addr, period)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            to_binary(&rewards)
+        }
+        QueryMsg::Deposits {
+            depositor,
+            pool,
+            period,
+        } => {
+            let depositor = deps.api.addr_validate(&depositor)?;
+            let pool_addr = deps.api.addr_validate(&pool)?;
+            to_binary(
+                &DEPOSITS
+                    .may_load(deps.storage, (&pool_addr, period, &depositor))?
+                    .unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// Manages contract migration.
+/// There are no released versions to migrate from yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Err(ContractError::MigrationError {})
+}
@@ -0,0 +1,55 @@
+use astroport_governance::errors::CommonError;
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// This enum describes bribes contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Pool is not whitelisted: {0}")]
+    PoolIsNotWhitelisted(String),
+
+    #[error("Rewards can only be attached to future periods")]
+    InvalidPeriod {},
+
+    #[error("Reward token is not allowed: {0}")]
+    AssetNotAllowed(String),
+
+    #[error("Deposit amount must be greater than 0")]
+    ZeroDeposit {},
+
+    #[error("Too many reward tokens are attached to the pool at this period. Max: {0}")]
+    TooManyBribeAssets(usize),
+
+    #[error("Rewards can't be claimed until pools are tuned for this period")]
+    NotTunedYet {},
+
+    #[error("Rewards were already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Rewards can only be reclaimed if nobody voted for the pool at this period")]
+    PoolWasVotedFor {},
+
+    #[error("Nothing to reclaim")]
+    NothingToReclaim {},
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
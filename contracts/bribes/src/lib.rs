@@ -0,0 +1,7 @@
+pub mod contract;
+mod error;
+pub mod state;
+
+// During development this import could be replaced with another astroport version.
+// However, in production, the astroport version should be the same for all contracts.
+use astroport_governance::astroport;
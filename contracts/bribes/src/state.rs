@@ -0,0 +1,31 @@
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::astroport::common::OwnershipProposal;
+use astroport_governance::bribes::{BribeResponse, ConfigResponse};
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+/// This structure stores the main parameters for the bribes contract.
+pub type Config = ConfigResponse;
+
+/// This structure describes a reward attached to a pool at a specific period.
+pub type Bribe = BribeResponse;
+
+/// Stores the contract config at the given key.
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Reward tokens that can be attached to pools by key ( asset denom or contract address ).
+pub const ALLOWED_ASSETS: Map<String, AssetInfo> = Map::new("allowed_assets");
+
+/// Contains a proposal to change contract ownership.
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Rewards attached to a pool at a specific period by key ( pool_addr -> period ).
+pub const BRIBES: Map<(&Addr, u64), Vec<Bribe>> = Map::new("bribes");
+
+/// Rewards attached by a depositor to a pool at a specific period by key ( pool_addr -> period -> depositor ).
+/// They are returned to the depositor if nobody voted for the pool at that period.
+pub const DEPOSITS: Map<(&Addr, u64, &Addr), Vec<Asset>> = Map::new("deposits");
+
+/// Hashset based on [`Map`]. It stores null object by key ( user -> pool_addr -> period -> asset denom or contract address ).
+/// This hashset contains all reward tokens a user has already claimed from a specific pool at a specific period.
+pub const CLAIMED: Map<(&Addr, &Addr, u64, &str), ()> = Map::new("claimed");
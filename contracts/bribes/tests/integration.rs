@@ -0,0 +1,582 @@
+use astroport_governance::astroport::asset::{Asset, AssetInfo};
+use astroport_governance::bribes::{
+    BribeResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use astroport_governance::generator_controller::ExecuteMsg as ControllerExecuteMsg;
+use astroport_governance::utils::WEEK;
+use astroport_tests::{controller_helper::ControllerHelper, mock_app, TerraAppExtension};
+use cosmwasm_std::{
+    coins, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_multi_test::{App, BankSudo, ContractWrapper, Executor, SudoMsg};
+
+const DENOM: &str = "ubribe";
+
+fn instantiate_bribes(router: &mut App, owner: &Addr, controller: &Addr) -> Addr {
+    let bribes_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_bribes::contract::execute,
+        astroport_bribes::contract::instantiate,
+        astroport_bribes::contract::query,
+    ));
+    let bribes_code_id = router.store_code(bribes_contract);
+
+    router
+        .instantiate_contract(
+            bribes_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                owner: owner.to_string(),
+                generator_controller: controller.to_string(),
+                allowed_assets: vec![AssetInfo::NativeToken {
+                    denom: DENOM.to_string(),
+                }],
+            },
+            &[],
+            "Bribes",
+            None,
+        )
+        .unwrap()
+}
+
+fn deposit_bribe(
+    router: &mut App,
+    bribes: &Addr,
+    sender: &str,
+    pool: &Addr,
+    period: u64,
+    amount: u128,
+) -> anyhow::Result<()> {
+    router
+        .sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: sender.to_string(),
+            amount: coins(amount, DENOM),
+        }))
+        .unwrap();
+    router
+        .execute_contract(
+            Addr::unchecked(sender),
+            bribes.clone(),
+            &ExecuteMsg::DepositBribe {
+                pool: pool.to_string(),
+                period,
+            },
+            &coins(amount, DENOM),
+        )
+        .map(|_| ())
+}
+
+fn claim(
+    router: &mut App,
+    bribes: &Addr,
+    user: &str,
+    pool: &Addr,
+    period: u64,
+) -> anyhow::Result<()> {
+    router
+        .execute_contract(
+            Addr::unchecked(user),
+            bribes.clone(),
+            &ExecuteMsg::Claim {
+                pool: pool.to_string(),
+                period,
+                assets: None,
+            },
+            &[],
+        )
+        .map(|_| ())
+}
+
+fn query_claimable(
+    router: &mut App,
+    bribes: &Addr,
+    user: &str,
+    pool: &Addr,
+    period: u64,
+) -> Vec<Asset> {
+    router
+        .wrap()
+        .query_wasm_smart(
+            bribes,
+            &QueryMsg::Claimable {
+                user: user.to_string(),
+                pool: pool.to_string(),
+                period,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn check_bribes_distribution() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let bribes = instantiate_bribes(&mut router, &owner, &helper.controller);
+
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pools[0].to_string()]), None)
+        .unwrap();
+
+    let vote_period = router.block_period() + 1;
+
+    // Rewards can't be attached to a period which has already started
+    let err = deposit_bribe(
+        &mut router,
+        &bribes,
+        "briber",
+        &pools[0],
+        vote_period - 1,
+        1000,
+    )
+    .unwrap_err();
+    assert_eq!(
+        "Rewards can only be attached to future periods",
+        err.root_cause().to_string()
+    );
+    // Rewards can't be attached to pools which aren't whitelisted
+    let err =
+        deposit_bribe(&mut router, &bribes, "briber", &pools[1], vote_period, 1000).unwrap_err();
+    assert_eq!(
+        format!("Pool is not whitelisted: {}", pools[1]),
+        err.root_cause().to_string()
+    );
+
+    deposit_bribe(&mut router, &bribes, "briber", &pools[0], vote_period, 1000).unwrap();
+    let resp: Vec<BribeResponse> = router
+        .wrap()
+        .query_wasm_smart(
+            &bribes,
+            &QueryMsg::Bribes {
+                pool: pools[0].to_string(),
+                period: vote_period,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        vec![BribeResponse {
+            asset_info: AssetInfo::NativeToken {
+                denom: DENOM.to_string()
+            },
+            amount: Uint128::new(1000),
+            claimed: Uint128::zero(),
+        }]
+    );
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper.escrow_helper.mint_xastro(&mut router, "user2", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user2", 10 * WEEK, 50f32)
+        .unwrap();
+
+    helper
+        .vote(&mut router, "user1", vec![(pools[0].as_str(), 10000)])
+        .unwrap();
+    helper
+        .vote(&mut router, "user2", vec![(pools[0].as_str(), 5000)])
+        .unwrap();
+
+    // Rewards can't be claimed before tuning
+    let err = claim(&mut router, &bribes, "user1", &pools[0], vote_period).unwrap_err();
+    assert_eq!(
+        "Rewards can't be claimed until pools are tuned for this period",
+        err.root_cause().to_string()
+    );
+
+    router.next_block(2 * WEEK);
+    helper.tune(&mut router).unwrap();
+
+    let user1_claimable = query_claimable(&mut router, &bribes, "user1", &pools[0], vote_period);
+    let user2_claimable = query_claimable(&mut router, &bribes, "user2", &pools[0], vote_period);
+    // user1 applied 4 times more voting power to the pool than user2
+    assert!((799..=800).contains(&user1_claimable[0].amount.u128()));
+    assert!((199..=200).contains(&user2_claimable[0].amount.u128()));
+    assert!(user1_claimable[0].amount + user2_claimable[0].amount <= Uint128::new(1000));
+
+    claim(&mut router, &bribes, "user1", &pools[0], vote_period).unwrap();
+    claim(&mut router, &bribes, "user2", &pools[0], vote_period).unwrap();
+
+    let balance = router.wrap().query_balance("user1", DENOM).unwrap();
+    assert_eq!(balance.amount, user1_claimable[0].amount);
+    let balance = router.wrap().query_balance("user2", DENOM).unwrap();
+    assert_eq!(balance.amount, user2_claimable[0].amount);
+
+    let err = claim(&mut router, &bribes, "user1", &pools[0], vote_period).unwrap_err();
+    assert_eq!("Rewards were already claimed", err.root_cause().to_string());
+    assert_eq!(
+        query_claimable(&mut router, &bribes, "user1", &pools[0], vote_period),
+        vec![]
+    );
+
+    // Users who didn't vote for the pool have nothing to claim
+    let err = claim(&mut router, &bribes, "user3", &pools[0], vote_period).unwrap_err();
+    assert_eq!("Nothing to claim", err.root_cause().to_string());
+}
+
+#[test]
+fn check_reclaim_bribes() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let bribes = instantiate_bribes(&mut router, &owner, &helper.controller);
+
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(
+            &mut router,
+            "owner",
+            Some(vec![pools[0].to_string(), pools[1].to_string()]),
+            None,
+        )
+        .unwrap();
+
+    let vote_period = router.block_period() + 1;
+    deposit_bribe(
+        &mut router,
+        &bribes,
+        "briber1",
+        &pools[0],
+        vote_period,
+        1000,
+    )
+    .unwrap();
+    deposit_bribe(&mut router, &bribes, "briber2", &pools[0], vote_period, 500).unwrap();
+    deposit_bribe(&mut router, &bribes, "briber1", &pools[1], vote_period, 300).unwrap();
+
+    let deposits: Vec<Asset> = router
+        .wrap()
+        .query_wasm_smart(
+            &bribes,
+            &QueryMsg::Deposits {
+                depositor: "briber1".to_string(),
+                pool: pools[0].to_string(),
+                period: vote_period,
+            },
+        )
+        .unwrap();
+    assert_eq!(deposits[0].amount.u128(), 1000);
+
+    // Only pools[1] receives votes
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(&mut router, "user1", vec![(pools[1].as_str(), 10000)])
+        .unwrap();
+
+    // Rewards can't be reclaimed before tuning
+    let reclaim = |router: &mut App, depositor: &str, pool: &Addr| {
+        router.execute_contract(
+            Addr::unchecked(depositor),
+            bribes.clone(),
+            &ExecuteMsg::Reclaim {
+                pool: pool.to_string(),
+                period: vote_period,
+            },
+            &[],
+        )
+    };
+    let err = reclaim(&mut router, "briber1", &pools[0]).unwrap_err();
+    assert_eq!(
+        "Rewards can't be claimed until pools are tuned for this period",
+        err.root_cause().to_string()
+    );
+
+    router.next_block(2 * WEEK);
+    helper.tune(&mut router).unwrap();
+
+    // Rewards attached to a pool which was voted for belong to voters
+    let err = reclaim(&mut router, "briber1", &pools[1]).unwrap_err();
+    assert_eq!(
+        "Rewards can only be reclaimed if nobody voted for the pool at this period",
+        err.root_cause().to_string()
+    );
+    // Only depositors can reclaim rewards
+    let err = reclaim(&mut router, "user1", &pools[0]).unwrap_err();
+    assert_eq!("Nothing to reclaim", err.root_cause().to_string());
+
+    reclaim(&mut router, "briber1", &pools[0]).unwrap();
+    let balance = router.wrap().query_balance("briber1", DENOM).unwrap();
+    assert_eq!(balance.amount.u128(), 1000);
+    let err = reclaim(&mut router, "briber1", &pools[0]).unwrap_err();
+    assert_eq!("Nothing to reclaim", err.root_cause().to_string());
+
+    let resp: Vec<BribeResponse> = router
+        .wrap()
+        .query_wasm_smart(
+            &bribes,
+            &QueryMsg::Bribes {
+                pool: pools[0].to_string(),
+                period: vote_period,
+            },
+        )
+        .unwrap();
+    assert_eq!(resp[0].amount.u128(), 500);
+
+    reclaim(&mut router, "briber2", &pools[0]).unwrap();
+    let balance = router.wrap().query_balance("briber2", DENOM).unwrap();
+    assert_eq!(balance.amount.u128(), 500);
+    let resp: Vec<BribeResponse> = router
+        .wrap()
+        .query_wasm_smart(
+            &bribes,
+            &QueryMsg::Bribes {
+                pool: pools[0].to_string(),
+                period: vote_period,
+            },
+        )
+        .unwrap();
+    assert!(resp.is_empty());
+}
+
+#[test]
+fn check_bribes_after_pool_migration() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let bribes = instantiate_bribes(&mut router, &owner, &helper.controller);
+
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pools[0].to_string()]), None)
+        .unwrap();
+
+    let vote_period = router.block_period() + 1;
+    deposit_bribe(&mut router, &bribes, "briber", &pools[0], vote_period, 1000).unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(&mut router, "user1", vec![(pools[0].as_str(), 10000)])
+        .unwrap();
+
+    router.next_block(2 * WEEK);
+    helper.tune(&mut router).unwrap();
+
+    // Votes for the replaced LP token are moved to the new one
+    router
+        .execute_contract(
+            owner,
+            helper.controller.clone(),
+            &ControllerExecuteMsg::MigratePoolVotes {
+                from_lp: pools[0].to_string(),
+                to_lp: pools[1].to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Rewards attached to the replaced LP token still belong to voters
+    let err = router
+        .execute_contract(
+            Addr::unchecked("briber"),
+            bribes.clone(),
+            &ExecuteMsg::Reclaim {
+                pool: pools[0].to_string(),
+                period: vote_period,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Rewards can only be reclaimed if nobody voted for the pool at this period",
+        err.root_cause().to_string()
+    );
+
+    let claimable = query_claimable(&mut router, &bribes, "user1", &pools[0], vote_period);
+    assert!((999..=1000).contains(&claimable[0].amount.u128()));
+    claim(&mut router, &bribes, "user1", &pools[0], vote_period).unwrap();
+    let balance = router.wrap().query_balance("user1", DENOM).unwrap();
+    assert_eq!(balance.amount, claimable[0].amount);
+}
+
+/// A CW20 token which forwards deposits but whose transfers always fail, e.g. a paused token.
+fn paused_token_execute(
+    _: DepsMut,
+    _: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => Ok(Response::new().add_message(
+            Cw20ReceiveMsg {
+                sender: info.sender.to_string(),
+                amount,
+                msg,
+            }
+            .into_cosmos_msg(contract)?,
+        )),
+        _ => Err(StdError::generic_err("Token is paused")),
+    }
+}
+
+fn paused_token_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn paused_token_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_binary(&Empty {})
+}
+
+#[test]
+fn check_failing_reward_token() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let bribes = instantiate_bribes(&mut router, &owner, &helper.controller);
+
+    let paused_token_code = router.store_code(Box::new(ContractWrapper::new_with_empty(
+        paused_token_execute,
+        paused_token_instantiate,
+        paused_token_query,
+    )));
+    let paused_token = router
+        .instantiate_contract(
+            paused_token_code,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "Paused token",
+            None,
+        )
+        .unwrap();
+    let paused_token_info = AssetInfo::Token {
+        contract_addr: paused_token.clone(),
+    };
+    let native_info = AssetInfo::NativeToken {
+        denom: DENOM.to_string(),
+    };
+
+    let pool = helper
+        .create_pool_with_tokens(&mut router, "FOO", "BAR")
+        .unwrap();
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pool.to_string()]), None)
+        .unwrap();
+
+    let vote_period = router.block_period() + 1;
+    let deposit_token = |router: &mut App| {
+        router.execute_contract(
+            Addr::unchecked("briber"),
+            paused_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: bribes.to_string(),
+                amount: Uint128::new(1000),
+                msg: to_binary(&Cw20HookMsg::DepositBribe {
+                    pool: pool.to_string(),
+                    period: vote_period,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // Only allowed reward tokens can be attached
+    let err = deposit_token(&mut router).unwrap_err();
+    assert_eq!(
+        format!("Reward token is not allowed: {paused_token}"),
+        err.root_cause().to_string()
+    );
+
+    let update_allowed_assets = ExecuteMsg::UpdateAllowedAssets {
+        add: Some(vec![paused_token_info.clone()]),
+        remove: None,
+    };
+    let err = router
+        .execute_contract(
+            Addr::unchecked("briber"),
+            bribes.clone(),
+            &update_allowed_assets,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
+    router
+        .execute_contract(owner, bribes.clone(), &update_allowed_assets, &[])
+        .unwrap();
+    let allowed: Vec<AssetInfo> = router
+        .wrap()
+        .query_wasm_smart(&bribes, &QueryMsg::AllowedAssets {})
+        .unwrap();
+    assert!(allowed.contains(&native_info) && allowed.contains(&paused_token_info));
+
+    deposit_token(&mut router).unwrap();
+    deposit_bribe(&mut router, &bribes, "briber", &pool, vote_period, 1000).unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(&mut router, "user1", vec![(pool.as_str(), 10000)])
+        .unwrap();
+
+    router.next_block(2 * WEEK);
+    helper.tune(&mut router).unwrap();
+
+    // The failing token reverts a claim of every reward
+    let err = claim(&mut router, &bribes, "user1", &pool, vote_period).unwrap_err();
+    assert_eq!("Token is paused", err.root_cause().to_string());
+
+    // But the other rewards can be claimed without it
+    let claim_native = ExecuteMsg::Claim {
+        pool: pool.to_string(),
+        period: vote_period,
+        assets: Some(vec![native_info.clone()]),
+    };
+    router
+        .execute_contract(Addr::unchecked("user1"), bribes.clone(), &claim_native, &[])
+        .unwrap();
+    let balance = router.wrap().query_balance("user1", DENOM).unwrap();
+    assert!((999..=1000).contains(&balance.amount.u128()));
+
+    let err = router
+        .execute_contract(Addr::unchecked("user1"), bribes.clone(), &claim_native, &[])
+        .unwrap_err();
+    assert_eq!("Rewards were already claimed", err.root_cause().to_string());
+
+    // The failing token stays claimable
+    let claimable = query_claimable(&mut router, &bribes, "user1", &pool, vote_period);
+    assert_eq!(claimable.len(), 1);
+    assert_eq!(claimable[0].info, paused_token_info);
+}
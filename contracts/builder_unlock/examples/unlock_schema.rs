@@ -1,10 +1,11 @@
-use astroport_governance::builder_unlock::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use astroport_governance::builder_unlock::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use cosmwasm_schema::write_api;
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         query: QueryMsg,
-        execute: ExecuteMsg
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
     }
 }
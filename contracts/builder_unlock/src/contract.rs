@@ -1,32 +1,62 @@
-use crate::astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_ownable::{Action, OwnershipError};
 use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 
 use crate::astroport::asset::addr_opt_validate;
-use crate::contract::helpers::{compute_unlocked_amount, compute_withdraw_amount};
-use crate::migration::MigrateMsg;
+use crate::astroport::staking::Cw20HookMsg as StakingHookMsg;
+use crate::contract::helpers::{
+    aggregate_allocations, allocation_event, allocation_hook_msgs, astro_transfer_msgs,
+    check_account_allocation_cap, compute_unlocked_amount, compute_withdraw_amount,
+    drop_expired_receiver_proposal, has_split_proposals, load_allocations,
+    load_schedule_update_params, locked_voting_power, may_load_allocations,
+    may_load_allocations_at_height, query_astro_balance, query_staked_position,
+    register_allocation_id, state_attributes, sync_delegated_power, unstake_shortfall_msgs,
+};
+use crate::migration::{
+    allocations_migration_pending, migrate_allocations_to_v140, migrate_config_to_v140,
+};
 use astroport_governance::builder_unlock::msg::{
-    AllocationResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, SimulateWithdrawResponse,
-    StateResponse,
+    AllocationResponse, AllocationsFilter, AllocationsOrder, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, QueryMsg, ReceiveMsg, ReceiverProposalResponse, ScheduleUpdateValidation,
+    SimulateWithdrawResponse, SolvencyResponse, StakingStateResponse, StateResponse,
+    TotalUnlockedResponse,
+};
+use astroport_governance::builder_unlock::{
+    AllocationChange, AllocationParams, AllocationStatus, Config, PendingDecrease, Revocation,
+    Schedule, State, MAX_RECEIVER_PROPOSAL_TTL,
 };
-use astroport_governance::builder_unlock::{AllocationParams, AllocationStatus, Config, Schedule};
 
+use astroport_governance::errors::CommonError;
+use astroport_governance::migration::{load_migratable_version, migration_response};
+use astroport_governance::utils::get_period;
+use astroport_governance::voting_escrow::{get_lock_info, Cw20HookMsg as VotingEscrowHookMsg};
 use astroport_governance::{DEFAULT_LIMIT, MAX_LIMIT};
 
-use crate::state::{CONFIG, OWNERSHIP_PROPOSAL, PARAMS, STATE, STATUS};
+use crate::state::{
+    ALLOCATION_COUNTS, CONFIG, DELEGATED_POWER, FROZEN_ACCOUNTS, PARAMS, PENDING_DECREASES,
+    PENDING_LOCK, RECEIVER_PROPOSAL_EXPIRY, REVOCATIONS, SPLIT_PROPOSALS, STAKED_ASTRO, STATE,
+    STATUS, VOTING_DELEGATES,
+};
 
 // Version and name used for contract migration.
 const CONTRACT_NAME: &str = "builder-unlock";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply ID of the message staking ASTRO withdrawn to be locked in vxASTRO
+const LOCK_REPLY_ID: u64 = 1;
+
+/// Reply ID of allocation hook notifications
+const ALLOCATION_HOOK_REPLY_ID: u64 = 2;
+
 /// Creates a new contract with the specified parameters in the `msg` variable.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -36,15 +66,23 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
 
     STATE.save(deps.storage, &Default::default())?;
 
     CONFIG.save(
         deps.storage,
         &Config {
-            owner: deps.api.addr_validate(&msg.owner)?,
             astro_token: deps.api.addr_validate(&msg.astro_token)?,
             max_allocations_amount: msg.max_allocations_amount,
+            decrease_notice_period: msg.decrease_notice_period.unwrap_or_default(),
+            astro_staking: None,
+            yield_recipient: None,
+            max_allocation_per_account: None,
+            max_total_accounts: None,
+            voting_escrow: None,
+            paused: false,
+            allocation_hooks: vec![],
         },
     )?;
     Ok(Response::default())
@@ -57,6 +95,8 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::Withdraw** Withdraw unlocked ASTRO.
 ///
+/// * **ExecuteMsg::WithdrawAndLock** Withdraw unlocked ASTRO and lock it in vxASTRO.
+///
 /// * **ExecuteMsg::TransferOwnership** Transfer contract ownership.
 ///
 /// * **ExecuteMsg::ProposeNewReceiver** Propose a new receiver for a specific ASTRO unlock schedule.
@@ -65,9 +105,32 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::ClaimReceiver**  Claim the position as a receiver for a specific unlock schedule.
 ///
+/// * **ExecuteMsg::ProposeSplitReceivers** Propose splitting an allocation among several new receivers.
+///
+/// * **ExecuteMsg::DropSplitReceivers** Drop the unclaimed part of an allocation split.
+///
+/// * **ExecuteMsg::ClaimSplitReceiver** Claim a part of a split allocation.
+///
 /// * **ExecuteMsg::IncreaseAllocation** Increase ASTRO allocation for receiver.
 ///
-/// * **ExecuteMsg::DecreaseAllocation** Decrease ASTRO allocation for receiver.
+/// * **ExecuteMsg::DecreaseAllocation** Decrease ASTRO allocation for receiver or queue the decrease
+/// if a notice period is configured.
+///
+/// * **ExecuteMsg::DelegateVotingPower** Delegate the voting power of locked allocations.
+///
+/// * **ExecuteMsg::ApplyDecreaseAllocation** Apply a queued allocation decrease.
+///
+/// * **ExecuteMsg::CancelDecreaseAllocation** Cancel a queued allocation decrease.
+///
+/// * **ExecuteMsg::Revoke** Revoke an allocation returning unvested ASTRO to the unallocated tokens.
+///
+/// * **ExecuteMsg::FreezeAccount** Block withdrawals and receiver claims for an account.
+///
+/// * **ExecuteMsg::UnfreezeAccount** Unblock withdrawals and receiver claims for an account.
+///
+/// * **ExecuteMsg::Pause** Suspend withdrawals, receiver changes and allocation changes.
+///
+/// * **ExecuteMsg::Unpause** Resume withdrawals, receiver changes and allocation changes.
 ///
 /// * **ExecuteMsg::TransferUnallocated** Transfer unallocated tokens.
 ///
@@ -77,103 +140,275 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::ClaimOwnership** Claims contract ownership.
 ///
+/// * **ExecuteMsg::UpdateOwnership** Update contract ownership in the cw-ownable format.
+///
 /// * **ExecuteMsg::UpdateConfig** Update contract configuration.
+///
+/// * **ExecuteMsg::UpdateStakingConfig** Set the ASTRO staking contract and the yield recipient.
+///
+/// * **ExecuteMsg::StakeAstro** Stake locked ASTRO in the staking contract.
+///
+/// * **ExecuteMsg::UnstakeAstro** Unstake ASTRO from the staking contract.
+///
+/// * **ExecuteMsg::ClaimStakingYield** Send the staking yield to the yield recipient.
+///
+/// * **ExecuteMsg::MigrateAllocations** Migrate the allocations left after a contract migration.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    if !matches!(msg, ExecuteMsg::MigrateAllocations { .. })
+        && allocations_migration_pending(deps.storage)
+    {
+        return Err(StdError::generic_err(
+            "Allocations are being migrated. Finish the migration with migrate_allocations first",
+        ));
+    }
+
+    if matches!(
+        msg,
+        ExecuteMsg::Receive(..)
+            | ExecuteMsg::Withdraw { .. }
+            | ExecuteMsg::WithdrawAndLock { .. }
+            | ExecuteMsg::ProposeNewReceiver { .. }
+            | ExecuteMsg::DropNewReceiver {}
+            | ExecuteMsg::ClaimReceiver { .. }
+            | ExecuteMsg::ProposeSplitReceivers { .. }
+            | ExecuteMsg::DropSplitReceivers { .. }
+            | ExecuteMsg::ClaimSplitReceiver { .. }
+            | ExecuteMsg::IncreaseAllocation { .. }
+            | ExecuteMsg::DecreaseAllocation { .. }
+            | ExecuteMsg::ApplyDecreaseAllocation { .. }
+            | ExecuteMsg::CancelDecreaseAllocation { .. }
+            | ExecuteMsg::Revoke { .. }
+            | ExecuteMsg::UpdateUnlockSchedules { .. }
+    ) && CONFIG.load(deps.storage)?.paused
+    {
+        return Err(CommonError::Paused {}.into());
+    }
+
     match msg {
-        ExecuteMsg::Receive(cw20_msg) => execute_receive_cw20(deps, info, cw20_msg),
-        ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
-        ExecuteMsg::ProposeNewReceiver { new_receiver } => {
-            execute_propose_new_receiver(deps, info, new_receiver)
+        ExecuteMsg::Receive(cw20_msg) => execute_receive_cw20(deps, env, info, cw20_msg),
+        ExecuteMsg::Withdraw { recipient } => execute_withdraw(deps, env, info, recipient),
+        ExecuteMsg::WithdrawAndLock { lock_time } => {
+            execute_withdraw_and_lock(deps, env, info, lock_time)
         }
-        ExecuteMsg::DropNewReceiver {} => execute_drop_new_receiver(deps, info),
+        ExecuteMsg::ProposeNewReceiver {
+            new_receiver,
+            expires_in,
+        } => execute_propose_new_receiver(deps, env, info, new_receiver, expires_in),
+        ExecuteMsg::DropNewReceiver {} => execute_drop_new_receiver(deps, env, info),
         ExecuteMsg::ClaimReceiver { prev_receiver } => {
-            execute_claim_receiver(deps, info, prev_receiver)
+            execute_claim_receiver(deps, env, info, prev_receiver)
+        }
+        ExecuteMsg::ProposeSplitReceivers {
+            receivers,
+            allocation_id,
+        } => execute_propose_split_receivers(
+            deps,
+            env,
+            info,
+            receivers,
+            allocation_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::DropSplitReceivers { allocation_id } => {
+            execute_drop_split_receivers(deps, info, allocation_id.unwrap_or_default())
         }
-        ExecuteMsg::IncreaseAllocation { receiver, amount } => {
+        ExecuteMsg::ClaimSplitReceiver {
+            prev_receiver,
+            allocation_id,
+        } => execute_claim_split_receiver(
+            deps,
+            env,
+            info,
+            prev_receiver,
+            allocation_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::IncreaseAllocation {
+            receiver,
+            amount,
+            allocation_id,
+        } => {
             let config = CONFIG.load(deps.storage)?;
-            if info.sender != config.owner {
+            if !cw_ownable::is_owner(deps.storage, &info.sender)? {
                 return Err(StdError::generic_err(
                     "Only the contract owner can increase allocations",
                 ));
             }
-            execute_increase_allocation(deps, &config, receiver, amount, None)
+            execute_increase_allocation(
+                deps,
+                env,
+                &config,
+                receiver,
+                allocation_id.unwrap_or_default(),
+                amount,
+                None,
+            )
         }
-        ExecuteMsg::DecreaseAllocation { receiver, amount } => {
-            execute_decrease_allocation(deps, env, info, receiver, amount)
+        ExecuteMsg::DecreaseAllocation {
+            receiver,
+            amount,
+            allocation_id,
+        } => execute_decrease_allocation(
+            deps,
+            env,
+            info,
+            receiver,
+            allocation_id.unwrap_or_default(),
+            amount,
+        ),
+        ExecuteMsg::DelegateVotingPower { delegate } => {
+            execute_delegate_voting_power(deps, env, info, delegate)
         }
+        ExecuteMsg::ApplyDecreaseAllocation {
+            receiver,
+            allocation_id,
+        } => execute_apply_decrease_allocation(
+            deps,
+            env,
+            info,
+            receiver,
+            allocation_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::CancelDecreaseAllocation {
+            receiver,
+            allocation_id,
+        } => execute_cancel_decrease_allocation(
+            deps,
+            info,
+            receiver,
+            allocation_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::Revoke {
+            account,
+            allocation_id,
+        } => execute_revoke(deps, env, info, account, allocation_id.unwrap_or_default()),
+        ExecuteMsg::FreezeAccount { account } => execute_set_frozen(deps, info, account, true),
+        ExecuteMsg::UnfreezeAccount { account } => execute_set_frozen(deps, info, account, false),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
         ExecuteMsg::TransferUnallocated { amount, recipient } => {
-            execute_transfer_unallocated(deps, info, amount, recipient)
+            execute_transfer_unallocated(deps, env, info, amount, recipient)
         }
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
         } => {
-            let config: Config = CONFIG.load(deps.storage)?;
+            let expiry = Expiration::AtTime(env.block.time.plus_seconds(expires_in));
 
-            propose_new_owner(
+            update_ownership(
                 deps,
-                info,
                 env,
-                new_owner,
-                expires_in,
-                config.owner,
-                OWNERSHIP_PROPOSAL,
+                info,
+                Action::TransferOwnership {
+                    new_owner,
+                    expiry: Some(expiry),
+                },
             )
         }
-        ExecuteMsg::DropOwnershipProposal {} => {
-            let config: Config = CONFIG.load(deps.storage)?;
-
-            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
-        }
-        ExecuteMsg::ClaimOwnership {} => {
-            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
-                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
-                    v.owner = new_owner;
-                    Ok(v)
-                })?;
-
-                Ok(())
-            })
-        }
+        ExecuteMsg::DropOwnershipProposal {} => drop_ownership_proposal(deps, env, info),
+        ExecuteMsg::ClaimOwnership {} => update_ownership(deps, env, info, Action::AcceptOwnership),
+        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
         ExecuteMsg::UpdateConfig {
             new_max_allocations_amount,
-        } => update_config(deps, info, new_max_allocations_amount),
+            new_decrease_notice_period,
+            new_max_allocation_per_account,
+            new_max_total_accounts,
+            new_voting_escrow,
+            new_allocation_hooks,
+        } => update_config(
+            deps,
+            info,
+            new_max_allocations_amount,
+            new_decrease_notice_period,
+            new_max_allocation_per_account,
+            new_max_total_accounts,
+            new_voting_escrow,
+            new_allocation_hooks,
+        ),
+        ExecuteMsg::UpdateStakingConfig {
+            astro_staking,
+            yield_recipient,
+        } => update_staking_config(deps, env, info, astro_staking, yield_recipient),
+        ExecuteMsg::StakeAstro { amount } => execute_stake_astro(deps, env, info, amount),
+        ExecuteMsg::UnstakeAstro { amount } => execute_unstake_astro(deps, env, info, amount),
+        ExecuteMsg::ClaimStakingYield {} => execute_claim_staking_yield(deps, env),
         ExecuteMsg::UpdateUnlockSchedules {
             new_unlock_schedules,
-        } => update_unlock_schedules(deps, env, info, new_unlock_schedules),
+            allocation_id,
+        } => update_unlock_schedules(
+            deps,
+            env,
+            info,
+            new_unlock_schedules,
+            allocation_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::MigrateAllocations { limit } => execute_migrate_allocations(deps, env, limit),
     }
 }
 
+/// Migrates the allocations left after a contract migration in batches.
+///
+/// * **limit** amount of accounts to migrate.
+fn execute_migrate_allocations(deps: DepsMut, env: Env, limit: Option<u32>) -> StdResult<Response> {
+    if !allocations_migration_pending(deps.storage) {
+        return Err(StdError::generic_err(
+            "All allocations are already migrated",
+        ));
+    }
+
+    let finished = migrate_allocations_to_v140(
+        deps.storage,
+        &env,
+        limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_allocations")
+        .add_attribute("finished", finished.to_string()))
+}
+
 /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
 ///
 /// * **cw20_msg** CW20 message to process.
 fn execute_receive_cw20(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
 ) -> StdResult<Response> {
     match from_binary(&cw20_msg.msg)? {
         ReceiveMsg::CreateAllocations { allocations } => execute_create_allocations(
             deps,
+            env,
             cw20_msg.sender,
             info.sender,
             cw20_msg.amount,
             allocations,
         ),
-        ReceiveMsg::IncreaseAllocation { user, amount } => {
+        ReceiveMsg::IncreaseAllocation {
+            user,
+            amount,
+            allocation_id,
+        } => {
             let config = CONFIG.load(deps.storage)?;
 
             if config.astro_token != info.sender {
                 return Err(StdError::generic_err("Only ASTRO can be deposited"));
             }
-            if deps.api.addr_validate(&cw20_msg.sender)? != config.owner {
+            if !cw_ownable::is_owner(deps.storage, &deps.api.addr_validate(&cw20_msg.sender)?)? {
                 return Err(StdError::generic_err(
                     "Only the contract owner can increase allocations",
                 ));
             }
 
-            execute_increase_allocation(deps, &config, user, amount, Some(cw20_msg.amount))
+            execute_increase_allocation(
+                deps,
+                env,
+                &config,
+                user,
+                allocation_id.unwrap_or_default(),
+                amount,
+                Some(cw20_msg.amount),
+            )
         }
     }
 }
@@ -183,27 +418,161 @@ fn execute_receive_cw20(
 /// ## Queries
 /// * **QueryMsg::Config {}** Return the contract configuration.
 ///
+/// * **QueryMsg::Ownership {}** Return the contract ownership in the cw-ownable format.
+///
 /// * **QueryMsg::State {}** Return the contract state (number of ASTRO that still need to be withdrawn).
 ///
-/// * **QueryMsg::Allocation {}** Return the allocation details for a specific account.
+/// * **QueryMsg::StakingState {}** Return the amount of staked ASTRO and the accrued staking yield.
+///
+/// * **QueryMsg::Solvency {}** Compare the ASTRO held by the contract against its accounting.
+///
+/// * **QueryMsg::Allocation {}** Return the aggregated allocation details for a specific account.
 ///
-/// * **QueryMsg::UnlockedTokens {}** Return the amount of unlocked ASTRO for a specific account.
+/// * **QueryMsg::AccountAllocations {}** Return the details of every allocation of a specific account.
+///
+/// * **QueryMsg::UnlockedTokens {}** Return the total amount of unlocked ASTRO for a specific account.
 ///
 /// * **QueryMsg::SimulateWithdraw {}** Return the result of a withdrawal simulation.
+///
+/// * **QueryMsg::UnlockedTokensBatch {}** Return the total amount of unlocked ASTRO for multiple accounts.
+///
+/// * **QueryMsg::SimulateWithdrawBatch {}** Return the results of withdrawal simulations for multiple accounts.
+///
+/// * **QueryMsg::TotalUnlockedAt {}** Return the amount of unlocked and locked ASTRO across all allocations.
+///
+/// * **QueryMsg::SplitProposal {}** Return unclaimed parts of a proposed allocation split.
+///
+/// * **QueryMsg::ReceiverProposal {}** Return the pending receiver change proposal of a specific account.
+///
+/// * **QueryMsg::IsFrozen {}** Return whether withdrawals for a specific account are frozen.
+///
+/// * **QueryMsg::ValidateScheduleUpdates {}** Dry-run unlock schedule updates for multiple accounts.
+///
+/// * **QueryMsg::Revocation {}** Return the revocation record for a specific account.
+///
+/// * **QueryMsg::AllocationAtHeight {}** Return the allocation of a specific account at a block height.
+///
+/// * **QueryMsg::VotingPower {}** Return the locked allocation voting power of a specific account.
+///
+/// * **QueryMsg::VotingDelegate {}** Return the voting power delegate of a specific account.
+///
+/// * **QueryMsg::PendingDecrease {}** Return the queued allocation decrease for a specific account.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Ownership {} => to_binary(&cw_ownable::get_ownership(deps.storage)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::StakingState {} => to_binary(&query_staking_state(deps, env)?),
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, env)?),
         QueryMsg::Allocation { account } => to_binary(&query_allocation(deps, account)?),
+        QueryMsg::AccountAllocations { account } => {
+            to_binary(&query_account_allocations(deps, account)?)
+        }
         QueryMsg::UnlockedTokens { account } => {
             to_binary(&query_tokens_unlocked(deps, env, account)?)
         }
         QueryMsg::SimulateWithdraw { account, timestamp } => {
             to_binary(&query_simulate_withdraw(deps, env, account, timestamp)?)
         }
-        QueryMsg::Allocations { start_after, limit } => {
-            to_binary(&query_allocations(deps, start_after, limit)?)
+        QueryMsg::Allocations {
+            start_after,
+            limit,
+            filter,
+            order_by,
+        } => to_binary(&query_allocations(
+            deps,
+            env,
+            start_after,
+            limit,
+            filter,
+            order_by.unwrap_or(AllocationsOrder::Account),
+        )?),
+        QueryMsg::UnlockedTokensBatch { accounts } => {
+            check_batch_size(&accounts)?;
+            to_binary(
+                &accounts
+                    .into_iter()
+                    .map(|account| {
+                        let unlocked = query_tokens_unlocked(deps, env.clone(), account.clone())?;
+                        Ok((account, unlocked))
+                    })
+                    .collect::<StdResult<Vec<_>>>()?,
+            )
+        }
+        QueryMsg::SimulateWithdrawBatch {
+            accounts,
+            timestamp,
+        } => {
+            check_batch_size(&accounts)?;
+            to_binary(
+                &accounts
+                    .into_iter()
+                    .map(|account| {
+                        let simulation =
+                            query_simulate_withdraw(deps, env.clone(), account.clone(), timestamp)?;
+                        Ok((account, simulation))
+                    })
+                    .collect::<StdResult<Vec<_>>>()?,
+            )
+        }
+        QueryMsg::TotalUnlockedAt { timestamp } => {
+            to_binary(&query_total_unlocked_at(deps, timestamp)?)
+        }
+        QueryMsg::SplitProposal {
+            account,
+            allocation_id,
+        } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(
+                &SPLIT_PROPOSALS
+                    .may_load(deps.storage, (&account, allocation_id.unwrap_or_default()))?,
+            )
+        }
+        QueryMsg::ReceiverProposal { account } => {
+            to_binary(&query_receiver_proposal(deps, env, account)?)
+        }
+        QueryMsg::IsFrozen { account } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(&FROZEN_ACCOUNTS.has(deps.storage, &account))
+        }
+        QueryMsg::ValidateScheduleUpdates {
+            new_unlock_schedules,
+            allocation_id,
+        } => to_binary(&query_validate_schedule_updates(
+            deps,
+            new_unlock_schedules,
+            allocation_id.unwrap_or_default(),
+        )?),
+        QueryMsg::Revocation {
+            account,
+            allocation_id,
+        } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(
+                &REVOCATIONS
+                    .may_load(deps.storage, (&account, allocation_id.unwrap_or_default()))?,
+            )
+        }
+        QueryMsg::AllocationAtHeight { account, height } => {
+            to_binary(&query_allocation_at_height(deps, account, height)?)
+        }
+        QueryMsg::VotingPower { account, height } => {
+            to_binary(&query_voting_power(deps, account, height)?)
+        }
+        QueryMsg::VotingDelegate { account } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(&VOTING_DELEGATES.may_load(deps.storage, &account)?)
+        }
+        QueryMsg::PendingDecrease {
+            account,
+            allocation_id,
+        } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(
+                &PENDING_DECREASES
+                    .may_load(deps.storage, (&account, allocation_id.unwrap_or_default()))?,
+            )
         }
     }
 }
@@ -216,9 +585,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 ///
 /// * **deposit_amount** tokens sent along with the call (should equal the sum of allocation amounts)
 ///
-/// * **deposit_amount** new allocations being created.
+/// * **allocations** new allocations being created. An account which already has allocations receives
+/// a new allocation with the next allocation ID.
 fn execute_create_allocations(
     deps: DepsMut,
+    env: Env,
     creator: String,
     deposit_token: Addr,
     deposit_amount: Uint128,
@@ -227,7 +598,7 @@ fn execute_create_allocations(
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
 
-    if deps.api.addr_validate(&creator)? != config.owner {
+    if !cw_ownable::is_owner(deps.storage, &deps.api.addr_validate(&creator)?)? {
         return Err(StdError::generic_err(
             "Only the contract owner can create allocations",
         ));
@@ -256,108 +627,341 @@ fn execute_create_allocations(
         )));
     }
 
+    let mut total_accounts = match config.max_total_accounts {
+        Some(_) => ALLOCATION_COUNTS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count() as u64,
+        None => 0,
+    };
+
+    let mut events = vec![];
+    let mut hook_msgs = vec![];
     for (user_unchecked, params) in allocations {
         params.validate(&user_unchecked)?;
         let user = deps.api.addr_validate(&user_unchecked)?;
 
-        if PARAMS.has(deps.storage, &user) {
+        if let Some(max_total_accounts) = config.max_total_accounts {
+            if !ALLOCATION_COUNTS.has(deps.storage, &user) {
+                total_accounts += 1;
+                if total_accounts > max_total_accounts {
+                    return Err(StdError::generic_err(format!(
+                        "The number of accounts with allocations can't exceed {max_total_accounts}"
+                    )));
+                }
+            }
+        }
+
+        drop_expired_receiver_proposal(deps.storage, &env, &user)?;
+        let allocations = may_load_allocations(deps.storage, &user)?;
+        if allocations
+            .iter()
+            .any(|(_, params, _)| params.proposed_receiver.is_some())
+        {
             return Err(StdError::generic_err(format!(
-                "Allocation (params) already exists for {user}"
+                "Allocations of {user} are being transferred to a new receiver"
             )));
         }
-        PARAMS.save(deps.storage, &user, &params)?;
-        STATUS.save(deps.storage, &user, &AllocationStatus::new())?;
+
+        let prev_locked = locked_voting_power(deps.storage, &user, None)?;
+        let allocation_id = register_allocation_id(deps.storage, &user, None)?;
+        PARAMS.save(
+            deps.storage,
+            (&user, allocation_id),
+            &params,
+            env.block.height,
+        )?;
+        STATUS.save(
+            deps.storage,
+            (&user, allocation_id),
+            &AllocationStatus::new(),
+            env.block.height,
+        )?;
+        check_account_allocation_cap(deps.storage, &config, &user)?;
+        sync_delegated_power(deps.storage, &user, prev_locked, env.block.height)?;
+
+        hook_msgs.extend(allocation_hook_msgs(
+            &config,
+            AllocationChange::Create,
+            &user,
+            allocation_id,
+            params.amount,
+        )?);
+        events.push(allocation_event(
+            "create_allocation",
+            &user,
+            allocation_id,
+            &params,
+        ));
     }
 
     STATE.save(deps.storage, &state)?;
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_events(events)
+        .add_attribute("action", "create_allocations")
+        .add_attributes(state_attributes(&state)))
 }
 
-/// Allow allocation recipients to withdraw unlocked ASTRO.
-fn execute_withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+/// Allow allocation recipients to withdraw unlocked ASTRO from all their allocations.
+///
+/// * **recipient** address that receives the withdrawn ASTRO. The sender receives it by default.
+fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?;
+    let recipient = recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
 
-    let params = PARAMS.load(deps.storage, &info.sender)?;
+    let astro_to_withdraw = withdraw_unlocked_astro(deps.storage, &env, &info.sender)?;
 
-    if params.proposed_receiver.is_some() {
+    Ok(Response::new()
+        .add_messages(astro_transfer_msgs(
+            deps,
+            &env,
+            &config,
+            &recipient,
+            astro_to_withdraw,
+        )?)
+        .add_attribute("astro_withdrawn", astro_to_withdraw)
+        .add_attribute("recipient", recipient))
+}
+
+/// Withdraw unlocked ASTRO, stake it in the ASTRO staking contract and deposit the received xASTRO
+/// into the sender's vxASTRO lock. The xASTRO is deposited in [`reply`] once the amount is known.
+///
+/// * **lock_time** minimum time in seconds the sender's vxASTRO lock must still last.
+fn execute_withdraw_and_lock(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock_time: Option<u64>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let astro_staking = config
+        .astro_staking
+        .clone()
+        .ok_or_else(|| StdError::generic_err("ASTRO staking contract is not set"))?;
+    let voting_escrow = config
+        .voting_escrow
+        .clone()
+        .ok_or_else(|| StdError::generic_err("Voting escrow contract is not set"))?;
+
+    let lock_info = get_lock_info(&deps.querier, &voting_escrow, &info.sender).map_err(|_| {
+        StdError::generic_err(format!("{} doesn't have a vxASTRO lock", info.sender))
+    })?;
+    if lock_info.end <= get_period(env.block.time.seconds() + lock_time.unwrap_or_default())? {
+        return Err(StdError::generic_err(format!(
+            "vxASTRO lock of {} expires too early. Extend the lock first",
+            info.sender
+        )));
+    }
+
+    let astro_to_lock = withdraw_unlocked_astro(deps.storage, &env, &info.sender)?;
+    let (unstake_msgs, unstaked_shares) =
+        unstake_shortfall_msgs(deps.branch(), &env, &config, astro_to_lock)?;
+    let position = query_staked_position(&deps.querier, &astro_staking, &env)?;
+    PENDING_LOCK.save(
+        deps.storage,
+        &(info.sender.clone(), position.shares - unstaked_shares),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(unstake_msgs)
+        .add_submessage(SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: config.astro_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: astro_staking.to_string(),
+                    amount: astro_to_lock,
+                    msg: to_binary(&StakingHookMsg::Enter {})?,
+                })?,
+                funds: vec![],
+            },
+            LOCK_REPLY_ID,
+        ))
+        .add_attribute("action", "withdraw_and_lock")
+        .add_attribute("astro_withdrawn", astro_to_lock))
+}
+
+/// Deposits the xASTRO received for the ASTRO withdrawn in [`execute_withdraw_and_lock`]
+/// into the vxASTRO lock of the account. Also handles failed allocation hook notifications, whose
+/// state changes are reverted while the allocation change which triggered them is kept.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+    match (msg.id, msg.result) {
+        (LOCK_REPLY_ID, _) => {
+            let config = CONFIG.load(deps.storage)?;
+            let (account, xastro_balance) = PENDING_LOCK.load(deps.storage)?;
+            PENDING_LOCK.remove(deps.storage);
+
+            let astro_staking = config
+                .astro_staking
+                .ok_or_else(|| StdError::generic_err("ASTRO staking contract is not set"))?;
+            let voting_escrow = config
+                .voting_escrow
+                .ok_or_else(|| StdError::generic_err("Voting escrow contract is not set"))?;
+
+            let position = query_staked_position(&deps.querier, &astro_staking, &env)?;
+            let xastro_to_lock = position.shares.checked_sub(xastro_balance)?;
+
+            Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: position.xastro_token.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: voting_escrow.to_string(),
+                        amount: xastro_to_lock,
+                        msg: to_binary(&VotingEscrowHookMsg::DepositFor {
+                            user: account.to_string(),
+                        })?,
+                    })?,
+                    funds: vec![],
+                })
+                .add_attribute("xastro_locked", xastro_to_lock)
+                .add_attribute("user", account))
+        }
+        (ALLOCATION_HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "allocation_hook_failed")
+            .add_attribute("error", err)),
+        _ => Err(StdError::generic_err("Unknown reply ID")),
+    }
+}
+
+/// Marks all unlocked ASTRO of **account** as withdrawn and returns its amount.
+fn withdraw_unlocked_astro(
+    storage: &mut dyn Storage,
+    env: &Env,
+    account: &Addr,
+) -> StdResult<Uint128> {
+    let mut state = STATE.load(storage)?;
+
+    drop_expired_receiver_proposal(storage, env, account)?;
+    let allocations = load_allocations(storage, account)?;
+
+    if FROZEN_ACCOUNTS.has(storage, account) {
+        return Err(StdError::generic_err(format!(
+            "Account {account} is frozen"
+        )));
+    }
+
+    if allocations
+        .iter()
+        .any(|(_, params, _)| params.proposed_receiver.is_some())
+        || has_split_proposals(storage, account)
+    {
         return Err(StdError::generic_err(
             "You may not withdraw once you proposed new receiver!",
         ));
     }
 
-    let mut status = STATUS.load(deps.storage, &info.sender)?;
+    let prev_locked = locked_voting_power(storage, account, None)?;
+    let mut astro_to_withdraw = Uint128::zero();
+    for (allocation_id, params, mut status) in allocations {
+        let withdraw_amount =
+            compute_withdraw_amount(env.block.time.seconds(), &params, &status).astro_to_withdraw;
 
-    let SimulateWithdrawResponse { astro_to_withdraw } =
-        compute_withdraw_amount(env.block.time.seconds(), &params, &status);
+        if !withdraw_amount.is_zero() {
+            status.astro_withdrawn += withdraw_amount;
+            STATUS.save(storage, (account, allocation_id), &status, env.block.height)?;
+            astro_to_withdraw += withdraw_amount;
+        }
+    }
 
     if astro_to_withdraw.is_zero() {
         return Err(StdError::generic_err("No unlocked ASTRO to be withdrawn"));
     }
 
-    status.astro_withdrawn += astro_to_withdraw;
+    sync_delegated_power(storage, account, prev_locked, env.block.height)?;
     state.remaining_astro_tokens -= astro_to_withdraw;
 
-    // SAVE :: state & allocation
-    STATE.save(deps.storage, &state)?;
-
-    // Update status
-    STATUS.save(deps.storage, &info.sender, &status)?;
+    // SAVE :: state
+    STATE.save(storage, &state)?;
 
-    Ok(Response::new()
-        .add_message(WasmMsg::Execute {
-            contract_addr: config.astro_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.to_string(),
-                amount: astro_to_withdraw,
-            })?,
-            funds: vec![],
-        })
-        .add_attribute("astro_withdrawn", astro_to_withdraw))
+    Ok(astro_to_withdraw)
 }
 
-/// Allows the current allocation receiver to propose a new receiver.
+/// Allows the current allocation receiver to propose a new receiver for all their allocations.
 ///
-/// * **new_receiver** new proposed receiver for the allocation.
+/// * **new_receiver** new proposed receiver for the allocations.
+///
+/// * **expires_in** the validity period of the proposal in seconds.
 fn execute_propose_new_receiver(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_receiver: String,
+    expires_in: u64,
 ) -> StdResult<Response> {
-    let mut alloc_params = PARAMS.load(deps.storage, &info.sender)?;
+    if expires_in == 0 || expires_in > MAX_RECEIVER_PROPOSAL_TTL {
+        return Err(StdError::generic_err(format!(
+            "Parameter expires_in must be between 1 and {MAX_RECEIVER_PROPOSAL_TTL} seconds"
+        )));
+    }
+
+    drop_expired_receiver_proposal(deps.storage, &env, &info.sender)?;
+    let allocations = load_allocations(deps.storage, &info.sender)?;
     let new_receiver = deps.api.addr_validate(&new_receiver)?;
 
-    match alloc_params.proposed_receiver {
+    match allocations[0].1.proposed_receiver.clone() {
         Some(proposed_receiver) => {
             return Err(StdError::generic_err(format!(
                 "Proposed receiver already set to {proposed_receiver}"
             )));
         }
         None => {
-            if PARAMS.has(deps.storage, &new_receiver) {
+            if has_split_proposals(deps.storage, &info.sender) {
+                return Err(StdError::generic_err(
+                    "You may not propose a new receiver while an allocation split is proposed",
+                ));
+            }
+
+            if !may_load_allocations(deps.storage, &new_receiver)?.is_empty() {
                 return Err(StdError::generic_err(
                     "Invalid new_receiver. Proposed receiver already has an ASTRO allocation",
                 ));
             }
 
-            alloc_params.proposed_receiver = Some(new_receiver.clone());
-            PARAMS.save(deps.storage, &info.sender, &alloc_params)?;
+            for (allocation_id, mut alloc_params, _) in allocations {
+                alloc_params.proposed_receiver = Some(new_receiver.clone());
+                PARAMS.save(
+                    deps.storage,
+                    (&info.sender, allocation_id),
+                    &alloc_params,
+                    env.block.height,
+                )?;
+            }
         }
     }
 
+    let expires_at = env.block.time.seconds() + expires_in;
+    RECEIVER_PROPOSAL_EXPIRY.save(deps.storage, &info.sender, &expires_at)?;
+
     Ok(Response::new()
         .add_attribute("action", "ProposeNewReceiver")
-        .add_attribute("proposed_receiver", new_receiver))
+        .add_attribute("proposed_receiver", new_receiver)
+        .add_attribute("expires_at", expires_at.to_string()))
 }
 
-/// Drop the new proposed receiver for a specific allocation.
-fn execute_drop_new_receiver(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
-    let mut alloc_params = PARAMS.load(deps.storage, &info.sender)?;
+/// Drop the new proposed receiver for the allocations of the sender.
+fn execute_drop_new_receiver(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let allocations = load_allocations(deps.storage, &info.sender)?;
 
-    match alloc_params.proposed_receiver {
+    match allocations[0].1.proposed_receiver.clone() {
         Some(proposed_receiver) => {
-            alloc_params.proposed_receiver = None;
-            PARAMS.save(deps.storage, &info.sender, &alloc_params)?;
+            for (allocation_id, mut alloc_params, _) in allocations {
+                alloc_params.proposed_receiver = None;
+                PARAMS.save(
+                    deps.storage,
+                    (&info.sender, allocation_id),
+                    &alloc_params,
+                    env.block.height,
+                )?;
+            }
+            RECEIVER_PROPOSAL_EXPIRY.remove(deps.storage, &info.sender);
 
             Ok(Response::new()
                 .add_attribute("action", "DropNewReceiver")
@@ -367,78 +971,726 @@ fn execute_drop_new_receiver(deps: DepsMut, info: MessageInfo) -> StdResult<Resp
     }
 }
 
-/// Decrease an address' ASTRO allocation.
+/// Allows the current allocation receiver to propose splitting an allocation among several new receivers.
+/// Each part can be claimed independently with [`execute_claim_split_receiver`].
 ///
-/// * **receiver** address that will have its allocation decreased.
+/// * **receivers** new receivers along with the amounts they receive.
 ///
-/// * **amount** ASTRO amount to decrease the allocation by.
-fn execute_decrease_allocation(
+/// * **allocation_id** ID of the allocation to split.
+fn execute_propose_split_receivers(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    receiver: String,
-    amount: Uint128,
+    receivers: Vec<(String, Uint128)>,
+    allocation_id: u64,
 ) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    drop_expired_receiver_proposal(deps.storage, &env, &info.sender)?;
+    let params = PARAMS.load(deps.storage, (&info.sender, allocation_id))?;
+
+    if params.proposed_receiver.is_some() {
         return Err(StdError::generic_err(
-            "Only the contract owner can decrease allocations",
+            "You may not split an allocation once you proposed new receiver!",
         ));
     }
 
-    let receiver = deps.api.addr_validate(&receiver)?;
+    if SPLIT_PROPOSALS.has(deps.storage, (&info.sender, allocation_id)) {
+        return Err(StdError::generic_err(format!(
+            "Allocation {allocation_id} split is already proposed"
+        )));
+    }
 
-    let mut state = STATE.load(deps.storage)?;
-    let mut params = PARAMS.load(deps.storage, &receiver)?;
-    let mut status = STATUS.load(deps.storage, &receiver)?;
+    if receivers.is_empty() {
+        return Err(StdError::generic_err("Split receivers must not be empty"));
+    }
 
-    let unlocked_amount = compute_unlocked_amount(
-        env.block.time.seconds(),
-        params.amount,
-        &params.unlock_schedule,
-        status.unlocked_amount_checkpoint,
-    );
-    let locked_amount = params.amount - unlocked_amount;
+    let mut split = Vec::with_capacity(receivers.len());
+    for (receiver, amount) in receivers {
+        let receiver = deps.api.addr_validate(&receiver)?;
 
-    if locked_amount < amount {
+        if receiver == info.sender || split.iter().any(|(addr, _)| addr == &receiver) {
+            return Err(StdError::generic_err(format!(
+                "Invalid split receiver {receiver}"
+            )));
+        }
+
+        if amount.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "Split amount must not be zero. Receiver: {receiver}"
+            )));
+        }
+
+        split.push((receiver, amount));
+    }
+
+    let split_amount: Uint128 = split.iter().map(|(_, amount)| *amount).sum();
+    if split_amount != params.amount {
         return Err(StdError::generic_err(format!(
-            "Insufficient amount of lock to decrease allocation, user has locked {locked_amount} ASTRO."
+            "Split amounts must sum up to the allocation amount: {} != {}",
+            split_amount, params.amount
         )));
     }
 
-    params.amount = params.amount.checked_sub(amount)?;
-    status.unlocked_amount_checkpoint = unlocked_amount;
-    state.unallocated_tokens = state.unallocated_tokens.checked_add(amount)?;
-    state.remaining_astro_tokens = state.remaining_astro_tokens.checked_sub(amount)?;
-
-    STATUS.save(deps.storage, &receiver, &status)?;
-    PARAMS.save(deps.storage, &receiver, &params)?;
-    STATE.save(deps.storage, &state)?;
+    SPLIT_PROPOSALS.save(deps.storage, (&info.sender, allocation_id), &split)?;
 
     Ok(Response::new().add_attributes(vec![
-        attr("action", "execute_decrease_allocation"),
-        attr("receiver", receiver),
-        attr("amount", amount),
+        attr("action", "ProposeSplitReceivers"),
+        attr("allocation_id", allocation_id.to_string()),
+        attr(
+            "proposed_receivers",
+            split
+                .iter()
+                .map(|(receiver, _)| receiver.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
     ]))
 }
 
-/// Increase an address' ASTRO allocation.
-///
-/// * **receiver** address that will have its allocation incrased.
+/// Drop the unclaimed part of a proposed allocation split.
 ///
-/// * **amount** ASTRO amount to increase the allocation by.
-///
-/// * **deposit_amount** is amount of ASTRO to increase the allocation by using CW20 Receive.
-fn execute_increase_allocation(
+/// * **allocation_id** ID of the split allocation.
+fn execute_drop_split_receivers(
     deps: DepsMut,
+    info: MessageInfo,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    if !SPLIT_PROPOSALS.has(deps.storage, (&info.sender, allocation_id)) {
+        return Err(StdError::generic_err("Allocation split is not proposed"));
+    }
+
+    SPLIT_PROPOSALS.remove(deps.storage, (&info.sender, allocation_id));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "DropSplitReceivers"),
+        attr("allocation_id", allocation_id.to_string()),
+    ]))
+}
+
+/// Allows a proposed split receiver to claim their part of an allocation. The part is transferred as
+/// a new allocation with the same unlock schedule and a proportional share of the allocation status.
+///
+/// * **prev_receiver** receiver of the split allocation.
+///
+/// * **allocation_id** ID of the split allocation.
+fn execute_claim_split_receiver(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prev_receiver: String,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    let prev_receiver_addr = deps.api.addr_validate(&prev_receiver)?;
+
+    if FROZEN_ACCOUNTS.has(deps.storage, &prev_receiver_addr) {
+        return Err(StdError::generic_err(format!(
+            "Account {prev_receiver_addr} is frozen"
+        )));
+    }
+
+    let mut split = SPLIT_PROPOSALS
+        .may_load(deps.storage, (&prev_receiver_addr, allocation_id))?
+        .ok_or_else(|| StdError::generic_err("Allocation split is not proposed"))?;
+    let position = split
+        .iter()
+        .position(|(receiver, _)| receiver == &info.sender)
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "{} is not a split receiver of the allocation",
+                info.sender
+            ))
+        })?;
+    let (_, amount) = split.remove(position);
+
+    let mut params = PARAMS.load(deps.storage, (&prev_receiver_addr, allocation_id))?;
+    let mut status = STATUS.load(deps.storage, (&prev_receiver_addr, allocation_id))?;
+
+    // The last part takes the rest of the status so that rounding errors don't accumulate
+    let part_status = if amount >= params.amount {
+        status.clone()
+    } else {
+        AllocationStatus {
+            astro_withdrawn: status.astro_withdrawn.multiply_ratio(amount, params.amount),
+            unlocked_amount_checkpoint: status
+                .unlocked_amount_checkpoint
+                .multiply_ratio(amount, params.amount),
+        }
+    };
+
+    params.amount = params.amount.checked_sub(amount)?;
+    status.astro_withdrawn = status
+        .astro_withdrawn
+        .checked_sub(part_status.astro_withdrawn)?;
+    status.unlocked_amount_checkpoint = status
+        .unlocked_amount_checkpoint
+        .checked_sub(part_status.unlocked_amount_checkpoint)?;
+
+    let prev_receiver_locked = locked_voting_power(deps.storage, &prev_receiver_addr, None)?;
+    let receiver_locked = locked_voting_power(deps.storage, &info.sender, None)?;
+    let receiver_allocation_id = register_allocation_id(deps.storage, &info.sender, None)?;
+    PARAMS.save(
+        deps.storage,
+        (&info.sender, receiver_allocation_id),
+        &AllocationParams {
+            amount,
+            unlock_schedule: params.unlock_schedule.clone(),
+            proposed_receiver: None,
+        },
+        env.block.height,
+    )?;
+    STATUS.save(
+        deps.storage,
+        (&info.sender, receiver_allocation_id),
+        &part_status,
+        env.block.height,
+    )?;
+
+    if params.amount.is_zero() {
+        PARAMS.remove(
+            deps.storage,
+            (&prev_receiver_addr, allocation_id),
+            env.block.height,
+        )?;
+        STATUS.remove(
+            deps.storage,
+            (&prev_receiver_addr, allocation_id),
+            env.block.height,
+        )?;
+        PENDING_DECREASES.remove(deps.storage, (&prev_receiver_addr, allocation_id));
+    } else {
+        PARAMS.save(
+            deps.storage,
+            (&prev_receiver_addr, allocation_id),
+            &params,
+            env.block.height,
+        )?;
+        STATUS.save(
+            deps.storage,
+            (&prev_receiver_addr, allocation_id),
+            &status,
+            env.block.height,
+        )?;
+    }
+
+    if split.is_empty() {
+        SPLIT_PROPOSALS.remove(deps.storage, (&prev_receiver_addr, allocation_id));
+    } else {
+        SPLIT_PROPOSALS.save(deps.storage, (&prev_receiver_addr, allocation_id), &split)?;
+    }
+
+    sync_delegated_power(
+        deps.storage,
+        &prev_receiver_addr,
+        prev_receiver_locked,
+        env.block.height,
+    )?;
+    sync_delegated_power(
+        deps.storage,
+        &info.sender,
+        receiver_locked,
+        env.block.height,
+    )?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut hook_msgs = allocation_hook_msgs(
+        &config,
+        AllocationChange::Transfer {
+            prev_account: prev_receiver_addr.to_string(),
+        },
+        &info.sender,
+        receiver_allocation_id,
+        amount,
+    )?;
+    hook_msgs.extend(allocation_hook_msgs(
+        &config,
+        AllocationChange::Decrease,
+        &prev_receiver_addr,
+        allocation_id,
+        params.amount,
+    )?);
+
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_attributes(vec![
+            attr("action", "ClaimSplitReceiver"),
+            attr("prev_receiver", prev_receiver),
+            attr("receiver", info.sender),
+            attr("amount", amount),
+            attr("allocation_id", receiver_allocation_id.to_string()),
+        ]))
+}
+
+/// Decrease an address' ASTRO allocation. If a notice period is configured, the decrease is queued
+/// and can be applied with [`execute_apply_decrease_allocation`] once the notice period has passed.
+///
+/// * **receiver** address that will have its allocation decreased.
+///
+/// * **allocation_id** ID of the receiver's allocation to decrease.
+///
+/// * **amount** ASTRO amount to decrease the allocation by.
+fn execute_decrease_allocation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: String,
+    allocation_id: u64,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can decrease allocations",
+        ));
+    }
+
+    let receiver = deps.api.addr_validate(&receiver)?;
+
+    if config.decrease_notice_period == 0 {
+        let (params, state) =
+            decrease_allocation(deps.storage, &env, &receiver, allocation_id, amount)?;
+
+        return Ok(Response::new()
+            .add_submessages(allocation_hook_msgs(
+                &config,
+                AllocationChange::Decrease,
+                &receiver,
+                allocation_id,
+                params.amount,
+            )?)
+            .add_event(allocation_event(
+                "decrease_allocation",
+                &receiver,
+                allocation_id,
+                &params,
+            ))
+            .add_attributes(vec![
+                attr("action", "execute_decrease_allocation"),
+                attr("receiver", receiver),
+                attr("allocation_id", allocation_id.to_string()),
+                attr("amount", amount),
+            ])
+            .add_attributes(state_attributes(&state)));
+    }
+
+    if PENDING_DECREASES.has(deps.storage, (&receiver, allocation_id)) {
+        return Err(StdError::generic_err(format!(
+            "A decrease of allocation {allocation_id} is already queued for {receiver}"
+        )));
+    }
+
+    // Fail early if the decrease can't be applied at the moment
+    let params = PARAMS.load(deps.storage, (&receiver, allocation_id))?;
+    let status = STATUS.load(deps.storage, (&receiver, allocation_id))?;
+    let locked_amount = params.amount
+        - compute_unlocked_amount(
+            env.block.time.seconds(),
+            params.amount,
+            &params.unlock_schedule,
+            status.unlocked_amount_checkpoint,
+        );
+    if locked_amount < amount {
+        return Err(StdError::generic_err(format!(
+            "Insufficient amount of lock to decrease allocation, user has locked {locked_amount} ASTRO."
+        )));
+    }
+
+    let effective_at = env.block.time.seconds() + config.decrease_notice_period;
+    PENDING_DECREASES.save(
+        deps.storage,
+        (&receiver, allocation_id),
+        &PendingDecrease {
+            amount,
+            effective_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "queue_decrease_allocation"),
+        attr("receiver", receiver),
+        attr("allocation_id", allocation_id.to_string()),
+        attr("amount", amount),
+        attr("effective_at", effective_at.to_string()),
+    ]))
+}
+
+/// Delegate the voting power of the sender's locked allocations to another account.
+/// Delegating to the sender's own address removes the delegation.
+///
+/// * **delegate** account that receives the voting power.
+fn execute_delegate_voting_power(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> StdResult<Response> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let prev_delegate = VOTING_DELEGATES.may_load(deps.storage, &info.sender)?;
+    let locked = locked_voting_power(deps.storage, &info.sender, None)?;
+
+    if let Some(prev_delegate) = &prev_delegate {
+        if prev_delegate == &delegate {
+            return Err(StdError::generic_err(format!(
+                "Voting power is already delegated to {delegate}"
+            )));
+        }
+
+        let delegated = DELEGATED_POWER
+            .load(deps.storage, prev_delegate)?
+            .checked_sub(locked)?;
+        DELEGATED_POWER.save(deps.storage, prev_delegate, &delegated, env.block.height)?;
+    }
+
+    if delegate == info.sender {
+        if prev_delegate.is_none() {
+            return Err(StdError::generic_err("Voting power is not delegated"));
+        }
+
+        VOTING_DELEGATES.remove(deps.storage, &info.sender, env.block.height)?;
+    } else {
+        VOTING_DELEGATES.save(deps.storage, &info.sender, &delegate, env.block.height)?;
+        let delegated = DELEGATED_POWER
+            .may_load(deps.storage, &delegate)?
+            .unwrap_or_default()
+            .checked_add(locked)?;
+        DELEGATED_POWER.save(deps.storage, &delegate, &delegated, env.block.height)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "delegate_voting_power"),
+        attr("account", info.sender),
+        attr("delegate", delegate),
+    ]))
+}
+
+/// Apply a queued allocation decrease once its notice period has passed.
+///
+/// * **receiver** address that will have its allocation decreased.
+///
+/// * **allocation_id** ID of the receiver's allocation to decrease.
+fn execute_apply_decrease_allocation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: String,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can decrease allocations",
+        ));
+    }
+
+    let receiver = deps.api.addr_validate(&receiver)?;
+    let pending = PENDING_DECREASES
+        .may_load(deps.storage, (&receiver, allocation_id))?
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "No decrease of allocation {allocation_id} is queued for {receiver}"
+            ))
+        })?;
+
+    if env.block.time.seconds() < pending.effective_at {
+        return Err(StdError::generic_err(format!(
+            "The decrease of allocation {allocation_id} for {receiver} can't be applied before {}",
+            pending.effective_at
+        )));
+    }
+
+    let (params, state) =
+        decrease_allocation(deps.storage, &env, &receiver, allocation_id, pending.amount)?;
+    PENDING_DECREASES.remove(deps.storage, (&receiver, allocation_id));
+
+    Ok(Response::new()
+        .add_submessages(allocation_hook_msgs(
+            &config,
+            AllocationChange::Decrease,
+            &receiver,
+            allocation_id,
+            params.amount,
+        )?)
+        .add_event(allocation_event(
+            "decrease_allocation",
+            &receiver,
+            allocation_id,
+            &params,
+        ))
+        .add_attributes(vec![
+            attr("action", "apply_decrease_allocation"),
+            attr("receiver", receiver),
+            attr("allocation_id", allocation_id.to_string()),
+            attr("amount", pending.amount),
+        ])
+        .add_attributes(state_attributes(&state)))
+}
+
+/// Cancel a queued allocation decrease.
+///
+/// * **receiver** address whose queued decrease is cancelled.
+///
+/// * **allocation_id** ID of the receiver's allocation.
+fn execute_cancel_decrease_allocation(
+    deps: DepsMut,
+    info: MessageInfo,
+    receiver: String,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can decrease allocations",
+        ));
+    }
+
+    let receiver = deps.api.addr_validate(&receiver)?;
+    if !PENDING_DECREASES.has(deps.storage, (&receiver, allocation_id)) {
+        return Err(StdError::generic_err(format!(
+            "No decrease of allocation {allocation_id} is queued for {receiver}"
+        )));
+    }
+
+    PENDING_DECREASES.remove(deps.storage, (&receiver, allocation_id));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_decrease_allocation"),
+        attr("receiver", receiver),
+        attr("allocation_id", allocation_id.to_string()),
+    ]))
+}
+
+/// Removes **amount** of locked ASTRO from an allocation and returns it to the unallocated tokens.
+/// Returns the updated allocation parameters and the global state.
+fn decrease_allocation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    receiver: &Addr,
+    allocation_id: u64,
+    amount: Uint128,
+) -> StdResult<(AllocationParams, State)> {
+    let mut state = STATE.load(storage)?;
+    let mut params = PARAMS.load(storage, (receiver, allocation_id))?;
+    let mut status = STATUS.load(storage, (receiver, allocation_id))?;
+    let prev_locked = locked_voting_power(storage, receiver, None)?;
+
+    let unlocked_amount = compute_unlocked_amount(
+        env.block.time.seconds(),
+        params.amount,
+        &params.unlock_schedule,
+        status.unlocked_amount_checkpoint,
+    );
+    let locked_amount = params.amount - unlocked_amount;
+
+    if locked_amount < amount {
+        return Err(StdError::generic_err(format!(
+            "Insufficient amount of lock to decrease allocation, user has locked {locked_amount} ASTRO."
+        )));
+    }
+
+    params.amount = params.amount.checked_sub(amount)?;
+    status.unlocked_amount_checkpoint = unlocked_amount;
+    state.unallocated_tokens = state.unallocated_tokens.checked_add(amount)?;
+    state.remaining_astro_tokens = state.remaining_astro_tokens.checked_sub(amount)?;
+
+    STATUS.save(
+        storage,
+        (receiver, allocation_id),
+        &status,
+        env.block.height,
+    )?;
+    PARAMS.save(
+        storage,
+        (receiver, allocation_id),
+        &params,
+        env.block.height,
+    )?;
+    sync_delegated_power(storage, receiver, prev_locked, env.block.height)?;
+    STATE.save(storage, &state)?;
+
+    Ok((params, state))
+}
+
+/// Revoke an address' ASTRO allocation. Already unlocked ASTRO can still be withdrawn by the account
+/// while the unvested remainder is returned to the unallocated tokens.
+///
+/// * **account** address whose allocation is revoked.
+///
+/// * **allocation_id** ID of the account's allocation to revoke.
+fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account: String,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can revoke allocations",
+        ));
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    if REVOCATIONS.has(deps.storage, (&account, allocation_id)) {
+        return Err(StdError::generic_err(format!(
+            "Allocation {allocation_id} is already revoked for {account}"
+        )));
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    let mut params = PARAMS.load(deps.storage, (&account, allocation_id))?;
+    let mut status = STATUS.load(deps.storage, (&account, allocation_id))?;
+    let prev_locked = locked_voting_power(deps.storage, &account, None)?;
+
+    let unlocked_amount = compute_unlocked_amount(
+        env.block.time.seconds(),
+        params.amount,
+        &params.unlock_schedule,
+        status.unlocked_amount_checkpoint,
+    );
+    let unvested_amount = params.amount.checked_sub(unlocked_amount)?;
+
+    params.amount = unlocked_amount;
+    status.unlocked_amount_checkpoint = unlocked_amount;
+    state.unallocated_tokens = state.unallocated_tokens.checked_add(unvested_amount)?;
+    state.remaining_astro_tokens = state.remaining_astro_tokens.checked_sub(unvested_amount)?;
+
+    STATUS.save(
+        deps.storage,
+        (&account, allocation_id),
+        &status,
+        env.block.height,
+    )?;
+    PARAMS.save(
+        deps.storage,
+        (&account, allocation_id),
+        &params,
+        env.block.height,
+    )?;
+    sync_delegated_power(deps.storage, &account, prev_locked, env.block.height)?;
+    STATE.save(deps.storage, &state)?;
+    REVOCATIONS.save(
+        deps.storage,
+        (&account, allocation_id),
+        &Revocation {
+            revoked_at: env.block.time.seconds(),
+            unvested_amount,
+        },
+    )?;
+    // Nothing is left to decrease once the allocation is revoked
+    PENDING_DECREASES.remove(deps.storage, (&account, allocation_id));
+
+    Ok(Response::new()
+        .add_submessages(allocation_hook_msgs(
+            &config,
+            AllocationChange::Decrease,
+            &account,
+            allocation_id,
+            params.amount,
+        )?)
+        .add_attributes(vec![
+            attr("action", "execute_revoke"),
+            attr("account", account),
+            attr("allocation_id", allocation_id.to_string()),
+            attr("unvested_amount", unvested_amount),
+        ]))
+}
+
+/// Freeze or unfreeze withdrawals and receiver claims for an account. Unlock schedules are not affected.
+///
+/// * **account** address whose withdrawals are frozen or unfrozen.
+///
+/// * **frozen** whether the account should be frozen.
+fn execute_set_frozen(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+    frozen: bool,
+) -> StdResult<Response> {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can freeze accounts",
+        ));
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    load_allocations(deps.storage, &account)?;
+
+    if FROZEN_ACCOUNTS.has(deps.storage, &account) == frozen {
+        return Err(StdError::generic_err(format!(
+            "Account {account} is already {}",
+            if frozen { "frozen" } else { "unfrozen" }
+        )));
+    }
+
+    if frozen {
+        FROZEN_ACCOUNTS.save(deps.storage, &account, &true)?;
+    } else {
+        FROZEN_ACCOUNTS.remove(deps.storage, &account);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr(
+            "action",
+            if frozen {
+                "freeze_account"
+            } else {
+                "unfreeze_account"
+            },
+        ),
+        attr("account", account),
+    ]))
+}
+
+/// Suspend or resume withdrawals, receiver changes and allocation changes. Only the contract owner
+/// can execute this.
+///
+/// * **paused** whether the contract should be paused.
+fn execute_set_paused(deps: DepsMut, info: MessageInfo, paused: bool) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can pause the contract",
+        ));
+    }
+
+    if config.paused == paused {
+        return Err(StdError::generic_err(format!(
+            "The contract is already {}",
+            if paused { "paused" } else { "unpaused" }
+        )));
+    }
+
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+/// Increase an address' ASTRO allocation.
+///
+/// * **receiver** address that will have its allocation incrased.
+///
+/// * **allocation_id** ID of the receiver's allocation to increase.
+///
+/// * **amount** ASTRO amount to increase the allocation by.
+///
+/// * **deposit_amount** is amount of ASTRO to increase the allocation by using CW20 Receive.
+fn execute_increase_allocation(
+    deps: DepsMut,
+    env: Env,
     config: &Config,
     receiver: String,
+    allocation_id: u64,
     amount: Uint128,
     deposit_amount: Option<Uint128>,
 ) -> StdResult<Response> {
     let receiver = deps.api.addr_validate(&receiver)?;
 
-    match PARAMS.may_load(deps.storage, &receiver)? {
+    if REVOCATIONS.has(deps.storage, (&receiver, allocation_id)) {
+        return Err(StdError::generic_err(format!(
+            "Allocation {allocation_id} is revoked for {receiver}"
+        )));
+    }
+
+    match PARAMS.may_load(deps.storage, (&receiver, allocation_id))? {
         Some(mut params) => {
             let mut state = STATE.load(deps.storage)?;
 
@@ -462,22 +1714,43 @@ fn execute_increase_allocation(
                 )));
             }
 
+            let prev_locked = locked_voting_power(deps.storage, &receiver, None)?;
             params.amount = params.amount.checked_add(amount)?;
             state.unallocated_tokens = state.unallocated_tokens.checked_sub(amount)?;
             state.remaining_astro_tokens = state.remaining_astro_tokens.checked_add(amount)?;
 
-            PARAMS.save(deps.storage, &receiver, &params)?;
+            PARAMS.save(
+                deps.storage,
+                (&receiver, allocation_id),
+                &params,
+                env.block.height,
+            )?;
+            check_account_allocation_cap(deps.storage, config, &receiver)?;
+            sync_delegated_power(deps.storage, &receiver, prev_locked, env.block.height)?;
             STATE.save(deps.storage, &state)?;
+
+            Ok(Response::new()
+                .add_submessages(allocation_hook_msgs(
+                    config,
+                    AllocationChange::Increase,
+                    &receiver,
+                    allocation_id,
+                    params.amount,
+                )?)
+                .add_event(allocation_event(
+                    "increase_allocation",
+                    &receiver,
+                    allocation_id,
+                    &params,
+                ))
+                .add_attribute("action", "execute_increase_allocation")
+                .add_attribute("amount", amount)
+                .add_attribute("receiver", receiver)
+                .add_attribute("allocation_id", allocation_id.to_string())
+                .add_attributes(state_attributes(&state)))
         }
-        None => {
-            return Err(StdError::generic_err("Proposed receiver not set"));
-        }
+        None => Err(StdError::generic_err("Proposed receiver not set")),
     }
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_increase_allocation")
-        .add_attribute("amount", amount)
-        .add_attribute("receiver", receiver))
 }
 
 /// Transfer unallocated ASTRO tokens to a recipient.
@@ -487,13 +1760,14 @@ fn execute_increase_allocation(
 /// * **recipient** transfer recipient.
 fn execute_transfer_unallocated(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
     recipient: Option<String>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
-    if config.owner != info.sender {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
         return Err(StdError::generic_err(
             "Only contract owner can transfer unallocated ASTRO.",
         ));
@@ -512,38 +1786,51 @@ fn execute_transfer_unallocated(
     state.total_astro_deposited = state.total_astro_deposited.checked_sub(amount)?;
 
     let recipient = addr_opt_validate(deps.api, &recipient)?.unwrap_or_else(|| info.sender.clone());
-    let msg = WasmMsg::Execute {
-        contract_addr: config.astro_token.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: recipient.to_string(),
-            amount,
-        })?,
-        funds: vec![],
-    };
 
     STATE.save(deps.storage, &state)?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_transfer_unallocated")
         .add_attribute("amount", amount)
-        .add_message(msg))
+        .add_messages(astro_transfer_msgs(
+            deps, &env, &config, &recipient, amount,
+        )?))
 }
 
-/// Allows a newly proposed allocation receiver to claim the ownership of that allocation.
+/// Allows a newly proposed allocation receiver to claim the ownership of all allocations of the previous receiver.
 ///
-/// * **prev_receiver** this is the previous receiver for the allocation.
+/// * **prev_receiver** this is the previous receiver for the allocations.
 fn execute_claim_receiver(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     prev_receiver: String,
 ) -> StdResult<Response> {
     let prev_receiver_addr = deps.api.addr_validate(&prev_receiver)?;
-    let mut alloc_params = PARAMS.load(deps.storage, &prev_receiver_addr)?;
+    let allocations = load_allocations(deps.storage, &prev_receiver_addr)?;
+
+    if FROZEN_ACCOUNTS.has(deps.storage, &prev_receiver_addr) {
+        return Err(StdError::generic_err(format!(
+            "Account {prev_receiver_addr} is frozen"
+        )));
+    }
 
-    match alloc_params.proposed_receiver {
+    if let Some(expires_at) =
+        RECEIVER_PROPOSAL_EXPIRY.may_load(deps.storage, &prev_receiver_addr)?
+    {
+        if env.block.time.seconds() > expires_at {
+            return Err(StdError::generic_err("Proposed receiver expired"));
+        }
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut hook_msgs = vec![];
+    match allocations[0].1.proposed_receiver.clone() {
         Some(proposed_receiver) => {
             if proposed_receiver == info.sender {
-                if let Some(sender_params) = PARAMS.may_load(deps.storage, &info.sender)? {
+                let sender_allocations = may_load_allocations(deps.storage, &info.sender)?;
+                if !sender_allocations.is_empty() {
+                    let sender_params = aggregate_allocations(&sender_allocations).params;
                     return Err(StdError::generic_err(format!(
                         "The proposed receiver already has an ASTRO allocation of {} ASTRO, that ends at {}",
                         sender_params.amount,
@@ -551,17 +1838,82 @@ fn execute_claim_receiver(
                     )));
                 }
 
-                // Transfers allocation parameters
-                // 1. Save the allocation for the new receiver
-                alloc_params.proposed_receiver = None;
-                PARAMS.save(deps.storage, &info.sender, &alloc_params)?;
-                // 2. Remove the allocation info from the previous owner
-                PARAMS.remove(deps.storage, &prev_receiver_addr);
-                // Transfers Allocation Status
-                let status = STATUS.load(deps.storage, &prev_receiver_addr)?;
-
-                STATUS.save(deps.storage, &info.sender, &status)?;
-                STATUS.remove(deps.storage, &prev_receiver_addr)
+                let prev_receiver_locked =
+                    locked_voting_power(deps.storage, &prev_receiver_addr, None)?;
+                for (allocation_id, mut alloc_params, status) in allocations {
+                    // Transfers allocation parameters
+                    // 1. Save the allocation for the new receiver
+                    alloc_params.proposed_receiver = None;
+                    register_allocation_id(deps.storage, &info.sender, Some(allocation_id))?;
+                    PARAMS.save(
+                        deps.storage,
+                        (&info.sender, allocation_id),
+                        &alloc_params,
+                        env.block.height,
+                    )?;
+                    // 2. Remove the allocation info from the previous owner
+                    PARAMS.remove(
+                        deps.storage,
+                        (&prev_receiver_addr, allocation_id),
+                        env.block.height,
+                    )?;
+                    // Transfers Allocation Status
+                    STATUS.save(
+                        deps.storage,
+                        (&info.sender, allocation_id),
+                        &status,
+                        env.block.height,
+                    )?;
+                    STATUS.remove(
+                        deps.storage,
+                        (&prev_receiver_addr, allocation_id),
+                        env.block.height,
+                    )?;
+                    // Transfers the revocation record
+                    if let Some(revocation) =
+                        REVOCATIONS.may_load(deps.storage, (&prev_receiver_addr, allocation_id))?
+                    {
+                        REVOCATIONS.save(
+                            deps.storage,
+                            (&info.sender, allocation_id),
+                            &revocation,
+                        )?;
+                        REVOCATIONS.remove(deps.storage, (&prev_receiver_addr, allocation_id))
+                    }
+                    // Transfers the queued decrease
+                    if let Some(pending) = PENDING_DECREASES
+                        .may_load(deps.storage, (&prev_receiver_addr, allocation_id))?
+                    {
+                        PENDING_DECREASES.save(
+                            deps.storage,
+                            (&info.sender, allocation_id),
+                            &pending,
+                        )?;
+                        PENDING_DECREASES.remove(deps.storage, (&prev_receiver_addr, allocation_id))
+                    }
+
+                    hook_msgs.extend(allocation_hook_msgs(
+                        &config,
+                        AllocationChange::Transfer {
+                            prev_account: prev_receiver_addr.to_string(),
+                        },
+                        &info.sender,
+                        allocation_id,
+                        alloc_params.amount,
+                    )?);
+                }
+                sync_delegated_power(
+                    deps.storage,
+                    &prev_receiver_addr,
+                    prev_receiver_locked,
+                    env.block.height,
+                )?;
+                sync_delegated_power(
+                    deps.storage,
+                    &info.sender,
+                    Uint128::zero(),
+                    env.block.height,
+                )?;
             } else {
                 return Err(StdError::generic_err(format!(
                     "Proposed receiver mismatch, actual proposed receiver : {proposed_receiver}"
@@ -572,12 +1924,15 @@ fn execute_claim_receiver(
             return Err(StdError::generic_err("Proposed receiver not set"));
         }
     }
+    RECEIVER_PROPOSAL_EXPIRY.remove(deps.storage, &prev_receiver_addr);
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "ClaimReceiver"),
-        attr("prev_receiver", prev_receiver),
-        attr("receiver", info.sender),
-    ]))
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_attributes(vec![
+            attr("action", "ClaimReceiver"),
+            attr("prev_receiver", prev_receiver),
+            attr("receiver", info.sender),
+        ]))
 }
 
 /// Updates builder unlock contract parameters.
@@ -585,10 +1940,15 @@ fn update_config(
     deps: DepsMut,
     info: MessageInfo,
     new_max_allocations_amount: Uint128,
+    new_decrease_notice_period: Option<u64>,
+    new_max_allocation_per_account: Option<Uint128>,
+    new_max_total_accounts: Option<u64>,
+    new_voting_escrow: Option<String>,
+    new_allocation_hooks: Option<Vec<String>>,
 ) -> StdResult<Response> {
     let mut config = CONFIG.load(deps.storage)?;
 
-    if info.sender != config.owner {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
         return Err(StdError::generic_err(
             "Only the contract owner can change config",
         ));
@@ -603,52 +1963,440 @@ fn update_config(
         )));
     }
 
-    config.max_allocations_amount = new_max_allocations_amount;
-    CONFIG.save(deps.storage, &config)?;
+    config.max_allocations_amount = new_max_allocations_amount;
+    let mut response = Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("new_max_allocations_amount", new_max_allocations_amount);
+
+    if let Some(new_decrease_notice_period) = new_decrease_notice_period {
+        config.decrease_notice_period = new_decrease_notice_period;
+        response = response.add_attribute(
+            "new_decrease_notice_period",
+            new_decrease_notice_period.to_string(),
+        );
+    }
+
+    if let Some(new_max_allocation_per_account) = new_max_allocation_per_account {
+        config.max_allocation_per_account = Some(new_max_allocation_per_account);
+        response = response.add_attribute(
+            "new_max_allocation_per_account",
+            new_max_allocation_per_account,
+        );
+    }
+
+    if let Some(new_max_total_accounts) = new_max_total_accounts {
+        config.max_total_accounts = Some(new_max_total_accounts);
+        response =
+            response.add_attribute("new_max_total_accounts", new_max_total_accounts.to_string());
+    }
+
+    if let Some(new_voting_escrow) = new_voting_escrow {
+        config.voting_escrow = Some(deps.api.addr_validate(&new_voting_escrow)?);
+        response = response.add_attribute("new_voting_escrow", new_voting_escrow);
+    }
+
+    if let Some(new_allocation_hooks) = new_allocation_hooks {
+        config.allocation_hooks = new_allocation_hooks
+            .iter()
+            .map(|hook| deps.api.addr_validate(hook))
+            .collect::<StdResult<_>>()?;
+        response = response.add_attribute("new_allocation_hooks", new_allocation_hooks.join(","));
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+/// Updates builder unlock schedules for specified accounts.
+///
+/// * **allocation_id** ID of the allocations to update.
+fn update_unlock_schedules(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_unlock_schedules: Vec<(String, Schedule)>,
+    allocation_id: u64,
+) -> StdResult<Response> {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can change config",
+        ));
+    }
+
+    let mut events = vec![];
+    for (account, new_schedule) in new_unlock_schedules {
+        let (account_addr, mut params) =
+            load_schedule_update_params(deps.storage, deps.api, &account, allocation_id)?;
+
+        let mut status = STATUS.load(deps.storage, (&account_addr, allocation_id))?;
+
+        let unlocked_amount_checkpoint = compute_unlocked_amount(
+            env.block.time.seconds(),
+            params.amount,
+            &params.unlock_schedule,
+            status.unlocked_amount_checkpoint,
+        );
+
+        if unlocked_amount_checkpoint > status.unlocked_amount_checkpoint {
+            status.unlocked_amount_checkpoint = unlocked_amount_checkpoint;
+            STATUS.save(
+                deps.storage,
+                (&account_addr, allocation_id),
+                &status,
+                env.block.height,
+            )?;
+        }
+
+        params.update_schedule(new_schedule, &account)?;
+        PARAMS.save(
+            deps.storage,
+            (&account_addr, allocation_id),
+            &params,
+            env.block.height,
+        )?;
+
+        events.push(allocation_event(
+            "update_unlock_schedule",
+            &account_addr,
+            allocation_id,
+            &params,
+        ));
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "update_unlock_schedules"))
+}
+
+/// Sets the ASTRO staking contract and the staking yield recipient. Only the owner can execute this.
+///
+/// * **astro_staking** ASTRO staking contract. It can't be changed while ASTRO is staked.
+///
+/// * **yield_recipient** account that receives the staking yield.
+fn update_staking_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    astro_staking: Option<String>,
+    yield_recipient: Option<String>,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can change config",
+        ));
+    }
+
+    let mut response = Response::new().add_attribute("action", "update_staking_config");
+
+    if let Some(astro_staking) = astro_staking {
+        let astro_staking = deps.api.addr_validate(&astro_staking)?;
+
+        if let Some(current_staking) = &config.astro_staking {
+            let position = query_staked_position(&deps.querier, current_staking, &env)?;
+            if !position.shares.is_zero() {
+                return Err(StdError::generic_err(
+                    "Unstake all ASTRO before changing the staking contract",
+                ));
+            }
+        }
+
+        response = response.add_attribute("astro_staking", &astro_staking);
+        config.astro_staking = Some(astro_staking);
+    }
+
+    if let Some(yield_recipient) = yield_recipient {
+        let yield_recipient = deps.api.addr_validate(&yield_recipient)?;
+        response = response.add_attribute("yield_recipient", &yield_recipient);
+        config.yield_recipient = Some(yield_recipient);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+/// Stakes locked ASTRO in the staking contract. Only the owner can execute this.
+///
+/// * **amount** amount of ASTRO to stake.
+fn execute_stake_astro(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can stake ASTRO",
+        ));
+    }
+
+    let astro_staking = config
+        .astro_staking
+        .ok_or_else(|| StdError::generic_err("ASTRO staking contract is not set"))?;
+
+    let astro_balance = query_astro_balance(&deps.querier, &config.astro_token, &env)?;
+    if amount.is_zero() || amount > astro_balance {
+        return Err(StdError::generic_err(format!(
+            "Amount to stake must be between 1 and {astro_balance} ASTRO"
+        )));
+    }
+
+    let staked_astro = STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default();
+    STAKED_ASTRO.save(deps.storage, &(staked_astro + amount))?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: astro_staking.to_string(),
+                amount,
+                msg: to_binary(&StakingHookMsg::Enter {})?,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![attr("action", "stake_astro"), attr("amount", amount)]))
+}
+
+/// Unstakes ASTRO from the staking contract. Only the owner can execute this.
+///
+/// * **amount** amount of staked ASTRO to unstake. All staked ASTRO is unstaked by default.
+fn execute_unstake_astro(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err(
+            "Only the contract owner can unstake ASTRO",
+        ));
+    }
+
+    let astro_staking = config
+        .astro_staking
+        .ok_or_else(|| StdError::generic_err("ASTRO staking contract is not set"))?;
+
+    let staked_astro = STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default();
+    let amount = amount.unwrap_or(staked_astro);
+    if amount.is_zero() || amount > staked_astro {
+        return Err(StdError::generic_err(format!(
+            "Amount to unstake must be between 1 and {staked_astro} ASTRO"
+        )));
+    }
+
+    let position = query_staked_position(&deps.querier, &astro_staking, &env)?;
+    let shares = position.shares_for(amount);
+
+    STAKED_ASTRO.save(deps.storage, &(staked_astro - amount))?;
+
+    Ok(Response::new()
+        .add_message(position.unstake_msg(&astro_staking, shares)?)
+        .add_attributes(vec![
+            attr("action", "unstake_astro"),
+            attr("amount", amount),
+            attr("xastro_amount", shares),
+        ]))
+}
+
+/// Sends xASTRO worth the staking yield accrued on staked ASTRO to the yield recipient.
+fn execute_claim_staking_yield(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let astro_staking = config
+        .astro_staking
+        .ok_or_else(|| StdError::generic_err("ASTRO staking contract is not set"))?;
+    let yield_recipient = config
+        .yield_recipient
+        .ok_or_else(|| StdError::generic_err("Staking yield recipient is not set"))?;
+
+    let position = query_staked_position(&deps.querier, &astro_staking, &env)?;
+    let pending_yield = position
+        .astro_value()
+        .saturating_sub(STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default());
+    // Round down so that the xASTRO left is still worth the staked ASTRO
+    let yield_shares = pending_yield.multiply_ratio(position.total_shares, position.total_deposit);
+
+    if yield_shares.is_zero() {
+        return Err(StdError::generic_err("No staking yield to claim"));
+    }
 
     Ok(Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("new_max_allocations_amount", new_max_allocations_amount))
+        .add_message(WasmMsg::Execute {
+            contract_addr: position.xastro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: yield_recipient.to_string(),
+                amount: yield_shares,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "claim_staking_yield"),
+            attr("recipient", yield_recipient),
+            attr("astro_amount", pending_yield),
+            attr("xastro_amount", yield_shares),
+        ]))
 }
 
-/// Updates builder unlock schedules for specified accounts.
-fn update_unlock_schedules(
+/// Updates contract ownership with a cw-ownable [`Action`].
+///
+/// * **action** ownership change to apply.
+fn update_ownership(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    new_unlock_schedules: Vec<(String, Schedule)>,
+    action: Action,
 ) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-
-    if info.sender != config.owner {
+    if action == Action::RenounceOwnership {
         return Err(StdError::generic_err(
-            "Only the contract owner can change config",
+            "Contract ownership can't be renounced",
         ));
     }
 
-    for (account, new_schedule) in new_unlock_schedules {
-        let account_addr = deps.api.addr_validate(&account)?;
-        let mut params = PARAMS.load(deps.storage, &account_addr)?;
+    let ownership =
+        cw_ownable::update_ownership(deps, &env.block, &info.sender, action).map_err(|err| {
+            match err {
+                OwnershipError::Std(err) => err,
+                OwnershipError::NotOwner => StdError::generic_err("Unauthorized"),
+                err => StdError::generic_err(err.to_string()),
+            }
+        })?;
 
-        let mut status = STATUS.load(deps.storage, &account_addr)?;
+    Ok(Response::new()
+        .add_attribute("action", "update_ownership")
+        .add_attributes(ownership.into_attributes()))
+}
 
-        let unlocked_amount_checkpoint = compute_unlocked_amount(
-            env.block.time.seconds(),
-            params.amount,
-            &params.unlock_schedule,
-            status.unlocked_amount_checkpoint,
-        );
+/// Removes the pending ownership transfer. cw-ownable has no action for this, so the transfer is
+/// replaced with an already expired transfer to the current owner which can't be accepted.
+fn drop_ownership_proposal(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    if !cw_ownable::is_owner(deps.storage, &info.sender)? {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
 
-        if unlocked_amount_checkpoint > status.unlocked_amount_checkpoint {
-            status.unlocked_amount_checkpoint = unlocked_amount_checkpoint;
-            STATUS.save(deps.storage, &account_addr, &status)?;
+    let ownership = cw_ownable::update_ownership(
+        deps,
+        &env.block,
+        &info.sender,
+        Action::TransferOwnership {
+            new_owner: info.sender.to_string(),
+            expiry: Some(Expiration::AtTime(env.block.time)),
+        },
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "drop_ownership_proposal")
+        .add_attributes(ownership.into_attributes()))
+}
+
+/// Dry-run unlock schedule updates and return the error every account update would fail with.
+/// Updates of the same account are validated against the schedule set by the previous update,
+/// as it happens in [`update_unlock_schedules`].
+///
+/// * **new_unlock_schedules** new unlock schedules of accounts.
+///
+/// * **allocation_id** ID of the allocations to update.
+fn query_validate_schedule_updates(
+    deps: Deps,
+    new_unlock_schedules: Vec<(String, Schedule)>,
+    allocation_id: u64,
+) -> StdResult<Vec<ScheduleUpdateValidation>> {
+    let mut updated: Vec<(Addr, AllocationParams)> = vec![];
+
+    Ok(new_unlock_schedules
+        .into_iter()
+        .map(|(account, new_schedule)| {
+            let result =
+                load_schedule_update_params(deps.storage, deps.api, &account, allocation_id)
+                    .and_then(|(account_addr, params)| {
+                        let position = updated.iter().position(|(addr, _)| addr == &account_addr);
+                        let mut params = position
+                            .map(|position| updated[position].1.clone())
+                            .unwrap_or(params);
+
+                        params.update_schedule(new_schedule, &account)?;
+
+                        match position {
+                            Some(position) => updated[position].1 = params,
+                            None => updated.push((account_addr, params)),
+                        }
+                        Ok(())
+                    });
+
+            ScheduleUpdateValidation {
+                account,
+                error: result.err().map(|err| err.to_string()),
+            }
+        })
+        .collect())
+}
+
+/// Return the amount of staked ASTRO and the staking yield accrued on it.
+fn query_staking_state(deps: Deps, env: Env) -> StdResult<StakingStateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let staked_astro = STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default();
+
+    match config.astro_staking {
+        Some(astro_staking) => {
+            let position = query_staked_position(&deps.querier, &astro_staking, &env)?;
+            let astro_value = position.astro_value();
+
+            Ok(StakingStateResponse {
+                staked_astro,
+                xastro_amount: position.shares,
+                astro_value,
+                pending_yield: astro_value.saturating_sub(staked_astro),
+            })
         }
+        None => Ok(StakingStateResponse {
+            staked_astro,
+            xastro_amount: Uint128::zero(),
+            astro_value: Uint128::zero(),
+            pending_yield: Uint128::zero(),
+        }),
+    }
+}
 
-        params.update_schedule(new_schedule, &account)?;
-        PARAMS.save(deps.storage, &account_addr, &params)?;
+/// Compare the ASTRO held by the contract (including the staked principal) against
+/// `total_astro_deposited - total_withdrawn` and `remaining_astro_tokens + unallocated_tokens`.
+fn query_solvency(deps: Deps, env: Env) -> StdResult<SolvencyResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    let mut astro_balance = query_astro_balance(&deps.querier, &config.astro_token, &env)?;
+    if let Some(astro_staking) = &config.astro_staking {
+        let staked_astro = STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default();
+        let position = query_staked_position(&deps.querier, astro_staking, &env)?;
+        // Staking yield belongs to the yield recipient and is not counted
+        astro_balance += position.astro_value().min(staked_astro);
     }
 
-    Ok(Response::new().add_attribute("action", "update_unlock_schedules"))
+    let total_withdrawn = STATUS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, status)| status.astro_withdrawn))
+        .sum::<StdResult<Uint128>>()?;
+
+    let expected_balance = state.total_astro_deposited.saturating_sub(total_withdrawn);
+    let owed_balance = state.remaining_astro_tokens + state.unallocated_tokens;
+
+    Ok(SolvencyResponse {
+        astro_balance,
+        total_withdrawn,
+        expected_balance,
+        owed_balance,
+        surplus: astro_balance.saturating_sub(expected_balance),
+        deficit: expected_balance.saturating_sub(astro_balance),
+        accounting_drift: expected_balance
+            .saturating_sub(owed_balance)
+            .max(owed_balance.saturating_sub(expected_balance)),
+    })
 }
 
 /// Return the global distribution state.
@@ -661,46 +2409,211 @@ pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
     })
 }
 
-/// Return information about a specific allocation.
+/// Return aggregated information about all allocations of a specific account.
+/// The unlock schedule and the proposed receiver are taken from the first allocation of the account.
 ///
 /// * **account** account whose allocation we query.
 fn query_allocation(deps: Deps, account: String) -> StdResult<AllocationResponse> {
     let account_checked = deps.api.addr_validate(&account)?;
 
-    Ok(AllocationResponse {
-        params: PARAMS
-            .may_load(deps.storage, &account_checked)?
-            .unwrap_or_default(),
-        status: STATUS
-            .may_load(deps.storage, &account_checked)?
-            .unwrap_or_default(),
-    })
+    Ok(aggregate_allocations(&may_load_allocations(
+        deps.storage,
+        &account_checked,
+    )?))
+}
+
+/// Return an account's aggregated allocation as it was at a specific block height.
+///
+/// * **account** account whose allocation we query.
+///
+/// * **height** block height at which we query the allocation.
+fn query_allocation_at_height(
+    deps: Deps,
+    account: String,
+    height: u64,
+) -> StdResult<AllocationResponse> {
+    let account_checked = deps.api.addr_validate(&account)?;
+
+    Ok(aggregate_allocations(&may_load_allocations_at_height(
+        deps.storage,
+        &account_checked,
+        height,
+    )?))
+}
+
+/// Return the pending receiver change proposal of an account. Expired proposals are not returned.
+///
+/// * **account** account whose receiver change proposal we query.
+fn query_receiver_proposal(
+    deps: Deps,
+    env: Env,
+    account: String,
+) -> StdResult<Option<ReceiverProposalResponse>> {
+    let account = deps.api.addr_validate(&account)?;
+    let expires_at = RECEIVER_PROPOSAL_EXPIRY.may_load(deps.storage, &account)?;
+
+    if matches!(expires_at, Some(expires_at) if env.block.time.seconds() > expires_at) {
+        return Ok(None);
+    }
+
+    Ok(may_load_allocations(deps.storage, &account)?
+        .into_iter()
+        .next()
+        .and_then(|(_, params, _)| params.proposed_receiver)
+        .map(|proposed_receiver| ReceiverProposalResponse {
+            proposed_receiver,
+            expires_at,
+        }))
+}
+
+/// Return the voting power of an account's locked allocations along with the voting power delegated to it.
+/// The voting power of an allocation is the amount of ASTRO that is not withdrawn yet.
+///
+/// * **account** account whose voting power we query.
+///
+/// * **height** block height at which we query the voting power (the current state by default).
+fn query_voting_power(deps: Deps, account: String, height: Option<u64>) -> StdResult<Uint128> {
+    let account = deps.api.addr_validate(&account)?;
+
+    let delegate = match height {
+        Some(height) => VOTING_DELEGATES.may_load_at_height(deps.storage, &account, height)?,
+        None => VOTING_DELEGATES.may_load(deps.storage, &account)?,
+    };
+    let voting_power = if delegate.is_some() {
+        Uint128::zero()
+    } else {
+        locked_voting_power(deps.storage, &account, height)?
+    };
+
+    let delegated_power = match height {
+        Some(height) => DELEGATED_POWER.may_load_at_height(deps.storage, &account, height)?,
+        None => DELEGATED_POWER.may_load(deps.storage, &account)?,
+    };
+
+    Ok(voting_power.checked_add(delegated_power.unwrap_or_default())?)
+}
+
+/// Return information about every allocation of a specific account.
+///
+/// * **account** account whose allocations we query.
+fn query_account_allocations(
+    deps: Deps,
+    account: String,
+) -> StdResult<Vec<(u64, AllocationResponse)>> {
+    let account_checked = deps.api.addr_validate(&account)?;
+
+    Ok(may_load_allocations(deps.storage, &account_checked)?
+        .into_iter()
+        .map(|(allocation_id, params, status)| {
+            (allocation_id, AllocationResponse { params, status })
+        })
+        .collect())
 }
 
-/// Return information about a specific allocation.
+/// Return aggregated information about allocations of multiple accounts.
 ///
 /// * **start_after** account from which to start querying.
 ///
 /// * **limit** max amount of entries to return.
+///
+/// * **filter** return only the accounts whose allocations match the filter.
+///
+/// * **order_by** sorting order of the returned allocations. Sorting by the remaining amount
+/// iterates over all accounts.
 fn query_allocations(
     deps: Deps,
+    env: Env,
     start_after: Option<String>,
     limit: Option<u32>,
+    filter: Option<AllocationsFilter>,
+    order_by: AllocationsOrder,
 ) -> StdResult<Vec<(Addr, AllocationParams)>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let default_start;
+    let start_after = addr_opt_validate(deps.api, &start_after)?;
 
-    let start = if let Some(start_after) = start_after {
-        default_start = deps.api.addr_validate(&start_after)?;
-        Some(Bound::exclusive(&default_start))
-    } else {
-        None
+    let start = match (&start_after, &order_by) {
+        (Some(start_after), AllocationsOrder::Account) => {
+            Some(Bound::exclusive((start_after, u64::MAX)))
+        }
+        _ => None,
     };
 
-    PARAMS
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
-        .collect()
+    let mut result: Vec<(Addr, AllocationParams, Uint128)> = vec![];
+    let mut last_account: Option<Addr> = None;
+    for key in PARAMS.keys(deps.storage, start, None, Order::Ascending) {
+        let (account, _) = key?;
+        if last_account.as_ref() == Some(&account) {
+            continue;
+        }
+        last_account = Some(account.clone());
+
+        if order_by == AllocationsOrder::Account && result.len() == limit {
+            break;
+        }
+
+        let allocations = may_load_allocations(deps.storage, &account)?;
+        if let Some(filter) = &filter {
+            if !allocations_match_filter(deps, &env, &account, &allocations, filter) {
+                continue;
+            }
+        }
+
+        let aggregated = aggregate_allocations(&allocations);
+        let remaining_amount = aggregated
+            .params
+            .amount
+            .saturating_sub(aggregated.status.astro_withdrawn);
+        result.push((account, aggregated.params, remaining_amount));
+    }
+
+    if order_by == AllocationsOrder::RemainingAmount {
+        result.sort_by(|(a_account, _, a_remaining), (b_account, _, b_remaining)| {
+            b_remaining
+                .cmp(a_remaining)
+                .then_with(|| a_account.cmp(b_account))
+        });
+
+        if let Some(start_after) = start_after {
+            let position = result
+                .iter()
+                .position(|(account, _, _)| account == &start_after)
+                .map_or(0, |position| position + 1);
+            result.drain(..position);
+        }
+        result.truncate(limit);
+    }
+
+    Ok(result
+        .into_iter()
+        .map(|(account, params, _)| (account, params))
+        .collect())
+}
+
+/// Returns whether the allocations of an account match the filter of the Allocations query.
+fn allocations_match_filter(
+    deps: Deps,
+    env: &Env,
+    account: &Addr,
+    allocations: &[(u64, AllocationParams, AllocationStatus)],
+    filter: &AllocationsFilter,
+) -> bool {
+    match filter {
+        AllocationsFilter::Active | AllocationsFilter::FullyUnlocked => {
+            let fully_unlocked = allocations.iter().all(|(_, params, status)| {
+                compute_unlocked_amount(
+                    env.block.time.seconds(),
+                    params.amount,
+                    &params.unlock_schedule,
+                    status.unlocked_amount_checkpoint,
+                ) >= params.amount
+            });
+
+            fully_unlocked == (filter == &AllocationsFilter::FullyUnlocked)
+        }
+        AllocationsFilter::Revoked => allocations
+            .iter()
+            .any(|(allocation_id, _, _)| REVOCATIONS.has(deps.storage, (account, *allocation_id))),
+    }
 }
 
 /// Return the total amount of unlocked tokens for a specific account.
@@ -709,18 +2622,20 @@ fn query_allocations(
 fn query_tokens_unlocked(deps: Deps, env: Env, account: String) -> StdResult<Uint128> {
     let account_checked = deps.api.addr_validate(&account)?;
 
-    let params = PARAMS.load(deps.storage, &account_checked)?;
-    let status = STATUS.load(deps.storage, &account_checked)?;
-
-    Ok(compute_unlocked_amount(
-        env.block.time.seconds(),
-        params.amount,
-        &params.unlock_schedule,
-        status.unlocked_amount_checkpoint,
-    ))
+    Ok(load_allocations(deps.storage, &account_checked)?
+        .iter()
+        .map(|(_, params, status)| {
+            compute_unlocked_amount(
+                env.block.time.seconds(),
+                params.amount,
+                &params.unlock_schedule,
+                status.unlocked_amount_checkpoint,
+            )
+        })
+        .sum())
 }
 
-/// Simulate a token withdrawal.
+/// Simulate a token withdrawal from all allocations of an account.
 ///
 /// * **account** account for which we simulate a withdrawal.
 ///
@@ -732,38 +2647,72 @@ fn query_simulate_withdraw(
     timestamp: Option<u64>,
 ) -> StdResult<SimulateWithdrawResponse> {
     let account_checked = deps.api.addr_validate(&account)?;
+    let timestamp = timestamp.unwrap_or_else(|| env.block.time.seconds());
 
-    let params = PARAMS.load(deps.storage, &account_checked)?;
-    let status = STATUS.load(deps.storage, &account_checked)?;
+    Ok(SimulateWithdrawResponse {
+        astro_to_withdraw: load_allocations(deps.storage, &account_checked)?
+            .iter()
+            .map(|(_, params, status)| {
+                compute_withdraw_amount(timestamp, params, status).astro_to_withdraw
+            })
+            .sum(),
+    })
+}
 
-    Ok(compute_withdraw_amount(
-        timestamp.unwrap_or_else(|| env.block.time.seconds()),
-        &params,
-        &status,
-    ))
+/// Return the amount of unlocked and still locked ASTRO computed from all unlock schedules.
+///
+/// * **timestamp** timestamp at which the unlocked amount is computed.
+fn query_total_unlocked_at(deps: Deps, timestamp: u64) -> StdResult<TotalUnlockedResponse> {
+    let mut total_amount = Uint128::zero();
+    let mut unlocked_amount = Uint128::zero();
+
+    for item in PARAMS.range(deps.storage, None, None, Order::Ascending) {
+        let ((account, allocation_id), params) = item?;
+        let status = STATUS.load(deps.storage, (&account, allocation_id))?;
+
+        total_amount = total_amount.checked_add(params.amount)?;
+        unlocked_amount = unlocked_amount.checked_add(compute_unlocked_amount(
+            timestamp,
+            params.amount,
+            &params.unlock_schedule,
+            status.unlocked_amount_checkpoint,
+        ))?;
+    }
+
+    Ok(TotalUnlockedResponse {
+        unlocked_amount,
+        locked_amount: total_amount.checked_sub(unlocked_amount)?,
+    })
+}
+
+/// Checks that a batch query doesn't exceed [`MAX_LIMIT`] accounts.
+fn check_batch_size(accounts: &[String]) -> StdResult<()> {
+    if accounts.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "The number of accounts in a batch query cannot exceed {MAX_LIMIT}"
+        )));
+    }
+
+    Ok(())
 }
 
 /// Manages contract migration
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    let contract_version = get_contract_version(deps.storage)?;
-
-    match contract_version.contract.as_ref() {
-        "builder-unlock" => match contract_version.version.as_ref() {
-            "1.2.0" => {}
-            "1.2.2" => {}
-            _ => return Err(StdError::generic_err("Contract can't be migrated!")),
-        },
-        _ => return Err(StdError::generic_err("Contract can't be migrated!")),
-    };
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version =
+        load_migratable_version(deps.storage, CONTRACT_NAME, &["1.2.0", "1.2.2", "1.2.3"])?
+            .ok_or_else(|| StdError::generic_err("Contract can't be migrated!"))?;
+
+    // Allocations are migrated in batches. The ones left are migrated with ExecuteMsg::MigrateAllocations
+    let finished = migrate_allocations_to_v140(deps.storage, &env, MAX_LIMIT)?;
+    migrate_config_to_v140(deps.branch(), &env)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::new()
-        .add_attribute("previous_contract_name", &contract_version.contract)
-        .add_attribute("previous_contract_version", &contract_version.version)
-        .add_attribute("new_contract_name", CONTRACT_NAME)
-        .add_attribute("new_contract_version", CONTRACT_VERSION))
+    Ok(
+        migration_response(&contract_version, CONTRACT_NAME, CONTRACT_VERSION)
+            .add_attribute("allocations_migrated", finished.to_string()),
+    )
 }
 
 //----------------------------------------------------------------------------------------
@@ -771,10 +2720,451 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response
 //----------------------------------------------------------------------------------------
 
 mod helpers {
-    use cosmwasm_std::Uint128;
+    use cosmwasm_std::{
+        attr, to_binary, Addr, Api, Attribute, DepsMut, Env, Event, Order, QuerierWrapper,
+        StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+    };
+    use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+    use astroport_governance::builder_unlock::msg::{AllocationResponse, SimulateWithdrawResponse};
+    use astroport_governance::builder_unlock::{
+        AllocationChange, AllocationHookExecuteMsg, AllocationHookMsg, AllocationParams,
+        AllocationStatus, Config, Schedule, State, ALLOCATION_HOOK_GAS_LIMIT,
+    };
+
+    use super::ALLOCATION_HOOK_REPLY_ID;
+
+    use crate::astroport::staking::{
+        ConfigResponse as StakingConfigResponse, Cw20HookMsg as StakingHookMsg,
+        QueryMsg as StakingQueryMsg,
+    };
+    use crate::state::{
+        ALLOCATION_COUNTS, DELEGATED_POWER, PARAMS, RECEIVER_PROPOSAL_EXPIRY, REVOCATIONS,
+        SPLIT_PROPOSALS, STAKED_ASTRO, STATUS, VOTING_DELEGATES,
+    };
+
+    /// Returns all allocations of an account ordered by allocation ID
+    pub fn may_load_allocations(
+        storage: &dyn Storage,
+        account: &Addr,
+    ) -> StdResult<Vec<(u64, AllocationParams, AllocationStatus)>> {
+        PARAMS
+            .prefix(account)
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (allocation_id, params) = item?;
+                let status = STATUS.load(storage, (account, allocation_id))?;
+                Ok((allocation_id, params, status))
+            })
+            .collect()
+    }
+
+    /// Returns all allocations of an account or an error if the account doesn't have any allocations
+    pub fn load_allocations(
+        storage: &dyn Storage,
+        account: &Addr,
+    ) -> StdResult<Vec<(u64, AllocationParams, AllocationStatus)>> {
+        let allocations = may_load_allocations(storage, account)?;
+        if allocations.is_empty() {
+            return Err(StdError::not_found(
+                "astroport_governance::builder_unlock::AllocationParams",
+            ));
+        }
+
+        Ok(allocations)
+    }
+
+    /// Returns all allocations of an account as they were at a specific block height
+    pub fn may_load_allocations_at_height(
+        storage: &dyn Storage,
+        account: &Addr,
+        height: u64,
+    ) -> StdResult<Vec<(u64, AllocationParams, AllocationStatus)>> {
+        let allocations_count = ALLOCATION_COUNTS
+            .may_load(storage, account)?
+            .unwrap_or_default();
+
+        let mut allocations = vec![];
+        for allocation_id in 0..allocations_count {
+            if let Some(params) =
+                PARAMS.may_load_at_height(storage, (account, allocation_id), height)?
+            {
+                let status = STATUS
+                    .may_load_at_height(storage, (account, allocation_id), height)?
+                    .unwrap_or_default();
+                allocations.push((allocation_id, params, status));
+            }
+        }
+
+        Ok(allocations)
+    }
+
+    /// Registers an allocation ID of an account. A new ID is assigned if **allocation_id** is not specified.
+    pub fn register_allocation_id(
+        storage: &mut dyn Storage,
+        account: &Addr,
+        allocation_id: Option<u64>,
+    ) -> StdResult<u64> {
+        let allocations_count = ALLOCATION_COUNTS
+            .may_load(storage, account)?
+            .unwrap_or_default();
+        let allocation_id = allocation_id.unwrap_or(allocations_count);
+
+        ALLOCATION_COUNTS.save(storage, account, &allocations_count.max(allocation_id + 1))?;
+
+        Ok(allocation_id)
+    }
+
+    /// Checks that the total amount of all allocations of an account doesn't exceed the per-account cap
+    pub fn check_account_allocation_cap(
+        storage: &dyn Storage,
+        config: &Config,
+        account: &Addr,
+    ) -> StdResult<()> {
+        if let Some(max_allocation_per_account) = config.max_allocation_per_account {
+            let total_amount: Uint128 = may_load_allocations(storage, account)?
+                .iter()
+                .map(|(_, params, _)| params.amount)
+                .sum();
+
+            if total_amount > max_allocation_per_account {
+                return Err(StdError::generic_err(format!(
+                    "Allocations of {account} can't exceed {max_allocation_per_account} ASTRO"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount of ASTRO which is not withdrawn yet from all allocations of an account
+    /// at a specific block height (the current state by default)
+    pub fn locked_voting_power(
+        storage: &dyn Storage,
+        account: &Addr,
+        height: Option<u64>,
+    ) -> StdResult<Uint128> {
+        match height {
+            Some(height) => may_load_allocations_at_height(storage, account, height)?,
+            None => may_load_allocations(storage, account)?,
+        }
+        .into_iter()
+        .try_fold(
+            Uint128::zero(),
+            |acc, (_, params, status)| -> StdResult<_> {
+                Ok(acc.checked_add(params.amount.saturating_sub(status.astro_withdrawn))?)
+            },
+        )
+    }
+
+    /// Applies the change of an account's locked voting power to the voting power delegated to its
+    /// delegate. Must be called after every change of the account's allocations.
+    ///
+    /// * **prev_locked** locked voting power of the account before the change.
+    pub fn sync_delegated_power(
+        storage: &mut dyn Storage,
+        account: &Addr,
+        prev_locked: Uint128,
+        height: u64,
+    ) -> StdResult<()> {
+        if let Some(delegate) = VOTING_DELEGATES.may_load(storage, account)? {
+            let locked = locked_voting_power(storage, account, None)?;
+            if locked != prev_locked {
+                let delegated = DELEGATED_POWER
+                    .may_load(storage, &delegate)?
+                    .unwrap_or_default()
+                    .checked_add(locked)?
+                    .checked_sub(prev_locked)?;
+                DELEGATED_POWER.save(storage, &delegate, &delegated, height)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The xASTRO position of the contract in the ASTRO staking contract
+    pub struct StakedPosition {
+        /// xASTRO token address
+        pub xastro_token: Addr,
+        /// Amount of xASTRO held by the contract
+        pub shares: Uint128,
+        /// Total supply of xASTRO
+        pub total_shares: Uint128,
+        /// Total amount of ASTRO in the staking contract
+        pub total_deposit: Uint128,
+    }
+
+    impl StakedPosition {
+        /// Returns the amount of ASTRO the held xASTRO is worth
+        pub fn astro_value(&self) -> Uint128 {
+            if self.total_shares.is_zero() {
+                return Uint128::zero();
+            }
+            self.shares
+                .multiply_ratio(self.total_deposit, self.total_shares)
+        }
 
-    use astroport_governance::builder_unlock::msg::SimulateWithdrawResponse;
-    use astroport_governance::builder_unlock::{AllocationParams, AllocationStatus, Schedule};
+        /// Returns the amount of xASTRO to unstake to receive at least **amount** ASTRO.
+        /// The result is capped by the held xASTRO.
+        pub fn shares_for(&self, amount: Uint128) -> Uint128 {
+            if self.total_deposit.is_zero() {
+                return Uint128::zero();
+            }
+            let mut shares = amount.multiply_ratio(self.total_shares, self.total_deposit);
+            if shares.multiply_ratio(self.total_deposit, self.total_shares) < amount {
+                shares += Uint128::one();
+            }
+            shares.min(self.shares)
+        }
+
+        /// Returns the message unstaking **shares** xASTRO. Unstaked ASTRO is sent to the contract.
+        pub fn unstake_msg(&self, astro_staking: &Addr, shares: Uint128) -> StdResult<WasmMsg> {
+            Ok(WasmMsg::Execute {
+                contract_addr: self.xastro_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: astro_staking.to_string(),
+                    amount: shares,
+                    msg: to_binary(&StakingHookMsg::Leave {})?,
+                })?,
+                funds: vec![],
+            })
+        }
+    }
+
+    /// Returns the xASTRO position of the contract in the ASTRO staking contract
+    pub fn query_staked_position(
+        querier: &QuerierWrapper,
+        astro_staking: &Addr,
+        env: &Env,
+    ) -> StdResult<StakedPosition> {
+        let staking_config: StakingConfigResponse =
+            querier.query_wasm_smart(astro_staking, &StakingQueryMsg::Config {})?;
+        let shares: BalanceResponse = querier.query_wasm_smart(
+            &staking_config.share_token_addr,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+
+        Ok(StakedPosition {
+            xastro_token: staking_config.share_token_addr,
+            shares: shares.balance,
+            total_shares: querier
+                .query_wasm_smart(astro_staking, &StakingQueryMsg::TotalShares {})?,
+            total_deposit: querier
+                .query_wasm_smart(astro_staking, &StakingQueryMsg::TotalDeposit {})?,
+        })
+    }
+
+    /// Returns the ASTRO balance of the contract
+    pub fn query_astro_balance(
+        querier: &QuerierWrapper,
+        astro_token: &Addr,
+        env: &Env,
+    ) -> StdResult<Uint128> {
+        let balance: BalanceResponse = querier.query_wasm_smart(
+            astro_token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        Ok(balance.balance)
+    }
+
+    /// Returns the messages unstaking the ASTRO the contract lacks to cover **amount** along with
+    /// the amount of xASTRO being unstaked.
+    pub fn unstake_shortfall_msgs(
+        deps: DepsMut,
+        env: &Env,
+        config: &Config,
+        amount: Uint128,
+    ) -> StdResult<(Vec<WasmMsg>, Uint128)> {
+        if let Some(astro_staking) = &config.astro_staking {
+            let astro_balance = query_astro_balance(&deps.querier, &config.astro_token, env)?;
+
+            if astro_balance < amount {
+                let to_unstake = amount - astro_balance;
+                let position = query_staked_position(&deps.querier, astro_staking, env)?;
+                let shares = position.shares_for(to_unstake);
+
+                if !shares.is_zero() {
+                    let staked_astro = STAKED_ASTRO.may_load(deps.storage)?.unwrap_or_default();
+                    STAKED_ASTRO.save(deps.storage, &staked_astro.saturating_sub(to_unstake))?;
+
+                    return Ok((vec![position.unstake_msg(astro_staking, shares)?], shares));
+                }
+            }
+        }
+
+        Ok((vec![], Uint128::zero()))
+    }
+
+    /// Returns the messages transferring **amount** ASTRO to **recipient**. If the contract doesn't hold
+    /// enough ASTRO, the rest is unstaked from the staking contract first.
+    pub fn astro_transfer_msgs(
+        deps: DepsMut,
+        env: &Env,
+        config: &Config,
+        recipient: &Addr,
+        amount: Uint128,
+    ) -> StdResult<Vec<WasmMsg>> {
+        let (mut msgs, _) = unstake_shortfall_msgs(deps, env, config, amount)?;
+
+        msgs.push(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        });
+
+        Ok(msgs)
+    }
+
+    /// Checks that the unlock schedule of an allocation can be updated and returns the
+    /// current allocation parameters
+    pub fn load_schedule_update_params(
+        storage: &dyn Storage,
+        api: &dyn Api,
+        account: &str,
+        allocation_id: u64,
+    ) -> StdResult<(Addr, AllocationParams)> {
+        let account_addr = api.addr_validate(account)?;
+        if REVOCATIONS.has(storage, (&account_addr, allocation_id)) {
+            return Err(StdError::generic_err(format!(
+                "Allocation {allocation_id} is revoked for {account}"
+            )));
+        }
+        let params = PARAMS.load(storage, (&account_addr, allocation_id))?;
+
+        Ok((account_addr, params))
+    }
+
+    /// Drops the receiver change proposal of an account if it has expired
+    pub fn drop_expired_receiver_proposal(
+        storage: &mut dyn Storage,
+        env: &Env,
+        account: &Addr,
+    ) -> StdResult<()> {
+        if let Some(expires_at) = RECEIVER_PROPOSAL_EXPIRY.may_load(storage, account)? {
+            if env.block.time.seconds() > expires_at {
+                for (allocation_id, mut params, _) in may_load_allocations(storage, account)? {
+                    params.proposed_receiver = None;
+                    PARAMS.save(storage, (account, allocation_id), &params, env.block.height)?;
+                }
+                RECEIVER_PROPOSAL_EXPIRY.remove(storage, account);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds an event describing the current parameters of an allocation so that indexers
+    /// can follow allocation changes without querying the contract state
+    pub fn allocation_event(
+        ty: &str,
+        account: &Addr,
+        allocation_id: u64,
+        params: &AllocationParams,
+    ) -> Event {
+        Event::new(ty).add_attributes(vec![
+            attr("account", account),
+            attr("allocation_id", allocation_id.to_string()),
+            attr("amount", params.amount),
+            attr("start_time", params.unlock_schedule.start_time.to_string()),
+            attr("cliff", params.unlock_schedule.cliff.to_string()),
+            attr("duration", params.unlock_schedule.duration.to_string()),
+        ])
+    }
+
+    /// Builds messages notifying all allocation hook contracts about an allocation change.
+    /// Every notification is limited to [`ALLOCATION_HOOK_GAS_LIMIT`] gas and a failing or
+    /// out of gas hook can't block the allocation change, its error is handled in `reply`.
+    ///
+    /// * **amount** allocation amount after the change.
+    pub fn allocation_hook_msgs(
+        config: &Config,
+        change: AllocationChange,
+        account: &Addr,
+        allocation_id: u64,
+        amount: Uint128,
+    ) -> StdResult<Vec<SubMsg>> {
+        if config.allocation_hooks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let msg = to_binary(&AllocationHookExecuteMsg::AllocationHook(
+            AllocationHookMsg {
+                change,
+                account: account.to_string(),
+                allocation_id,
+                amount,
+            },
+        ))?;
+
+        Ok(config
+            .allocation_hooks
+            .iter()
+            .map(|hook| {
+                SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: hook.to_string(),
+                        msg: msg.clone(),
+                        funds: vec![],
+                    },
+                    ALLOCATION_HOOK_REPLY_ID,
+                )
+                .with_gas_limit(ALLOCATION_HOOK_GAS_LIMIT)
+            })
+            .collect())
+    }
+
+    /// Returns the global unlock totals as response attributes
+    pub fn state_attributes(state: &State) -> Vec<Attribute> {
+        vec![
+            attr("total_astro_deposited", state.total_astro_deposited),
+            attr("remaining_astro_tokens", state.remaining_astro_tokens),
+            attr("unallocated_tokens", state.unallocated_tokens),
+        ]
+    }
+
+    /// Returns whether an account has proposed allocation splits
+    pub fn has_split_proposals(storage: &dyn Storage, account: &Addr) -> bool {
+        SPLIT_PROPOSALS
+            .prefix(account)
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+    }
+
+    /// Aggregates allocations of an account. Amounts are summed up while the unlock schedule and
+    /// the proposed receiver are taken from the first allocation.
+    pub fn aggregate_allocations(
+        allocations: &[(u64, AllocationParams, AllocationStatus)],
+    ) -> AllocationResponse {
+        let mut response = allocations
+            .first()
+            .map(|(_, params, _)| AllocationResponse {
+                params: AllocationParams {
+                    amount: Uint128::zero(),
+                    ..params.clone()
+                },
+                status: AllocationStatus::new(),
+            })
+            .unwrap_or_else(|| AllocationResponse {
+                params: AllocationParams::default(),
+                status: AllocationStatus::default(),
+            });
+
+        for (_, params, status) in allocations {
+            response.params.amount += params.amount;
+            response.status.astro_withdrawn += status.astro_withdrawn;
+            response.status.unlocked_amount_checkpoint += status.unlocked_amount_checkpoint;
+        }
+
+        response
+    }
 
     /// Computes number of tokens that are now unlocked for a given allocation
     pub fn compute_unlocked_amount(
@@ -783,14 +3173,29 @@ mod helpers {
         schedule: &Schedule,
         unlock_checkpoint: Uint128,
     ) -> Uint128 {
+        // Portion of tokens unlocked at the start time
+        let unlocked_at_start = if timestamp >= schedule.start_time {
+            schedule
+                .percent_at_start
+                .map(|percent| amount * percent)
+                .unwrap_or_default()
+        } else {
+            Uint128::zero()
+        };
+
         // Tokens haven't begun unlocking
         if timestamp < schedule.start_time + schedule.cliff {
-            unlock_checkpoint
+            unlock_checkpoint.max(unlocked_at_start)
         }
-        // Tokens unlock linearly between start time and end time
+        // Tokens unlock linearly (or in periodic tranches) between start time and end time
         else if (timestamp < schedule.start_time + schedule.duration) && schedule.duration != 0 {
-            let unlocked_amount =
-                amount.multiply_ratio(timestamp - schedule.start_time, schedule.duration);
+            let mut elapsed = timestamp - schedule.start_time;
+            if let Some(unlock_period) = schedule.unlock_period {
+                elapsed -= elapsed % unlock_period;
+            }
+            // The rest of the tokens unlock according to the schedule, so the total never exceeds the amount
+            let unlocked_amount = unlocked_at_start
+                + (amount - unlocked_at_start).multiply_ratio(elapsed, schedule.duration);
 
             if unlocked_amount > unlock_checkpoint {
                 unlocked_amount
@@ -819,7 +3224,7 @@ mod helpers {
         );
 
         // Withdrawal amount is unlocked amount minus the amount already withdrawn
-        let astro_withdrawable = astro_unlocked - status.astro_withdrawn;
+        let astro_withdrawable = astro_unlocked.saturating_sub(status.astro_withdrawn);
 
         SimulateWithdrawResponse {
             astro_to_withdraw: astro_withdrawable,
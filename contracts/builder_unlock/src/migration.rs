@@ -1,5 +1,113 @@
+use crate::astroport::common::OwnershipProposal;
+use crate::state::{ALLOCATION_COUNTS, CONFIG, PARAMS, STATUS};
+use astroport_governance::builder_unlock::{AllocationParams, AllocationStatus, Config};
+use astroport_governance::migration::migrate_map;
+
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, DepsMut, Env, Order, StdError, StdResult, Storage, Timestamp, Uint128};
+use cw_ownable::Action;
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
 
-/// This structure describes a migration message.
+/// This structure stores the contract configuration before V1.4.0
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct ConfigV120 {
+    /// Account that can create new unlock schedules
+    pub owner: Addr,
+    /// Address of ASTRO token
+    pub astro_token: Addr,
+    /// Max ASTRO tokens to allocate
+    pub max_allocations_amount: Uint128,
+}
+
+/// Allocation parameters for each unlock recipient before V1.4.0
+pub const PARAMS_V120: Map<&Addr, AllocationParams> = Map::new("params");
+/// The status of each unlock schedule before V1.4.0
+pub const STATUS_V120: Map<&Addr, AllocationStatus> = Map::new("status");
+/// The contract configuration before V1.4.0
+pub const CONFIG_V120: Item<ConfigV120> = Item::new("config");
+/// Proposal to change contract ownership before V1.4.0
+pub const OWNERSHIP_PROPOSAL_V120: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Migrate allocations to V1.4.0 in batches. Every existing allocation becomes the first allocation
+/// (ID 0) of its account. Migrated accounts are removed from the legacy maps, so every batch
+/// continues with the remaining ones.
+///
+/// * **limit** amount of accounts to migrate.
+///
+/// Returns whether every allocation is migrated.
+pub(crate) fn migrate_allocations_to_v140(
+    storage: &mut dyn Storage,
+    env: &Env,
+    limit: u32,
+) -> StdResult<bool> {
+    let migrated = migrate_map(
+        storage,
+        &PARAMS_V120,
+        None,
+        Some(limit as usize),
+        |storage, account, params| {
+            let status = STATUS_V120.load(storage, &account)?;
+
+            PARAMS.save(storage, (&account, 0), &params, env.block.height)?;
+            STATUS.save(storage, (&account, 0), &status, env.block.height)?;
+            ALLOCATION_COUNTS.save(storage, &account, &1)?;
+            PARAMS_V120.remove(storage, &account);
+            STATUS_V120.remove(storage, &account);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(migrated < limit as usize)
+}
+
+/// Returns whether allocations created before V1.4.0 are still waiting to be migrated.
+pub(crate) fn allocations_migration_pending(storage: &dyn Storage) -> bool {
+    PARAMS_V120
+        .keys_raw(storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+}
+
+/// Migrate the contract configuration and ownership to V1.4.0. The owner moves from the config
+/// to cw-ownable and a pending ownership proposal becomes a pending ownership transfer with the
+/// same expiration time.
+pub(crate) fn migrate_config_to_v140(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let config = CONFIG_V120.load(deps.storage)?;
+
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(config.owner.as_str()))?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            astro_token: config.astro_token,
+            max_allocations_amount: config.max_allocations_amount,
+            decrease_notice_period: 0,
+            astro_staking: None,
+            yield_recipient: None,
+            max_allocation_per_account: None,
+            max_total_accounts: None,
+            voting_escrow: None,
+            paused: false,
+            allocation_hooks: vec![],
+        },
+    )?;
+
+    if let Some(proposal) = OWNERSHIP_PROPOSAL_V120.may_load(deps.storage)? {
+        if env.block.time.seconds() <= proposal.ttl {
+            cw_ownable::update_ownership(
+                deps,
+                &env.block,
+                &config.owner,
+                Action::TransferOwnership {
+                    new_owner: proposal.owner.to_string(),
+                    expiry: Some(Expiration::AtTime(Timestamp::from_seconds(proposal.ttl))),
+                },
+            )
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+        }
+        OWNERSHIP_PROPOSAL_V120.remove(deps.storage);
+    }
+
+    Ok(())
+}
@@ -1,16 +1,56 @@
-use crate::astroport::common::OwnershipProposal;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 
-use astroport_governance::builder_unlock::{AllocationParams, AllocationStatus, Config, State};
+use astroport_governance::builder_unlock::{
+    AllocationParams, AllocationStatus, Config, PendingDecrease, Revocation, State,
+};
 
 /// Stores the contract configuration
 pub const CONFIG: Item<Config> = Item::new("config");
 /// Stores global unlcok state such as the total amount of ASTRO tokens still to be distributed
 pub const STATE: Item<State> = Item::new("state");
-/// Allocation parameters for each unlock recipient
-pub const PARAMS: Map<&Addr, AllocationParams> = Map::new("params");
-/// The status of each unlock schedule
-pub const STATUS: Map<&Addr, AllocationStatus> = Map::new("status");
-/// Contains a proposal to change contract ownership
-pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+/// Allocation parameters for each unlock recipient and allocation ID
+pub const PARAMS: SnapshotMap<(&Addr, u64), AllocationParams> = SnapshotMap::new(
+    "allocation_params",
+    "allocation_params__checkpoints",
+    "allocation_params__changelog",
+    Strategy::EveryBlock,
+);
+/// The status of each unlock schedule (recipient, allocation ID)
+pub const STATUS: SnapshotMap<(&Addr, u64), AllocationStatus> = SnapshotMap::new(
+    "allocation_status",
+    "allocation_status__checkpoints",
+    "allocation_status__changelog",
+    Strategy::EveryBlock,
+);
+/// The number of allocation IDs ever assigned to each account. Used to look up removed allocations at past heights
+pub const ALLOCATION_COUNTS: Map<&Addr, u64> = Map::new("allocation_counts");
+/// Revocation records of revoked allocations (recipient, allocation ID)
+pub const REVOCATIONS: Map<(&Addr, u64), Revocation> = Map::new("revocations");
+/// Queued allocation decreases (receiver, allocation ID)
+pub const PENDING_DECREASES: Map<(&Addr, u64), PendingDecrease> = Map::new("pending_decreases");
+/// Expiration timestamps of receiver change proposals
+pub const RECEIVER_PROPOSAL_EXPIRY: Map<&Addr, u64> = Map::new("receiver_proposal_expiry");
+/// Unclaimed parts of proposed allocation splits (receiver, allocation ID)
+pub const SPLIT_PROPOSALS: Map<(&Addr, u64), Vec<(Addr, Uint128)>> = Map::new("split_proposals");
+/// Voting power delegates of allocation holders
+pub const VOTING_DELEGATES: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "voting_delegates",
+    "voting_delegates__checkpoints",
+    "voting_delegates__changelog",
+    Strategy::EveryBlock,
+);
+/// Total voting power of locked allocations delegated to each delegate
+pub const DELEGATED_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "delegated_power",
+    "delegated_power__checkpoints",
+    "delegated_power__changelog",
+    Strategy::EveryBlock,
+);
+/// Amount of ASTRO staked in the ASTRO staking contract. The xASTRO worth more than this amount is the staking yield
+pub const STAKED_ASTRO: Item<Uint128> = Item::new("staked_astro");
+/// The account whose withdrawn ASTRO is being locked in vxASTRO and the contract's xASTRO balance
+/// before the withdrawn ASTRO is staked
+pub const PENDING_LOCK: Item<(Addr, Uint128)> = Item::new("pending_lock");
+/// Accounts whose withdrawals are frozen
+pub const FROZEN_ACCOUNTS: Map<&Addr, bool> = Map::new("frozen_accounts");
@@ -1,13 +1,33 @@
+use astroport::staking::{
+    ConfigResponse as StakingConfigResponse, Cw20HookMsg as StakingHookMsg,
+    InstantiateMsg as StakingInstantiateMsg, QueryMsg as StakingQueryMsg,
+};
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
-use astroport_governance::builder_unlock::{AllocationParams, Schedule};
+use astroport_governance::builder_unlock::{
+    AllocationChange, AllocationHookExecuteMsg, AllocationHookMsg, AllocationParams,
+    PendingDecrease, Revocation, Schedule,
+};
 
 use astroport_governance::builder_unlock::msg::{
-    AllocationResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg,
-    SimulateWithdrawResponse, StateResponse,
+    AllocationResponse, AllocationsFilter, AllocationsOrder, ConfigResponse, ExecuteMsg,
+    InstantiateMsg, QueryMsg, ReceiveMsg, ReceiverProposalResponse, ScheduleUpdateValidation,
+    SimulateWithdrawResponse, SolvencyResponse, StakingStateResponse, StateResponse,
+    TotalUnlockedResponse,
+};
+use astroport_governance::utils::{EPOCH_START, WEEK};
+use astroport_governance::voting_escrow::{
+    Cw20HookMsg as VotingEscrowHookMsg, InstantiateMsg as VotingEscrowInstantiateMsg,
+    LockInfoResponse, QueryMsg as VotingEscrowQueryMsg,
+};
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult, Timestamp, Uint128,
 };
-use cosmwasm_std::{attr, to_binary, Addr, StdResult, Timestamp, Uint128};
 use cw20::BalanceResponse;
 use cw_multi_test::{App, BasicApp, ContractWrapper, Executor};
+use cw_ownable::{Action, Ownership};
+use cw_storage_plus::Item;
+use cw_utils::Expiration;
 
 const OWNER: &str = "owner";
 
@@ -49,11 +69,14 @@ fn init_contracts(app: &mut App) -> (Addr, Addr, InstantiateMsg) {
         .unwrap();
 
     // Instantiate the contract
-    let unlock_contract = Box::new(ContractWrapper::new(
-        builder_unlock::contract::execute,
-        builder_unlock::contract::instantiate,
-        builder_unlock::contract::query,
-    ));
+    let unlock_contract = Box::new(
+        ContractWrapper::new(
+            builder_unlock::contract::execute,
+            builder_unlock::contract::instantiate,
+            builder_unlock::contract::query,
+        )
+        .with_reply(builder_unlock::contract::reply),
+    );
 
     let unlock_code_id = app.store_code(unlock_contract);
 
@@ -61,6 +84,7 @@ fn init_contracts(app: &mut App) -> (Addr, Addr, InstantiateMsg) {
         owner: OWNER.clone().to_string(),
         astro_token: astro_token_instance.to_string(),
         max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+        decrease_notice_period: None,
     };
 
     // Init contract
@@ -138,9 +162,14 @@ fn proper_initialization() {
         .unwrap();
 
     // Check config
-    assert_eq!(init_msg.owner, resp.owner);
     assert_eq!(init_msg.astro_token, resp.astro_token);
 
+    let ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(Addr::unchecked(init_msg.owner)));
+
     // Check state
     let resp: StateResponse = app
         .wrap()
@@ -189,13 +218,16 @@ fn test_transfer_ownership() {
     )
     .unwrap();
 
+    let ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(Addr::unchecked("new_owner")));
+
     let resp: ConfigResponse = app
         .wrap()
         .query_wasm_smart(&unlock_instance, &QueryMsg::Config {})
         .unwrap();
-
-    // Check config
-    assert_eq!("new_owner".to_string(), resp.owner);
     assert_eq!(init_msg.astro_token, resp.astro_token);
 }
 
@@ -221,6 +253,8 @@ fn test_create_allocations() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -233,6 +267,8 @@ fn test_create_allocations() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -245,6 +281,8 @@ fn test_create_allocations() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -411,7 +449,9 @@ fn test_create_allocations() {
         Schedule {
             start_time: 1642402274u64,
             cliff: 0u64,
-            duration: 31536000u64
+            duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         }
     );
 
@@ -432,7 +472,9 @@ fn test_create_allocations() {
         Schedule {
             start_time: 1642402274u64,
             cliff: 7776000u64,
-            duration: 31536000u64
+            duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         }
     );
 
@@ -453,30 +495,49 @@ fn test_create_allocations() {
         Schedule {
             start_time: 1642402274u64,
             cliff: 7776000u64,
-            duration: 31536000u64
+            duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         }
     );
 
-    // ######    ERROR :: Allocation already exists for user {}     ######
-    err = app
-        .execute_contract(
-            Addr::unchecked(OWNER.clone()),
-            astro_instance.clone(),
-            &cw20::Cw20ExecuteMsg::Send {
-                contract: unlock_instance.clone().to_string(),
-                amount: Uint128::from(5_000_000_000000u64),
-                msg: to_binary(&ReceiveMsg::CreateAllocations {
-                    allocations: vec![allocations[0].clone()],
-                })
-                .unwrap(),
+    // A second grant creates a new allocation for the same account
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::from(5_000_000_000000u64),
+            msg: to_binary(&ReceiveMsg::CreateAllocations {
+                allocations: vec![allocations[0].clone()],
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    check_alloc_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor_1"),
+        Uint128::from(10_000_000_000000u64),
+    );
+
+    let resp: Vec<(u64, AllocationResponse)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::AccountAllocations {
+                account: "investor_1".to_string(),
             },
-            &[],
         )
-        .unwrap_err();
+        .unwrap();
     assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Allocation (params) already exists for investor_1"
+        resp.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        vec![0, 1]
     );
+    assert_eq!(resp[1].1.params, allocations[0].1);
 }
 
 #[test]
@@ -501,6 +562,8 @@ fn test_withdraw() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -513,6 +576,8 @@ fn test_withdraw() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -525,6 +590,8 @@ fn test_withdraw() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -551,7 +618,7 @@ fn test_withdraw() {
         .execute_contract(
             Addr::unchecked(OWNER.clone()),
             unlock_instance.clone(),
-            &ExecuteMsg::Withdraw {},
+            &ExecuteMsg::Withdraw { recipient: None },
             &[],
         )
         .unwrap_err();
@@ -579,7 +646,7 @@ fn test_withdraw() {
     app.execute_contract(
         Addr::unchecked("investor_1".clone()),
         unlock_instance.clone(),
-        &ExecuteMsg::Withdraw {},
+        &ExecuteMsg::Withdraw { recipient: None },
         &[],
     )
     .unwrap();
@@ -643,7 +710,7 @@ fn test_withdraw() {
         .execute_contract(
             Addr::unchecked("investor_1".clone()),
             unlock_instance.clone(),
-            &ExecuteMsg::Withdraw {},
+            &ExecuteMsg::Withdraw { recipient: None },
             &[],
         )
         .unwrap_err();
@@ -690,7 +757,7 @@ fn test_withdraw() {
     app.execute_contract(
         Addr::unchecked("investor_1".clone()),
         unlock_instance.clone(),
-        &ExecuteMsg::Withdraw {},
+        &ExecuteMsg::Withdraw { recipient: None },
         &[],
     )
     .unwrap();
@@ -712,7 +779,7 @@ fn test_withdraw() {
         .execute_contract(
             Addr::unchecked("investor_1".clone()),
             unlock_instance.clone(),
-            &ExecuteMsg::Withdraw {},
+            &ExecuteMsg::Withdraw { recipient: None },
             &[],
         )
         .unwrap_err();
@@ -794,7 +861,7 @@ fn test_withdraw() {
     app.execute_contract(
         Addr::unchecked("team_1".clone()),
         unlock_instance.clone(),
-        &ExecuteMsg::Withdraw {},
+        &ExecuteMsg::Withdraw { recipient: None },
         &[],
     )
     .unwrap();
@@ -850,6 +917,8 @@ fn test_propose_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -862,6 +931,8 @@ fn test_propose_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -874,6 +945,8 @@ fn test_propose_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -902,6 +975,7 @@ fn test_propose_new_receiver() {
             unlock_instance.clone(),
             &ExecuteMsg::ProposeNewReceiver {
                 new_receiver: "investor_1_new".to_string(),
+                expires_in: 86400,
             },
             &[],
         )
@@ -918,6 +992,7 @@ fn test_propose_new_receiver() {
             unlock_instance.clone(),
             &ExecuteMsg::ProposeNewReceiver {
                 new_receiver: "team_1".to_string(),
+                expires_in: 86400,
             },
             &[],
         )
@@ -933,6 +1008,7 @@ fn test_propose_new_receiver() {
         unlock_instance.clone(),
         &ExecuteMsg::ProposeNewReceiver {
             new_receiver: "investor_1_new".to_string(),
+            expires_in: 86400,
         },
         &[],
     )
@@ -959,6 +1035,7 @@ fn test_propose_new_receiver() {
             unlock_instance.clone(),
             &ExecuteMsg::ProposeNewReceiver {
                 new_receiver: "investor_1_new_".to_string(),
+                expires_in: 86400,
             },
             &[],
         )
@@ -991,6 +1068,8 @@ fn test_drop_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1003,6 +1082,8 @@ fn test_drop_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1015,6 +1096,8 @@ fn test_drop_new_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1071,6 +1154,7 @@ fn test_drop_new_receiver() {
         unlock_instance.clone(),
         &ExecuteMsg::ProposeNewReceiver {
             new_receiver: "investor_1_new".to_string(),
+            expires_in: 86400,
         },
         &[],
     )
@@ -1132,6 +1216,8 @@ fn test_claim_receiver() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1144,6 +1230,8 @@ fn test_claim_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1156,6 +1244,8 @@ fn test_claim_receiver() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1182,7 +1272,7 @@ fn test_claim_receiver() {
         .execute_contract(
             Addr::unchecked(OWNER.clone()),
             unlock_instance.clone(),
-            &ExecuteMsg::Withdraw {},
+            &ExecuteMsg::Withdraw { recipient: None },
             &[],
         )
         .unwrap_err();
@@ -1214,6 +1304,7 @@ fn test_claim_receiver() {
         unlock_instance.clone(),
         &ExecuteMsg::ProposeNewReceiver {
             new_receiver: "investor_1_new".to_string(),
+            expires_in: 86400,
         },
         &[],
     )
@@ -1269,6 +1360,8 @@ fn test_claim_receiver() {
                 start_time: 0u64,
                 cliff: 0u64,
                 duration: 0u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1293,6 +1386,8 @@ fn test_claim_receiver() {
                 start_time: alloc_resp_before.params.unlock_schedule.start_time,
                 cliff: alloc_resp_before.params.unlock_schedule.cliff,
                 duration: alloc_resp_before.params.unlock_schedule.duration,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1355,6 +1450,8 @@ fn test_increase_and_decrease_allocation() {
                 start_time: 1_571_797_419u64,
                 cliff: 300u64,
                 duration: 1_534_700u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1393,7 +1490,7 @@ fn test_increase_and_decrease_allocation() {
     app.execute_contract(
         Addr::unchecked("investor".to_string()),
         unlock_instance.clone(),
-        &ExecuteMsg::Withdraw {},
+        &ExecuteMsg::Withdraw { recipient: None },
         &[],
     )
     .unwrap();
@@ -1419,6 +1516,7 @@ fn test_increase_and_decrease_allocation() {
             &ExecuteMsg::DecreaseAllocation {
                 receiver: "investor".to_string(),
                 amount: Uint128::from(4_918_550_856_846u128),
+                allocation_id: None,
             },
             &[],
         )
@@ -1434,6 +1532,7 @@ fn test_increase_and_decrease_allocation() {
         &ExecuteMsg::DecreaseAllocation {
             receiver: "investor".to_string(),
             amount: Uint128::from(1_000_000_000_000u128),
+            allocation_id: None,
         },
         &[],
     )
@@ -1468,6 +1567,7 @@ fn test_increase_and_decrease_allocation() {
             &ExecuteMsg::IncreaseAllocation {
                 receiver: "investor".to_string(),
                 amount: Uint128::from(1_000_000_000_001u128),
+                allocation_id: None,
             },
             &[],
         )
@@ -1510,6 +1610,7 @@ fn test_increase_and_decrease_allocation() {
             msg: to_binary(&ReceiveMsg::IncreaseAllocation {
                 amount: Uint128::from(500_000_001_000u128),
                 user: "investor".to_string(),
+                allocation_id: None,
             })
             .unwrap(),
         },
@@ -1521,7 +1622,7 @@ fn test_increase_and_decrease_allocation() {
     app.execute_contract(
         Addr::unchecked("investor".to_string()),
         unlock_instance.clone(),
-        &ExecuteMsg::Withdraw {},
+        &ExecuteMsg::Withdraw { recipient: None },
         &[],
     )
     .unwrap();
@@ -1593,6 +1694,8 @@ fn test_updates_schedules() {
                 start_time: 1642402274u64,
                 cliff: 0u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1605,6 +1708,8 @@ fn test_updates_schedules() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1617,6 +1722,8 @@ fn test_updates_schedules() {
                 start_time: 1642402274u64,
                 cliff: 7776000u64,
                 duration: 31536000u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1663,6 +1770,8 @@ fn test_updates_schedules() {
             start_time: 1642402274u64,
             cliff: 0u64,
             duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         },
     )
     .unwrap();
@@ -1678,6 +1787,8 @@ fn test_updates_schedules() {
             start_time: 1642402274u64,
             cliff: 7776000u64,
             duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         },
     )
     .unwrap();
@@ -1693,6 +1804,8 @@ fn test_updates_schedules() {
             start_time: 1642402274u64,
             cliff: 7776000u64,
             duration: 31536000u64,
+            unlock_period: None,
+            percent_at_start: None,
         },
     )
     .unwrap();
@@ -1709,8 +1822,11 @@ fn test_updates_schedules() {
                         start_time: 123u64,
                         cliff: 123u64,
                         duration: 123u64,
+                        unlock_period: None,
+                        percent_at_start: None,
                     },
                 )],
+                allocation_id: None,
             },
             &[],
         )
@@ -1732,6 +1848,8 @@ fn test_updates_schedules() {
                             start_time: 123u64,
                             cliff: 123u64,
                             duration: 123u64,
+                            unlock_period: None,
+                            percent_at_start: None,
                         },
                     ),
                     (
@@ -1740,9 +1858,12 @@ fn test_updates_schedules() {
                             start_time: 123u64,
                             cliff: 123u64,
                             duration: 123u64,
+                            unlock_period: None,
+                            percent_at_start: None,
                         },
                     ),
                 ],
+                allocation_id: None,
             },
             &[],
         )
@@ -1763,6 +1884,8 @@ fn test_updates_schedules() {
                         start_time: 1642402284u64,
                         cliff: 8776000u64,
                         duration: 31536001u64,
+                        unlock_period: None,
+                        percent_at_start: None,
                     },
                 ),
                 (
@@ -1771,9 +1894,12 @@ fn test_updates_schedules() {
                         start_time: 1642402284u64,
                         cliff: 8776000u64,
                         duration: 31536001u64,
+                        unlock_period: None,
+                        percent_at_start: None,
                     },
                 ),
             ],
+            allocation_id: None,
         },
         &[],
     )
@@ -1790,6 +1916,8 @@ fn test_updates_schedules() {
             start_time: 1642402284u64,
             cliff: 8776000u64,
             duration: 31536001u64,
+            unlock_period: None,
+            percent_at_start: None,
         },
     )
     .unwrap();
@@ -1805,6 +1933,8 @@ fn test_updates_schedules() {
             start_time: 1642402284u64,
             cliff: 8776000u64,
             duration: 31536001u64,
+            unlock_period: None,
+            percent_at_start: None,
         },
     )
     .unwrap();
@@ -1817,6 +1947,8 @@ fn test_updates_schedules() {
             &QueryMsg::Allocations {
                 start_after: None,
                 limit: None,
+                filter: None,
+                order_by: None,
             },
         )
         .unwrap();
@@ -1830,6 +1962,8 @@ fn test_updates_schedules() {
                     start_time: 1642402284u64,
                     cliff: 8776000u64,
                     duration: 31536001u64,
+                    unlock_period: None,
+                    percent_at_start: None,
                 },
                 proposed_receiver: None,
             },
@@ -1842,6 +1976,8 @@ fn test_updates_schedules() {
                     start_time: 1642402274,
                     cliff: 0,
                     duration: 31536000,
+                    unlock_period: None,
+                    percent_at_start: None,
                 },
                 proposed_receiver: None,
             },
@@ -1854,6 +1990,8 @@ fn test_updates_schedules() {
                     start_time: 1642402284u64,
                     cliff: 8776000u64,
                     duration: 31536001u64,
+                    unlock_period: None,
+                    percent_at_start: None,
                 },
                 proposed_receiver: None,
             },
@@ -1869,6 +2007,8 @@ fn test_updates_schedules() {
             &QueryMsg::Allocations {
                 start_after: Some("investor_1".to_string()),
                 limit: None,
+                filter: None,
+                order_by: None,
             },
         )
         .unwrap();
@@ -1880,6 +2020,8 @@ fn test_updates_schedules() {
                 start_time: 1642402284u64,
                 cliff: 8776000u64,
                 duration: 31536001u64,
+                unlock_period: None,
+                percent_at_start: None,
             },
             proposed_receiver: None,
         },
@@ -1887,6 +2029,3772 @@ fn test_updates_schedules() {
     assert_eq!(comparing_values, resp);
 }
 
+#[test]
+fn test_revoke_allocation() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::from(5_000_000_000000u64),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 300u64,
+                duration: 1_534_700u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::from(5_000_000_000000u64),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 1000;
+        bi.time = bi.time.plus_seconds(5_000);
+    });
+
+    // Only the owner can revoke allocations
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Revoke {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can revoke allocations"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::Revoke {
+            account: "investor".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Unvested ASTRO is returned to the unallocated tokens
+    let resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        resp.unallocated_astro_tokens,
+        Uint128::new(4_983_710_171_369)
+    );
+    assert_eq!(resp.remaining_astro_tokens, Uint128::new(16_289_828_631));
+
+    let resp: Option<Revocation> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Revocation {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        Some(Revocation {
+            revoked_at: 1_571_802_419,
+            unvested_amount: Uint128::new(4_983_710_171_369),
+        })
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::Revoke {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allocation 0 is already revoked for investor"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::IncreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(1_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allocation 0 is revoked for investor"
+    );
+
+    // The account can still withdraw tokens unlocked before the revocation
+    app.update_block(|bi| {
+        bi.height += 4000;
+        bi.time = bi.time.plus_seconds(20_000);
+    });
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(16_289_828_631),
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(16_289_828_631));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw { recipient: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: No unlocked ASTRO to be withdrawn"
+    );
+}
+
+#[test]
+fn test_multiple_allocations() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![
+        (
+            "investor".to_string(),
+            AllocationParams {
+                amount: Uint128::new(1_000_000),
+                unlock_schedule: Schedule {
+                    start_time: 1_571_797_419u64,
+                    cliff: 0u64,
+                    duration: 1_000u64,
+                    unlock_period: None,
+                    percent_at_start: None,
+                },
+                proposed_receiver: None,
+            },
+        ),
+        (
+            "investor".to_string(),
+            AllocationParams {
+                amount: Uint128::new(2_000_000),
+                unlock_schedule: Schedule {
+                    start_time: 1_571_797_419u64,
+                    cliff: 0u64,
+                    duration: 2_000u64,
+                    unlock_period: None,
+                    percent_at_start: None,
+                },
+                proposed_receiver: None,
+            },
+        ),
+    ];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(3_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    check_alloc_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(3_000_000),
+    );
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    // Unlocked tokens are aggregated across allocations with independent schedules
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(1_000_000),
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(1_000_000));
+
+    // Only the second allocation has enough locked tokens
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(1_000_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Insufficient amount of lock to decrease allocation, user has locked 500000 ASTRO."
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::DecreaseAllocation {
+            receiver: "investor".to_string(),
+            amount: Uint128::new(1_000_000),
+            allocation_id: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.amount, Uint128::new(2_000_000));
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(1_000_000));
+
+    let resp: Vec<(u64, AllocationResponse)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::AccountAllocations {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp[0].1.params.amount, Uint128::new(1_000_000));
+    assert_eq!(resp[1].1.params.amount, Uint128::new(1_000_000));
+
+    // Both allocations are fully unlocked after the longest schedule ends
+    app.update_block(|bi| {
+        bi.height += 1000;
+        bi.time = bi.time.plus_seconds(10_000);
+    });
+
+    let resp: SimulateWithdrawResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdraw {
+                account: "investor".to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.astro_to_withdraw, Uint128::new(1_000_000));
+}
+
+#[test]
+fn test_periodic_unlock_schedule() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let mut allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_200_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 100u64,
+                duration: 1_200u64,
+                unlock_period: Some(0),
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.clone().to_string(),
+                amount: Uint128::new(1_200_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations {
+                    allocations: allocations.clone(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The unlock period must be greater than 0 and not exceed the duration: 0 <= 1200. Account: investor"
+    );
+
+    allocations[0].1.unlock_schedule.unlock_period = Some(100);
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_200_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Tokens unlock in tranches every 100 seconds after the cliff
+    for (timestamp, unlocked) in [
+        (1_571_797_419u64 + 99, 0u128),
+        (1_571_797_419u64 + 150, 100_000u128),
+        (1_571_797_419u64 + 1_199, 1_100_000u128),
+        (1_571_797_419u64 + 1_200, 1_200_000u128),
+    ] {
+        let resp: SimulateWithdrawResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::SimulateWithdraw {
+                    account: "investor".to_string(),
+                    timestamp: Some(timestamp),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.astro_to_withdraw, Uint128::new(unlocked));
+    }
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(250);
+    });
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(200_000),
+    );
+}
+
+#[test]
+fn test_percent_at_start() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let start_time = 1_571_797_419u64 + 100;
+    let mut allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time,
+                cliff: 500u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: Some(Decimal::percent(150)),
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.clone().to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations {
+                    allocations: allocations.clone(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The percent unlocked at start must not exceed 100%: 1.5. Account: investor"
+    );
+
+    allocations[0].1.unlock_schedule.percent_at_start = Some(Decimal::percent(25));
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // A quarter of the allocation is withdrawable at the start time regardless of the cliff
+    for (timestamp, unlocked) in [
+        (start_time - 1, 0u128),
+        (start_time, 250_000u128),
+        (start_time + 499, 250_000u128),
+        (start_time + 500, 625_000u128),
+        (start_time + 1_000, 1_000_000u128),
+    ] {
+        let resp: SimulateWithdrawResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::SimulateWithdraw {
+                    account: "investor".to_string(),
+                    timestamp: Some(timestamp),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.astro_to_withdraw, Uint128::new(unlocked));
+    }
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(100);
+    });
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(250_000));
+}
+
+#[test]
+fn test_freeze_account() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    // Only the owner can freeze accounts
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::FreezeAccount {
+                account: "investor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can freeze accounts"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::FreezeAccount {
+            account: "investor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let is_frozen: bool = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::IsFrozen {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(is_frozen);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw { recipient: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Account investor is frozen"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "investor_new".to_string(),
+            expires_in: 86400,
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor_new"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimReceiver {
+                prev_receiver: "investor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Account investor is frozen"
+    );
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::DropNewReceiver {},
+        &[],
+    )
+    .unwrap();
+
+    // The schedule keeps unlocking while the account is frozen
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(500_000),
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::UnfreezeAccount {
+            account: "investor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::UnfreezeAccount {
+                account: "investor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Account investor is already unfrozen"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(500_000));
+}
+
+#[test]
+fn test_batch_queries() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = ["investor_1", "investor_2"]
+        .iter()
+        .enumerate()
+        .map(|(i, account)| {
+            (
+                account.to_string(),
+                AllocationParams {
+                    amount: Uint128::new(1_000_000 * (i as u128 + 1)),
+                    unlock_schedule: Schedule {
+                        start_time: 1_571_797_419u64,
+                        cliff: 0u64,
+                        duration: 1_000u64,
+                        unlock_period: None,
+                        percent_at_start: None,
+                    },
+                    proposed_receiver: None,
+                },
+            )
+        })
+        .collect();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(3_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let accounts = vec!["investor_1".to_string(), "investor_2".to_string()];
+    let resp: Vec<(String, Uint128)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::UnlockedTokensBatch {
+                accounts: accounts.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        vec![
+            ("investor_1".to_string(), Uint128::new(500_000)),
+            ("investor_2".to_string(), Uint128::new(1_000_000)),
+        ]
+    );
+
+    let resp: Vec<(String, SimulateWithdrawResponse)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdrawBatch {
+                accounts,
+                timestamp: Some(1_571_797_419u64 + 1_000),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        vec![
+            (
+                "investor_1".to_string(),
+                SimulateWithdrawResponse {
+                    astro_to_withdraw: Uint128::new(1_000_000)
+                }
+            ),
+            (
+                "investor_2".to_string(),
+                SimulateWithdrawResponse {
+                    astro_to_withdraw: Uint128::new(2_000_000)
+                }
+            ),
+        ]
+    );
+
+    // Protocol-wide unlock curve
+    for (timestamp, unlocked) in [
+        (1_571_797_419u64, 0u128),
+        (1_571_797_419u64 + 250, 750_000u128),
+        (1_571_797_419u64 + 2_000, 3_000_000u128),
+    ] {
+        let resp: TotalUnlockedResponse = app
+            .wrap()
+            .query_wasm_smart(&unlock_instance, &QueryMsg::TotalUnlockedAt { timestamp })
+            .unwrap();
+        assert_eq!(
+            resp,
+            TotalUnlockedResponse {
+                unlocked_amount: Uint128::new(unlocked),
+                locked_amount: Uint128::new(3_000_000 - unlocked),
+            }
+        );
+    }
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<Vec<(String, Uint128)>>(
+            &unlock_instance,
+            &QueryMsg::UnlockedTokensBatch {
+                accounts: vec!["investor_1".to_string(); 31],
+            },
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("The number of accounts in a batch query cannot exceed 30"));
+}
+
+#[test]
+fn test_split_receivers() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ProposeSplitReceivers {
+                receivers: vec![
+                    ("alice".to_string(), Uint128::new(600_000)),
+                    ("bob".to_string(), Uint128::new(300_000)),
+                ],
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Split amounts must sum up to the allocation amount: 900000 != 1000000"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ProposeSplitReceivers {
+                receivers: vec![
+                    ("alice".to_string(), Uint128::new(600_000)),
+                    ("alice".to_string(), Uint128::new(400_000)),
+                ],
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Invalid split receiver alice"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeSplitReceivers {
+            receivers: vec![
+                ("alice".to_string(), Uint128::new(600_000)),
+                ("bob".to_string(), Uint128::new(400_000)),
+            ],
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw { recipient: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: You may not withdraw once you proposed new receiver!"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ProposeNewReceiver {
+                new_receiver: "investor_new".to_string(),
+                expires_in: 86400,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: You may not propose a new receiver while an allocation split is proposed"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("carol"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimSplitReceiver {
+                prev_receiver: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: carol is not a split receiver of the allocation"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimSplitReceiver {
+            prev_receiver: "investor".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Alice receives a proportional share of the withdrawn amount
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "alice".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.amount, Uint128::new(600_000));
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(300_000));
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.amount, Uint128::new(400_000));
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(200_000));
+
+    let split: Option<Vec<(Addr, Uint128)>> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::SplitProposal {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        split,
+        Some(vec![(Addr::unchecked("bob"), Uint128::new(400_000))])
+    );
+
+    app.execute_contract(
+        Addr::unchecked("bob"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimSplitReceiver {
+            prev_receiver: "investor".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The original allocation is removed once all parts are claimed
+    let allocations: Vec<(u64, AllocationResponse)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::AccountAllocations {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(allocations.is_empty());
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("alice"),
+        Uint128::new(600_000),
+    );
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("bob"),
+        Uint128::new(400_000),
+    );
+
+    app.execute_contract(
+        Addr::unchecked("bob"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+    let bob_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "bob".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bob_balance.balance, Uint128::new(200_000));
+}
+
+#[test]
+fn test_decrease_allocation_notice_period() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_decrease_notice_period: Some(100),
+            new_max_allocation_per_account: None,
+            new_max_total_accounts: None,
+            new_voting_escrow: None,
+            new_allocation_hooks: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The decrease is queued instead of being applied immediately
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(300_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1],
+        attr("action", "queue_decrease_allocation")
+    );
+    check_alloc_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(1_000_000),
+    );
+
+    let pending: Option<PendingDecrease> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::PendingDecrease {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pending,
+        Some(PendingDecrease {
+            amount: Uint128::new(300_000),
+            effective_at: 1_571_797_419u64 + 100,
+        })
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(100_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: A decrease of allocation 0 is already queued for investor"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::ApplyDecreaseAllocation {
+                receiver: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The decrease of allocation 0 for investor can't be applied before 1571797519"
+    );
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(100);
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ApplyDecreaseAllocation {
+                receiver: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can decrease allocations"
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::ApplyDecreaseAllocation {
+                receiver: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1],
+        attr("action", "apply_decrease_allocation")
+    );
+    check_alloc_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(700_000),
+    );
+
+    let pending: Option<PendingDecrease> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::PendingDecrease {
+                account: "investor".to_string(),
+                allocation_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pending, None);
+
+    // A queued decrease can be cancelled
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::DecreaseAllocation {
+            receiver: "investor".to_string(),
+            amount: Uint128::new(100_000),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::CancelDecreaseAllocation {
+            receiver: "investor".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(100);
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::ApplyDecreaseAllocation {
+                receiver: "investor".to_string(),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: No decrease of allocation 0 is queued for investor"
+    );
+    check_alloc_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("investor"),
+        Uint128::new(700_000),
+    );
+}
+
+#[test]
+fn test_delegate_voting_power() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let schedule = Schedule {
+        start_time: 1_571_797_419u64,
+        cliff: 0u64,
+        duration: 1_000u64,
+        unlock_period: None,
+        percent_at_start: None,
+    };
+    let allocations: Vec<(String, AllocationParams)> = vec![
+        (
+            "investor".to_string(),
+            AllocationParams {
+                amount: Uint128::new(1_000_000),
+                unlock_schedule: schedule.clone(),
+                proposed_receiver: None,
+            },
+        ),
+        (
+            "foundation".to_string(),
+            AllocationParams {
+                amount: Uint128::new(500_000),
+                unlock_schedule: schedule,
+                proposed_receiver: None,
+            },
+        ),
+    ];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_500_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_voting_power = |app: &App, account: &str| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::VotingPower {
+                    account: account.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        query_voting_power(&app, "foundation"),
+        Uint128::new(500_000)
+    );
+    assert_eq!(query_voting_power(&app, "delegate"), Uint128::zero());
+
+    app.execute_contract(
+        Addr::unchecked("foundation"),
+        unlock_instance.clone(),
+        &ExecuteMsg::DelegateVotingPower {
+            delegate: "delegate".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("foundation"),
+            unlock_instance.clone(),
+            &ExecuteMsg::DelegateVotingPower {
+                delegate: "delegate".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Voting power is already delegated to delegate"
+    );
+
+    let delegate: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingDelegate {
+                account: "foundation".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(delegate, Some(Addr::unchecked("delegate")));
+    assert_eq!(query_voting_power(&app, "foundation"), Uint128::zero());
+    assert_eq!(query_voting_power(&app, "delegate"), Uint128::new(500_000));
+
+    // Delegated voting power adds up
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::DelegateVotingPower {
+            delegate: "delegate".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_voting_power(&app, "investor"), Uint128::zero());
+    assert_eq!(
+        query_voting_power(&app, "delegate"),
+        Uint128::new(1_500_000)
+    );
+
+    // Withdrawn ASTRO doesn't count towards the voting power
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_voting_power(&app, "delegate"),
+        Uint128::new(1_000_000)
+    );
+
+    // Delegating to oneself removes the delegation
+    app.execute_contract(
+        Addr::unchecked("foundation"),
+        unlock_instance.clone(),
+        &ExecuteMsg::DelegateVotingPower {
+            delegate: "foundation".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_voting_power(&app, "foundation"),
+        Uint128::new(500_000)
+    );
+    assert_eq!(query_voting_power(&app, "delegate"), Uint128::new(500_000));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("foundation"),
+            unlock_instance.clone(),
+            &ExecuteMsg::DelegateVotingPower {
+                delegate: "foundation".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Voting power is not delegated"
+    );
+
+    // Allocation changes of delegators are applied to the delegated voting power
+    app.update_block(|bi| bi.height += 1);
+    let revoke_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::Revoke {
+            account: "investor".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_voting_power(&app, "delegate"), Uint128::zero());
+
+    // The delegated voting power before the revocation is kept
+    let voting_power: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingPower {
+                account: "delegate".to_string(),
+                height: Some(revoke_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(voting_power, Uint128::new(500_000));
+}
+
+#[test]
+fn test_allocation_at_height() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    let creation_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_allocation_at = |app: &App, account: &str, height: u64| -> AllocationResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::AllocationAtHeight {
+                    account: account.to_string(),
+                    height,
+                },
+            )
+            .unwrap()
+    };
+
+    // Snapshots are taken at the beginning of a block
+    assert_eq!(
+        query_allocation_at(&app, "investor", creation_height)
+            .params
+            .amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_allocation_at(&app, "investor", creation_height + 1)
+            .params
+            .amount,
+        Uint128::new(1_000_000)
+    );
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let withdraw_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_allocation_at(&app, "investor", withdraw_height)
+            .status
+            .astro_withdrawn,
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_allocation_at(&app, "investor", withdraw_height + 1)
+            .status
+            .astro_withdrawn,
+        Uint128::new(500_000)
+    );
+
+    // Withdrawing doesn't reduce the voting power at past heights
+    let voting_power: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingPower {
+                account: "investor".to_string(),
+                height: Some(withdraw_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(voting_power, Uint128::new(1_000_000));
+    let voting_power: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingPower {
+                account: "investor".to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(voting_power, Uint128::new(500_000));
+
+    app.update_block(|bi| {
+        bi.height += 1;
+        bi.time = bi.time.plus_seconds(5);
+    });
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "investor_new".to_string(),
+            expires_in: 86400,
+        },
+        &[],
+    )
+    .unwrap();
+    let transfer_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked("investor_new"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimReceiver {
+            prev_receiver: "investor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Transferred allocations are still available at past heights
+    assert_eq!(
+        query_allocation_at(&app, "investor", transfer_height)
+            .params
+            .amount,
+        Uint128::new(1_000_000)
+    );
+    assert_eq!(
+        query_allocation_at(&app, "investor", transfer_height + 1)
+            .params
+            .amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_allocation_at(&app, "investor_new", transfer_height)
+            .params
+            .amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_allocation_at(&app, "investor_new", transfer_height + 1)
+            .params
+            .amount,
+        Uint128::new(1_000_000)
+    );
+}
+
+#[test]
+fn test_allocations_filters_and_order() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocation = |amount: u128, duration: u64| AllocationParams {
+        amount: Uint128::new(amount),
+        unlock_schedule: Schedule {
+            start_time: 1_571_797_419u64,
+            cliff: 0u64,
+            duration,
+            unlock_period: None,
+            percent_at_start: None,
+        },
+        proposed_receiver: None,
+    };
+    let allocations: Vec<(String, AllocationParams)> = vec![
+        ("builder_a".to_string(), allocation(1_000_000, 1_000)),
+        ("builder_b".to_string(), allocation(2_000_000, 2_000)),
+        ("builder_c".to_string(), allocation(500_000, 100)),
+    ];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(3_500_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::Revoke {
+            account: "builder_a".to_string(),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_accounts = |app: &App,
+                          start_after: Option<&str>,
+                          limit: Option<u32>,
+                          filter: Option<AllocationsFilter>,
+                          order_by: Option<AllocationsOrder>|
+     -> Vec<String> {
+        let resp: Vec<(Addr, AllocationParams)> = app
+            .wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::Allocations {
+                    start_after: start_after.map(|addr| addr.to_string()),
+                    limit,
+                    filter,
+                    order_by,
+                },
+            )
+            .unwrap();
+        resp.into_iter()
+            .map(|(account, _)| account.to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        query_accounts(&app, None, None, Some(AllocationsFilter::Active), None),
+        vec!["builder_b"]
+    );
+    assert_eq!(
+        query_accounts(
+            &app,
+            None,
+            None,
+            Some(AllocationsFilter::FullyUnlocked),
+            None
+        ),
+        vec!["builder_a", "builder_c"]
+    );
+    assert_eq!(
+        query_accounts(&app, None, None, Some(AllocationsFilter::Revoked), None),
+        vec!["builder_a"]
+    );
+    assert_eq!(
+        query_accounts(
+            &app,
+            Some("builder_a"),
+            Some(1),
+            Some(AllocationsFilter::FullyUnlocked),
+            None
+        ),
+        vec!["builder_c"]
+    );
+
+    // Ties in the remaining amount are ordered by account
+    assert_eq!(
+        query_accounts(
+            &app,
+            None,
+            None,
+            None,
+            Some(AllocationsOrder::RemainingAmount)
+        ),
+        vec!["builder_b", "builder_a", "builder_c"]
+    );
+    assert_eq!(
+        query_accounts(
+            &app,
+            Some("builder_b"),
+            Some(1),
+            None,
+            Some(AllocationsOrder::RemainingAmount)
+        ),
+        vec!["builder_a"]
+    );
+}
+
+#[test]
+fn test_receiver_proposal_expiry() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ProposeNewReceiver {
+                new_receiver: "investor_new".to_string(),
+                expires_in: 0,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Parameter expires_in must be between 1 and 1209600 seconds"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "investor_new".to_string(),
+            expires_in: 100,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: Option<ReceiverProposalResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::ReceiverProposal {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        proposal,
+        Some(ReceiverProposalResponse {
+            proposed_receiver: Addr::unchecked("investor_new"),
+            expires_at: Some(1_571_797_419u64 + 100),
+        })
+    );
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor_new"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimReceiver {
+                prev_receiver: "investor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Proposed receiver expired"
+    );
+
+    let proposal: Option<ReceiverProposalResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::ReceiverProposal {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(proposal, None);
+
+    // An expired proposal doesn't block withdrawals anymore
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.proposed_receiver, None);
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(500_000));
+}
+
+#[test]
+fn test_allocation_events() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 100u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.clone().to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-create_allocation")
+        .unwrap();
+    assert_eq!(
+        event.attributes[1..],
+        [
+            attr("account", "investor"),
+            attr("allocation_id", "0"),
+            attr("amount", "1000000"),
+            attr("start_time", "1571797419"),
+            attr("cliff", "100"),
+            attr("duration", "1000"),
+        ]
+    );
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .contains(&attr("remaining_astro_tokens", "1000000"))));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(400_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-decrease_allocation")
+        .unwrap();
+    assert!(event.attributes.contains(&attr("amount", "600000")));
+    assert!(res.events[1]
+        .attributes
+        .contains(&attr("unallocated_tokens", "400000")));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateUnlockSchedules {
+                new_unlock_schedules: vec![(
+                    "investor".to_string(),
+                    Schedule {
+                        start_time: 1_571_797_419u64,
+                        cliff: 100u64,
+                        duration: 2_000u64,
+                        unlock_period: None,
+                        percent_at_start: None,
+                    },
+                )],
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-update_unlock_schedule")
+        .unwrap();
+    assert!(event.attributes.contains(&attr("duration", "2000")));
+}
+
+#[test]
+fn test_withdraw_to_recipient() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw {
+                recipient: Some("treasury".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(res.events[1].attributes[2], attr("recipient", "treasury"));
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "treasury".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::new(500_000));
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::zero());
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(500_000));
+}
+
+#[test]
+fn test_validate_schedule_updates() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let schedule = Schedule {
+        start_time: 1_571_797_419u64,
+        cliff: 0u64,
+        duration: 1_000u64,
+        unlock_period: None,
+        percent_at_start: None,
+    };
+    let allocations: Vec<(String, AllocationParams)> = vec![
+        (
+            "investor_1".to_string(),
+            AllocationParams {
+                amount: Uint128::new(1_000_000),
+                unlock_schedule: schedule.clone(),
+                proposed_receiver: None,
+            },
+        ),
+        (
+            "investor_2".to_string(),
+            AllocationParams {
+                amount: Uint128::new(1_000_000),
+                unlock_schedule: schedule.clone(),
+                proposed_receiver: None,
+            },
+        ),
+    ];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(2_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let new_unlock_schedules = vec![
+        (
+            "investor_1".to_string(),
+            Schedule {
+                duration: 2_000u64,
+                ..schedule.clone()
+            },
+        ),
+        (
+            "investor_1".to_string(),
+            Schedule {
+                duration: 1_500u64,
+                ..schedule.clone()
+            },
+        ),
+        (
+            "investor_2".to_string(),
+            Schedule {
+                cliff: 100u64,
+                ..schedule.clone()
+            },
+        ),
+        ("investor_3".to_string(), schedule.clone()),
+    ];
+
+    let resp: Vec<ScheduleUpdateValidation> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::ValidateScheduleUpdates {
+                new_unlock_schedules: new_unlock_schedules.clone(),
+                allocation_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.len(), 4);
+    assert_eq!(
+        resp[0],
+        ScheduleUpdateValidation {
+            account: "investor_1".to_string(),
+            error: None,
+        }
+    );
+    // The second update is validated against the schedule set by the first one
+    assert_eq!(
+        resp[1].error,
+        Some("Generic error: The new duration value should be greater than or equal to the old one: 1500 >= 2000. Account error: investor_1".to_string())
+    );
+    assert_eq!(resp[2].error, None);
+    assert!(resp[3].error.is_some());
+
+    // The dry run matches the actual update
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateUnlockSchedules {
+                new_unlock_schedules,
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The new duration value should be greater than or equal to the old one: 1500 >= 2000. Account error: investor_1"
+    );
+
+    // Nothing is changed by the query
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor_1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.unlock_schedule, schedule);
+}
+
+#[test]
+fn test_cw_ownable_ownership() {
+    let mut app = mock_app();
+    let (unlock_instance, _, _) = init_contracts(&mut app);
+
+    let ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(Addr::unchecked(OWNER)));
+    assert_eq!(ownership.pending_owner, None);
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewOwner {
+            new_owner: "new_owner".to_string(),
+            expires_in: 100,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.pending_owner, Some(Addr::unchecked("new_owner")));
+    assert_eq!(
+        ownership.pending_expiry,
+        Some(Expiration::AtTime(app.block_info().time.plus_seconds(100)))
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_owner"),
+            unlock_instance.clone(),
+            &ExecuteMsg::DropOwnershipProposal {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::DropOwnershipProposal {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+        &[],
+    )
+    .unwrap_err();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateOwnership(Action::RenounceOwnership),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Contract ownership can't be renounced"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+            new_owner: "new_owner".to_string(),
+            expiry: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+        &[],
+    )
+    .unwrap();
+
+    let ownership: Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(Addr::unchecked("new_owner")));
+    assert_eq!(ownership.pending_owner, None);
+
+    // Owner checks follow the cw-ownable owner
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can pause the contract"
+    );
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Pause {},
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_stake_locked_astro() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    // Instantiate the ASTRO staking contract
+    let token_code_id = app.store_code(Box::new(ContractWrapper::new(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    )));
+    let staking_code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_staking::contract::execute,
+            astroport_staking::contract::instantiate,
+            astroport_staking::contract::query,
+        )
+        .with_reply_empty(astroport_staking::contract::reply),
+    ));
+    let staking_instance = app
+        .instantiate_contract(
+            staking_code_id,
+            Addr::unchecked(OWNER),
+            &StakingInstantiateMsg {
+                owner: OWNER.to_string(),
+                token_code_id,
+                deposit_token_addr: astro_instance.to_string(),
+                marketing: None,
+            },
+            &[],
+            "xASTRO",
+            None,
+        )
+        .unwrap();
+    let staking_config: StakingConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&staking_instance, &StakingQueryMsg::Config {})
+        .unwrap();
+    let xastro_instance = staking_config.share_token_addr;
+
+    // Someone else stakes first so that the xASTRO price is exactly 1 ASTRO
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&StakingHookMsg::Enter {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.clone().to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::StakeAstro {
+                amount: Uint128::new(800_000),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: ASTRO staking contract is not set"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_owner"),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateStakingConfig {
+                astro_staking: Some(staking_instance.to_string()),
+                yield_recipient: Some("treasury".to_string()),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can change config"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateStakingConfig {
+            astro_staking: Some(staking_instance.to_string()),
+            yield_recipient: Some("treasury".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::StakeAstro {
+                amount: Uint128::new(1_000_001),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Amount to stake must be between 1 and 1000000 ASTRO"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::StakeAstro {
+            amount: Uint128::new(800_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The staking contract receives rewards, so xASTRO is worth 1.1 ASTRO
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: staking_instance.to_string(),
+            amount: Uint128::new(180_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: StakingStateResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::StakingState {})
+        .unwrap();
+    assert_eq!(
+        resp,
+        StakingStateResponse {
+            staked_astro: Uint128::new(800_000),
+            xastro_amount: Uint128::new(800_000),
+            astro_value: Uint128::new(880_000),
+            pending_yield: Uint128::new(80_000),
+        }
+    );
+
+    // The yield is sent to the recipient chosen by governance
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimStakingYield {},
+        &[],
+    )
+    .unwrap();
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xastro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "treasury".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::new(72_727));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimStakingYield {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: No staking yield to claim"
+    );
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    // Withdrawals still honor the unlock schedule and unstake ASTRO when needed
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::new(500_000));
+
+    let resp: StakingStateResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::StakingState {})
+        .unwrap();
+    assert_eq!(resp.staked_astro, Uint128::new(500_000));
+    assert_eq!(resp.xastro_amount, Uint128::new(454_545));
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: staking_instance.to_string(),
+            amount: Uint128::new(10_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The staking contract can't be changed while ASTRO is staked
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateStakingConfig {
+                astro_staking: Some("new_staking".to_string()),
+                yield_recipient: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Unstake all ASTRO before changing the staking contract"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UnstakeAstro { amount: None },
+        &[],
+    )
+    .unwrap();
+
+    let resp: StakingStateResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::StakingState {})
+        .unwrap();
+    assert_eq!(resp.staked_astro, Uint128::zero());
+    assert_eq!(resp.pending_yield, resp.astro_value);
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::new(1_000_000));
+}
+
+#[test]
+fn test_allocation_caps() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_owner"),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+                new_decrease_notice_period: None,
+                new_max_allocation_per_account: Some(Uint128::new(1_000_000)),
+                new_max_total_accounts: Some(2),
+                new_voting_escrow: None,
+                new_allocation_hooks: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can change config"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_decrease_notice_period: None,
+            new_max_allocation_per_account: Some(Uint128::new(1_000_000)),
+            new_max_total_accounts: Some(2),
+            new_voting_escrow: None,
+            new_allocation_hooks: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.max_allocation_per_account,
+        Some(Uint128::new(1_000_000))
+    );
+    assert_eq!(config.max_total_accounts, Some(2));
+
+    let allocation = |amount: u128| AllocationParams {
+        amount: Uint128::new(amount),
+        unlock_schedule: Schedule {
+            start_time: 1_571_797_419u64,
+            cliff: 0u64,
+            duration: 1_000u64,
+            unlock_period: None,
+            percent_at_start: None,
+        },
+        proposed_receiver: None,
+    };
+
+    // Two allocations of the same account can't exceed the per-account cap
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.to_string(),
+                amount: Uint128::new(1_500_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations {
+                    allocations: vec![
+                        ("investor_1".to_string(), allocation(1_000_000)),
+                        ("investor_1".to_string(), allocation(500_000)),
+                    ],
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allocations of investor_1 can't exceed 1000000 ASTRO"
+    );
+
+    // Three new accounts exceed the accounts cap
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.to_string(),
+                amount: Uint128::new(3_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations {
+                    allocations: vec![
+                        ("investor_1".to_string(), allocation(1_000)),
+                        ("investor_2".to_string(), allocation(1_000)),
+                        ("investor_3".to_string(), allocation(1_000)),
+                    ],
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The number of accounts with allocations can't exceed 2"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(1_500_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations {
+                allocations: vec![
+                    ("investor_1".to_string(), allocation(500_000)),
+                    ("investor_2".to_string(), allocation(500_000)),
+                    ("investor_1".to_string(), allocation(500_000)),
+                ],
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Existing accounts can still receive new allocations
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(400_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations {
+                allocations: vec![("investor_2".to_string(), allocation(400_000))],
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Increasing an allocation is capped as well
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.to_string(),
+                amount: Uint128::new(100_001),
+                msg: to_binary(&ReceiveMsg::IncreaseAllocation {
+                    amount: Uint128::new(100_001),
+                    user: "investor_2".to_string(),
+                    allocation_id: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allocations of investor_2 can't exceed 1000000 ASTRO"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(100_000),
+            msg: to_binary(&ReceiveMsg::IncreaseAllocation {
+                amount: Uint128::new(100_000),
+                user: "investor_2".to_string(),
+                allocation_id: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor_2".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.amount, Uint128::new(600_000));
+}
+
+#[test]
+fn test_solvency() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let resp: SolvencyResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Solvency {})
+        .unwrap();
+    assert_eq!(
+        resp,
+        SolvencyResponse {
+            astro_balance: Uint128::new(500_000),
+            total_withdrawn: Uint128::new(500_000),
+            expected_balance: Uint128::new(500_000),
+            owed_balance: Uint128::new(500_000),
+            surplus: Uint128::zero(),
+            deficit: Uint128::zero(),
+            accounting_drift: Uint128::zero(),
+        }
+    );
+
+    // ASTRO sent directly to the contract shows up as surplus
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: unlock_instance.to_string(),
+            amount: Uint128::new(1_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: SolvencyResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Solvency {})
+        .unwrap();
+    assert_eq!(resp.astro_balance, Uint128::new(501_000));
+    assert_eq!(resp.surplus, Uint128::new(1_000));
+    assert_eq!(resp.deficit, Uint128::zero());
+    assert_eq!(resp.accounting_drift, Uint128::zero());
+}
+
+#[test]
+fn test_withdraw_and_lock() {
+    let mut app = mock_app();
+    app.update_block(|bi| bi.time = Timestamp::from_seconds(EPOCH_START));
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    // Instantiate the ASTRO staking contract
+    let token_code_id = app.store_code(Box::new(ContractWrapper::new(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    )));
+    let staking_code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_staking::contract::execute,
+            astroport_staking::contract::instantiate,
+            astroport_staking::contract::query,
+        )
+        .with_reply_empty(astroport_staking::contract::reply),
+    ));
+    let staking_instance = app
+        .instantiate_contract(
+            staking_code_id,
+            Addr::unchecked(OWNER),
+            &StakingInstantiateMsg {
+                owner: OWNER.to_string(),
+                token_code_id,
+                deposit_token_addr: astro_instance.to_string(),
+                marketing: None,
+            },
+            &[],
+            "xASTRO",
+            None,
+        )
+        .unwrap();
+    let staking_config: StakingConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&staking_instance, &StakingQueryMsg::Config {})
+        .unwrap();
+    let xastro_instance = staking_config.share_token_addr;
+
+    // Someone else stakes first so that the xASTRO price is exactly 1 ASTRO
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&StakingHookMsg::Enter {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Instantiate the vxASTRO contract
+    let voting_escrow_code_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            voting_escrow::contract::execute,
+            voting_escrow::contract::instantiate,
+            voting_escrow::contract::query,
+        )
+        .with_reply_empty(voting_escrow::contract::reply),
+    ));
+    let voting_escrow_instance = app
+        .instantiate_contract(
+            voting_escrow_code_id,
+            Addr::unchecked(OWNER),
+            &VotingEscrowInstantiateMsg {
+                owner: OWNER.to_string(),
+                guardian_addr: None,
+                deposit_token_addr: xastro_instance.to_string(),
+                marketing: None,
+                logo_urls_whitelist: vec![],
+            },
+            &[],
+            "vxASTRO",
+            None,
+        )
+        .unwrap();
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: EPOCH_START,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::WithdrawAndLock { lock_time: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: ASTRO staking contract is not set"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateStakingConfig {
+            astro_staking: Some(staking_instance.to_string()),
+            yield_recipient: Some("treasury".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::WithdrawAndLock { lock_time: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Voting escrow contract is not set"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_decrease_notice_period: None,
+            new_max_allocation_per_account: None,
+            new_max_total_accounts: None,
+            new_voting_escrow: Some(voting_escrow_instance.to_string()),
+            new_allocation_hooks: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::WithdrawAndLock { lock_time: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: investor doesn't have a vxASTRO lock"
+    );
+
+    // The investor creates a vxASTRO lock
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        xastro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: "investor".to_string(),
+            amount: Uint128::new(1_000),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        xastro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: voting_escrow_instance.to_string(),
+            amount: Uint128::new(1_000),
+            msg: to_binary(&VotingEscrowHookMsg::CreateLock { time: 2 * WEEK }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::WithdrawAndLock {
+                lock_time: Some(4 * WEEK),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: vxASTRO lock of investor expires too early. Extend the lock first"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::WithdrawAndLock { lock_time: None },
+        &[],
+    )
+    .unwrap();
+
+    let lock_info: LockInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_escrow_instance,
+            &VotingEscrowQueryMsg::LockInfo {
+                user: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lock_info.amount, Uint128::new(501_000));
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xastro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: unlock_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::zero());
+
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.status.astro_withdrawn, Uint128::new(500_000));
+}
+
+#[test]
+fn test_pause() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations {
+                allocations: allocations.clone(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only the contract owner can pause the contract"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::Pause {},
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract is already paused"
+    );
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert!(resp.paused);
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw { recipient: None },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract is paused"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("investor"),
+            unlock_instance.clone(),
+            &ExecuteMsg::ProposeNewReceiver {
+                new_receiver: "new_receiver".to_string(),
+                expires_in: 86400,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract is paused"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract is paused"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: "investor".to_string(),
+                amount: Uint128::new(1_000),
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract is paused"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::Unpause {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw { recipient: None },
+        &[],
+    )
+    .unwrap();
+
+    let bal_resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bal_resp.balance, Uint128::new(500_000));
+}
+
+const RECEIVED_HOOKS: Item<Vec<AllocationHookMsg>> = Item::new("received_hooks");
+
+fn hook_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    RECEIVED_HOOKS.save(deps.storage, &vec![])?;
+    Ok(Response::new())
+}
+
+fn hook_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: AllocationHookExecuteMsg,
+) -> StdResult<Response> {
+    let AllocationHookExecuteMsg::AllocationHook(hook) = msg;
+    RECEIVED_HOOKS.update(deps.storage, |mut hooks| -> StdResult<_> {
+        hooks.push(hook);
+        Ok(hooks)
+    })?;
+    Ok(Response::new())
+}
+
+fn hook_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&RECEIVED_HOOKS.load(deps.storage)?)
+}
+
+#[test]
+fn test_allocation_hooks() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    let hook_code_id = app.store_code(Box::new(ContractWrapper::new(
+        hook_execute,
+        hook_instantiate,
+        hook_query,
+    )));
+    let hook_instance = app
+        .instantiate_contract(
+            hook_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "hook",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_decrease_notice_period: None,
+            new_max_allocation_per_account: None,
+            new_max_total_accounts: None,
+            new_voting_escrow: None,
+            new_allocation_hooks: Some(vec![hook_instance.to_string()]),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: unlock_instance.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::DecreaseAllocation {
+            receiver: "investor".to_string(),
+            amount: Uint128::new(100_000),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::IncreaseAllocation {
+            receiver: "investor".to_string(),
+            amount: Uint128::new(50_000),
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "new_investor".to_string(),
+            expires_in: 86400,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("new_investor"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimReceiver {
+            prev_receiver: "investor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let hooks: Vec<AllocationHookMsg> = app
+        .wrap()
+        .query_wasm_smart(&hook_instance, &Empty {})
+        .unwrap();
+    assert_eq!(
+        hooks,
+        vec![
+            AllocationHookMsg {
+                change: AllocationChange::Create,
+                account: "investor".to_string(),
+                allocation_id: 0,
+                amount: Uint128::new(1_000_000),
+            },
+            AllocationHookMsg {
+                change: AllocationChange::Decrease,
+                account: "investor".to_string(),
+                allocation_id: 0,
+                amount: Uint128::new(900_000),
+            },
+            AllocationHookMsg {
+                change: AllocationChange::Increase,
+                account: "investor".to_string(),
+                allocation_id: 0,
+                amount: Uint128::new(950_000),
+            },
+            AllocationHookMsg {
+                change: AllocationChange::Transfer {
+                    prev_account: "investor".to_string()
+                },
+                account: "new_investor".to_string(),
+                allocation_id: 0,
+                amount: Uint128::new(950_000),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_failing_allocation_hook() {
+    let mut app = mock_app();
+    let (unlock_instance, astro_instance, _) = init_contracts(&mut app);
+
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(OWNER.clone()),
+        astro_instance.clone(),
+        Uint128::new(1_000_000_000_000_000),
+        OWNER.to_string(),
+    );
+
+    // The ASTRO token doesn't handle allocation hooks
+    app.execute_contract(
+        Addr::unchecked(OWNER.clone()),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_decrease_notice_period: None,
+            new_max_allocation_per_account: None,
+            new_max_total_accounts: None,
+            new_voting_escrow: None,
+            new_allocation_hooks: Some(vec![astro_instance.to_string()]),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        "investor".to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked(OWNER.clone()),
+            astro_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: unlock_instance.to_string(),
+                amount: Uint128::new(1_000_000),
+                msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value == "allocation_hook_failed")));
+
+    // The failing hook doesn't block the allocation change
+    let resp: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "investor".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.params.amount, Uint128::new(1_000_000));
+}
+
 fn check_allocation(
     app: &mut App,
     unlock_instance: &Addr,
@@ -0,0 +1,453 @@
+use astroport_governance::astroport::common::{
+    claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport_governance::builder_unlock::msg::{
+    AllocationResponse, ExecuteMsg as BuilderUnlockExecuteMsg, QueryMsg as BuilderUnlockQueryMsg,
+};
+use astroport_governance::builder_unlock::Config as BuilderUnlockConfig;
+use astroport_governance::builder_unlock_nft::{
+    Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, QuerierWrapper, Reply,
+    Response, StdError, StdResult, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw721::{ContractInfoResponse, Cw721Execute, Cw721Query};
+use cw721_base::state::Cw721Contract;
+use cw721_base::MintMsg;
+
+use crate::error::ContractError;
+use crate::state::{
+    WithdrawContext, CLAIMABLE, CONFIG, OWNERSHIP_PROPOSAL, WITHDRAW_CONTEXT, WRAP_CONTEXT,
+};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "builder-unlock-nft";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Reply ID of the builder unlock split claim sent in [`wrap`]
+const WRAP_REPLY_ID: u64 = 1;
+/// Reply ID of the builder unlock withdrawal sent in [`withdraw`]
+const WITHDRAW_REPLY_ID: u64 = 2;
+
+/// Wrapped allocations are stored as cw721 tokens without extension.
+type WrapperContract<'a> = Cw721Contract<'a, Empty, Empty, Empty, Empty>;
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let builder_unlock = deps.api.addr_validate(&msg.builder_unlock)?;
+    let unlock_config: BuilderUnlockConfig = deps
+        .querier
+        .query_wasm_smart(&builder_unlock, &BuilderUnlockQueryMsg::Config {})?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_validate(&msg.owner)?,
+            builder_unlock,
+            astro_token: unlock_config.astro_token,
+            transfers_enabled: false,
+        },
+    )?;
+
+    let tract = WrapperContract::default();
+    tract.contract_info.save(
+        deps.storage,
+        &ContractInfoResponse {
+            name: msg.name,
+            symbol: msg.symbol,
+        },
+    )?;
+    // Only the contract itself mints wrapped allocations
+    tract.minter.save(deps.storage, &env.contract.address)?;
+
+    Ok(Response::default())
+}
+
+/// Exposes execute functions available in the contract.
+///
+/// ## Variants
+/// * **ExecuteMsg::Wrap { allocation_id }** Wrap a builder unlock allocation into an NFT.
+///
+/// * **ExecuteMsg::Withdraw { token_id }** Withdraw unlocked ASTRO of a wrapped allocation.
+///
+/// * **ExecuteMsg::TransferNft { recipient, token_id }** Transfer a wrapped allocation.
+///
+/// * **ExecuteMsg::SendNft { contract, token_id, msg }** Send a wrapped allocation to a contract.
+///
+/// * **ExecuteMsg::UpdateConfig { transfers_enabled }** Update contract parameters.
+///
+/// * **ExecuteMsg::ProposeNewOwner { new_owner, expires_in }** Creates a new request to change
+/// contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Wrap { allocation_id } => wrap(deps, info, allocation_id),
+        ExecuteMsg::Withdraw { token_id } => withdraw(deps, env, info, token_id),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => {
+            ensure_transfers_enabled(deps.as_ref())?;
+            WrapperContract::default()
+                .transfer_nft(deps, env, info, recipient, token_id)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => {
+            ensure_transfers_enabled(deps.as_ref())?;
+            WrapperContract::default()
+                .send_nft(deps, env, info, contract, token_id, msg)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::UpdateConfig { transfers_enabled } => {
+            update_config(deps, info, transfers_enabled)
+        }
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        }
+    }
+}
+
+/// Claims a split of the sender's builder unlock allocation proposed to this contract.
+/// The claimed allocation is minted as an NFT to the sender in [`reply`].
+///
+/// * **allocation_id** ID of the sender's allocation in the builder unlock contract.
+fn wrap(
+    deps: DepsMut,
+    info: MessageInfo,
+    allocation_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    WRAP_CONTEXT.save(deps.storage, &info.sender)?;
+
+    let claim_msg = WasmMsg::Execute {
+        contract_addr: config.builder_unlock.to_string(),
+        msg: to_binary(&BuilderUnlockExecuteMsg::ClaimSplitReceiver {
+            prev_receiver: info.sender.to_string(),
+            allocation_id,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(claim_msg, WRAP_REPLY_ID))
+        .add_attributes(vec![
+            attr("action", "wrap"),
+            attr("builder", info.sender),
+            attr(
+                "allocation_id",
+                allocation_id.unwrap_or_default().to_string(),
+            ),
+        ]))
+}
+
+/// Withdraws unlocked ASTRO of all wrapped allocations from the builder unlock contract.
+/// Withdrawn ASTRO is credited to the respective NFTs and the credit of the requested NFT
+/// is paid out to its owner in [`reply`].
+///
+/// * **token_id** wrapped allocation to withdraw ASTRO for.
+fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let token = WrapperContract::default()
+        .tokens
+        .load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let withdrawn = query_wrapped_allocations(deps.querier, &config, &env.contract.address)?
+        .into_iter()
+        .map(|(id, allocation)| (id, allocation.status.astro_withdrawn))
+        .collect();
+    WITHDRAW_CONTEXT.save(
+        deps.storage,
+        &WithdrawContext {
+            token_id: token_id.clone(),
+            withdrawn,
+        },
+    )?;
+
+    let withdraw_msg = WasmMsg::Execute {
+        contract_addr: config.builder_unlock.to_string(),
+        msg: to_binary(&BuilderUnlockExecuteMsg::Withdraw { recipient: None })?,
+        funds: vec![],
+    };
+
+    // The withdrawal fails if nothing is unlocked, yet ASTRO credited by previous withdrawals
+    // can still be paid out
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(withdraw_msg, WITHDRAW_REPLY_ID))
+        .add_attributes(vec![attr("action", "withdraw"), attr("token_id", token_id)]))
+}
+
+/// Updates contract parameters. Only the owner can execute this method.
+///
+/// * **transfers_enabled** whether secondary transfers of wrapped allocations are allowed.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    transfers_enabled: Option<bool>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(transfers_enabled) = transfers_enabled {
+        config.transfers_enabled = transfers_enabled;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_config"),
+        attr("transfers_enabled", config.transfers_enabled.to_string()),
+    ]))
+}
+
+/// Mints wrapped allocations after a split claim and pays out withdrawn ASTRO after a withdrawal.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let tract = WrapperContract::default();
+
+    match msg.id {
+        WRAP_REPLY_ID => {
+            let builder = WRAP_CONTEXT.load(deps.storage)?;
+            WRAP_CONTEXT.remove(deps.storage);
+
+            // The claimed split is the only allocation of this contract without a token
+            let mut allocation_id = None;
+            for (id, _) in query_wrapped_allocations(deps.querier, &config, &env.contract.address)?
+            {
+                if tract
+                    .tokens
+                    .may_load(deps.storage, &id.to_string())?
+                    .is_none()
+                {
+                    allocation_id = Some(id);
+                    break;
+                }
+            }
+            let allocation_id = allocation_id.ok_or(ContractError::NothingWrapped {})?;
+
+            let minter = MessageInfo {
+                sender: env.contract.address.clone(),
+                funds: vec![],
+            };
+            tract.mint(
+                deps,
+                env,
+                minter,
+                MintMsg {
+                    token_id: allocation_id.to_string(),
+                    owner: builder.to_string(),
+                    token_uri: None,
+                    extension: Empty {},
+                },
+            )?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "mint_wrapped_allocation"),
+                attr("owner", builder),
+                attr("token_id", allocation_id.to_string()),
+            ]))
+        }
+        WITHDRAW_REPLY_ID => {
+            let context = WITHDRAW_CONTEXT.load(deps.storage)?;
+            WITHDRAW_CONTEXT.remove(deps.storage);
+
+            if msg.result.is_ok() {
+                for (id, allocation) in
+                    query_wrapped_allocations(deps.querier, &config, &env.contract.address)?
+                {
+                    let withdrawn_before = context
+                        .withdrawn
+                        .iter()
+                        .find(|(prev_id, _)| *prev_id == id)
+                        .map(|(_, amount)| *amount)
+                        .unwrap_or_default();
+                    let withdrawn = allocation
+                        .status
+                        .astro_withdrawn
+                        .saturating_sub(withdrawn_before);
+
+                    if !withdrawn.is_zero() {
+                        CLAIMABLE.update::<_, StdError>(
+                            deps.storage,
+                            &id.to_string(),
+                            |amount| Ok(amount.unwrap_or_default() + withdrawn),
+                        )?;
+                    }
+                }
+            }
+
+            let amount = CLAIMABLE
+                .may_load(deps.storage, &context.token_id)?
+                .unwrap_or_default();
+            if amount.is_zero() {
+                return Err(ContractError::NothingToWithdraw {});
+            }
+            CLAIMABLE.remove(deps.storage, &context.token_id);
+
+            let owner = tract.tokens.load(deps.storage, &context.token_id)?.owner;
+
+            Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: config.astro_token.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: owner.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                })
+                .add_attributes(vec![
+                    attr("action", "pay_out_withdrawn"),
+                    attr("token_id", context.token_id),
+                    attr("recipient", owner),
+                    attr("astro_withdrawn", amount),
+                ]))
+        }
+        _ => Err(StdError::generic_err(format!("Unknown reply ID: {}", msg.id)).into()),
+    }
+}
+
+/// Exposes all the queries available in the contract.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::Allocation { token_id }** Returns a wrapped allocation.
+///
+/// * **QueryMsg::Claimable { token_id }** Returns the amount of ASTRO credited to a wrapped allocation.
+///
+/// * **QueryMsg::OwnerOf { token_id }** Returns the owner of a wrapped allocation.
+///
+/// * **QueryMsg::Tokens { owner, start_after, limit }** Returns wrapped allocations of an account.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let tract = WrapperContract::default();
+
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Allocation { token_id } => to_binary(&query_allocation(deps, env, token_id)?),
+        QueryMsg::Claimable { token_id } => to_binary(
+            &CLAIMABLE
+                .may_load(deps.storage, &token_id)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::OwnerOf { token_id } => {
+            to_binary(&tract.tokens.load(deps.storage, &token_id)?.owner)
+        }
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&tract.tokens(deps, owner, start_after, limit)?.tokens),
+    }
+}
+
+/// Returns the parameters and current status of a wrapped allocation.
+///
+/// * **token_id** wrapped allocation to query.
+fn query_allocation(deps: Deps, env: Env, token_id: String) -> StdResult<AllocationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    query_wrapped_allocations(deps.querier, &config, &env.contract.address)?
+        .into_iter()
+        .find(|(id, _)| id.to_string() == token_id)
+        .map(|(_, allocation)| allocation)
+        .ok_or_else(|| StdError::generic_err(format!("Allocation {token_id} is not wrapped")))
+}
+
+/// Returns an error if secondary transfers of wrapped allocations are disabled.
+fn ensure_transfers_enabled(deps: Deps) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.transfers_enabled {
+        Ok(())
+    } else {
+        Err(ContractError::TransfersDisabled {})
+    }
+}
+
+/// Returns all allocations held by this contract in the builder unlock contract.
+fn query_wrapped_allocations(
+    querier: QuerierWrapper,
+    config: &Config,
+    contract_addr: &Addr,
+) -> StdResult<Vec<(u64, AllocationResponse)>> {
+    querier.query_wasm_smart(
+        &config.builder_unlock,
+        &BuilderUnlockQueryMsg::AccountAllocations {
+            account: contract_addr.to_string(),
+        },
+    )
+}
+
+/// Used for contract migration. Returns a default object of type [`Response`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Response::default())
+}
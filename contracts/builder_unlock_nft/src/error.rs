@@ -0,0 +1,28 @@
+use astroport_governance::errors::CommonError;
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// This enum describes builder unlock NFT contract errors
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("{0}")]
+    Cw721(#[from] cw721_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Transfers of wrapped allocations are disabled")]
+    TransfersDisabled {},
+
+    #[error("No allocation was wrapped")]
+    NothingWrapped {},
+
+    #[error("No unlocked ASTRO to be withdrawn")]
+    NothingToWithdraw {},
+}
@@ -0,0 +1,30 @@
+use astroport_governance::astroport::common::OwnershipProposal;
+use astroport_governance::builder_unlock_nft::Config;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+
+/// This structure stores the withdrawal context between [`crate::contract::execute`] and [`crate::contract::reply`].
+#[cw_serde]
+pub struct WithdrawContext {
+    /// Wrapped allocation the withdrawal was requested for
+    pub token_id: String,
+    /// Amount of ASTRO withdrawn from every wrapped allocation before the withdrawal
+    pub withdrawn: Vec<(u64, Uint128)>,
+}
+
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Account which is wrapping an allocation in the current transaction
+pub const WRAP_CONTEXT: Item<Addr> = Item::new("wrap_context");
+
+/// Withdrawal which is processed in the current transaction
+pub const WITHDRAW_CONTEXT: Item<WithdrawContext> = Item::new("withdraw_context");
+
+/// ASTRO withdrawn from the builder unlock contract and not yet paid out to NFT owners.
+/// The key is a token ID
+pub const CLAIMABLE: Map<&str, Uint128> = Map::new("claimable");
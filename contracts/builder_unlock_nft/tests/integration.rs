@@ -0,0 +1,380 @@
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use astroport_governance::builder_unlock::msg::{
+    AllocationResponse, ExecuteMsg as UnlockExecuteMsg, InstantiateMsg as UnlockInstantiateMsg,
+    QueryMsg as UnlockQueryMsg, ReceiveMsg,
+};
+use astroport_governance::builder_unlock::{AllocationParams, Schedule};
+use astroport_governance::builder_unlock_nft::{Config, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_std::{to_binary, Addr, Uint128};
+use cw20::BalanceResponse;
+use cw_multi_test::{App, BasicApp, ContractWrapper, Executor};
+
+const OWNER: &str = "owner";
+const BUILDER: &str = "builder";
+
+fn mock_app() -> App {
+    BasicApp::default()
+}
+
+/// Returns the ASTRO token, the builder unlock contract and the NFT wrapper addresses.
+fn init_contracts(app: &mut App) -> (Addr, Addr, Addr) {
+    let astro_token_code_id = app.store_code(Box::new(ContractWrapper::new(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    )));
+
+    let astro_token = app
+        .instantiate_contract(
+            astro_token_code_id,
+            Addr::unchecked(OWNER),
+            &TokenInstantiateMsg {
+                name: String::from("Astro token"),
+                symbol: String::from("ASTRO"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: OWNER.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("ASTRO"),
+            None,
+        )
+        .unwrap();
+
+    let unlock_code_id = app.store_code(Box::new(ContractWrapper::new(
+        builder_unlock::contract::execute,
+        builder_unlock::contract::instantiate,
+        builder_unlock::contract::query,
+    )));
+
+    let builder_unlock = app
+        .instantiate_contract(
+            unlock_code_id,
+            Addr::unchecked(OWNER),
+            &UnlockInstantiateMsg {
+                owner: OWNER.to_string(),
+                astro_token: astro_token.to_string(),
+                max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+                decrease_notice_period: None,
+            },
+            &[],
+            "unlock",
+            None,
+        )
+        .unwrap();
+
+    let wrapper_code_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            builder_unlock_nft::contract::execute,
+            builder_unlock_nft::contract::instantiate,
+            builder_unlock_nft::contract::query,
+        )
+        .with_reply(builder_unlock_nft::contract::reply),
+    ));
+
+    let wrapper = app
+        .instantiate_contract(
+            wrapper_code_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                owner: OWNER.to_string(),
+                builder_unlock: builder_unlock.to_string(),
+                name: "Builder unlock allocations".to_string(),
+                symbol: "BUA".to_string(),
+            },
+            &[],
+            "wrapper",
+            None,
+        )
+        .unwrap();
+
+    // Create an allocation for the builder
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_token.clone(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: OWNER.to_string(),
+            amount: Uint128::new(1_000_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let allocations: Vec<(String, AllocationParams)> = vec![(
+        BUILDER.to_string(),
+        AllocationParams {
+            amount: Uint128::new(1_000_000),
+            unlock_schedule: Schedule {
+                start_time: 1_571_797_419u64,
+                cliff: 0u64,
+                duration: 1_000u64,
+                unlock_period: None,
+                percent_at_start: None,
+            },
+            proposed_receiver: None,
+        },
+    )];
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        astro_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: builder_unlock.to_string(),
+            amount: Uint128::new(1_000_000),
+            msg: to_binary(&ReceiveMsg::CreateAllocations { allocations }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    (astro_token, builder_unlock, wrapper)
+}
+
+fn check_astro_balance(app: &mut App, astro_token: &Addr, account: &str, amount: u128) {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            astro_token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: account.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(amount));
+}
+
+#[test]
+fn proper_initialization() {
+    let mut app = mock_app();
+    let (astro_token, builder_unlock, wrapper) = init_contracts(&mut app);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&wrapper, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            owner: Addr::unchecked(OWNER),
+            builder_unlock,
+            astro_token,
+            transfers_enabled: false,
+        }
+    );
+}
+
+#[test]
+fn wrap_transfer_and_withdraw() {
+    let mut app = mock_app();
+    let (astro_token, builder_unlock, wrapper) = init_contracts(&mut app);
+
+    // Nothing to wrap without a split proposal
+    let err = app
+        .execute_contract(
+            Addr::unchecked(BUILDER),
+            wrapper.clone(),
+            &ExecuteMsg::Wrap {
+                allocation_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allocation split is not proposed"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(BUILDER),
+        builder_unlock.clone(),
+        &UnlockExecuteMsg::ProposeSplitReceivers {
+            receivers: vec![
+                (wrapper.to_string(), Uint128::new(400_000)),
+                ("alice".to_string(), Uint128::new(600_000)),
+            ],
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(BUILDER),
+        wrapper.clone(),
+        &ExecuteMsg::Wrap {
+            allocation_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let owner: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            &wrapper,
+            &QueryMsg::OwnerOf {
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(owner, Addr::unchecked(BUILDER));
+
+    let tokens: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(
+            &wrapper,
+            &QueryMsg::Tokens {
+                owner: BUILDER.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(tokens, vec!["0".to_string()]);
+
+    let allocation: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &wrapper,
+            &QueryMsg::Allocation {
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(allocation.params.amount, Uint128::new(400_000));
+
+    // The unlock contract still enforces the schedule for the wrapper
+    let allocation: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &builder_unlock,
+            &UnlockQueryMsg::Allocation {
+                account: wrapper.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(allocation.params.amount, Uint128::new(400_000));
+
+    // Transfers are disabled by default
+    let err = app
+        .execute_contract(
+            Addr::unchecked(BUILDER),
+            wrapper.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Transfers of wrapped allocations are disabled"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(BUILDER),
+            wrapper.clone(),
+            &ExecuteMsg::UpdateConfig {
+                transfers_enabled: Some(true),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        wrapper.clone(),
+        &ExecuteMsg::UpdateConfig {
+            transfers_enabled: Some(true),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(BUILDER),
+        wrapper.clone(),
+        &ExecuteMsg::TransferNft {
+            recipient: "buyer".to_string(),
+            token_id: "0".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|bi| {
+        bi.height += 100;
+        bi.time = bi.time.plus_seconds(500);
+    });
+
+    // Only the NFT owner can withdraw
+    let err = app
+        .execute_contract(
+            Addr::unchecked(BUILDER),
+            wrapper.clone(),
+            &ExecuteMsg::Withdraw {
+                token_id: "0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        Addr::unchecked("buyer"),
+        wrapper.clone(),
+        &ExecuteMsg::Withdraw {
+            token_id: "0".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    check_astro_balance(&mut app, &astro_token, "buyer", 200_000);
+    check_astro_balance(&mut app, &astro_token, wrapper.as_str(), 0);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("buyer"),
+            wrapper.clone(),
+            &ExecuteMsg::Withdraw {
+                token_id: "0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "No unlocked ASTRO to be withdrawn"
+    );
+
+    // Clawback rights are still enforced by the unlock contract
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        builder_unlock.clone(),
+        &UnlockExecuteMsg::DecreaseAllocation {
+            receiver: wrapper.to_string(),
+            amount: Uint128::new(100_000),
+            allocation_id: Some(0),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let allocation: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &wrapper,
+            &QueryMsg::Allocation {
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(allocation.params.amount, Uint128::new(300_000));
+}
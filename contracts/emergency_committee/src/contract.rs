@@ -0,0 +1,334 @@
+use astroport_governance::assembly::ExecuteMsg as AssemblyExecuteMsg;
+use astroport_governance::emergency_committee::{
+    CommitteeProposal, CommitteeProposalStatus, Config, EmergencyAction, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use astroport_governance::generator_controller::ExecuteMsg as ControllerExecuteMsg;
+use astroport_governance::pagination::paginate_map;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::state::{CONFIG, PROPOSALS, PROPOSAL_COUNT};
+
+// Version info for contract migration.
+const CONTRACT_NAME: &str = "emergency-committee";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut members = validate_addresses(deps.api, &msg.members)?;
+    members.sort();
+
+    let config = Config {
+        members,
+        threshold: msg.threshold,
+        voting_period: msg.voting_period,
+        generator_controller: deps.api.addr_validate(&msg.generator_controller)?,
+        assembly: deps.api.addr_validate(&msg.assembly)?,
+    };
+    validate_members(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::Propose { action }** Proposes an emergency action.
+///
+/// * **ExecuteMsg::Approve { proposal_id }** Approves a proposed action.
+///
+/// * **ExecuteMsg::Execute { proposal_id }** Executes an action with enough approvals.
+///
+/// * **ExecuteMsg::UpdateMembers { add, remove, threshold }** Rotates committee members.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match msg {
+        ExecuteMsg::Propose { action } => {
+            assert_member(&config, &info.sender)?;
+            propose(deps, env, info, config, action)
+        }
+        ExecuteMsg::Approve { proposal_id } => {
+            assert_member(&config, &info.sender)?;
+            approve(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::Execute { proposal_id } => {
+            assert_member(&config, &info.sender)?;
+            execute_action(deps, env, config, proposal_id)
+        }
+        ExecuteMsg::UpdateMembers {
+            add,
+            remove,
+            threshold,
+        } => update_members(deps, info, config, add, remove, threshold),
+    }
+}
+
+/// Checks that the sender is a committee member.
+fn assert_member(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    if !config.members.contains(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+/// Checks that the committee has members, no duplicates and a reachable threshold.
+fn validate_members(config: &Config) -> Result<(), ContractError> {
+    let mut members = config.members.clone();
+    members.sort();
+    members.dedup();
+
+    if members.is_empty() || members.len() != config.members.len() {
+        return Err(ContractError::InvalidMembers {});
+    }
+
+    if config.threshold == 0 || config.threshold as usize > members.len() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+
+    Ok(())
+}
+
+/// Adds and removes committee members and updates the threshold. Approvals of removed members
+/// stop counting towards open proposals.
+///
+/// * **add** members to add.
+///
+/// * **remove** members to remove.
+///
+/// * **threshold** the new number of approvals required to execute an action.
+fn update_members(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut config: Config,
+    add: Vec<String>,
+    remove: Vec<String>,
+    threshold: Option<u32>,
+) -> Result<Response, ContractError> {
+    if info.sender != config.assembly {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let remove = validate_addresses(deps.api, &remove)?;
+    config.members.retain(|member| !remove.contains(member));
+    config.members.extend(validate_addresses(deps.api, &add)?);
+    config.members.sort();
+
+    if let Some(threshold) = threshold {
+        config.threshold = threshold;
+    }
+
+    validate_members(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_members")
+        .add_attribute("threshold", config.threshold.to_string()))
+}
+
+/// Validates a list of addresses.
+fn validate_addresses(api: &dyn Api, addresses: &[String]) -> StdResult<Vec<Addr>> {
+    addresses
+        .iter()
+        .map(|address| api.addr_validate(address))
+        .collect()
+}
+
+/// Proposes an emergency action and approves it on behalf of the proposer.
+///
+/// * **action** the proposed action.
+fn propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: Config,
+    action: EmergencyAction,
+) -> Result<Response, ContractError> {
+    let proposal_id =
+        PROPOSAL_COUNT.update::<_, ContractError>(deps.storage, |count| Ok(count + 1))?;
+
+    PROPOSALS.save(
+        deps.storage,
+        proposal_id,
+        &CommitteeProposal {
+            proposal_id,
+            action,
+            proposer: info.sender.clone(),
+            approvals: vec![info.sender.clone()],
+            expires_at: env.block.time.seconds() + config.voting_period,
+            status: CommitteeProposalStatus::Open,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposer", info.sender))
+}
+
+/// Approves a proposed action.
+///
+/// * **proposal_id** proposal identifier.
+fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_open_proposal(deps.as_ref(), &env, proposal_id)?;
+
+    if proposal.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved(proposal_id));
+    }
+
+    proposal.approvals.push(info.sender.clone());
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("member", info.sender))
+}
+
+/// Executes an action that collected enough approvals.
+///
+/// * **proposal_id** proposal identifier.
+fn execute_action(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_open_proposal(deps.as_ref(), &env, proposal_id)?;
+
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|member| config.members.contains(member))
+        .count();
+    if (approvals as u32) < config.threshold {
+        return Err(ContractError::NotEnoughApprovals(proposal_id));
+    }
+
+    proposal.status = CommitteeProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_message(action_msg(&config, &proposal.action)?)
+        .add_attribute("action", "execute")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Loads a proposal that can still be approved or executed.
+fn load_open_proposal(
+    deps: Deps,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<CommitteeProposal, ContractError> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.status == CommitteeProposalStatus::Executed {
+        return Err(ContractError::ProposalExecuted(proposal_id));
+    }
+
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::ProposalExpired(proposal_id));
+    }
+
+    Ok(proposal)
+}
+
+/// Builds the message for an allowed action. This is the only place where the committee
+/// creates messages, so it can't execute anything outside of [`EmergencyAction`].
+fn action_msg(config: &Config, action: &EmergencyAction) -> StdResult<CosmosMsg> {
+    match action {
+        EmergencyAction::PauseTuning {} => Ok(WasmMsg::Execute {
+            contract_addr: config.generator_controller.to_string(),
+            msg: to_binary(&ControllerExecuteMsg::PauseTuning {})?,
+            funds: vec![],
+        }
+        .into()),
+        EmergencyAction::PauseAssembly {} => assembly_msg(config, &AssemblyExecuteMsg::Pause {}),
+        EmergencyAction::UnpauseAssembly {} => {
+            assembly_msg(config, &AssemblyExecuteMsg::Unpause {})
+        }
+        EmergencyAction::VetoProposal { proposal_id } => assembly_msg(
+            config,
+            &AssemblyExecuteMsg::VetoProposal {
+                proposal_id: *proposal_id,
+            },
+        ),
+    }
+}
+
+fn assembly_msg(config: &Config, msg: &AssemblyExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.assembly.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Exposes all the queries available in the contract.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::Proposal { proposal_id }** Returns a proposed action.
+///
+/// * **QueryMsg::Proposals { start_after, limit }** Returns a page of proposed actions.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Proposal { proposal_id } => {
+            to_binary(&PROPOSALS.load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::Proposals { start_after, limit } => {
+            let proposals = paginate_map(
+                &PROPOSALS,
+                deps.storage,
+                start_after,
+                limit,
+                Order::Ascending,
+            )?
+            .into_iter()
+            .map(|(_, proposal)| proposal)
+            .collect::<Vec<_>>();
+            to_binary(&proposals)
+        }
+    }
+}
+
+/// Manages contract migration.
+/// There are no released versions to migrate from yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Err(ContractError::MigrationError {})
+}
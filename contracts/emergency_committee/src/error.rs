@@ -0,0 +1,33 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// This enum describes emergency committee contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The committee must have at least one member and no duplicates")]
+    InvalidMembers {},
+
+    #[error("Threshold must be between 1 and the number of members")]
+    InvalidThreshold {},
+
+    #[error("Proposal {0} expired")]
+    ProposalExpired(u64),
+
+    #[error("Proposal {0} was already executed")]
+    ProposalExecuted(u64),
+
+    #[error("Proposal {0} was already approved by this member")]
+    AlreadyApproved(u64),
+
+    #[error("Proposal {0} doesn't have enough approvals")]
+    NotEnoughApprovals(u64),
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
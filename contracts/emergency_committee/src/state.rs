@@ -0,0 +1,11 @@
+use astroport_governance::emergency_committee::{CommitteeProposal, Config};
+use cw_storage_plus::{Item, Map};
+
+/// Stores the contract config
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Number of proposed actions
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+
+/// Proposed actions keyed by proposal ID
+pub const PROPOSALS: Map<u64, CommitteeProposal> = Map::new("proposals");
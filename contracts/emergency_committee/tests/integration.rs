@@ -0,0 +1,355 @@
+use astroport_governance::assembly::ExecuteMsg as AssemblyExecuteMsg;
+use astroport_governance::emergency_committee::{
+    CommitteeProposal, CommitteeProposalStatus, Config, EmergencyAction, ExecuteMsg,
+    InstantiateMsg, QueryMsg,
+};
+use astroport_governance::generator_controller::ExecuteMsg as ControllerExecuteMsg;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, OwnedDeps, WasmMsg};
+use emergency_committee::contract::{execute, instantiate, query};
+use emergency_committee::error::ContractError;
+
+const MEMBERS: [&str; 3] = ["member1", "member2", "member3"];
+const GENERATOR_CONTROLLER: &str = "generator_controller";
+const ASSEMBLY: &str = "assembly";
+const VOTING_PERIOD: u64 = 86400;
+
+#[test]
+fn test_instantiate_validation() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        members: vec!["member1".to_string(), "member1".to_string()],
+        threshold: 1,
+        voting_period: VOTING_PERIOD,
+        generator_controller: GENERATOR_CONTROLLER.to_string(),
+        assembly: ASSEMBLY.to_string(),
+    };
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMembers {});
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            members: MEMBERS.iter().map(ToString::to_string).collect(),
+            threshold: 4,
+            ..msg
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidThreshold {});
+}
+
+#[test]
+fn test_pause_tuning() {
+    let mut deps = instantiate_committee();
+
+    let propose_msg = ExecuteMsg::Propose {
+        action: EmergencyAction::PauseTuning {},
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random", &[]),
+        propose_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        propose_msg,
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyApproved(1));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughApprovals(1));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[1], &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[2], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GENERATOR_CONTROLLER.to_string(),
+            msg: to_binary(&ControllerExecuteMsg::PauseTuning {}).unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[2], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalExecuted(1));
+
+    let proposal: CommitteeProposal = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(proposal.status, CommitteeProposalStatus::Executed);
+    assert_eq!(proposal.approvals.len(), 2);
+}
+
+#[test]
+fn test_proposal_expiration() {
+    let mut deps = instantiate_committee();
+
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MEMBERS[0], &[]),
+            ExecuteMsg::Propose {
+                action: EmergencyAction::PauseTuning {},
+            },
+        )
+        .unwrap();
+    }
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(VOTING_PERIOD);
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MEMBERS[1], &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalExpired(1));
+
+    let proposals: Vec<CommitteeProposal> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Proposals {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal_id, 2);
+}
+
+#[test]
+fn test_veto_proposal() {
+    let mut deps = instantiate_committee();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Propose {
+            action: EmergencyAction::VetoProposal { proposal_id: 7 },
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[1], &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ASSEMBLY.to_string(),
+            msg: to_binary(&AssemblyExecuteMsg::VetoProposal { proposal_id: 7 }).unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_update_members() {
+    let mut deps = instantiate_committee();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Propose {
+            action: EmergencyAction::PauseAssembly {},
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[1], &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let update_msg = ExecuteMsg::UpdateMembers {
+        add: vec!["member4".to_string()],
+        remove: vec![MEMBERS[1].to_string()],
+        threshold: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        update_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::UpdateMembers {
+            add: vec![],
+            remove: vec![],
+            threshold: Some(4),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidThreshold {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::UpdateMembers {
+            add: vec![MEMBERS[0].to_string()],
+            remove: vec![],
+            threshold: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMembers {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        update_msg,
+    )
+    .unwrap();
+
+    let config: Config =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.members, vec!["member1", "member3", "member4"]);
+
+    // The approval of the removed member doesn't count anymore
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[1], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughApprovals(1));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member4", &[]),
+        ExecuteMsg::Approve { proposal_id: 1 },
+    )
+    .unwrap();
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MEMBERS[0], &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ASSEMBLY.to_string(),
+            msg: to_binary(&AssemblyExecuteMsg::Pause {}).unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+fn instantiate_committee() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            members: MEMBERS.iter().map(ToString::to_string).collect(),
+            threshold: 2,
+            voting_period: VOTING_PERIOD,
+            generator_controller: GENERATOR_CONTROLLER.to_string(),
+            assembly: ASSEMBLY.to_string(),
+        },
+    )
+    .unwrap();
+
+    deps
+}
@@ -1,29 +1,49 @@
 use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult, Uint128,
+    attr, coin, entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw20::Cw20ReceiveMsg;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
 use cw_storage_plus::Bound;
+use std::collections::BTreeMap;
 
 use astroport_governance::escrow_fee_distributor::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PeriodStatsResponse, QueryMsg,
+    SimulateClaimResponse, MAX_NATIVE_DENOMS,
 };
 use astroport_governance::utils::{get_period, CLAIM_LIMIT, MIN_CLAIM_LIMIT};
-use astroport_governance::voting_escrow::{get_total_voting_power_at, get_voting_power_at};
+use astroport_governance::voting_escrow::{
+    get_lock_info, get_total_voting_power_at, get_total_voting_power_at_by_period,
+    get_voting_power_at, CheckpointHookMsg, Cw20HookMsg as VotingEscrowHookMsg,
+    DEFAULT_PERIODS_LIMIT,
+};
 
 use crate::astroport;
 use crate::astroport::asset::addr_opt_validate;
+use crate::astroport::staking::{
+    ConfigResponse as StakingConfigResponse, Cw20HookMsg as StakingHookMsg,
+    QueryMsg as StakingQueryMsg,
+};
 use crate::error::ContractError;
-use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL, REWARDS_PER_WEEK};
-use crate::utils::{calc_claim_amount, calculate_reward, transfer_token_amount};
+use crate::state::{
+    CheckpointSync, ClaimAndLock, Config, CLAIMED_PER_WEEK, CLAIM_AND_LOCK, CONFIG,
+    NATIVE_CLAIMED_PER_WEEK, NATIVE_DENOMS, NATIVE_REWARDS_PER_WEEK, OWNERSHIP_PROPOSAL,
+    RECYCLED_UNTIL, REWARDS_PER_WEEK, USER_CHECKPOINTS, USER_CHECKPOINT_SYNC,
+};
+use crate::utils::{
+    calc_claim_amount, calc_period_rewards, calculate_reward, transfer_native_coins,
+    transfer_token_amount,
+};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-escrow-fee-distributor";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply ID used to lock xASTRO received after staking claimed ASTRO.
+const CLAIM_AND_LOCK_REPLY_ID: u64 = 1;
+
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -42,6 +62,18 @@ pub fn instantiate(
         }
     }
 
+    if msg.claim_expiry_periods == Some(0) {
+        return Err(StdError::generic_err(
+            "Claim expiry periods must be greater than 0!",
+        ));
+    }
+
+    if msg.max_periods_per_claim == Some(0) {
+        return Err(StdError::generic_err(
+            "Max periods per claim must be greater than 0!",
+        ));
+    }
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -50,6 +82,9 @@ pub fn instantiate(
             voting_escrow_addr: deps.api.addr_validate(&msg.voting_escrow_addr)?,
             is_claim_disabled: msg.is_claim_disabled.unwrap_or(false),
             claim_many_limit: msg.claim_many_limit.unwrap_or(CLAIM_LIMIT),
+            astro_staking: addr_opt_validate(deps.api, &msg.astro_staking)?,
+            claim_expiry_periods: msg.claim_expiry_periods,
+            max_periods_per_claim: msg.max_periods_per_claim.unwrap_or(DEFAULT_PERIODS_LIMIT),
         },
     )?;
 
@@ -65,13 +100,21 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
 ///
-/// * **ExecuteMsg::Claim { recipient }** Claims ASTRO fees from the distributor and sends them to the recipient.
+/// * **ExecuteMsg::Claim { recipient, max_periods }** Claims ASTRO fees from the distributor and sends them to the recipient.
+///
+/// * **ExecuteMsg::ClaimAndLock {}** Claims ASTRO fees, stakes them and deposits received xASTRO in the sender's vxASTRO lock.
 ///
 /// * **ExecuteMsg::ClaimMany { receivers }** Perform multiple fee claims in a single transaction.
 ///
 /// * **ExecuteMsg::Receive(msg)** Parse incoming messages from the ASTRO token.
 ///
-/// * **ExecuteMsg::UpdateConfig { claim_many_limit, is_claim_disabled}** Updates general settings.
+/// * **ExecuteMsg::UpdateConfig { claim_many_limit, is_claim_disabled, claim_expiry_periods, max_periods_per_claim, native_denoms }** Updates general settings.
+///
+/// * **ExecuteMsg::RecycleExpired {}** Moves expired unclaimed rewards to the current period distribution.
+///
+/// * **ExecuteMsg::CheckpointHook(checkpoint)** Saves a staker's voting power checkpoint pushed by the voting escrow.
+///
+/// * **ExecuteMsg::ReceiveTokens {}** Adds allowed native coins sent along with the message to the current period distribution.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -110,11 +153,28 @@ pub fn execute(
             recipient,
             max_periods,
         } => claim(deps, env, info, recipient, max_periods),
+        ExecuteMsg::ClaimAndLock {} => claim_and_lock(deps, env, info),
         ExecuteMsg::ClaimMany { receivers } => claim_many(deps, env, receivers),
         ExecuteMsg::UpdateConfig {
             claim_many_limit,
             is_claim_disabled,
-        } => update_config(deps, info, claim_many_limit, is_claim_disabled),
+            astro_staking,
+            claim_expiry_periods,
+            max_periods_per_claim,
+            native_denoms,
+        } => update_config(
+            deps,
+            info,
+            claim_many_limit,
+            is_claim_disabled,
+            astro_staking,
+            claim_expiry_periods,
+            max_periods_per_claim,
+            native_denoms,
+        ),
+        ExecuteMsg::RecycleExpired {} => recycle_expired(deps, env),
+        ExecuteMsg::CheckpointHook(checkpoint) => checkpoint_hook(deps, info, checkpoint),
+        ExecuteMsg::ReceiveTokens {} => receive_native_tokens(deps, env, info),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
     }
 }
@@ -146,9 +206,71 @@ fn receive_cw20(
     Ok(Response::new())
 }
 
+/// Adds native coins sent along with the message to the current period distribution.
+/// Only denoms in [`NATIVE_DENOMS`] are accepted, so junk denoms can't take up the per period denom limit.
+fn receive_native_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let funds: Vec<_> = info
+        .funds
+        .into_iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+    if funds.is_empty() {
+        return Err(ContractError::NoFundsSent {});
+    }
+
+    let native_denoms = NATIVE_DENOMS.may_load(deps.storage)?.unwrap_or_default();
+    if let Some(coin) = funds
+        .iter()
+        .find(|coin| !native_denoms.contains(&coin.denom))
+    {
+        return Err(ContractError::DenomNotAllowed(coin.denom.clone()));
+    }
+
+    let curr_period = get_period(env.block.time.seconds())?;
+
+    for coin in &funds {
+        NATIVE_REWARDS_PER_WEEK.update(
+            deps.storage,
+            (curr_period, &coin.denom),
+            |amount| -> StdResult<_> { Ok(amount.unwrap_or_default().checked_add(coin.amount)?) },
+        )?;
+    }
+
+    let denoms_count = NATIVE_REWARDS_PER_WEEK
+        .prefix(curr_period)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if denoms_count > MAX_NATIVE_DENOMS {
+        return Err(ContractError::NativeDenomsLimitExceeded(MAX_NATIVE_DENOMS));
+    }
+
+    // Register the period as funded so it is covered by rewards recycling and period stats
+    if !REWARDS_PER_WEEK.has(deps.storage, curr_period) {
+        REWARDS_PER_WEEK.save(deps.storage, curr_period, &Uint128::zero())?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "receive_tokens"),
+        attr(
+            "amount",
+            funds
+                .iter()
+                .map(|coin| coin.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+    ]))
+}
+
 /// Claims ASTRO staking rewards from this contract and sends them to the `recipient`.
 ///
 /// * **recipient** address that will receive the ASTRO staking rewards.
+///
+/// * **max_periods** max amount of periods to claim rewards for. Subsequent calls continue from the last claimed period.
 pub fn claim(
     deps: DepsMut,
     env: Env,
@@ -166,27 +288,152 @@ pub fn claim(
         addr_opt_validate(deps.api, &recipient)?.unwrap_or_else(|| info.sender.clone());
     let current_period = get_period(env.block.time.seconds())?;
 
-    let claim_amount = calc_claim_amount(
-        deps,
-        current_period,
-        &info.sender,
-        &config.voting_escrow_addr,
-        max_periods,
-    )?;
+    let claimed = calc_claim_amount(deps, current_period, &info.sender, &config, max_periods)?;
 
-    let transfer_msg = transfer_token_amount(&config.astro_token, &recipient_addr, claim_amount)?;
+    let transfer_msg = transfer_token_amount(&config.astro_token, &recipient_addr, claimed.amount)?;
 
     let response = Response::new()
         .add_attributes(vec![
             attr("action", "claim"),
             attr("address", recipient_addr.to_string()),
-            attr("amount", claim_amount.to_string()),
+            attr("amount", claimed.amount.to_string()),
+            attr("remaining_periods", claimed.remaining_periods.to_string()),
+            attr(
+                "native_amount",
+                claimed
+                    .native_amount
+                    .iter()
+                    .map(|coin| coin.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
         ])
-        .add_messages(transfer_msg);
+        .add_messages(transfer_msg)
+        .add_messages(transfer_native_coins(
+            &recipient_addr,
+            claimed.native_amount,
+        ));
 
     Ok(response)
 }
 
+/// Claims ASTRO staking rewards for the sender and stakes them in the ASTRO staking contract.
+/// Received xASTRO is deposited in the sender's vxASTRO lock in [`reply`].
+fn claim_and_lock(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.is_claim_disabled {
+        return Err(ContractError::ClaimDisabled {});
+    }
+
+    let astro_staking = config
+        .astro_staking
+        .clone()
+        .ok_or(ContractError::StakingNotSet {})?;
+    let current_period = get_period(env.block.time.seconds())?;
+
+    let lock_info = get_lock_info(&deps.querier, &config.voting_escrow_addr, &info.sender)?;
+    if lock_info.amount.is_zero() || lock_info.end <= current_period {
+        return Err(ContractError::LockExpired {});
+    }
+
+    let claimed = calc_claim_amount(deps.branch(), current_period, &info.sender, &config, None)?;
+    let claim_amount = claimed.amount;
+    if claim_amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    // Only xASTRO received for the claimed ASTRO is locked, xASTRO already held by the contract stays
+    let staking_config: StakingConfigResponse = deps
+        .querier
+        .query_wasm_smart(&astro_staking, &StakingQueryMsg::Config {})?;
+    let xastro_balance: BalanceResponse = deps.querier.query_wasm_smart(
+        &staking_config.share_token_addr,
+        &Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    CLAIM_AND_LOCK.save(
+        deps.storage,
+        &ClaimAndLock {
+            user: info.sender.clone(),
+            xastro_balance_before: xastro_balance.balance,
+        },
+    )?;
+
+    let stake_msg = WasmMsg::Execute {
+        contract_addr: config.astro_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: astro_staking.to_string(),
+            amount: claim_amount,
+            msg: to_binary(&StakingHookMsg::Enter {})?,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(stake_msg, CLAIM_AND_LOCK_REPLY_ID))
+        .add_messages(transfer_native_coins(&info.sender, claimed.native_amount))
+        .add_attributes(vec![
+            attr("action", "claim_and_lock"),
+            attr("address", info.sender),
+            attr("amount", claim_amount),
+        ]))
+}
+
+/// Deposits xASTRO received from the ASTRO staking contract in the vxASTRO lock of the user
+/// which called [`claim_and_lock`]. Only the xASTRO received in this transaction is deposited.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CLAIM_AND_LOCK_REPLY_ID => {
+            let config = CONFIG.load(deps.storage)?;
+            let ClaimAndLock {
+                user,
+                xastro_balance_before,
+            } = CLAIM_AND_LOCK.load(deps.storage)?;
+            CLAIM_AND_LOCK.remove(deps.storage);
+
+            let astro_staking = config
+                .astro_staking
+                .ok_or(ContractError::StakingNotSet {})?;
+            let staking_config: StakingConfigResponse = deps
+                .querier
+                .query_wasm_smart(&astro_staking, &StakingQueryMsg::Config {})?;
+            let xastro_balance: BalanceResponse = deps.querier.query_wasm_smart(
+                &staking_config.share_token_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            let xastro_amount = xastro_balance.balance.checked_sub(xastro_balance_before)?;
+
+            let lock_msg = WasmMsg::Execute {
+                contract_addr: staking_config.share_token_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: config.voting_escrow_addr.to_string(),
+                    amount: xastro_amount,
+                    msg: to_binary(&VotingEscrowHookMsg::DepositFor {
+                        user: user.to_string(),
+                    })?,
+                })?,
+                funds: vec![],
+            };
+
+            Ok(Response::new().add_message(lock_msg).add_attributes(vec![
+                attr("action", "lock_claimed"),
+                attr("address", user),
+                attr("xastro_amount", xastro_amount),
+            ]))
+        }
+        _ => Err(StdError::generic_err(format!("Unknown reply ID: {}", msg.id)).into()),
+    }
+}
+
 /// Make multiple ASTRO fee claims in a single call.
 ///
 /// * **receivers** list of addresses that will receive the claimed ASTRO.
@@ -207,18 +454,16 @@ fn claim_many(
 
     let mut claim_total_amount = Uint128::zero();
     let mut transfer_msg = vec![];
+    let mut receiver_attrs = vec![];
     let current_period = get_period(env.block.time.seconds())?;
 
     for receiver in receivers {
         let receiver_addr = deps.api.addr_validate(&receiver)?;
-        let claim_amount = calc_claim_amount(
-            deps.branch(),
-            current_period,
-            &receiver_addr,
-            &config.voting_escrow_addr,
-            None,
-        )?;
+        let claimed =
+            calc_claim_amount(deps.branch(), current_period, &receiver_addr, &config, None)?;
+        let claim_amount = claimed.amount;
 
+        transfer_msg.extend(transfer_native_coins(&receiver_addr, claimed.native_amount));
         if !claim_amount.is_zero() {
             transfer_msg.extend(transfer_token_amount(
                 &config.astro_token,
@@ -227,6 +472,12 @@ fn claim_many(
             )?);
             claim_total_amount = claim_total_amount.checked_add(claim_amount)?;
         };
+
+        receiver_attrs.push(attr("receiver", receiver_addr));
+        receiver_attrs.push(attr(
+            "remaining_periods",
+            claimed.remaining_periods.to_string(),
+        ));
     }
 
     let response = Response::new()
@@ -234,6 +485,7 @@ fn claim_many(
             attr("action", "claim_many"),
             attr("amount", claim_total_amount.to_string()),
         ])
+        .add_attributes(receiver_attrs)
         .add_messages(transfer_msg);
 
     Ok(response)
@@ -244,11 +496,24 @@ fn claim_many(
 /// * **claim_many_limit** max amount of rewards slots to claim in one transaction.
 ///
 /// * **is_claim_disabled** whether reward claims are disabled or not.
+///
+/// * **astro_staking** ASTRO staking contract used by [`ExecuteMsg::ClaimAndLock`].
+///
+/// * **claim_expiry_periods** amount of periods after which unclaimed rewards can be recycled.
+///
+/// * **max_periods_per_claim** max amount of periods processed in a single claim.
+///
+/// * **native_denoms** native denoms accepted by [`ExecuteMsg::ReceiveTokens`].
+#[allow(clippy::too_many_arguments)]
 fn update_config(
     deps: DepsMut,
     info: MessageInfo,
     claim_many_limit: Option<u64>,
     is_claim_disabled: Option<bool>,
+    astro_staking: Option<String>,
+    claim_expiry_periods: Option<u64>,
+    max_periods_per_claim: Option<u64>,
+    native_denoms: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -281,11 +546,181 @@ fn update_config(
         attributes.push(attr("claim_many_limit", claim_many_limit.to_string()));
     };
 
+    if let Some(astro_staking) = astro_staking {
+        config.astro_staking = Some(deps.api.addr_validate(&astro_staking)?);
+        attributes.push(attr("astro_staking", astro_staking));
+    };
+
+    if let Some(claim_expiry_periods) = claim_expiry_periods {
+        if claim_expiry_periods == 0 {
+            return Err(
+                StdError::generic_err("Claim expiry periods must be greater than 0!").into(),
+            );
+        }
+
+        config.claim_expiry_periods = Some(claim_expiry_periods);
+        attributes.push(attr(
+            "claim_expiry_periods",
+            claim_expiry_periods.to_string(),
+        ));
+    };
+
+    if let Some(max_periods_per_claim) = max_periods_per_claim {
+        if max_periods_per_claim == 0 {
+            return Err(
+                StdError::generic_err("Max periods per claim must be greater than 0!").into(),
+            );
+        }
+
+        config.max_periods_per_claim = max_periods_per_claim;
+        attributes.push(attr(
+            "max_periods_per_claim",
+            max_periods_per_claim.to_string(),
+        ));
+    };
+
+    if let Some(mut native_denoms) = native_denoms {
+        native_denoms.sort();
+        native_denoms.dedup();
+        if native_denoms.len() > MAX_NATIVE_DENOMS {
+            return Err(ContractError::NativeDenomsLimitExceeded(MAX_NATIVE_DENOMS));
+        }
+        if native_denoms.iter().any(|denom| denom.is_empty()) {
+            return Err(StdError::generic_err("Native denom can't be empty!").into());
+        }
+
+        attributes.push(attr("native_denoms", native_denoms.join(",")));
+        NATIVE_DENOMS.save(deps.storage, &native_denoms)?;
+    };
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// Saves a staker's voting power checkpoint pushed by the voting escrow contract.
+/// Stored checkpoints are used to calculate the staker's voting power at claim time without querying the voting escrow.
+/// If the checkpoint nonce shows that previous checkpoints were missed, stored checkpoints are trusted
+/// only from the period of the received checkpoint.
+///
+/// * **checkpoint** the staker's latest voting power checkpoint.
+fn checkpoint_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    checkpoint: CheckpointHookMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.voting_escrow_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user = deps.api.addr_validate(&checkpoint.user)?;
+    let synced_from = USER_CHECKPOINT_SYNC
+        .may_load(deps.storage, &user)?
+        .filter(|sync| sync.nonce + 1 == checkpoint.nonce)
+        .map(|sync| sync.synced_from)
+        .unwrap_or(checkpoint.start);
+    USER_CHECKPOINT_SYNC.save(
+        deps.storage,
+        &user,
+        &CheckpointSync {
+            nonce: checkpoint.nonce,
+            synced_from,
+        },
+    )?;
+    USER_CHECKPOINTS.save(deps.storage, (&user, checkpoint.start), &checkpoint)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "checkpoint_hook"),
+        attr("user", user),
+        attr("period", checkpoint.start.to_string()),
+    ]))
+}
+
+/// Moves rewards which weren't claimed within the claim expiry window to the current period distribution.
+/// At most [`DEFAULT_PERIODS_LIMIT`] periods are processed in a single call.
+fn recycle_expired(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let claim_expiry_periods = config
+        .claim_expiry_periods
+        .ok_or(ContractError::ClaimExpiryNotSet {})?;
+
+    let current_period = get_period(env.block.time.seconds())?;
+    let expired_until = current_period.saturating_sub(claim_expiry_periods);
+    let recycled_from = RECYCLED_UNTIL.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut recycled_until = recycled_from.max(expired_until);
+    let mut recycled_amount = Uint128::zero();
+    let mut recycled_native: BTreeMap<String, Uint128> = BTreeMap::new();
+    if recycled_from < expired_until {
+        let expired_periods = REWARDS_PER_WEEK
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(recycled_from)),
+                Some(Bound::exclusive(expired_until)),
+                Order::Ascending,
+            )
+            .take(DEFAULT_PERIODS_LIMIT as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        if expired_periods.len() == DEFAULT_PERIODS_LIMIT as usize {
+            recycled_until = expired_periods.last().unwrap().0 + 1;
+        }
+
+        for (period, rewards) in expired_periods {
+            let claimed = CLAIMED_PER_WEEK
+                .may_load(deps.storage, period)?
+                .unwrap_or_default();
+            recycled_amount = recycled_amount.checked_add(rewards.checked_sub(claimed)?)?;
+
+            let native_rewards = NATIVE_REWARDS_PER_WEEK
+                .prefix(period)
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (denom, rewards) in native_rewards {
+                let claimed = NATIVE_CLAIMED_PER_WEEK
+                    .may_load(deps.storage, (period, &denom))?
+                    .unwrap_or_default();
+                let amount = recycled_native.entry(denom).or_default();
+                *amount = amount.checked_add(rewards.checked_sub(claimed)?)?;
+            }
+        }
+    }
+
+    RECYCLED_UNTIL.save(deps.storage, &recycled_until)?;
+
+    if !recycled_amount.is_zero() {
+        REWARDS_PER_WEEK.update(deps.storage, current_period, |rewards| -> StdResult<_> {
+            Ok(rewards.unwrap_or_default().checked_add(recycled_amount)?)
+        })?;
+    }
+
+    for (denom, amount) in recycled_native.iter() {
+        if !amount.is_zero() {
+            NATIVE_REWARDS_PER_WEEK.update(
+                deps.storage,
+                (current_period, denom),
+                |rewards| -> StdResult<_> { Ok(rewards.unwrap_or_default().checked_add(*amount)?) },
+            )?;
+        }
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "recycle_expired"),
+        attr("amount", recycled_amount),
+        attr(
+            "native_amount",
+            recycled_native
+                .into_iter()
+                .filter(|(_, amount)| !amount.is_zero())
+                .map(|(denom, amount)| coin(amount.u128(), denom).to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        attr("recycled_until", recycled_until.to_string()),
+    ]))
+}
+
 /// Expose available contract queries.
 ///
 /// ## Queries
@@ -295,8 +730,14 @@ fn update_config(
 ///
 /// * **QueryMsg::AvailableRewardPerWeek { start_after, limit }** Returns a vector with total amounts
 /// of ASTRO distributed as rewards every week to stakers.
+///
+/// * **QueryMsg::SimulateClaim { user, max_periods }** Returns the amount of ASTRO rewards a user would claim now
+/// along with rewards for every claimed period.
+///
+/// * **QueryMsg::PeriodStats { start_after, limit }** Returns deposited and claimed rewards along with the total
+/// vxASTRO supply for every funded period.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::UserReward { user, timestamp } => {
             to_binary(&query_user_reward(deps, user, timestamp)?)
@@ -305,6 +746,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AvailableRewardPerWeek { start_after, limit } => {
             to_binary(&query_available_reward_per_week(deps, start_after, limit)?)
         }
+        QueryMsg::SimulateClaim { user, max_periods } => {
+            to_binary(&simulate_claim(deps, env, user, max_periods)?)
+        }
+        QueryMsg::PeriodStats { start_after, limit } => {
+            to_binary(&query_period_stats(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -339,6 +786,44 @@ fn query_available_reward_per_week(
         .collect()
 }
 
+/// Returns reward distribution statistics for every funded period.
+///
+/// * **start_after** period from which to start querying.
+///
+/// * **limit** max amount of entries to return.
+fn query_period_stats(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Vec<PeriodStatsResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    REWARDS_PER_WEEK
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (period, deposited) = item?;
+            let claimed = CLAIMED_PER_WEEK
+                .may_load(deps.storage, period)?
+                .unwrap_or_default();
+            let total_voting_power = get_total_voting_power_at_by_period(
+                &deps.querier,
+                &config.voting_escrow_addr,
+                period,
+            )?;
+
+            Ok(PeriodStatsResponse {
+                period,
+                deposited,
+                claimed,
+                total_voting_power,
+            })
+        })
+        .collect()
+}
+
 /// Returns the amount of rewards a user accrued at a specific timestamp.
 ///
 /// * **user** user for which we return the amount of rewards.
@@ -365,6 +850,49 @@ fn query_user_reward(deps: Deps, user: String, timestamp: u64) -> StdResult<Uint
     }
 }
 
+/// Returns the amount of rewards a user would claim at the current block along with the per period breakdown.
+///
+/// * **user** user for which we simulate the claim.
+///
+/// * **max_periods** maximum number of periods to claim.
+fn simulate_claim(
+    deps: Deps,
+    env: Env,
+    user: String,
+    max_periods: Option<u64>,
+) -> StdResult<SimulateClaimResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let user = deps.api.addr_validate(&user)?;
+
+    let period_rewards = calc_period_rewards(
+        deps,
+        get_period(env.block.time.seconds())?,
+        &user,
+        &config,
+        max_periods,
+    )?;
+    let rewards_per_period = period_rewards.rewards;
+    let amount = rewards_per_period
+        .iter()
+        .try_fold(Uint128::zero(), |acc, (_, reward)| acc.checked_add(*reward))?;
+
+    let mut native_amount: BTreeMap<String, Uint128> = BTreeMap::new();
+    for (_, reward) in period_rewards.native_rewards {
+        let amount = native_amount.entry(reward.denom).or_default();
+        *amount = amount.checked_add(reward.amount)?;
+    }
+
+    Ok(SimulateClaimResponse {
+        amount,
+        rewards_per_period,
+        native_amount: native_amount
+            .into_iter()
+            .map(|(denom, amount)| coin(amount.u128(), denom))
+            .collect(),
+        remaining_periods: period_rewards.remaining_periods,
+    })
+}
+
 /// Returns the contract configuration.
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -375,6 +903,10 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         voting_escrow_addr: config.voting_escrow_addr,
         is_claim_disabled: config.is_claim_disabled,
         claim_many_limit: config.claim_many_limit,
+        astro_staking: config.astro_staking,
+        claim_expiry_periods: config.claim_expiry_periods,
+        max_periods_per_claim: config.max_periods_per_claim,
+        native_denoms: NATIVE_DENOMS.may_load(deps.storage)?.unwrap_or_default(),
     };
 
     Ok(resp)
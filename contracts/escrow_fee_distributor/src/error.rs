@@ -1,3 +1,4 @@
+use astroport_governance::errors::CommonError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -8,6 +9,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -17,6 +21,27 @@ pub enum ContractError {
     #[error("Claiming is disabled!")]
     ClaimDisabled {},
 
+    #[error("Nothing to claim!")]
+    NothingToClaim {},
+
+    #[error("ASTRO staking contract is not set!")]
+    StakingNotSet {},
+
+    #[error("Lock expired or doesn't exist!")]
+    LockExpired {},
+
+    #[error("Claim expiry is not set!")]
+    ClaimExpiryNotSet {},
+
+    #[error("No native coins were sent!")]
+    NoFundsSent {},
+
+    #[error("Exceeded the limit of {0} native denoms per period!")]
+    NativeDenomsLimitExceeded(usize),
+
+    #[error("Native denom {0} is not allowed!")]
+    DenomNotAllowed(String),
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 }
@@ -2,6 +2,7 @@ use cosmwasm_schema::cw_serde;
 
 use crate::astroport::common::OwnershipProposal;
 
+use astroport_governance::voting_escrow::CheckpointHookMsg;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
 
@@ -18,13 +19,54 @@ pub struct Config {
     pub claim_many_limit: u64,
     /// Whether reward claiming is disabled
     pub is_claim_disabled: bool,
+    /// ASTRO staking contract address
+    pub astro_staking: Option<Addr>,
+    /// Amount of periods after which unclaimed rewards can be recycled
+    pub claim_expiry_periods: Option<u64>,
+    /// Max amount of periods processed in a single claim
+    pub max_periods_per_claim: u64,
 }
 
 /// Stores the contract config at the given key.
 pub const CONFIG: Item<Config> = Item::new("config");
 /// Contains information about weekly distributed rewards.
 pub const REWARDS_PER_WEEK: Map<u64, Uint128> = Map::new("rewards_per_week");
+/// Contains information about weekly distributed native coin rewards (period, denom).
+pub const NATIVE_REWARDS_PER_WEEK: Map<(u64, &str), Uint128> = Map::new("native_rewards_per_week");
+/// Contains the native denoms the owner allows to be distributed as rewards.
+pub const NATIVE_DENOMS: Item<Vec<String>> = Item::new("native_denoms");
+/// Contains information about weekly claimed native coin rewards (period, denom).
+pub const NATIVE_CLAIMED_PER_WEEK: Map<(u64, &str), Uint128> = Map::new("native_claimed_per_week");
+/// Contains information about weekly claimed rewards.
+pub const CLAIMED_PER_WEEK: Map<u64, Uint128> = Map::new("claimed_per_week");
+/// Contains the first period which wasn't recycled yet.
+pub const RECYCLED_UNTIL: Item<u64> = Item::new("recycled_until");
+/// Contains stakers' voting power checkpoints pushed by the voting escrow contract (user, period).
+pub const USER_CHECKPOINTS: Map<(&Addr, u64), CheckpointHookMsg> = Map::new("user_checkpoints");
+
+/// This structure describes which of a staker's pushed checkpoints can be trusted.
+#[cw_serde]
+pub struct CheckpointSync {
+    /// Nonce of the latest received checkpoint
+    pub nonce: u64,
+    /// The period since which no checkpoint was missed
+    pub synced_from: u64,
+}
+
+/// Contains the checkpoint synchronization state for every staker.
+pub const USER_CHECKPOINT_SYNC: Map<&Addr, CheckpointSync> = Map::new("user_checkpoint_sync");
 /// Contains information about the last week of reward issuance.
 pub const LAST_CLAIM_PERIOD: Map<&Addr, u64> = Map::new("last_claim_period");
+/// This structure describes a claim which is being staked and locked in the current transaction.
+#[cw_serde]
+pub struct ClaimAndLock {
+    /// The user whose claimed ASTRO is being staked and locked
+    pub user: Addr,
+    /// The contract's xASTRO balance before the claimed ASTRO was staked
+    pub xastro_balance_before: Uint128,
+}
+
+/// Stores the claim which is being staked and locked in the current transaction.
+pub const CLAIM_AND_LOCK: Item<ClaimAndLock> = Item::new("claim_and_lock");
 /// Contains the proposal to change contract ownership.
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
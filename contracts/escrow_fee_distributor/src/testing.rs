@@ -14,6 +14,9 @@ fn proper_initialization() {
         voting_escrow_addr: "voting_escrow".to_string(),
         claim_many_limit: None,
         is_claim_disabled: None,
+        astro_staking: None,
+        claim_expiry_periods: None,
+        max_periods_per_claim: None,
     };
 
     let env = mock_env();
@@ -28,7 +31,11 @@ fn proper_initialization() {
             astro_token: Addr::unchecked("token"),
             voting_escrow_addr: Addr::unchecked("voting_escrow"),
             claim_many_limit: 10,
-            is_claim_disabled: false
+            is_claim_disabled: false,
+            astro_staking: None,
+            claim_expiry_periods: None,
+            max_periods_per_claim: 20,
+            native_denoms: vec![],
         }
     );
 }
@@ -1,16 +1,22 @@
 use std::cmp::min;
+use std::collections::BTreeMap;
 
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, StdError, StdResult, Storage, Uint128, WasmMsg,
+    coin, to_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Order, StdError, StdResult,
+    Storage, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Bound;
 
 use astroport_governance::voting_escrow::{
-    get_lock_info, QueryMsg as VotingQueryMsg, VotingPowerResponse, DEFAULT_PERIODS_LIMIT,
+    get_lock_info, QueryMsg as VotingQueryMsg, VotingPowerResponse,
 };
 
 use crate::error::ContractError;
-use crate::state::{LAST_CLAIM_PERIOD, REWARDS_PER_WEEK};
+use crate::state::{
+    Config, CLAIMED_PER_WEEK, LAST_CLAIM_PERIOD, NATIVE_CLAIMED_PER_WEEK, NATIVE_REWARDS_PER_WEEK,
+    RECYCLED_UNTIL, REWARDS_PER_WEEK, USER_CHECKPOINTS, USER_CHECKPOINT_SYNC,
+};
 
 /// Transfer tokens to another address.
 ///
@@ -40,6 +46,22 @@ pub(crate) fn transfer_token_amount(
     Ok(messages)
 }
 
+/// Send native coins to another address.
+///
+/// * **recipient** address of the coins recipient.
+///
+/// * **coins** coins to transfer.
+pub(crate) fn transfer_native_coins(recipient: &Addr, coins: Vec<Coin>) -> Vec<CosmosMsg> {
+    if coins.is_empty() {
+        vec![]
+    } else {
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins,
+        })]
+    }
+}
+
 /// Returns the amount of rewards distributed to a user for a specific period.
 ///
 /// * **period** period for which we calculate the user's reward.
@@ -62,34 +84,70 @@ pub(crate) fn calculate_reward(
         .map_err(|e| StdError::generic_err(format!("{e:?}")))
 }
 
-/// Calculates the amount of ASTRO available to claim by a specific address.
+/// This structure describes rewards calculated for a range of unclaimed periods.
+pub(crate) struct PeriodRewards {
+    /// The period from which the next claim should start
+    pub next_period: u64,
+    /// Amount of claimable periods left after this claim
+    pub remaining_periods: u64,
+    /// Rewards for every processed period
+    pub rewards: Vec<(u64, Uint128)>,
+    /// Native coin rewards for every processed period
+    pub native_rewards: Vec<(u64, Coin)>,
+}
+
+/// This structure describes rewards claimed by a user.
+pub(crate) struct ClaimedRewards {
+    /// Amount of claimed ASTRO
+    pub amount: Uint128,
+    /// Claimed native coins
+    pub native_amount: Vec<Coin>,
+    /// Amount of claimable periods left after this claim
+    pub remaining_periods: u64,
+}
+
+/// Calculates ASTRO rewards available to claim by a specific address for every unclaimed period.
+/// At most [`Config::max_periods_per_claim`] periods are processed.
 ///
 /// * **current_period** current epoch number.
 ///
 /// * **account** account for which we calculate the amount of ASTRO rewards available to claim.
 ///
-/// * **voting_escrow_addr** vxASTRO contract address.
+/// * **config** contract configuration.
 ///
 /// * **max_periods** maximum number of periods to claim.
-pub(crate) fn calc_claim_amount(
-    deps: DepsMut,
+pub(crate) fn calc_period_rewards(
+    deps: Deps,
     current_period: u64,
     account: &Addr,
-    voting_escrow_addr: &Addr,
+    config: &Config,
     max_periods: Option<u64>,
-) -> StdResult<Uint128> {
+) -> StdResult<PeriodRewards> {
+    let voting_escrow_addr = &config.voting_escrow_addr;
     let user_lock_info = get_lock_info(&deps.querier, voting_escrow_addr, account)?;
 
+    // Pushed checkpoints can be used only if the latest one was received
+    let synced_from = USER_CHECKPOINT_SYNC
+        .may_load(deps.storage, account)?
+        .filter(|sync| sync.nonce == user_lock_info.checkpoint_nonce)
+        .map(|sync| sync.synced_from);
+
     let mut claim_period = LAST_CLAIM_PERIOD
         .may_load(deps.storage, account)?
-        .unwrap_or(user_lock_info.start);
+        .unwrap_or(user_lock_info.start)
+        .max(first_claimable_period(
+            deps.storage,
+            current_period,
+            config.claim_expiry_periods,
+        )?);
 
     let lock_end_period = user_lock_info.end;
-    let mut claim_amount: Uint128 = Default::default();
-    let max_period = min(
-        max_periods.unwrap_or(DEFAULT_PERIODS_LIMIT) + claim_period,
-        current_period,
-    );
+    let mut rewards = vec![];
+    let mut native_rewards = vec![];
+    let max_periods = max_periods
+        .unwrap_or(config.max_periods_per_claim)
+        .min(config.max_periods_per_claim);
+    let max_period = min(max_periods + claim_period, current_period);
 
     loop {
         // User cannot claim for the current period/
@@ -102,12 +160,12 @@ pub(crate) fn calc_claim_amount(
             break;
         }
 
-        let user_voting_power: VotingPowerResponse = deps.querier.query_wasm_smart(
+        let user_voting_power = user_voting_power_at_period(
+            deps,
             voting_escrow_addr,
-            &VotingQueryMsg::UserVotingPowerAtPeriod {
-                user: account.to_string(),
-                period: claim_period,
-            },
+            account,
+            claim_period,
+            synced_from,
         )?;
 
         let total_voting_power: VotingPowerResponse = deps.querier.query_wasm_smart(
@@ -117,19 +175,166 @@ pub(crate) fn calc_claim_amount(
             },
         )?;
 
-        if !user_voting_power.voting_power.is_zero() && !total_voting_power.voting_power.is_zero() {
-            claim_amount = claim_amount.checked_add(calculate_reward(
+        let reward = if !user_voting_power.is_zero() && !total_voting_power.voting_power.is_zero() {
+            for item in NATIVE_REWARDS_PER_WEEK.prefix(claim_period).range(
+                deps.storage,
+                None,
+                None,
+                Order::Ascending,
+            ) {
+                let (denom, amount) = item?;
+                let native_reward = user_voting_power
+                    .checked_multiply_ratio(amount, total_voting_power.voting_power)
+                    .map_err(|e| StdError::generic_err(format!("{e:?}")))?;
+                if !native_reward.is_zero() {
+                    native_rewards.push((claim_period, coin(native_reward.u128(), denom)));
+                }
+            }
+
+            calculate_reward(
                 deps.storage,
                 claim_period,
-                user_voting_power.voting_power,
+                user_voting_power,
                 total_voting_power.voting_power,
-            )?)?;
-        }
+            )?
+        } else {
+            Uint128::zero()
+        };
+        rewards.push((claim_period, reward));
 
         claim_period += 1;
     }
 
-    LAST_CLAIM_PERIOD.save(deps.storage, account, &claim_period)?;
+    let remaining_periods = min(current_period, lock_end_period + 1).saturating_sub(claim_period);
+
+    Ok(PeriodRewards {
+        next_period: claim_period,
+        remaining_periods,
+        rewards,
+        native_rewards,
+    })
+}
 
-    Ok(claim_amount)
+/// Calculates the amount of ASTRO available to claim by a specific address
+/// and moves the address' claim cursor to the first unclaimed period.
+/// Returns claimed ASTRO and native coins along with the amount of claimable periods left.
+///
+/// * **current_period** current epoch number.
+///
+/// * **account** account for which we calculate the amount of ASTRO rewards available to claim.
+///
+/// * **config** contract configuration.
+///
+/// * **max_periods** maximum number of periods to claim.
+pub(crate) fn calc_claim_amount(
+    deps: DepsMut,
+    current_period: u64,
+    account: &Addr,
+    config: &Config,
+    max_periods: Option<u64>,
+) -> StdResult<ClaimedRewards> {
+    let period_rewards =
+        calc_period_rewards(deps.as_ref(), current_period, account, config, max_periods)?;
+
+    let mut claim_amount = Uint128::zero();
+    for (period, reward) in period_rewards.rewards {
+        if reward.is_zero() {
+            continue;
+        }
+        CLAIMED_PER_WEEK.update(deps.storage, period, |claimed| -> StdResult<_> {
+            Ok(claimed.unwrap_or_default().checked_add(reward)?)
+        })?;
+        claim_amount = claim_amount.checked_add(reward)?;
+    }
+
+    let mut native_amount: BTreeMap<String, Uint128> = BTreeMap::new();
+    for (period, reward) in period_rewards.native_rewards {
+        NATIVE_CLAIMED_PER_WEEK.update(
+            deps.storage,
+            (period, &reward.denom),
+            |claimed| -> StdResult<_> {
+                Ok(claimed.unwrap_or_default().checked_add(reward.amount)?)
+            },
+        )?;
+        let amount = native_amount.entry(reward.denom).or_default();
+        *amount = amount.checked_add(reward.amount)?;
+    }
+
+    LAST_CLAIM_PERIOD.save(deps.storage, account, &period_rewards.next_period)?;
+
+    Ok(ClaimedRewards {
+        amount: claim_amount,
+        native_amount: native_amount
+            .into_iter()
+            .map(|(denom, amount)| coin(amount.u128(), denom))
+            .collect(),
+        remaining_periods: period_rewards.remaining_periods,
+    })
+}
+
+/// Returns the first period for which rewards can still be claimed.
+/// Rewards for periods which were recycled or are older than the claim expiry window are no longer claimable.
+///
+/// * **current_period** current epoch number.
+///
+/// * **claim_expiry_periods** amount of periods after which unclaimed rewards expire.
+pub(crate) fn first_claimable_period(
+    storage: &dyn Storage,
+    current_period: u64,
+    claim_expiry_periods: Option<u64>,
+) -> StdResult<u64> {
+    let recycled_until = RECYCLED_UNTIL.may_load(storage)?.unwrap_or_default();
+    let expired_until = claim_expiry_periods
+        .map(|expiry| current_period.saturating_sub(expiry))
+        .unwrap_or_default();
+
+    Ok(recycled_until.max(expired_until))
+}
+
+/// Returns a user's voting power at the specified period.
+/// Uses the latest checkpoint pushed by the voting escrow contract if the distributor received
+/// every checkpoint since `synced_from` and the period isn't earlier than that.
+/// Otherwise, the voting power is queried from the voting escrow contract.
+///
+/// * **voting_escrow_addr** vxASTRO contract address.
+///
+/// * **account** account for which we calculate the voting power.
+///
+/// * **period** period at which we calculate the voting power.
+///
+/// * **synced_from** period since which the user's pushed checkpoints are complete and current.
+pub(crate) fn user_voting_power_at_period(
+    deps: Deps,
+    voting_escrow_addr: &Addr,
+    account: &Addr,
+    period: u64,
+    synced_from: Option<u64>,
+) -> StdResult<Uint128> {
+    let last_checkpoint = if matches!(synced_from, Some(synced_from) if period >= synced_from) {
+        USER_CHECKPOINTS
+            .prefix(account)
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(period)),
+                Order::Descending,
+            )
+            .next()
+            .transpose()?
+    } else {
+        None
+    };
+
+    if let Some((_, checkpoint)) = last_checkpoint {
+        Ok(checkpoint.voting_power_at(period))
+    } else {
+        let user_voting_power: VotingPowerResponse = deps.querier.query_wasm_smart(
+            voting_escrow_addr,
+            &VotingQueryMsg::UserVotingPowerAtPeriod {
+                user: account.to_string(),
+                period,
+            },
+        )?;
+        Ok(user_voting_power.voting_power)
+    }
 }
@@ -1,20 +1,24 @@
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-use cosmwasm_std::{attr, to_binary, Addr, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{attr, coin, to_binary, Addr, StdResult, Timestamp, Uint128};
 
 use astroport_governance::utils::{get_period, EPOCH_START, WEEK};
 
 use astroport_governance::escrow_fee_distributor::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, QueryMsg,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, PeriodStatsResponse, QueryMsg, SimulateClaimResponse,
+    MAX_NATIVE_DENOMS,
 };
 use astroport_governance::voting_escrow::{
-    LockInfoResponse, QueryMsg as VotingEscrowQueryMsg, VotingPowerResponse,
+    CheckpointHookMsg, ConfigResponse as VotingEscrowConfigResponse,
+    ExecuteMsg as VotingEscrowExecuteMsg, LockInfoResponse, QueryMsg as VotingEscrowQueryMsg,
+    VotingPowerResponse,
 };
 
+use astroport_governance::astroport::staking::Cw20HookMsg as StakingHookMsg;
 use astroport_tests::base::{
     check_balance, mint, BaseAstroportTestInitMessage, BaseAstroportTestPackage, MULTIPLIER,
 };
 use cw20::Cw20ExecuteMsg;
-use cw_multi_test::{next_block, App, AppBuilder, BankKeeper, Executor};
+use cw_multi_test::{next_block, App, AppBuilder, BankKeeper, BankSudo, Executor, SudoMsg};
 
 const OWNER: &str = "owner";
 const USER1: &str = "user1";
@@ -23,6 +27,7 @@ const USER3: &str = "user3";
 const USER4: &str = "user4";
 const USER5: &str = "user5";
 const MAKER: &str = "maker";
+const DENOM: &str = "uluna";
 
 fn mock_app() -> App {
     let mut env = mock_env();
@@ -209,6 +214,10 @@ fn update_config() {
             &ExecuteMsg::UpdateConfig {
                 claim_many_limit: Some(20u64),
                 is_claim_disabled: Some(true),
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: None,
+                native_denoms: None,
             },
             &[],
         )
@@ -223,6 +232,10 @@ fn update_config() {
             &ExecuteMsg::UpdateConfig {
                 claim_many_limit: Some(20u64),
                 is_claim_disabled: Some(true),
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: None,
+                native_denoms: None,
             },
             &[],
         )
@@ -236,6 +249,31 @@ fn update_config() {
     assert_eq!(20u64, resp_config.claim_many_limit);
     assert_eq!(true, resp_config.is_claim_disabled);
 
+    // The owner can change the ASTRO staking contract used by ClaimAndLock
+    router_ref
+        .execute_contract(
+            owner.clone(),
+            escrow_fee_distributor.clone(),
+            &ExecuteMsg::UpdateConfig {
+                claim_many_limit: None,
+                is_claim_disabled: None,
+                astro_staking: Some("new_staking".to_string()),
+                claim_expiry_periods: None,
+                max_periods_per_claim: None,
+                native_denoms: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let resp_config: ConfigResponse = router_ref
+        .wrap()
+        .query_wasm_smart(&escrow_fee_distributor.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        Some(Addr::unchecked("new_staking")),
+        resp_config.astro_staking
+    );
+
     assert_eq!(
         vec![
             attr("action", "update_config"),
@@ -530,8 +568,41 @@ fn claim_max_period() {
         resp
     );
 
+    // Simulate claims for user1
+    let resp: SimulateClaimResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &base_pack.escrow_fee_distributor.clone().unwrap().address,
+            &QueryMsg::SimulateClaim {
+                user: user1.to_string(),
+                max_periods: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::new(50_000_000), resp.amount);
+    assert_eq!(1, resp.rewards_per_period.len());
+
+    let resp: SimulateClaimResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &base_pack.escrow_fee_distributor.clone().unwrap().address,
+            &QueryMsg::SimulateClaim {
+                user: user1.to_string(),
+                max_periods: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::new(100_000_000), resp.amount);
+    assert_eq!(
+        vec![Uint128::new(50_000_000), Uint128::new(50_000_000)],
+        resp.rewards_per_period[..2]
+            .iter()
+            .map(|(_, reward)| *reward)
+            .collect::<Vec<_>>()
+    );
+
     // Claim fees for max period for user1(firstly 1 period)
-    router_ref
+    let resp = router_ref
         .execute_contract(
             user1.clone(),
             base_pack.escrow_fee_distributor.clone().unwrap().address,
@@ -542,6 +613,11 @@ fn claim_max_period() {
             &[],
         )
         .unwrap();
+    // Lock periods are claimable until the lock end (inclusive)
+    assert_eq!(
+        resp.events[1].attributes[4],
+        attr("remaining_periods", "104")
+    );
 
     check_balance(
         router_ref,
@@ -550,17 +626,76 @@ fn claim_max_period() {
         50_000_000,
     );
 
+    // Limit periods processed in a single claim
+    let err = router_ref
+        .execute_contract(
+            owner.clone(),
+            base_pack.escrow_fee_distributor.clone().unwrap().address,
+            &ExecuteMsg::UpdateConfig {
+                claim_many_limit: None,
+                is_claim_disabled: None,
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: Some(0),
+                native_denoms: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Generic error: Max periods per claim must be greater than 0!",
+        err.root_cause().to_string()
+    );
     router_ref
+        .execute_contract(
+            owner.clone(),
+            base_pack.escrow_fee_distributor.clone().unwrap().address,
+            &ExecuteMsg::UpdateConfig {
+                claim_many_limit: None,
+                is_claim_disabled: None,
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: Some(10),
+                native_denoms: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let resp = router_ref
         .execute_contract(
             user1.clone(),
             base_pack.escrow_fee_distributor.clone().unwrap().address,
             &ExecuteMsg::Claim {
                 recipient: None,
-                max_periods: None,
+                max_periods: Some(50),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        resp.events[1].attributes[4],
+        attr("remaining_periods", "94")
+    );
+
+    // Claims for several accounts report the periods left for every account
+    let resp = router_ref
+        .execute_contract(
+            user1.clone(),
+            base_pack.escrow_fee_distributor.clone().unwrap().address,
+            &ExecuteMsg::ClaimMany {
+                receivers: vec![user1.to_string()],
             },
             &[],
         )
         .unwrap();
+    assert_eq!(
+        resp.events[1].attributes[3..],
+        [
+            attr("receiver", user1.to_string()),
+            attr("remaining_periods", "84")
+        ]
+    );
 
     // Claim fees for max period for user2
     router_ref
@@ -1034,6 +1169,10 @@ fn is_claim_enabled() {
             &ExecuteMsg::UpdateConfig {
                 claim_many_limit: None,
                 is_claim_disabled: Some(true),
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: None,
+                native_denoms: None,
             },
             &[],
         )
@@ -1104,6 +1243,10 @@ fn is_claim_enabled() {
             &ExecuteMsg::UpdateConfig {
                 claim_many_limit: None,
                 is_claim_disabled: Some(false),
+                astro_staking: None,
+                claim_expiry_periods: None,
+                max_periods_per_claim: None,
+                native_denoms: None,
             },
             &[],
         )
@@ -1139,3 +1282,803 @@ fn is_claim_enabled() {
         0,
     );
 }
+
+#[test]
+fn claim_and_lock() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked(OWNER);
+    let maker = Addr::unchecked(MAKER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let base_pack = init_astroport_test_package(router_ref).unwrap();
+    let distributor = base_pack.escrow_fee_distributor.clone().unwrap().address;
+    let xastro_token = base_pack.get_staking_xastro(router_ref);
+
+    // Stakes 200_000_000 ASTRO for user1 and locks 100_000_000 xASTRO for WEEK * 10
+    mint(
+        router_ref,
+        owner.clone(),
+        base_pack.astro_token.clone().unwrap().address,
+        &user1,
+        200,
+    );
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            base_pack.astro_token.clone().unwrap().address,
+            &Cw20ExecuteMsg::Send {
+                contract: base_pack.staking.clone().unwrap().address.to_string(),
+                msg: to_binary(&StakingHookMsg::Enter {}).unwrap(),
+                amount: Uint128::from(200 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+    base_pack
+        .create_lock(router_ref, user1.clone(), WEEK * 10, 100)
+        .unwrap();
+
+    mint(
+        router_ref,
+        owner.clone(),
+        base_pack.astro_token.clone().unwrap().address,
+        &maker,
+        100,
+    );
+
+    // Sends 100_000_000 ASTRO from the Maker to the distributor for the first period
+    router_ref
+        .execute_contract(
+            maker.clone(),
+            base_pack.astro_token.clone().unwrap().address,
+            &Cw20ExecuteMsg::Send {
+                contract: distributor.to_string(),
+                msg: to_binary(&Cw20HookMsg::ReceiveTokens {}).unwrap(),
+                amount: Uint128::from(100 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Rewards can't be locked for users without a lock
+    let err = router_ref
+        .execute_contract(
+            user2.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ClaimAndLock {},
+            &[],
+        )
+        .unwrap_err();
+    assert!(err.root_cause().to_string().contains("User is not found"));
+
+    // Rewards for the current period aren't available yet
+    let err = router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ClaimAndLock {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("Nothing to claim!", err.root_cause().to_string());
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    // xASTRO sent to the distributor by mistake isn't deposited in the claimer's lock
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            xastro_token.clone(),
+            &Cw20ExecuteMsg::Transfer {
+                recipient: distributor.to_string(),
+                amount: Uint128::from(10 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ClaimAndLock {},
+            &[],
+        )
+        .unwrap();
+
+    // Claimed ASTRO was staked and deposited in user1's lock
+    let resp: LockInfoResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &base_pack.voting_escrow.clone().unwrap().address,
+            &VotingEscrowQueryMsg::LockInfo {
+                user: user1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(resp.amount > Uint128::new(100_000_000));
+    check_balance(
+        router_ref,
+        &base_pack.astro_token.clone().unwrap().address,
+        &user1,
+        0,
+    );
+    check_balance(
+        router_ref,
+        &base_pack.astro_token.clone().unwrap().address,
+        &distributor,
+        0,
+    );
+    check_balance(
+        router_ref,
+        &xastro_token,
+        &distributor,
+        10 * MULTIPLIER as u128,
+    );
+}
+
+#[test]
+fn recycle_expired_rewards() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked(OWNER);
+    let maker = Addr::unchecked(MAKER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let base_pack = init_astroport_test_package(router_ref).unwrap();
+    let distributor = base_pack.escrow_fee_distributor.clone().unwrap().address;
+    let astro_token = base_pack.astro_token.clone().unwrap().address;
+    let xastro_token = base_pack.get_staking_xastro(router_ref);
+
+    for user in [user1.clone(), user2.clone()] {
+        mint(
+            router_ref,
+            base_pack.staking.clone().unwrap().address,
+            xastro_token.clone(),
+            &user,
+            100,
+        );
+        base_pack
+            .create_lock(router_ref, user.clone(), WEEK * 10, 100)
+            .unwrap();
+    }
+
+    // Recycling isn't possible until the claim expiry is set
+    let err = router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::RecycleExpired {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("Claim expiry is not set!", err.root_cause().to_string());
+
+    let err = router_ref
+        .execute_contract(
+            owner.clone(),
+            distributor.clone(),
+            &ExecuteMsg::UpdateConfig {
+                claim_many_limit: None,
+                is_claim_disabled: None,
+                astro_staking: None,
+                claim_expiry_periods: Some(0),
+                max_periods_per_claim: None,
+                native_denoms: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Generic error: Claim expiry periods must be greater than 0!",
+        err.root_cause().to_string()
+    );
+
+    router_ref
+        .execute_contract(
+            owner.clone(),
+            distributor.clone(),
+            &ExecuteMsg::UpdateConfig {
+                claim_many_limit: None,
+                is_claim_disabled: None,
+                astro_staking: None,
+                claim_expiry_periods: Some(2),
+                max_periods_per_claim: None,
+                native_denoms: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Sends 100_000_000 ASTRO from the Maker to the distributor for the first period
+    mint(router_ref, owner.clone(), astro_token.clone(), &maker, 100);
+    router_ref
+        .execute_contract(
+            maker.clone(),
+            astro_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: distributor.to_string(),
+                msg: to_binary(&Cw20HookMsg::ReceiveTokens {}).unwrap(),
+                amount: Uint128::from(100 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    // Only user1 claims rewards for the first period
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::Claim {
+                recipient: None,
+                max_periods: None,
+            },
+            &[],
+        )
+        .unwrap();
+    check_balance(router_ref, &astro_token, &user1, 50_000_000);
+
+    // Nothing is expired yet
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::RecycleExpired {},
+            &[],
+        )
+        .unwrap();
+    let resp: Vec<Uint128> = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &distributor,
+            &QueryMsg::AvailableRewardPerWeek {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(vec![Uint128::new(100_000_000)], resp);
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK * 2));
+
+    // Rewards for the first period are expired and user2's share is moved to the current period
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::RecycleExpired {},
+            &[],
+        )
+        .unwrap();
+    let resp: Vec<Uint128> = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &distributor,
+            &QueryMsg::AvailableRewardPerWeek {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![Uint128::new(100_000_000), Uint128::new(50_000_000)],
+        resp
+    );
+
+    // Expired rewards can't be claimed anymore
+    router_ref
+        .execute_contract(
+            user2.clone(),
+            distributor.clone(),
+            &ExecuteMsg::Claim {
+                recipient: None,
+                max_periods: None,
+            },
+            &[],
+        )
+        .unwrap();
+    check_balance(router_ref, &astro_token, &user2, 0);
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    // Recycled rewards are distributed between current stakers
+    for user in [user1.clone(), user2.clone()] {
+        router_ref
+            .execute_contract(
+                user.clone(),
+                distributor.clone(),
+                &ExecuteMsg::Claim {
+                    recipient: None,
+                    max_periods: None,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+    check_balance(router_ref, &astro_token, &user1, 75_000_000);
+    check_balance(router_ref, &astro_token, &user2, 25_000_000);
+    check_balance(router_ref, &astro_token, &distributor, 0);
+
+    let resp: Vec<PeriodStatsResponse> = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &distributor,
+            &QueryMsg::PeriodStats {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(2, resp.len());
+    assert_eq!(resp[0].period + 3, resp[1].period);
+    assert_eq!(Uint128::new(100_000_000), resp[0].deposited);
+    assert_eq!(Uint128::new(50_000_000), resp[0].claimed);
+    assert_eq!(Uint128::new(50_000_000), resp[1].deposited);
+    assert_eq!(Uint128::new(50_000_000), resp[1].claimed);
+    assert!(resp[0].total_voting_power > resp[1].total_voting_power);
+
+    let resp: Vec<PeriodStatsResponse> = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &distributor,
+            &QueryMsg::PeriodStats {
+                start_after: Some(resp[0].period),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(1, resp.len());
+    assert_eq!(Uint128::new(50_000_000), resp[0].deposited);
+}
+
+#[test]
+fn checkpoint_hooks() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked(OWNER);
+    let maker = Addr::unchecked(MAKER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let base_pack = init_astroport_test_package(router_ref).unwrap();
+    let distributor = base_pack.escrow_fee_distributor.clone().unwrap().address;
+    let voting_escrow = base_pack.voting_escrow.clone().unwrap().address;
+    let astro_token = base_pack.astro_token.clone().unwrap().address;
+    let xastro_token = base_pack.get_staking_xastro(router_ref);
+
+    // Only the voting escrow can push checkpoints
+    let err = router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::CheckpointHook(CheckpointHookMsg {
+                user: user1.to_string(),
+                power: Uint128::new(1_000_000_000),
+                slope: Uint128::zero(),
+                start: 0,
+                end: 1000,
+                nonce: 1,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
+
+    router_ref
+        .execute_contract(
+            owner.clone(),
+            voting_escrow.clone(),
+            &VotingEscrowExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                checkpoint_hooks: Some(vec![distributor.to_string()]),
+                generator_controller: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let resp: VotingEscrowConfigResponse = router_ref
+        .wrap()
+        .query_wasm_smart(&voting_escrow, &VotingEscrowQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(vec![distributor.clone()], resp.checkpoint_hooks);
+
+    for user in [user1.clone(), user2.clone()] {
+        mint(
+            router_ref,
+            base_pack.staking.clone().unwrap().address,
+            xastro_token.clone(),
+            &user,
+            100,
+        );
+    }
+    let resp = base_pack
+        .create_lock(router_ref, user1.clone(), WEEK * 10, 100)
+        .unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value == "checkpoint_hook")));
+    base_pack
+        .create_lock(router_ref, user2.clone(), WEEK * 5, 100)
+        .unwrap();
+
+    // Sends 100_000_000 ASTRO from the Maker to the distributor for the first period
+    mint(router_ref, owner.clone(), astro_token.clone(), &maker, 100);
+    router_ref
+        .execute_contract(
+            maker.clone(),
+            astro_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: distributor.to_string(),
+                msg: to_binary(&Cw20HookMsg::ReceiveTokens {}).unwrap(),
+                amount: Uint128::from(100 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    // Rewards calculated with pushed checkpoints match voting power in the voting escrow
+    let period = get_period(router_ref.block_info().time.seconds()).unwrap() - 1;
+    let total_vp: VotingPowerResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &voting_escrow,
+            &VotingEscrowQueryMsg::TotalVotingPowerAtPeriod { period },
+        )
+        .unwrap();
+    for user in [user1.clone(), user2.clone()] {
+        let user_vp: VotingPowerResponse = router_ref
+            .wrap()
+            .query_wasm_smart(
+                &voting_escrow,
+                &VotingEscrowQueryMsg::UserVotingPowerAtPeriod {
+                    user: user.to_string(),
+                    period,
+                },
+            )
+            .unwrap();
+        let expected =
+            Uint128::new(100_000_000).multiply_ratio(user_vp.voting_power, total_vp.voting_power);
+
+        router_ref
+            .execute_contract(
+                user.clone(),
+                distributor.clone(),
+                &ExecuteMsg::Claim {
+                    recipient: None,
+                    max_periods: None,
+                },
+                &[],
+            )
+            .unwrap();
+        check_balance(router_ref, &astro_token, &user, expected.u128());
+    }
+}
+
+#[test]
+fn failed_checkpoint_hook() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked(OWNER);
+    let maker = Addr::unchecked(MAKER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let mut base_pack = init_astroport_test_package(router_ref).unwrap();
+    let distributor = base_pack.escrow_fee_distributor.clone().unwrap().address;
+    let voting_escrow = base_pack.voting_escrow.clone().unwrap().address;
+    let astro_token = base_pack.astro_token.clone().unwrap().address;
+    let xastro_token = base_pack.get_staking_xastro(router_ref);
+
+    let set_checkpoint_hooks = |router: &mut App, hooks: Vec<String>| {
+        router
+            .execute_contract(
+                owner.clone(),
+                voting_escrow.clone(),
+                &VotingEscrowExecuteMsg::UpdateConfig {
+                    new_guardian: None,
+                    checkpoint_hooks: Some(hooks),
+                    generator_controller: None,
+                },
+                &[],
+            )
+            .unwrap();
+    };
+    set_checkpoint_hooks(router_ref, vec![distributor.to_string()]);
+
+    for user in [user1.clone(), user2.clone()] {
+        mint(
+            router_ref,
+            base_pack.staking.clone().unwrap().address,
+            xastro_token.clone(),
+            &user,
+            200,
+        );
+        base_pack
+            .create_lock(router_ref, user, WEEK * 10, 100)
+            .unwrap();
+    }
+
+    // The ASTRO token doesn't handle checkpoint hooks, so the distributor misses user1's checkpoint
+    set_checkpoint_hooks(router_ref, vec![astro_token.to_string()]);
+    let resp = base_pack
+        .extend_lock_amount(router_ref, USER1, 100)
+        .unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value == "checkpoint_hook_failed")));
+    set_checkpoint_hooks(router_ref, vec![distributor.to_string()]);
+
+    // Sends 100_000_000 ASTRO from the Maker to the distributor for the first period
+    mint(router_ref, owner.clone(), astro_token.clone(), &maker, 100);
+    router_ref
+        .execute_contract(
+            maker.clone(),
+            astro_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: distributor.to_string(),
+                msg: to_binary(&Cw20HookMsg::ReceiveTokens {}).unwrap(),
+                amount: Uint128::from(100 * MULTIPLIER as u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    // The stale checkpoint is ignored, rewards match voting power in the voting escrow
+    let period = get_period(router_ref.block_info().time.seconds()).unwrap() - 1;
+    let total_vp: VotingPowerResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &voting_escrow,
+            &VotingEscrowQueryMsg::TotalVotingPowerAtPeriod { period },
+        )
+        .unwrap();
+    for user in [user1.clone(), user2.clone()] {
+        let user_vp: VotingPowerResponse = router_ref
+            .wrap()
+            .query_wasm_smart(
+                &voting_escrow,
+                &VotingEscrowQueryMsg::UserVotingPowerAtPeriod {
+                    user: user.to_string(),
+                    period,
+                },
+            )
+            .unwrap();
+        let expected =
+            Uint128::new(100_000_000).multiply_ratio(user_vp.voting_power, total_vp.voting_power);
+
+        router_ref
+            .execute_contract(
+                user.clone(),
+                distributor.clone(),
+                &ExecuteMsg::Claim {
+                    recipient: None,
+                    max_periods: None,
+                },
+                &[],
+            )
+            .unwrap();
+        check_balance(router_ref, &astro_token, &user, expected.u128());
+    }
+}
+
+#[test]
+fn native_rewards_funding() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let maker = Addr::unchecked(MAKER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let base_pack = init_astroport_test_package(router_ref).unwrap();
+    let distributor = base_pack.escrow_fee_distributor.clone().unwrap().address;
+    let xastro_token = base_pack.get_staking_xastro(router_ref);
+
+    for user in [user1.clone(), user2.clone()] {
+        mint(
+            router_ref,
+            base_pack.staking.clone().unwrap().address,
+            xastro_token.clone(),
+            &user,
+            100,
+        );
+        base_pack
+            .create_lock(router_ref, user, WEEK * 10, 100)
+            .unwrap();
+    }
+
+    router_ref
+        .sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: maker.to_string(),
+            amount: vec![coin(100_000_000, DENOM), coin(10_000_000, "uusd")],
+        }))
+        .unwrap();
+
+    // Native coins must be sent along with the message
+    let err = router_ref
+        .execute_contract(
+            maker.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ReceiveTokens {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("No native coins were sent!", err.root_cause().to_string());
+
+    // Only denoms allowed by the owner are accepted
+    let err = router_ref
+        .execute_contract(
+            maker.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ReceiveTokens {},
+            &[coin(100_000_000, DENOM)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Native denom uluna is not allowed!",
+        err.root_cause().to_string()
+    );
+
+    let update_denoms_msg = |native_denoms: Vec<String>| ExecuteMsg::UpdateConfig {
+        claim_many_limit: None,
+        is_claim_disabled: None,
+        astro_staking: None,
+        claim_expiry_periods: None,
+        max_periods_per_claim: None,
+        native_denoms: Some(native_denoms),
+    };
+
+    let err = router_ref
+        .execute_contract(
+            maker.clone(),
+            distributor.clone(),
+            &update_denoms_msg(vec![DENOM.to_string()]),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
+
+    let err = router_ref
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            distributor.clone(),
+            &update_denoms_msg(
+                (0..=MAX_NATIVE_DENOMS)
+                    .map(|i| format!("denom{i}"))
+                    .collect(),
+            ),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        format!("Exceeded the limit of {MAX_NATIVE_DENOMS} native denoms per period!"),
+        err.root_cause().to_string()
+    );
+
+    router_ref
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            distributor.clone(),
+            &update_denoms_msg(vec![DENOM.to_string(), "uusd".to_string()]),
+            &[],
+        )
+        .unwrap();
+    let config: ConfigResponse = router_ref
+        .wrap()
+        .query_wasm_smart(&distributor, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        vec![DENOM.to_string(), "uusd".to_string()],
+        config.native_denoms
+    );
+
+    // A junk denom sent together with allowed ones is rejected as well
+    router_ref
+        .sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: maker.to_string(),
+            amount: vec![coin(1, "ujunk")],
+        }))
+        .unwrap();
+    let err = router_ref
+        .execute_contract(
+            maker.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ReceiveTokens {},
+            &[coin(100_000_000, DENOM), coin(1, "ujunk")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Native denom ujunk is not allowed!",
+        err.root_cause().to_string()
+    );
+
+    router_ref
+        .execute_contract(
+            maker.clone(),
+            distributor.clone(),
+            &ExecuteMsg::ReceiveTokens {},
+            &[coin(100_000_000, DENOM), coin(10_000_000, "uusd")],
+        )
+        .unwrap();
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|b| b.time = b.time.plus_seconds(WEEK));
+
+    let resp: SimulateClaimResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            &distributor,
+            &QueryMsg::SimulateClaim {
+                user: user1.to_string(),
+                max_periods: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), resp.amount);
+    assert_eq!(
+        vec![coin(50_000_000, DENOM), coin(5_000_000, "uusd")],
+        resp.native_amount
+    );
+
+    // Both users have the same voting power, so native rewards are split equally
+    for user in [user1.clone(), user2.clone()] {
+        router_ref
+            .execute_contract(
+                user.clone(),
+                distributor.clone(),
+                &ExecuteMsg::Claim {
+                    recipient: None,
+                    max_periods: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(
+            coin(50_000_000, DENOM),
+            router_ref.wrap().query_balance(&user, DENOM).unwrap()
+        );
+        assert_eq!(
+            coin(5_000_000, "uusd"),
+            router_ref.wrap().query_balance(&user, "uusd").unwrap()
+        );
+    }
+
+    // Claimed rewards can't be claimed twice
+    router_ref
+        .execute_contract(
+            user1.clone(),
+            distributor.clone(),
+            &ExecuteMsg::Claim {
+                recipient: None,
+                max_periods: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::new(50_000_000),
+        router_ref
+            .wrap()
+            .query_balance(&user1, DENOM)
+            .unwrap()
+            .amount
+    );
+}
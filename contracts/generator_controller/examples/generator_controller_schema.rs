@@ -0,0 +1,13 @@
+use astroport_governance::generator_controller::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use cosmwasm_schema::write_api;
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
+    }
+}
@@ -6,31 +6,34 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, Fraction, MessageInfo, Order,
+    attr, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
     Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use itertools::Itertools;
+use cw_storage_plus::Bound;
 
 use astroport_governance::generator_controller::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UserInfoResponse, VOTERS_MAX_LIMIT,
+    AvailableVotePowerResponse, ExecuteMsg, GaugeDelegationResponse, GaugeInfoResponse,
+    InstantiateMsg, MigrateMsg, QueryMsg, UserInfoResponse, VOTERS_MAX_LIMIT,
 };
 use astroport_governance::utils::{calc_voting_power, get_period, WEEK};
 use astroport_governance::voting_escrow::QueryMsg::CheckVotersAreBlacklisted;
 use astroport_governance::voting_escrow::{
     get_lock_info, get_voting_power, BlacklistedVotersResponse,
 };
+use astroport_governance::{DEFAULT_LIMIT, MAX_LIMIT};
 
 use crate::bps::BasicPoints;
 use crate::error::ContractError;
 use crate::state::{
-    Config, TuneInfo, UserInfo, VotedPoolInfo, CONFIG, OWNERSHIP_PROPOSAL, POOLS, TUNE_INFO,
-    USER_INFO,
+    Config, TuneInfo, UserInfo, VotedPoolInfo, CONFIG, GAUGE_DELEGATES, OWNERSHIP_PROPOSAL, POOLS,
+    POOL_MIGRATIONS, POOL_PERIODS, POOL_VOTES, TUNE_INFO, TUNING_PAUSED, USER_INFO,
+    USER_VOTES_HISTORY,
 };
 
 use crate::utils::{
-    cancel_user_changes, check_duplicated, filter_pools, get_pool_info, update_pool_info,
-    validate_pool, validate_pools_limit, vote_for_pool,
+    calc_pool_alloc_points, cancel_user_changes, check_duplicated, get_pool_info, move_pool_votes,
+    resolve_pool, update_pool_info, validate_pool, validate_pools_limit, vote_for_pool,
 };
 
 /// Contract name that is used for migration.
@@ -68,6 +71,7 @@ pub fn instantiate(
             main_pool: None,
             main_pool_min_alloc: Decimal::zero(),
             whitelisted_pools: vec![],
+            emergency_committee: None,
         },
     )?;
 
@@ -89,7 +93,9 @@ pub fn instantiate(
 /// * **ExecuteMsg::KickBlacklistedVoters { blacklisted_voters }** Removes all votes applied by
 /// blacklisted voters
 ///
-/// * **ExecuteMsg::Vote { votes }** Casts votes for pools
+/// * **ExecuteMsg::Vote { votes, persist }** Casts votes for pools
+///
+/// * **ExecuteMsg::RefreshVotes { voters }** Re-applies persistent votes using the latest voting power
 ///
 /// * **ExecuteMsg::TunePools** Launches pool tuning
 ///
@@ -97,7 +103,7 @@ pub fn instantiate(
 /// to receive allocation points
 ///
 /// * **ExecuteMsg::UpdateConfig { blacklisted_voters_limit }** Changes the number of blacklisted
-/// voters that can be kicked at once
+/// voters that can be kicked at once, the main pool settings and the emergency committee
 ///
 /// * **ExecuteMsg::UpdateWhitelist { add, remove }** Adds or removes lp tokens which are eligible
 /// to receive votes.
@@ -108,13 +114,30 @@ pub fn instantiate(
 /// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
 ///
 /// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+///
+/// * **ExecuteMsg::DelegateGaugeVotes { strategist }** Delegates emission voting power to a strategist.
+///
+/// * **ExecuteMsg::MigratePoolVotes { from_lp, to_lp }** Moves votes from a replaced LP token to a new one.
+///
+/// * **ExecuteMsg::PauseTuning {}** Freezes pools tuning.
+///
+/// * **ExecuteMsg::UnpauseTuning {}** Resumes pools tuning.
+///
+/// * **ExecuteMsg::UndelegateGaugeVotes {}** Revokes emission voting power delegation.
+///
+/// * **ExecuteMsg::VoteOnBehalf { voter, votes, persist }** Casts votes for pools on behalf of a delegator.
+///
+/// * **ExecuteMsg::PruneVotesHook { voter }** Removes votes of a voter whose lock expired or who was blacklisted.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ExecuteResult {
     match msg {
         ExecuteMsg::KickBlacklistedVoters { blacklisted_voters } => {
             kick_blacklisted_voters(deps, env, blacklisted_voters)
         }
-        ExecuteMsg::Vote { votes } => handle_vote(deps, env, info, votes),
+        ExecuteMsg::Vote { votes, persist } => {
+            handle_vote(deps, env, info.sender, votes, persist.unwrap_or_default())
+        }
+        ExecuteMsg::RefreshVotes { voters } => refresh_votes(deps, env, voters),
         ExecuteMsg::TunePools {} => tune_pools(deps, env),
         ExecuteMsg::ChangePoolsLimit { limit } => change_pools_limit(deps, info, limit),
         ExecuteMsg::UpdateConfig {
@@ -122,6 +145,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
             main_pool,
             main_pool_min_alloc,
             remove_main_pool,
+            emergency_committee,
         } => update_config(
             deps,
             info,
@@ -129,7 +153,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
             main_pool,
             main_pool_min_alloc,
             remove_main_pool,
+            emergency_committee,
         ),
+        ExecuteMsg::MigratePoolVotes { from_lp, to_lp } => {
+            migrate_pool_votes(deps, info, from_lp, to_lp)
+        }
+        ExecuteMsg::PauseTuning {} => pause_tuning(deps, info),
+        ExecuteMsg::UnpauseTuning {} => unpause_tuning(deps, info),
         ExecuteMsg::UpdateWhitelist { add, remove } => update_whitelist(deps, info, add, remove),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
@@ -165,9 +195,60 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
             })
             .map_err(Into::into)
         }
+        ExecuteMsg::DelegateGaugeVotes { strategist } => {
+            delegate_gauge_votes(deps, info, strategist)
+        }
+        ExecuteMsg::UndelegateGaugeVotes {} => undelegate_gauge_votes(deps, info),
+        ExecuteMsg::VoteOnBehalf {
+            voter,
+            votes,
+            persist,
+        } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            match GAUGE_DELEGATES.may_load(deps.storage, &voter)? {
+                Some(strategist) if strategist == info.sender => {
+                    handle_vote(deps, env, voter, votes, persist.unwrap_or_default())
+                }
+                _ => Err(ContractError::Unauthorized {}),
+            }
+        }
+        ExecuteMsg::PruneVotesHook { voter } => prune_votes_hook(deps, env, info, voter),
     }
 }
 
+/// Delegates the sender's emission voting power to a strategist.
+/// The strategist is able to vote for pools on behalf of the sender until the delegation is revoked.
+/// The sender is still able to vote by themselves.
+fn delegate_gauge_votes(deps: DepsMut, info: MessageInfo, strategist: String) -> ExecuteResult {
+    let strategist = deps.api.addr_validate(&strategist)?;
+    if strategist == info.sender {
+        return Err(ContractError::SelfGaugeDelegation {});
+    }
+
+    GAUGE_DELEGATES.save(deps.storage, &info.sender, &strategist)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "delegate_gauge_votes"),
+        attr("user", info.sender),
+        attr("strategist", strategist),
+    ]))
+}
+
+/// Revokes the sender's emission voting power delegation.
+/// Votes already cast by the strategist remain in place until the sender votes again.
+fn undelegate_gauge_votes(deps: DepsMut, info: MessageInfo) -> ExecuteResult {
+    if !GAUGE_DELEGATES.has(deps.storage, &info.sender) {
+        return Err(ContractError::GaugeVotesNotDelegated {});
+    }
+
+    GAUGE_DELEGATES.remove(deps.storage, &info.sender);
+
+    Ok(Response::new().add_attributes([
+        attr("action", "undelegate_gauge_votes"),
+        attr("user", info.sender),
+    ]))
+}
+
 /// Adds or removes lp tokens which are eligible to receive votes.
 /// Returns a [`ContractError`] on failure.
 fn update_whitelist(
@@ -212,8 +293,7 @@ fn update_whitelist(
 /// This function removes all votes applied by blacklisted voters.
 ///
 /// * **holders** list with blacklisted holders whose votes will be removed.
-fn kick_blacklisted_voters(deps: DepsMut, env: Env, voters: Vec<String>) -> ExecuteResult {
-    let block_period = get_period(env.block.time.seconds())?;
+fn kick_blacklisted_voters(mut deps: DepsMut, env: Env, voters: Vec<String>) -> ExecuteResult {
     let config = CONFIG.load(deps.storage)?;
 
     if voters.len() > config.blacklisted_voters_limit.unwrap_or(VOTERS_MAX_LIMIT) as usize {
@@ -240,44 +320,81 @@ fn kick_blacklisted_voters(deps: DepsMut, env: Env, voters: Vec<String>) -> Exec
 
     for voter in voters {
         let voter_addr = deps.api.addr_validate(&voter)?;
-        if let Some(user_info) = USER_INFO.may_load(deps.storage, &voter_addr)? {
-            if user_info.lock_end > block_period {
-                let user_last_vote_period = get_period(user_info.vote_ts)?;
-                // Calculate voting power before changes
-                let old_vp_at_period = calc_voting_power(
-                    user_info.slope,
-                    user_info.voting_power,
-                    user_last_vote_period,
-                    block_period,
-                );
-
-                // Cancel changes applied by previous votes
-                user_info.votes.iter().try_for_each(|(pool_addr, bps)| {
-                    cancel_user_changes(
-                        deps.storage,
-                        block_period + 1,
-                        pool_addr,
-                        *bps,
-                        old_vp_at_period,
-                        user_info.slope,
-                        user_info.lock_end,
-                    )
-                })?;
-
-                let user_info = UserInfo {
-                    vote_ts: env.block.time.seconds(),
-                    lock_end: block_period,
-                    ..Default::default()
-                };
-
-                USER_INFO.save(deps.storage, &voter_addr, &user_info)?;
-            }
-        }
+        remove_user_votes(deps.branch(), &env, &voter_addr)?;
     }
 
     Ok(Response::new().add_attribute("action", "kick_holders"))
 }
 
+/// Removes all votes of a vxASTRO holder. Called by the voting escrow contract when the holder's
+/// lock is withdrawn or the holder is blacklisted, so their votes are pruned in the same block.
+///
+/// * **voter** vxASTRO holder whose votes will be removed.
+fn prune_votes_hook(deps: DepsMut, env: Env, info: MessageInfo, voter: String) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.escrow_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let voter_addr = deps.api.addr_validate(&voter)?;
+    remove_user_votes(deps, &env, &voter_addr)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "prune_votes_hook"),
+        attr("voter", voter),
+    ]))
+}
+
+/// Cancels the changes applied by the user's votes and resets their [`UserInfo`].
+/// Votes of an expired lock have already decayed, so only their allocations are cleared.
+///
+/// * **voter** vxASTRO holder whose votes will be removed.
+fn remove_user_votes(deps: DepsMut, env: &Env, voter: &Addr) -> Result<(), ContractError> {
+    let block_period = get_period(env.block.time.seconds())?;
+    let user_info = match USER_INFO.may_load(deps.storage, voter)? {
+        Some(user_info) => user_info,
+        None => return Ok(()),
+    };
+
+    let user_info = if user_info.lock_end > block_period {
+        // Calculate voting power before changes
+        let old_vp_at_period = user_info.voting_power_at(block_period)?;
+
+        // Cancel changes applied by previous votes
+        user_info.votes.iter().try_for_each(|(pool_addr, bps)| {
+            cancel_user_changes(
+                deps.storage,
+                block_period + 1,
+                pool_addr,
+                *bps,
+                old_vp_at_period,
+                user_info.slope,
+                user_info.lock_end,
+            )
+        })?;
+
+        UserInfo {
+            vote_ts: env.block.time.seconds(),
+            lock_end: block_period,
+            ..Default::default()
+        }
+    } else if !user_info.votes.is_empty() {
+        // Keep vote_ts so the user isn't put on a vote cooldown after relocking
+        UserInfo {
+            votes: vec![],
+            persist: false,
+            ..user_info
+        }
+    } else {
+        return Ok(());
+    };
+
+    USER_INFO.save(deps.storage, voter, &user_info)?;
+    USER_VOTES_HISTORY.save(deps.storage, (voter, block_period + 1), &user_info)?;
+
+    Ok(())
+}
+
 /// The function checks that:
 /// * the user voting power is > 0,
 /// * user didn't vote for last 10 days,
@@ -290,16 +407,20 @@ fn kick_blacklisted_voters(deps: DepsMut, env: Env, voters: Vec<String>) -> Exec
 ///
 /// The function returns [`Response`] in case of success or [`ContractError`] in case of errors.
 ///
+/// * **user** is the vxASTRO holder whose voting power is used.
+///
 /// * **votes** is a vector of pairs ([`String`], [`u16`]).
 /// Tuple consists of pool address and percentage of user's voting power for a given pool.
 /// Percentage should be in BPS form.
+///
+/// * **persist** whether votes can be refreshed with the user's latest voting power via [`ExecuteMsg::RefreshVotes`].
 fn handle_vote(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    user: Addr,
     votes: Vec<(String, u16)>,
+    persist: bool,
 ) -> ExecuteResult {
-    let user = info.sender;
     let block_period = get_period(env.block.time.seconds())?;
     let config = CONFIG.load(deps.storage)?;
     let user_vp = get_voting_power(&deps.querier, &config.escrow_addr, &user)?;
@@ -361,14 +482,8 @@ fn handle_vote(
         })?;
 
     if user_info.lock_end > block_period {
-        let user_last_vote_period = get_period(user_info.vote_ts).unwrap_or(block_period);
         // Calculate voting power before changes
-        let old_vp_at_period = calc_voting_power(
-            user_info.slope,
-            user_info.voting_power,
-            user_last_vote_period,
-            block_period,
-        );
+        let old_vp_at_period = user_info.voting_power_at(block_period)?;
 
         // Cancel changes applied by previous votes
         user_info.votes.iter().try_for_each(|(pool_addr, bps)| {
@@ -405,13 +520,117 @@ fn handle_vote(
         slope: ve_lock_info.slope,
         lock_end: ve_lock_info.end,
         votes,
+        persist,
     };
 
     USER_INFO.save(deps.storage, &user, &user_info)?;
+    USER_VOTES_HISTORY.save(deps.storage, (&user, block_period + 1), &user_info)?;
 
     Ok(Response::new().add_attribute("action", "vote"))
 }
 
+/// This function re-applies persistent votes of the specified users using their latest vxASTRO voting power,
+/// so the users' allocation carries into subsequent tunes until changed.
+/// Votes for pools which are no longer whitelisted are dropped.
+/// Users without persistent votes or without voting power are skipped.
+/// The users' vote cooldown isn't affected.
+///
+/// * **voters** list of users whose votes will be refreshed.
+fn refresh_votes(deps: DepsMut, env: Env, voters: Vec<String>) -> ExecuteResult {
+    let block_period = get_period(env.block.time.seconds())?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if voters.len() > VOTERS_MAX_LIMIT as usize {
+        return Err(ContractError::RefreshVotersLimitExceeded {});
+    }
+
+    let mut refreshed = vec![];
+    for voter in voters {
+        let voter_addr = deps.api.addr_validate(&voter)?;
+        let user_info = match USER_INFO.may_load(deps.storage, &voter_addr)? {
+            Some(user_info) if user_info.persist => user_info,
+            _ => continue,
+        };
+        let user_vp = get_voting_power(&deps.querier, &config.escrow_addr, &voter_addr)?;
+        if user_vp.is_zero() {
+            continue;
+        }
+
+        if user_info.lock_end > block_period {
+            // Calculate voting power before changes
+            let old_vp_at_period = user_info.voting_power_at(block_period)?;
+
+            // Cancel changes applied by previous votes
+            user_info.votes.iter().try_for_each(|(pool_addr, bps)| {
+                cancel_user_changes(
+                    deps.storage,
+                    block_period + 1,
+                    pool_addr,
+                    *bps,
+                    old_vp_at_period,
+                    user_info.slope,
+                    user_info.lock_end,
+                )
+            })?;
+        }
+
+        let votes = user_info
+            .votes
+            .iter()
+            .map(|(pool_addr, bps)| Ok((resolve_pool(deps.storage, pool_addr)?, *bps)))
+            .collect::<StdResult<Vec<_>>>()?
+            .into_iter()
+            .filter(|(pool_addr, _)| config.whitelisted_pools.contains(pool_addr))
+            .collect::<Vec<_>>();
+
+        let ve_lock_info = get_lock_info(&deps.querier, &config.escrow_addr, &voter_addr)?;
+
+        // Votes are applied to the next period
+        votes.iter().try_for_each(|(pool_addr, bps)| {
+            vote_for_pool(
+                deps.storage,
+                block_period + 1,
+                pool_addr,
+                *bps,
+                user_vp,
+                ve_lock_info.slope,
+                ve_lock_info.end,
+            )
+        })?;
+
+        let refreshed_info = UserInfo {
+            vote_ts: env.block.time.seconds(),
+            voting_power: user_vp,
+            slope: ve_lock_info.slope,
+            lock_end: ve_lock_info.end,
+            votes,
+            persist: true,
+        };
+        USER_VOTES_HISTORY.save(
+            deps.storage,
+            (&voter_addr, block_period + 1),
+            &refreshed_info,
+        )?;
+
+        // Keep the last vote timestamp so the cooldown isn't affected. Voting power is adjusted,
+        // so it decays from the last vote period to the current voting power.
+        let vote_period = get_period(user_info.vote_ts)?;
+        let user_info = UserInfo {
+            vote_ts: user_info.vote_ts,
+            voting_power: user_vp
+                + ve_lock_info.slope * Uint128::from(block_period.saturating_sub(vote_period)),
+            ..refreshed_info
+        };
+        USER_INFO.save(deps.storage, &voter_addr, &user_info)?;
+
+        refreshed.push(voter_addr.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh_votes")
+        .add_attribute("voters", refreshed.join(",")))
+}
+
 /// The function checks that the last pools tuning happened >= 14 days ago.
 /// Then it calculates voting power for each pool at the current period, filters all pools which
 /// are not eligible to receive allocation points,
@@ -422,11 +641,15 @@ fn tune_pools(deps: DepsMut, env: Env) -> ExecuteResult {
     let config = CONFIG.load(deps.storage)?;
     let block_period = get_period(env.block.time.seconds())?;
 
+    if TUNING_PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::TuningPaused {});
+    }
+
     if env.block.time.seconds() - tune_info.tune_ts < TUNE_COOLDOWN {
         return Err(ContractError::CooldownError(TUNE_COOLDOWN / DAY));
     }
 
-    let pool_votes: Vec<_> = POOLS
+    let pool_votes = POOLS
         .keys(deps.as_ref().storage, None, None, Order::Ascending)
         .collect::<Vec<_>>()
         .into_iter()
@@ -440,60 +663,9 @@ fn tune_pools(deps: DepsMut, env: Env) -> ExecuteResult {
             }
             Ok((pool_addr, pool_info.vxastro_amount))
         })
-        .collect::<StdResult<Vec<_>>>()?
-        .into_iter()
-        .filter(|(_, vxastro_amount)| !vxastro_amount.is_zero())
-        .sorted_by(|(_, a), (_, b)| b.cmp(a)) // Sort in descending order
-        .collect();
-
-    tune_info.pool_alloc_points = filter_pools(
-        &deps.querier,
-        &config.generator_addr,
-        &config.factory_addr,
-        pool_votes,
-        config.pools_limit + 1, // +1 additional pool if we will need to remove the main pool
-    )?;
+        .collect::<StdResult<Vec<_>>>()?;
 
-    // Set allocation points for the main pool
-    match config.main_pool {
-        Some(main_pool) if !config.main_pool_min_alloc.is_zero() => {
-            // Main pool may appear in the pool list thus we need to eliminate its contribution in the total VP.
-            tune_info
-                .pool_alloc_points
-                .retain(|(pool, _)| pool != &main_pool.to_string());
-            // If there is no main pool in the filtered list then we need to remove additional pool
-            tune_info.pool_alloc_points = tune_info
-                .pool_alloc_points
-                .iter()
-                .take(config.pools_limit as usize)
-                .cloned()
-                .collect();
-
-            let total_vp: Uint128 = tune_info
-                .pool_alloc_points
-                .iter()
-                .fold(Uint128::zero(), |acc, (_, vp)| acc + vp);
-            // Calculate main pool contribution.
-            // Example (30% for the main pool): VP + x = y, x = 0.3y => y = VP/0.7  => x = 0.3 * VP / 0.7,
-            // where VP - total VP, x - main pool's contribution, y - new total VP.
-            // x = 0.3 * VP * (1-0.3)^(-1)
-            let main_pool_contribution = config.main_pool_min_alloc
-                * total_vp
-                * (Decimal::one() - config.main_pool_min_alloc).inv().unwrap();
-            tune_info
-                .pool_alloc_points
-                .push((main_pool.to_string(), main_pool_contribution))
-        }
-        _ => {
-            // there is no main pool or min alloc is 0%
-            tune_info.pool_alloc_points = tune_info
-                .pool_alloc_points
-                .iter()
-                .take(config.pools_limit as usize)
-                .cloned()
-                .collect();
-        }
-    }
+    tune_info.pool_alloc_points = calc_pool_alloc_points(&deps.querier, &config, pool_votes)?;
 
     if tune_info.pool_alloc_points.is_empty() {
         return Err(ContractError::TuneNoPools {});
@@ -526,6 +698,8 @@ fn tune_pools(deps: DepsMut, env: Env) -> ExecuteResult {
 /// * **main_pool_min_alloc** is a minimum percentage of ASTRO emissions that this pool should get every block
 ///
 /// * **remove_main_pool** should the main pool be removed or not
+///
+/// * **emergency_committee** is an address which is allowed to pause pools tuning
 fn update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -533,6 +707,7 @@ fn update_config(
     main_pool: Option<String>,
     main_pool_min_alloc: Option<Decimal>,
     remove_main_pool: Option<bool>,
+    emergency_committee: Option<String>,
 ) -> ExecuteResult {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -564,11 +739,118 @@ fn update_config(
         }
     }
 
+    if let Some(emergency_committee) = emergency_committee {
+        config.emergency_committee = Some(deps.api.addr_validate(&emergency_committee)?);
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attribute("action", "update_config"))
 }
 
+/// Only contract owner can call this function.
+/// The function moves all voting parameters and the whitelist entry from a replaced LP token to a new one.
+/// Users' votes for the replaced LP token are redirected to the new LP token.
+///
+/// * **from_lp** is the replaced LP token address.
+///
+/// * **to_lp** is the new LP token address.
+fn migrate_pool_votes(
+    deps: DepsMut,
+    info: MessageInfo,
+    from_lp: String,
+    to_lp: String,
+) -> ExecuteResult {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let from_pool = deps.api.addr_validate(&from_lp)?;
+    let to_pool = deps.api.addr_validate(&to_lp)?;
+
+    if from_pool == to_pool {
+        return Err(StdError::generic_err("LP tokens must be different").into());
+    }
+
+    // An LP token can be replaced only once
+    if POOL_MIGRATIONS.has(deps.storage, &from_pool) {
+        return Err(ContractError::PoolReplaced(from_lp));
+    }
+
+    // Replaced LP tokens can't be reused, otherwise replacements could form a cycle
+    if POOL_MIGRATIONS.has(deps.storage, &to_pool) {
+        return Err(ContractError::PoolReplaced(to_lp));
+    }
+
+    if POOLS.has(deps.storage, &to_pool)
+        || POOL_PERIODS
+            .prefix(&to_pool)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+    {
+        return Err(ContractError::PoolHasVotes(to_lp));
+    }
+
+    if config.whitelisted_pools.contains(&to_pool) {
+        return Err(ContractError::PoolIsWhitelisted(to_lp));
+    }
+
+    if config.main_pool.as_ref() == Some(&from_pool) {
+        config.main_pool = Some(to_pool.clone());
+    } else {
+        validate_pool(deps.as_ref(), &config, &to_pool)?;
+    }
+
+    if let Some(pos) = config
+        .whitelisted_pools
+        .iter()
+        .position(|pool| pool == &from_pool)
+    {
+        config.whitelisted_pools[pos] = to_pool.clone();
+    }
+
+    move_pool_votes(deps.storage, &from_pool, &to_pool)?;
+    POOL_MIGRATIONS.save(deps.storage, &from_pool, &to_pool)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "migrate_pool_votes"),
+        attr("from_lp", from_pool),
+        attr("to_lp", to_pool),
+    ]))
+}
+
+/// Only contract owner or emergency committee can call this function.
+/// The function freezes pools tuning. Users are still able to change their votes.
+fn pause_tuning(deps: DepsMut, info: MessageInfo) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner && Some(&info.sender) != config.emergency_committee.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    TUNING_PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::default().add_attribute("action", "pause_tuning"))
+}
+
+/// Only contract owner can call this function.
+/// The function resumes pools tuning.
+fn unpause_tuning(deps: DepsMut, info: MessageInfo) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    TUNING_PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::default().add_attribute("action", "unpause_tuning"))
+}
+
 /// Only contract owner can call this function.
 /// The function sets new limit of pools which are eligible to receive allocation points.
 ///
@@ -591,6 +873,8 @@ fn change_pools_limit(deps: DepsMut, info: MessageInfo, limit: u64) -> ExecuteRe
 /// ## Queries
 /// * **QueryMsg::UserInfo { user }** Fetch user information
 ///
+/// * **QueryMsg::AvailableVotePower { user }** Fetch user's voting power and unallocated basic points
+///
 /// * **QueryMsg::TuneInfo** Fetch last tuning information
 ///
 /// * **QueryMsg::Config** Fetch contract config
@@ -598,26 +882,179 @@ fn change_pools_limit(deps: DepsMut, info: MessageInfo, limit: u64) -> ExecuteRe
 /// * **QueryMsg::PoolInfo { pool_addr }** Fetch pool's voting information at the current period.
 ///
 /// * **QueryMsg::PoolInfoAtPeriod { pool_addr, period }** Fetch pool's voting information at a specified period.
+///
+/// * **QueryMsg::SimulateTune {}** Fetch allocation points pools would receive if tuning was executed now.
+///
+/// * **QueryMsg::GaugeDelegation { user }** Fetch the strategist the user delegated emission voting power to.
+///
+/// * **QueryMsg::UserVoteAtPeriod { user, pool_addr, period }** Fetch the user's contribution
+/// to pool's voting power at a specified period.
+///
+/// * **QueryMsg::IsTuningPaused {}** Fetch whether pools tuning is paused.
+///
+/// * **QueryMsg::PoolVotesAtPeriod { pool, period }** Fetch pool's voting information persisted at a specified period.
+///
+/// * **QueryMsg::PoolPeriods { pool, start_after, limit }** Fetch periods which have persisted pool's voting information.
+///
+/// * **QueryMsg::ResolvedPool { pool_addr }** Fetch the actual LP token address following LP token replacements.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::UserInfo { user } => to_binary(&user_info(deps, user)?),
+        QueryMsg::UserInfo { user } => to_binary(&user_info(deps, env, user)?),
+        QueryMsg::AvailableVotePower { user } => to_binary(&available_vote_power(deps, env, user)?),
         QueryMsg::TuneInfo {} => to_binary(&TUNE_INFO.load(deps.storage)?),
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::PoolInfo { pool_addr } => to_binary(&pool_info(deps, env, pool_addr, None)?),
         QueryMsg::PoolInfoAtPeriod { pool_addr, period } => {
             to_binary(&pool_info(deps, env, pool_addr, Some(period))?)
         }
+        QueryMsg::SimulateTune {} => to_binary(&simulate_tune(deps, env)?),
+        QueryMsg::GaugeDelegation { user } => to_binary(&gauge_delegation(deps, user)?),
+        QueryMsg::UserVoteAtPeriod {
+            user,
+            pool_addr,
+            period,
+        } => to_binary(&user_vote_at_period(deps, user, pool_addr, period)?),
+        QueryMsg::IsTuningPaused {} => {
+            to_binary(&TUNING_PAUSED.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::PoolVotesAtPeriod { pool, period } => {
+            let pool_addr = deps.api.addr_validate(&pool)?;
+            to_binary(&POOL_VOTES.may_load(deps.storage, (period, &pool_addr))?)
+        }
+        QueryMsg::PoolPeriods {
+            pool,
+            start_after,
+            limit,
+        } => to_binary(&pool_periods(deps, pool, start_after, limit)?),
+        QueryMsg::ResolvedPool { pool_addr } => {
+            let pool_addr = deps.api.addr_validate(&pool_addr)?;
+            to_binary(&resolve_pool(deps.storage, &pool_addr)?)
+        }
     }
 }
 
-/// Returns user information.
-fn user_info(deps: Deps, user: String) -> StdResult<UserInfoResponse> {
+/// Returns user information. The user's voting power is decayed to the current period.
+fn user_info(deps: Deps, env: Env, user: String) -> StdResult<UserInfoResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
-    USER_INFO
+    let block_period = get_period(env.block.time.seconds())?;
+    let mut user_info = USER_INFO
         .may_load(deps.storage, &user_addr)?
-        .map(UserInfo::into_response)
-        .ok_or_else(|| StdError::generic_err("User not found"))
+        .ok_or_else(|| StdError::generic_err("User not found"))?
+        .into_response(block_period)?;
+
+    // Redirect votes for replaced LP tokens
+    for (pool_addr, _) in user_info.votes.iter_mut() {
+        *pool_addr = resolve_pool(deps.storage, pool_addr)?;
+    }
+
+    Ok(user_info)
+}
+
+/// Returns user's current vxASTRO voting power along with basic points allocated by active votes
+/// and basic points which can still be allocated.
+fn available_vote_power(
+    deps: Deps,
+    env: Env,
+    user: String,
+) -> StdResult<AvailableVotePowerResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let config = CONFIG.load(deps.storage)?;
+    let block_period = get_period(env.block.time.seconds())?;
+    let voting_power = get_voting_power(&deps.querier, &config.escrow_addr, &user_addr)?;
+
+    let allocated_bps = match USER_INFO.may_load(deps.storage, &user_addr)? {
+        // Votes are no longer applied once the lock expires
+        Some(user_info) if user_info.lock_end > block_period => {
+            user_info.votes.iter().map(|(_, bps)| u16::from(*bps)).sum()
+        }
+        _ => 0,
+    };
+
+    Ok(AvailableVotePowerResponse {
+        voting_power,
+        allocated_bps,
+        remaining_bps: BasicPoints::MAX - allocated_bps,
+    })
+}
+
+/// Returns the strategist the user delegated emission voting power to.
+fn gauge_delegation(deps: Deps, user: String) -> StdResult<GaugeDelegationResponse> {
+    let user = deps.api.addr_validate(&user)?;
+    let strategist = GAUGE_DELEGATES.may_load(deps.storage, &user)?;
+    Ok(GaugeDelegationResponse { user, strategist })
+}
+
+/// Returns the user's contribution to pool's voting power at a specified period.
+/// The contribution is calculated using the latest user's vote applied before or at the specified period.
+fn user_vote_at_period(
+    deps: Deps,
+    user: String,
+    pool_addr: String,
+    period: u64,
+) -> StdResult<VotedPoolInfo> {
+    let user = deps.api.addr_validate(&user)?;
+    let pool_addr = deps.api.addr_validate(&pool_addr)?;
+
+    let last_vote = USER_VOTES_HISTORY
+        .prefix(&user)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(period)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+
+    if let Some((vote_period, user_info)) = last_vote {
+        // Slope changes are applied at the period after the lock end
+        if user_info.lock_end >= period {
+            for (pool, bps) in &user_info.votes {
+                if resolve_pool(deps.storage, pool)? == pool_addr {
+                    let slope = *bps * user_info.slope;
+                    return Ok(VotedPoolInfo {
+                        vxastro_amount: calc_voting_power(
+                            slope,
+                            *bps * user_info.voting_power,
+                            vote_period,
+                            period,
+                        ),
+                        slope,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(VotedPoolInfo::default())
+}
+
+/// Returns a list of periods which have persisted voting information for a given pool.
+///
+/// * **start_after** is an optional field that specifies whether the function should return
+/// a list of periods starting from a specific period onward.
+///
+/// * **limit** max amount of periods to return.
+fn pool_periods(
+    deps: Deps,
+    pool: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let pool_addr = deps.api.addr_validate(&pool)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    POOL_PERIODS
+        .prefix(&pool_addr)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
 }
 
 /// Returns pool's voting information at a specified period.
@@ -633,6 +1070,27 @@ fn pool_info(
     get_pool_info(deps.storage, period, &pool_addr)
 }
 
+/// Returns allocation points pools would receive if [`ExecuteMsg::TunePools`] was executed now.
+/// Tuning cooldown is not taken into account.
+fn simulate_tune(deps: Deps, env: Env) -> StdResult<GaugeInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let block_period = get_period(env.block.time.seconds())?;
+
+    let pool_votes = POOLS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|pool_addr| {
+            let pool_addr = pool_addr?;
+            let pool_info = get_pool_info(deps.storage, block_period, &pool_addr)?;
+            Ok((pool_addr, pool_info.vxastro_amount))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GaugeInfoResponse {
+        tune_ts: env.block.time.seconds(),
+        pool_alloc_points: calc_pool_alloc_points(&deps.querier, &config, pool_votes)?,
+    })
+}
+
 /// Manages contract migration
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
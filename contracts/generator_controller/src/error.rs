@@ -1,3 +1,4 @@
+use astroport_governance::errors::CommonError;
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
@@ -7,6 +8,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -46,6 +50,9 @@ pub enum ContractError {
     #[error("Exceeded voters limit for kick blacklisted voters operation!")]
     KickVotersLimitExceeded {},
 
+    #[error("Exceeded voters limit for refresh votes operation!")]
+    RefreshVotersLimitExceeded {},
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 
@@ -60,4 +67,19 @@ pub enum ContractError {
 
     #[error("Pool is not whitelisted: {0}")]
     PoolIsNotWhitelisted(String),
+
+    #[error("You can't delegate gauge votes to yourself")]
+    SelfGaugeDelegation {},
+
+    #[error("Gauge votes are not delegated")]
+    GaugeVotesNotDelegated {},
+
+    #[error("Pools tuning is paused")]
+    TuningPaused {},
+
+    #[error("Pool already has votes: {0}")]
+    PoolHasVotes(String),
+
+    #[error("LP token {0} was already replaced")]
+    PoolReplaced(String),
 }
@@ -4,8 +4,9 @@ use crate::bps::BasicPoints;
 use astroport_governance::generator_controller::{
     ConfigResponse, GaugeInfoResponse, UserInfoResponse, VotedPoolInfoResponse,
 };
+use astroport_governance::utils::{calc_voting_power, get_period};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, StdResult, Uint128};
 use cw_storage_plus::{Item, Map};
 
 /// This structure describes the main control config of generator controller contract.
@@ -24,24 +25,50 @@ pub struct UserInfo {
     pub slope: Uint128,
     pub lock_end: u64,
     pub votes: Vec<(Addr, BasicPoints)>,
+    #[serde(default)]
+    pub persist: bool,
 }
 
 impl UserInfo {
+    /// Returns the user's voting power decayed by the vxASTRO slope to the specified period.
+    /// The votes applied by the user decay at the same rate, thus the result reflects the weight
+    /// the user's allocations carry in the given period.
+    pub(crate) fn voting_power_at(&self, period: u64) -> StdResult<Uint128> {
+        if self.lock_end <= period || self.voting_power.is_zero() {
+            return Ok(Uint128::zero());
+        }
+
+        let vote_period = get_period(self.vote_ts)?;
+        if period <= vote_period {
+            return Ok(self.voting_power);
+        }
+
+        Ok(calc_voting_power(
+            self.slope,
+            self.voting_power,
+            vote_period,
+            period,
+        ))
+    }
+
     /// The function converts [`UserInfo`] object into [`UserInfoResponse`].
-    pub(crate) fn into_response(self) -> UserInfoResponse {
+    /// The voting power is decayed to the specified period.
+    pub(crate) fn into_response(self, period: u64) -> StdResult<UserInfoResponse> {
+        let voting_power = self.voting_power_at(period)?;
         let votes = self
             .votes
             .iter()
             .map(|(pool_addr, bps)| (pool_addr.clone(), u16::from(*bps)))
             .collect();
 
-        UserInfoResponse {
+        Ok(UserInfoResponse {
             vote_ts: self.vote_ts,
-            voting_power: self.voting_power,
+            voting_power,
             slope: self.slope,
             lock_end: self.lock_end,
             votes,
-        }
+            persist: self.persist,
+        })
     }
 }
 
@@ -61,9 +88,23 @@ pub const POOL_PERIODS: Map<(&Addr, u64), ()> = Map::new("pool_periods");
 /// Slope changes for a specific pool address by key ( pool_addr -> period ).
 pub const POOL_SLOPE_CHANGES: Map<(&Addr, u64), Uint128> = Map::new("pool_slope_changes");
 
+/// Replaced LP tokens by key ( old_lp_token -> new_lp_token ).
+/// Users' votes for replaced LP tokens are redirected to the new LP tokens.
+pub const POOL_MIGRATIONS: Map<&Addr, Addr> = Map::new("pool_migrations");
+
 /// User's voting information.
 pub const USER_INFO: Map<&Addr, UserInfo> = Map::new("user_info");
 
+/// History of users' votes by key ( user -> period ). Period is the first period when the votes are applied.
+/// It is used to verify a user's share of a pool's voting power at a past period.
+pub const USER_VOTES_HISTORY: Map<(&Addr, u64), UserInfo> = Map::new("user_votes_history");
+
+/// Strategists allowed to vote for pools on behalf of vxASTRO holders by key ( holder -> strategist ).
+pub const GAUGE_DELEGATES: Map<&Addr, Addr> = Map::new("gauge_delegates");
+
+/// Whether pools tuning is paused.
+pub const TUNING_PAUSED: Item<bool> = Item::new("tuning_paused");
+
 /// Last tuning information.
 pub const TUNE_INFO: Item<TuneInfo> = Item::new("tune_info");
 
@@ -5,18 +5,25 @@ use crate::astroport;
 use astroport::asset::{pair_info_by_pool, AssetInfo};
 use astroport::factory::PairType;
 use astroport_governance::generator_controller::ConfigResponse;
-use cosmwasm_std::{Addr, Deps, Order, QuerierWrapper, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{
+    Addr, Decimal, Deps, Fraction, Order, QuerierWrapper, StdError, StdResult, Storage, Uint128,
+};
 use cw_storage_plus::Bound;
+use itertools::Itertools;
 
 use crate::astroport::querier::query_pair_info;
 use astroport_governance::utils::calc_voting_power;
 
 use crate::bps::BasicPoints;
 use crate::error::ContractError;
-use crate::state::{VotedPoolInfo, POOLS, POOL_PERIODS, POOL_SLOPE_CHANGES, POOL_VOTES};
+use crate::state::{
+    VotedPoolInfo, POOLS, POOL_MIGRATIONS, POOL_PERIODS, POOL_SLOPE_CHANGES, POOL_VOTES,
+};
 
 /// Pools limit should be within the range `[2, 100]`
 const POOL_NUMBER_LIMIT: RangeInclusive<u64> = 2..=100;
+/// The max number of LP token replacements followed while resolving a pool
+const MAX_POOL_MIGRATION_HOPS: usize = 20;
 
 /// The enum defines math operations with voting power and slope.
 #[derive(Debug)]
@@ -90,6 +97,58 @@ pub(crate) fn filter_pools(
     Ok(pools)
 }
 
+/// Calculates allocation points for pools using their voting power at the current period.
+/// Pools with zero voting power and pools which are not eligible to receive allocation points
+/// are filtered out. Top X pools by voting power are taken, where X is 'config.pools_limit'.
+/// If the main pool is set, it receives at least 'config.main_pool_min_alloc' of the total allocation points.
+pub(crate) fn calc_pool_alloc_points(
+    querier: &QuerierWrapper,
+    config: &ConfigResponse,
+    pool_votes: Vec<(Addr, Uint128)>,
+) -> StdResult<Vec<(String, Uint128)>> {
+    let pool_votes = pool_votes
+        .into_iter()
+        .filter(|(_, vxastro_amount)| !vxastro_amount.is_zero())
+        .sorted_by(|(_, a), (_, b)| b.cmp(a)) // Sort in descending order
+        .collect();
+
+    let mut pool_alloc_points = filter_pools(
+        querier,
+        &config.generator_addr,
+        &config.factory_addr,
+        pool_votes,
+        config.pools_limit + 1, // +1 additional pool if we will need to remove the main pool
+    )?;
+
+    // Set allocation points for the main pool
+    match &config.main_pool {
+        Some(main_pool) if !config.main_pool_min_alloc.is_zero() => {
+            // Main pool may appear in the pool list thus we need to eliminate its contribution in the total VP.
+            pool_alloc_points.retain(|(pool, _)| pool != &main_pool.to_string());
+            // If there is no main pool in the filtered list then we need to remove additional pool
+            pool_alloc_points.truncate(config.pools_limit as usize);
+
+            let total_vp: Uint128 = pool_alloc_points
+                .iter()
+                .fold(Uint128::zero(), |acc, (_, vp)| acc + vp);
+            // Calculate main pool contribution.
+            // Example (30% for the main pool): VP + x = y, x = 0.3y => y = VP/0.7  => x = 0.3 * VP / 0.7,
+            // where VP - total VP, x - main pool's contribution, y - new total VP.
+            // x = 0.3 * VP * (1-0.3)^(-1)
+            let main_pool_contribution = config.main_pool_min_alloc
+                * total_vp
+                * (Decimal::one() - config.main_pool_min_alloc).inv().unwrap();
+            pool_alloc_points.push((main_pool.to_string(), main_pool_contribution))
+        }
+        _ => {
+            // there is no main pool or min alloc is 0%
+            pool_alloc_points.truncate(config.pools_limit as usize);
+        }
+    }
+
+    Ok(pool_alloc_points)
+}
+
 /// Cancels user changes using old voting parameters for a given pool.  
 /// Firstly, it removes slope change scheduled for previous lockup end period.  
 /// Secondly, it updates voting parameters for the given period, but without user's vote.
@@ -102,6 +161,8 @@ pub(crate) fn cancel_user_changes(
     old_slope: Uint128,
     old_lock_end: u64,
 ) -> StdResult<()> {
+    let pool_addr = &resolve_pool(storage, pool_addr)?;
+
     // Cancel scheduled slope changes
     let last_pool_period = fetch_last_pool_period(storage, period, pool_addr)?.unwrap_or(period);
     if last_pool_period < old_lock_end + 1 {
@@ -331,6 +392,57 @@ pub(crate) fn validate_pools_limit(number: u64) -> Result<u64, ContractError> {
     }
 }
 
+/// Returns the actual LP token address for a given pool following LP token replacements.
+/// Fails if the chain of replacements is longer than [`MAX_POOL_MIGRATION_HOPS`].
+pub(crate) fn resolve_pool(storage: &dyn Storage, pool_addr: &Addr) -> StdResult<Addr> {
+    let mut pool_addr = pool_addr.clone();
+    for _ in 0..MAX_POOL_MIGRATION_HOPS {
+        match POOL_MIGRATIONS.may_load(storage, &pool_addr)? {
+            Some(new_pool_addr) => pool_addr = new_pool_addr,
+            None => return Ok(pool_addr),
+        }
+    }
+
+    Err(StdError::generic_err(format!(
+        "Too many LP token replacements for {pool_addr}"
+    )))
+}
+
+/// Moves all voting parameters saved for a pool to a new LP token address.
+pub(crate) fn move_pool_votes(
+    storage: &mut dyn Storage,
+    from_pool: &Addr,
+    to_pool: &Addr,
+) -> StdResult<()> {
+    let periods = POOL_PERIODS
+        .prefix(from_pool)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for period in periods {
+        let pool_info = POOL_VOTES.load(storage, (period, from_pool))?;
+        POOL_VOTES.remove(storage, (period, from_pool));
+        POOL_PERIODS.remove(storage, (from_pool, period));
+        POOL_VOTES.save(storage, (period, to_pool), &pool_info)?;
+        POOL_PERIODS.save(storage, (to_pool, period), &())?;
+    }
+
+    let slope_changes = POOL_SLOPE_CHANGES
+        .prefix(from_pool)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (period, slope_change) in slope_changes {
+        POOL_SLOPE_CHANGES.remove(storage, (from_pool, period));
+        POOL_SLOPE_CHANGES.save(storage, (to_pool, period), &slope_change)?;
+    }
+
+    if POOLS.has(storage, from_pool) {
+        POOLS.remove(storage, from_pool);
+        POOLS.save(storage, to_pool, &())?;
+    }
+
+    Ok(())
+}
+
 /// Check if a pool isn't the main pool. Check if a pool is an LP token.
 /// Check if a pool is registered in the factory contract.
 pub fn validate_pool(
@@ -13,7 +13,7 @@ use astroport_governance::utils::{get_period, MAX_LOCK_TIME, WEEK};
 use astroport_tests::{
     controller_helper::ControllerHelper, escrow_helper::MULTIPLIER, mock_app, TerraAppExtension,
 };
-use generator_controller::state::TuneInfo;
+use generator_controller::state::{TuneInfo, VotedPoolInfo};
 
 #[test]
 fn update_configs() {
@@ -342,6 +342,13 @@ fn check_vote_works() {
         resp_votes
     );
 
+    let available = helper
+        .query_available_vote_power(&mut router, "user2")
+        .unwrap();
+    assert_eq!(available.voting_power, user_info.voting_power);
+    assert_eq!(available.allocated_bps, 10000);
+    assert_eq!(available.remaining_bps, 0);
+
     router.next_block(86400 * 10);
     // In 10 days user will be able to vote again
     helper
@@ -351,6 +358,152 @@ fn check_vote_works() {
             vec![(pools[0].as_str(), 500), (pools[1].as_str(), 9500)],
         )
         .unwrap();
+
+    let available = helper
+        .query_available_vote_power(&mut router, "user1")
+        .unwrap();
+    assert_eq!(available.allocated_bps, 0);
+    assert_eq!(available.remaining_bps, 10000);
+}
+
+#[test]
+fn check_user_vp_decay() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pool = helper
+        .create_pool_with_tokens(&mut router, "FOO", "BAR")
+        .unwrap();
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pool.to_string()]), None)
+        .unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(&mut router, "user1", vec![(pool.as_str(), 10000)])
+        .unwrap();
+
+    // Voting power reported by the controller decays along with vxASTRO
+    for _ in 0..3 {
+        router.next_block(WEEK);
+        let ve_power = helper
+            .escrow_helper
+            .query_user_vp(&mut router, "user1")
+            .unwrap();
+        let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+        assert_eq!(
+            ve_power,
+            user_info.voting_power.u128() as f32 / MULTIPLIER as f32
+        );
+    }
+
+    // Once the lock expires the user has no voting power left
+    router.next_block(10 * WEEK);
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert_eq!(user_info.voting_power.u128(), 0);
+}
+
+#[test]
+fn check_gauge_delegation() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pool = helper
+        .create_pool_with_tokens(&mut router, "FOO", "BAR")
+        .unwrap();
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pool.to_string()]), None)
+        .unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+
+    // Strategist can't vote without delegation
+    let err = helper
+        .vote_on_behalf(
+            &mut router,
+            "strategist",
+            "user1",
+            vec![(pool.as_str(), 10000)],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
+
+    let err = helper
+        .delegate_gauge_votes(&mut router, "user1", "user1")
+        .unwrap_err();
+    assert_eq!(
+        "You can't delegate gauge votes to yourself",
+        err.root_cause().to_string()
+    );
+
+    helper
+        .delegate_gauge_votes(&mut router, "user1", "strategist")
+        .unwrap();
+    let resp = helper.query_gauge_delegation(&mut router, "user1").unwrap();
+    assert_eq!(resp.strategist, Some(Addr::unchecked("strategist")));
+
+    // Only the chosen strategist can vote on behalf of the user
+    let err = helper
+        .vote_on_behalf(
+            &mut router,
+            "somebody",
+            "user1",
+            vec![(pool.as_str(), 10000)],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
+
+    helper
+        .vote_on_behalf(
+            &mut router,
+            "strategist",
+            "user1",
+            vec![(pool.as_str(), 10000)],
+        )
+        .unwrap();
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert_eq!(router.block_info().time.seconds(), user_info.vote_ts);
+    assert_eq!(user_info.votes.len(), 1);
+
+    // Vote cooldown is shared between the user and the strategist
+    let err = helper
+        .vote(&mut router, "user1", vec![(pool.as_str(), 5000)])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "You can only run this action every 10 days"
+    );
+
+    helper.undelegate_gauge_votes(&mut router, "user1").unwrap();
+    let resp = helper.query_gauge_delegation(&mut router, "user1").unwrap();
+    assert_eq!(resp.strategist, None);
+
+    let err = helper
+        .undelegate_gauge_votes(&mut router, "user1")
+        .unwrap_err();
+    assert_eq!(
+        "Gauge votes are not delegated",
+        err.root_cause().to_string()
+    );
+
+    router.next_block(86400 * 10);
+    let err = helper
+        .vote_on_behalf(
+            &mut router,
+            "strategist",
+            "user1",
+            vec![(pool.as_str(), 10000)],
+        )
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.root_cause().to_string());
 }
 
 fn create_unregistered_pool(
@@ -529,6 +682,8 @@ fn check_tuning() {
 
     router.next_block(WEEK);
 
+    let simulated = helper.query_simulate_tune(&mut router).unwrap();
+
     helper.tune(&mut router).unwrap();
 
     let resp: TuneInfo = router
@@ -537,6 +692,7 @@ fn check_tuning() {
         .unwrap();
     assert_eq!(get_period(resp.tune_ts).unwrap(), router.block_period());
     assert_eq!(resp.pool_alloc_points.len(), pools.len());
+    assert_eq!(simulated.pool_alloc_points, resp.pool_alloc_points);
     let total_apoints: u128 = resp
         .pool_alloc_points
         .iter()
@@ -545,6 +701,48 @@ fn check_tuning() {
         .sum();
     assert_eq!(total_apoints, 357423036);
 
+    // Pool votes used for tuning are persisted at the current period
+    let pool_votes: Option<VotedPoolInfo> = router
+        .wrap()
+        .query_wasm_smart(
+            helper.controller.clone(),
+            &QueryMsg::PoolVotesAtPeriod {
+                pool: pools[0].to_string(),
+                period: router.block_period(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pool_votes.unwrap(),
+        helper
+            .query_voted_pool_info(&mut router, pools[0].as_str())
+            .unwrap()
+    );
+    let pool_periods: Vec<u64> = router
+        .wrap()
+        .query_wasm_smart(
+            helper.controller.clone(),
+            &QueryMsg::PoolPeriods {
+                pool: pools[0].to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pool_periods.last().copied(), Some(router.block_period()));
+    let pool_periods: Vec<u64> = router
+        .wrap()
+        .query_wasm_smart(
+            helper.controller.clone(),
+            &QueryMsg::PoolPeriods {
+                pool: pools[0].to_string(),
+                start_after: Some(router.block_period()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(pool_periods.is_empty());
+
     router.next_block(2 * WEEK);
     // Reduce pools limit 5 -> 2 (5 is initial limit in integration tests)
     let limit = 2u64;
@@ -767,6 +965,431 @@ fn check_bad_pools_filtering() {
     assert_eq!(total_apoints, 36615382)
 }
 
+#[test]
+fn check_tuning_pause() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pool = helper
+        .create_pool_with_tokens(&mut router, "FOO", "BAR")
+        .unwrap();
+    helper
+        .update_whitelist(&mut router, "owner", Some(vec![pool.to_string()]), None)
+        .unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(&mut router, "user1", vec![(pool.as_str(), 10000)])
+        .unwrap();
+
+    router
+        .execute_contract(
+            owner.clone(),
+            helper.controller.clone(),
+            &ExecuteMsg::UpdateConfig {
+                blacklisted_voters_limit: None,
+                main_pool: None,
+                main_pool_min_alloc: None,
+                remove_main_pool: None,
+                emergency_committee: Some("committee".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let err = router
+        .execute_contract(
+            Addr::unchecked("somebody"),
+            helper.controller.clone(),
+            &ExecuteMsg::PauseTuning {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    router
+        .execute_contract(
+            Addr::unchecked("committee"),
+            helper.controller.clone(),
+            &ExecuteMsg::PauseTuning {},
+            &[],
+        )
+        .unwrap();
+    let is_paused: bool = router
+        .wrap()
+        .query_wasm_smart(helper.controller.clone(), &QueryMsg::IsTuningPaused {})
+        .unwrap();
+    assert!(is_paused);
+
+    router.next_block(2 * WEEK);
+    let err = helper.tune(&mut router).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Pools tuning is paused");
+
+    // Users are still able to change their votes
+    helper
+        .vote(&mut router, "user1", vec![(pool.as_str(), 5000)])
+        .unwrap();
+
+    // Only the owner can resume tuning
+    let err = router
+        .execute_contract(
+            Addr::unchecked("committee"),
+            helper.controller.clone(),
+            &ExecuteMsg::UnpauseTuning {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    router
+        .execute_contract(
+            owner,
+            helper.controller.clone(),
+            &ExecuteMsg::UnpauseTuning {},
+            &[],
+        )
+        .unwrap();
+    helper.tune(&mut router).unwrap();
+}
+
+#[test]
+fn check_migrate_pool_votes() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(
+            &mut router,
+            "owner",
+            Some(vec![pools[0].to_string(), pools[2].to_string()]),
+            None,
+        )
+        .unwrap();
+
+    helper.escrow_helper.mint_xastro(&mut router, "user1", 100);
+    helper
+        .escrow_helper
+        .create_lock(&mut router, "user1", 10 * WEEK, 100f32)
+        .unwrap();
+    helper
+        .vote(
+            &mut router,
+            "user1",
+            vec![(pools[0].as_str(), 6000), (pools[2].as_str(), 4000)],
+        )
+        .unwrap();
+
+    router.next_block(WEEK);
+    let old_pool_info = helper
+        .query_voted_pool_info(&mut router, pools[0].as_str())
+        .unwrap();
+
+    let migrate_msg = ExecuteMsg::MigratePoolVotes {
+        from_lp: pools[0].to_string(),
+        to_lp: pools[1].to_string(),
+    };
+    let err = router
+        .execute_contract(
+            Addr::unchecked("somebody"),
+            helper.controller.clone(),
+            &migrate_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Votes can't be merged with an LP token which already has votes
+    let err = router
+        .execute_contract(
+            owner.clone(),
+            helper.controller.clone(),
+            &ExecuteMsg::MigratePoolVotes {
+                from_lp: pools[0].to_string(),
+                to_lp: pools[2].to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Pool already has votes: {}", pools[2])
+    );
+
+    router
+        .execute_contract(owner, helper.controller.clone(), &migrate_msg, &[])
+        .unwrap();
+
+    let config = helper.query_config(&mut router).unwrap();
+    assert_eq!(
+        config.whitelisted_pools,
+        vec![pools[1].clone(), pools[2].clone()]
+    );
+    assert_eq!(
+        helper
+            .query_voted_pool_info(&mut router, pools[1].as_str())
+            .unwrap(),
+        old_pool_info
+    );
+    assert!(helper
+        .query_voted_pool_info(&mut router, pools[0].as_str())
+        .unwrap()
+        .vxastro_amount
+        .is_zero());
+
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert_eq!(user_info.votes[0].0, pools[1]);
+
+    // Migrating back to the replaced LP token would create a cycle
+    let err = router
+        .execute_contract(
+            Addr::unchecked("owner"),
+            helper.controller.clone(),
+            &ExecuteMsg::MigratePoolVotes {
+                from_lp: pools[1].to_string(),
+                to_lp: pools[0].to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("LP token {} was already replaced", pools[0])
+    );
+
+    // The replaced LP token can't be migrated once again
+    let another_pool = helper
+        .create_pool_with_tokens(&mut router, "FOO", "XYZ")
+        .unwrap();
+    let err = router
+        .execute_contract(
+            Addr::unchecked("owner"),
+            helper.controller.clone(),
+            &ExecuteMsg::MigratePoolVotes {
+                from_lp: pools[0].to_string(),
+                to_lp: another_pool.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("LP token {} was already replaced", pools[0])
+    );
+
+    // User's votes for the replaced LP token are cancelled from the new one
+    router.next_block(WEEK);
+    helper
+        .vote(&mut router, "user1", vec![(pools[2].as_str(), 10000)])
+        .unwrap();
+    let pool_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[1].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert!(pool_info.vxastro_amount.is_zero());
+    assert!(pool_info.slope.is_zero());
+}
+
+#[test]
+fn check_persistent_votes() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(
+            &mut router,
+            "owner",
+            Some(pools.iter().map(|pool| pool.to_string()).collect()),
+            None,
+        )
+        .unwrap();
+
+    for user in ["user1", "user2"] {
+        helper.escrow_helper.mint_xastro(&mut router, user, 100);
+        helper
+            .escrow_helper
+            .create_lock(&mut router, user, 10 * WEEK, 100f32)
+            .unwrap();
+    }
+    helper
+        .persistent_vote(&mut router, "user1", vec![(pools[0].as_str(), 10000)])
+        .unwrap();
+    helper
+        .vote(&mut router, "user2", vec![(pools[1].as_str(), 10000)])
+        .unwrap();
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert!(user_info.persist);
+    let vote_ts = user_info.vote_ts;
+
+    router.next_block(2 * WEEK);
+    for user in ["user1", "user2"] {
+        helper
+            .escrow_helper
+            .extend_lock_time(&mut router, user, 4 * WEEK)
+            .unwrap();
+    }
+    let pool2_info_before = helper
+        .query_voted_pool_info_at_period(&mut router, pools[1].as_str(), router.block_period() + 1)
+        .unwrap();
+
+    helper
+        .refresh_votes(&mut router, vec!["user1", "user2"])
+        .unwrap();
+
+    // Persistent votes are applied with the latest voting power
+    let ve_power = helper
+        .escrow_helper
+        .query_user_vp(&mut router, "user1")
+        .unwrap();
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert_eq!(
+        ve_power,
+        user_info.voting_power.u128() as f32 / MULTIPLIER as f32
+    );
+    assert_eq!(user_info.vote_ts, vote_ts);
+    let pool1_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[0].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert_eq!(pool1_info.vxastro_amount, user_info.voting_power);
+
+    // Regular votes are not refreshed
+    let pool2_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[1].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert_eq!(pool2_info, pool2_info_before);
+
+    // Refreshing doesn't affect the vote cooldown
+    helper
+        .vote(&mut router, "user1", vec![(pools[1].as_str(), 10000)])
+        .unwrap();
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert!(!user_info.persist);
+    let pool1_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[0].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert!(pool1_info.vxastro_amount.is_zero());
+}
+
+#[test]
+fn check_prune_votes_hook() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let helper = ControllerHelper::init(&mut router, &owner);
+    let pools = vec![
+        helper
+            .create_pool_with_tokens(&mut router, "FOO", "BAR")
+            .unwrap(),
+        helper
+            .create_pool_with_tokens(&mut router, "BAR", "ADN")
+            .unwrap(),
+    ];
+    helper
+        .update_whitelist(
+            &mut router,
+            "owner",
+            Some(pools.iter().map(|pool| pool.to_string()).collect()),
+            None,
+        )
+        .unwrap();
+
+    for (user, time) in [("user1", 2 * WEEK), ("user2", 10 * WEEK)] {
+        helper.escrow_helper.mint_xastro(&mut router, user, 100);
+        helper
+            .escrow_helper
+            .create_lock(&mut router, user, time, 100f32)
+            .unwrap();
+    }
+    helper
+        .persistent_vote(&mut router, "user1", vec![(pools[0].as_str(), 10000)])
+        .unwrap();
+    helper
+        .vote(&mut router, "user2", vec![(pools[1].as_str(), 10000)])
+        .unwrap();
+
+    // Only the voting escrow is allowed to prune votes
+    let err = router
+        .execute_contract(
+            Addr::unchecked("user1"),
+            helper.controller.clone(),
+            &ExecuteMsg::PruneVotesHook {
+                voter: "user2".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    router
+        .execute_contract(
+            owner.clone(),
+            helper.escrow_helper.escrow_instance.clone(),
+            &astroport_governance::voting_escrow::ExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                checkpoint_hooks: None,
+                generator_controller: Some(helper.controller.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    let config: astroport_governance::voting_escrow::ConfigResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &helper.escrow_helper.escrow_instance,
+            &astroport_governance::voting_escrow::QueryMsg::Config {},
+        )
+        .unwrap();
+    assert_eq!(config.generator_controller, Some(helper.controller.clone()));
+
+    // Votes are pruned right after the voter is blacklisted
+    let pool2_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[1].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert!(!pool2_info.vxastro_amount.is_zero());
+    helper
+        .escrow_helper
+        .update_blacklist(&mut router, Some(vec!["user2".to_string()]), None)
+        .unwrap();
+    let user_info = helper.query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user_info.votes, vec![]);
+    let pool2_info = helper
+        .query_voted_pool_info_at_period(&mut router, pools[1].as_str(), router.block_period() + 1)
+        .unwrap();
+    assert!(pool2_info.vxastro_amount.is_zero());
+
+    // Votes are pruned once the expired lock is withdrawn
+    router.next_block(2 * WEEK);
+    let vote_ts = helper
+        .query_user_info(&mut router, "user1")
+        .unwrap()
+        .vote_ts;
+    helper.escrow_helper.withdraw(&mut router, "user1").unwrap();
+    let user_info = helper.query_user_info(&mut router, "user1").unwrap();
+    assert_eq!(user_info.votes, vec![]);
+    assert!(!user_info.persist);
+    assert_eq!(user_info.vote_ts, vote_ts);
+}
+
 #[test]
 fn check_update_owner() {
     let mut app = mock_app();
@@ -0,0 +1,379 @@
+use astroport_governance::astroport::common::{
+    claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport_governance::ibc_controller::{
+    ChannelParams, Config, ExecuteMsg, InstantiateMsg, MigrateMsg, PacketStatus, ProposalPacket,
+    QueryMsg, TIMEOUT_LIMITS,
+};
+use astroport_governance::interchain::{Envelope, ForwardMetadata, HubToOutpostMsg};
+use astroport_governance::migration::{load_migratable_version, migration_response};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, IbcMsg, IbcTimeout, MessageInfo, Response,
+    StdError, StdResult, Storage,
+};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::state::{
+    CHANNEL_PARAMS, CONFIG, OUTPOST_TOTALS, OWNERSHIP_PROPOSAL, PROPOSAL_MESSAGES, PROPOSAL_PACKETS,
+};
+
+// Version info for contract migration.
+const CONTRACT_NAME: &str = "ibc-controller";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if !TIMEOUT_LIMITS.contains(&msg.timeout) {
+        return Err(ContractError::InvalidTimeout {});
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(&msg.owner)?,
+        assembly: deps.api.addr_validate(&msg.assembly)?,
+        timeout: msg.timeout,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", config.owner))
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::IbcExecuteProposal { channel_id, proposal_id, messages }** Sends
+/// proposal messages to a remote chain.
+///
+/// * **ExecuteMsg::ResendProposal { proposal_id }** Sends the messages of a proposal again
+/// after its packet timed out.
+///
+/// * **ExecuteMsg::SetChannelParams { channel_id, params }** Sets or removes IBC parameters
+/// for a channel.
+///
+/// * **ExecuteMsg::UpdateConfig { new_assembly, new_timeout }** Updates contract parameters.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change
+/// contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::IbcExecuteProposal {
+            channel_id,
+            proposal_id,
+            messages,
+            forward,
+        } => execute_proposal(deps, env, info, channel_id, proposal_id, messages, forward),
+        ExecuteMsg::ResendProposal { proposal_id } => resend_proposal(deps, env, info, proposal_id),
+        ExecuteMsg::SetChannelParams { channel_id, params } => {
+            set_channel_params(deps, info, channel_id, params)
+        }
+        ExecuteMsg::UpdateConfig {
+            new_assembly,
+            new_timeout,
+        } => update_config(deps, info, new_assembly, new_timeout),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        }
+    }
+}
+
+/// Sends the messages of a passed proposal to a remote chain and starts tracking the packet.
+///
+/// * **channel_id** IBC channel to the remote chain.
+///
+/// * **proposal_id** proposal identifier.
+///
+/// * **messages** messages to execute on the remote chain.
+///
+/// * **forward** route through intermediate chains.
+fn execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    channel_id: String,
+    proposal_id: u64,
+    messages: Vec<CosmosMsg>,
+    forward: Option<ForwardMetadata>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.assembly {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if PROPOSAL_PACKETS.has(deps.storage, proposal_id) {
+        return Err(ContractError::ProposalAlreadySent(proposal_id));
+    }
+
+    if let Some(max) = CHANNEL_PARAMS
+        .may_load(deps.storage, &channel_id)?
+        .and_then(|params| params.max_messages)
+    {
+        if messages.len() > max as usize {
+            return Err(ContractError::TooManyMessages { channel_id, max });
+        }
+    }
+
+    if let Some(forward) = &forward {
+        forward.validate()?;
+    }
+
+    PROPOSAL_MESSAGES.save(deps.storage, proposal_id, &messages)?;
+    let packet = send_proposal_packet(
+        deps.storage,
+        &env,
+        &config,
+        &channel_id,
+        proposal_id,
+        messages,
+        forward,
+    )?;
+
+    Ok(Response::new()
+        .add_message(packet)
+        .add_attribute("action", "ibc_execute_proposal")
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Sends the messages of a proposal again after its packet timed out. The Assembly keeps the
+/// proposal in progress until the new packet is acknowledged.
+///
+/// * **proposal_id** proposal identifier.
+fn resend_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner && info.sender != config.assembly {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let packet = PROPOSAL_PACKETS.load(deps.storage, proposal_id)?;
+    if packet.status != PacketStatus::TimedOut {
+        return Err(ContractError::ProposalNotTimedOut(proposal_id));
+    }
+
+    let messages = PROPOSAL_MESSAGES.load(deps.storage, proposal_id)?;
+    let msg = send_proposal_packet(
+        deps.storage,
+        &env,
+        &config,
+        &packet.channel_id,
+        proposal_id,
+        messages,
+        packet.forward,
+    )?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "resend_proposal")
+        .add_attribute("channel_id", packet.channel_id)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Resets the packet state of a proposal and builds the IBC message carrying its messages.
+/// The packet timeout is taken from the channel parameters if they are set.
+fn send_proposal_packet(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    channel_id: &str,
+    proposal_id: u64,
+    messages: Vec<CosmosMsg>,
+    forward: Option<ForwardMetadata>,
+) -> StdResult<IbcMsg> {
+    let timeout = CHANNEL_PARAMS
+        .may_load(storage, channel_id)?
+        .map_or(config.timeout, |params| params.timeout);
+
+    PROPOSAL_PACKETS.save(
+        storage,
+        proposal_id,
+        &ProposalPacket {
+            channel_id: channel_id.to_string(),
+            sequence: None,
+            sent_at: env.block.time.seconds(),
+            status: PacketStatus::Sent,
+            forward: forward.clone(),
+        },
+    )?;
+
+    Ok(IbcMsg::SendPacket {
+        channel_id: channel_id.to_string(),
+        data: Envelope::new(HubToOutpostMsg::ExecuteProposal {
+            proposal_id,
+            messages,
+        })
+        .with_forward(forward)
+        .to_packet_data()?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout)),
+    })
+}
+
+/// Sets IBC parameters for a channel. Removes them if **params** is `None`.
+///
+/// * **channel_id** IBC channel to the remote chain.
+///
+/// * **params** the new channel parameters.
+fn set_channel_params(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+    params: Option<ChannelParams>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.assembly {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(params) = &params {
+        if !TIMEOUT_LIMITS.contains(&params.timeout) {
+            return Err(ContractError::InvalidTimeout {});
+        }
+
+        if params.max_messages == Some(0) {
+            return Err(ContractError::InvalidMaxMessages {});
+        }
+
+        CHANNEL_PARAMS.save(deps.storage, &channel_id, params)?;
+    } else {
+        CHANNEL_PARAMS.remove(deps.storage, &channel_id);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_channel_params")
+        .add_attribute("channel_id", channel_id))
+}
+
+/// Updates contract parameters.
+///
+/// * **new_assembly** new Assembly contract address.
+///
+/// * **new_timeout** new IBC packet timeout in seconds.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_assembly: Option<String>,
+    new_timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(new_assembly) = new_assembly {
+        config.assembly = deps.api.addr_validate(&new_assembly)?;
+    }
+
+    if let Some(new_timeout) = new_timeout {
+        if !TIMEOUT_LIMITS.contains(&new_timeout) {
+            return Err(ContractError::InvalidTimeout {});
+        }
+        config.timeout = new_timeout;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Exposes all the queries available in the contract.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::ProposalState { proposal_id }** Returns the channel, sequence, send time and
+/// status of the packet sent for the specified proposal.
+///
+/// * **QueryMsg::ChannelParams { channel_id }** Returns the IBC parameters set for a channel.
+///
+/// * **QueryMsg::OutpostTotals { channel_id }** Returns the last totals synced by the outpost
+/// on the other end of a channel.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ProposalState { proposal_id } => {
+            to_binary(&PROPOSAL_PACKETS.load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ChannelParams { channel_id } => {
+            to_binary(&CHANNEL_PARAMS.may_load(deps.storage, &channel_id)?)
+        }
+        QueryMsg::OutpostTotals { channel_id } => {
+            to_binary(&OUTPOST_TOTALS.may_load(deps.storage, &channel_id)?)
+        }
+    }
+}
+
+/// Manages contract migration. The stored cw2 contract name and version must be migratable.
+/// There are no released versions to migrate from yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = load_migratable_version(deps.storage, CONTRACT_NAME, &[])?
+        .ok_or(ContractError::MigrationError {})?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(migration_response(
+        &contract_version,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+    ))
+}
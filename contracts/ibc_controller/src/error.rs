@@ -0,0 +1,55 @@
+use astroport_governance::errors::CommonError;
+use astroport_governance::ibc_controller::TIMEOUT_LIMITS;
+use astroport_governance::interchain::IBC_APP_VERSION;
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// This enum describes IBC controller contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error(
+        "Timeout must be within limits: {} <= timeout <= {}",
+        TIMEOUT_LIMITS.start(),
+        TIMEOUT_LIMITS.end()
+    )]
+    InvalidTimeout {},
+
+    #[error("Channel {channel_id} accepts at most {max} messages per proposal")]
+    TooManyMessages { channel_id: String, max: u32 },
+
+    #[error("Max messages per proposal must be greater than zero")]
+    InvalidMaxMessages {},
+
+    #[error("Proposal {0} was already sent")]
+    ProposalAlreadySent(u64),
+
+    #[error("Proposal {0} can't be resent because its packet didn't time out")]
+    ProposalNotTimedOut(u64),
+
+    #[error("Channel {0} doesn't lead to a registered outpost")]
+    UnknownOutpostChannel(String),
+
+    #[error("Totals of epoch {epoch} are older than the last synced epoch {last_epoch}")]
+    OutdatedTotals { epoch: u64, last_epoch: u64 },
+
+    #[error("Unexpected packet message")]
+    UnexpectedPacket {},
+
+    #[error("Only unordered channels are supported")]
+    InvalidChannelOrder {},
+
+    #[error("Invalid IBC version {0}. Expected {expected}", expected = IBC_APP_VERSION)]
+    InvalidIbcVersion(String),
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
@@ -0,0 +1,267 @@
+use astroport_governance::assembly::{ExecuteMsg as AssemblyExecuteMsg, ProposalStatus};
+use astroport_governance::ibc_controller::{IbcAckResult, OutpostTotals, PacketStatus};
+use astroport_governance::interchain::{
+    Envelope, HubToOutpostMsg, OutpostToHubMsg, IBC_APP_VERSION,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env, Ibc3ChannelOpenResponse,
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, Never, StdError, StdResult, Storage, WasmMsg,
+};
+
+use crate::error::ContractError;
+use crate::state::{CHANNEL_PARAMS, CONFIG, OUTPOST_TOTALS, PROPOSAL_PACKETS};
+
+/// Checks the channel order and version before a channel is opened.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(
+        msg.channel().order.clone(),
+        &msg.channel().version,
+        msg.counterparty_version(),
+    )?;
+
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: IBC_APP_VERSION.to_string(),
+    }))
+}
+
+/// Checks the channel order and version once the handshake is finished.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    validate_channel(
+        channel.order.clone(),
+        &channel.version,
+        msg.counterparty_version(),
+    )?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_connect")
+        .add_attribute("channel_id", &channel.endpoint.channel_id))
+}
+
+/// Allows channels to be closed. Packets sent over a closed channel time out.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_close")
+        .add_attribute("channel_id", &msg.channel().endpoint.channel_id))
+}
+
+/// Stores the totals synced by outposts. Errors are returned to the outpost as failed
+/// acknowledgements instead of failing the relayer transaction.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    do_packet_receive(deps, msg).or_else(|err| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(ack_fail(err.to_string()))
+            .add_attribute("action", "ibc_packet_receive"))
+    })
+}
+
+/// Saves the totals synced over a channel unless they are older than the last ones.
+/// Only outposts on channels with IBC parameters set by the Assembly can sync totals.
+fn do_packet_receive(
+    deps: DepsMut,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let channel_id = msg.packet.dest.channel_id;
+
+    match Envelope::<OutpostToHubMsg>::from_packet_data(&msg.packet.data)?.msg {
+        OutpostToHubMsg::TotalsSync {
+            epoch,
+            height,
+            total_voting_power,
+        } => {
+            if !CHANNEL_PARAMS.has(deps.storage, &channel_id) {
+                return Err(ContractError::UnknownOutpostChannel(channel_id));
+            }
+
+            if let Some(last_totals) = OUTPOST_TOTALS.may_load(deps.storage, &channel_id)? {
+                if epoch < last_totals.epoch {
+                    return Err(ContractError::OutdatedTotals {
+                        epoch,
+                        last_epoch: last_totals.epoch,
+                    });
+                }
+            }
+
+            OUTPOST_TOTALS.save(
+                deps.storage,
+                &channel_id,
+                &OutpostTotals {
+                    epoch,
+                    height,
+                    total_voting_power,
+                },
+            )?;
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(to_binary(&IbcAckResult::Ok(Binary::default()))?)
+                .add_attribute("action", "ibc_packet_receive")
+                .add_attribute("channel_id", channel_id)
+                .add_attribute("epoch", epoch.to_string())
+                .add_attribute("total_voting_power", total_voting_power))
+        }
+        _ => Err(ContractError::UnexpectedPacket {}),
+    }
+}
+
+/// Records the outcome of a proposal packet and reports it back to the Assembly. The relayer
+/// is paid the channel relayer fee if the contract balance covers it.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let proposal_id = proposal_id_from_packet(&msg.original_packet)?;
+    let sequence = msg.original_packet.sequence;
+
+    let (packet_status, proposal_status) = match from_binary(&msg.acknowledgement.data)? {
+        IbcAckResult::Ok(_) => (PacketStatus::Executed, ProposalStatus::Executed),
+        IbcAckResult::Error(error) => (PacketStatus::Failed { error }, ProposalStatus::Failed),
+    };
+
+    update_packet(deps.storage, proposal_id, sequence, packet_status)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let notify = WasmMsg::Execute {
+        contract_addr: config.assembly.to_string(),
+        msg: to_binary(&AssemblyExecuteMsg::IBCProposalCompleted {
+            proposal_id,
+            status: proposal_status,
+        })?,
+        funds: vec![],
+    };
+
+    let mut response = IbcBasicResponse::new().add_message(notify);
+
+    let channel_id = &msg.original_packet.src.channel_id;
+    if let Some(params) = CHANNEL_PARAMS.may_load(deps.storage, channel_id)? {
+        if !params.relayer_fee.is_empty() && has_balance(deps.as_ref(), &env, &params.relayer_fee)?
+        {
+            response = response.add_message(BankMsg::Send {
+                to_address: msg.relayer.to_string(),
+                amount: params.relayer_fee,
+            });
+        }
+    }
+
+    Ok(response
+        .add_attribute("action", "ibc_packet_ack")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+/// Marks a proposal packet as timed out. The Assembly isn't notified, so the proposal stays
+/// in progress until the packet is resent with [`ExecuteMsg::ResendProposal`].
+///
+/// [`ExecuteMsg::ResendProposal`]: astroport_governance::ibc_controller::ExecuteMsg::ResendProposal
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let proposal_id = proposal_id_from_packet(msg.packet())?;
+    let sequence = msg.packet().sequence;
+
+    update_packet(deps.storage, proposal_id, sequence, PacketStatus::TimedOut)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_packet_timeout")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+/// Checks that the channel is unordered and that both sides use [`IBC_APP_VERSION`].
+fn validate_channel(
+    order: IbcOrder,
+    version: &str,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidChannelOrder {});
+    }
+
+    if version != IBC_APP_VERSION {
+        return Err(ContractError::InvalidIbcVersion(version.to_string()));
+    }
+
+    if let Some(counterparty_version) = counterparty_version {
+        if counterparty_version != IBC_APP_VERSION {
+            return Err(ContractError::InvalidIbcVersion(
+                counterparty_version.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts the proposal ID from the data of a packet sent by this contract.
+fn proposal_id_from_packet(packet: &IbcPacket) -> StdResult<u64> {
+    match Envelope::<HubToOutpostMsg>::from_packet_data(&packet.data)?.msg {
+        HubToOutpostMsg::ExecuteProposal { proposal_id, .. } => Ok(proposal_id),
+        _ => Err(StdError::generic_err("Unexpected packet message")),
+    }
+}
+
+/// Builds an error acknowledgement.
+fn ack_fail(error: String) -> Binary {
+    // Serializing an enum holding a string can't fail
+    to_binary(&IbcAckResult::Error(error)).unwrap()
+}
+
+/// Checks whether the contract holds enough funds to pay the specified coins.
+fn has_balance(deps: Deps, env: &Env, coins: &[Coin]) -> StdResult<bool> {
+    for coin in coins {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &coin.denom)?;
+        if balance.amount < coin.amount {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Saves the sequence and the new status of a proposal packet.
+fn update_packet(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    sequence: u64,
+    status: PacketStatus,
+) -> StdResult<()> {
+    PROPOSAL_PACKETS.update::<_, StdError>(storage, proposal_id, |packet| {
+        let mut packet =
+            packet.ok_or_else(|| StdError::not_found(format!("Proposal packet {proposal_id}")))?;
+        packet.sequence = Some(sequence);
+        packet.status = status;
+        Ok(packet)
+    })?;
+
+    Ok(())
+}
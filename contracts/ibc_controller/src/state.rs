@@ -0,0 +1,22 @@
+use astroport_governance::astroport::common::OwnershipProposal;
+use astroport_governance::ibc_controller::{ChannelParams, Config, OutpostTotals, ProposalPacket};
+use cosmwasm_std::CosmosMsg;
+use cw_storage_plus::{Item, Map};
+
+/// Stores the contract config
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// IBC parameters set by the Assembly for specific channels
+pub const CHANNEL_PARAMS: Map<&str, ChannelParams> = Map::new("channel_params");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Proposal packets sent to remote chains keyed by proposal ID
+pub const PROPOSAL_PACKETS: Map<u64, ProposalPacket> = Map::new("proposal_packets");
+
+/// The last totals synced by outposts keyed by channel ID
+pub const OUTPOST_TOTALS: Map<&str, OutpostTotals> = Map::new("outpost_totals");
+
+/// Proposal messages keyed by proposal ID. They are kept to resend timed out packets
+pub const PROPOSAL_MESSAGES: Map<u64, Vec<CosmosMsg>> = Map::new("proposal_messages");
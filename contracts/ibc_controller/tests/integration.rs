@@ -0,0 +1,677 @@
+use astroport_governance::assembly::{ExecuteMsg as AssemblyExecuteMsg, ProposalStatus};
+use astroport_governance::ibc_controller::{
+    ChannelParams, Config, ExecuteMsg, IbcAckResult, InstantiateMsg, MigrateMsg, OutpostTotals,
+    PacketStatus, ProposalPacket, QueryMsg,
+};
+use astroport_governance::interchain::{
+    Envelope, ForwardMetadata, HubToOutpostMsg, OutpostToHubMsg, IBC_APP_VERSION, MAX_FORWARD_HOPS,
+};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_open_init,
+    mock_ibc_packet_ack, mock_ibc_packet_recv, mock_ibc_packet_timeout, mock_info, MockApi,
+    MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, BankMsg, Binary, CosmosMsg, IbcAcknowledgement, IbcMsg,
+    IbcOrder, IbcTimeout, OwnedDeps, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use ibc_controller::contract::{execute, instantiate, migrate, query};
+use ibc_controller::error::ContractError;
+use ibc_controller::ibc::{
+    ibc_channel_connect, ibc_channel_open, ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout,
+};
+use ibc_controller::state::PROPOSAL_PACKETS;
+
+const OWNER: &str = "owner";
+const ASSEMBLY: &str = "assembly";
+const CHANNEL: &str = "channel-0";
+
+#[test]
+fn test_channel_handshake() {
+    let mut deps = mock_dependencies();
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Ordered, IBC_APP_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidChannelOrder {});
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidIbcVersion("ics20-1".to_string()));
+
+    let resp = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(CHANNEL, IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+    assert_eq!(resp.unwrap().version, IBC_APP_VERSION);
+
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_connect_ack(CHANNEL, IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_execute_proposal_msg_format() {
+    // The Assembly must be able to talk to the astroport_ibc controller as well
+    assert_eq!(
+        to_binary(&execute_proposal_msg(1)).unwrap(),
+        Binary::from(
+            br#"{"ibc_execute_proposal":{"channel_id":"channel-0","proposal_id":1,"messages":[]}}"#
+                .as_slice()
+        )
+    );
+}
+
+#[test]
+fn test_update_config() {
+    let mut deps = instantiate_controller();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_assembly: None,
+            new_timeout: Some(120),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_assembly: None,
+            new_timeout: Some(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTimeout {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_assembly: Some("new_assembly".to_string()),
+            new_timeout: Some(120),
+        },
+    )
+    .unwrap();
+
+    let config: Config =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.assembly.as_str(), "new_assembly");
+    assert_eq!(config.timeout, 120);
+}
+
+#[test]
+fn test_proposal_packet_ack() {
+    let mut deps = instantiate_controller();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random", &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap();
+    assert!(matches!(
+        &resp.messages[0].msg,
+        CosmosMsg::Ibc(IbcMsg::SendPacket { channel_id, .. }) if channel_id == CHANNEL
+    ));
+
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 1).unwrap();
+    assert_eq!(packet.status, PacketStatus::Sent);
+    assert_eq!(packet.sequence, None);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalAlreadySent(1));
+
+    let ack = IbcAcknowledgement::new(to_binary(&IbcAckResult::Ok(Binary::default())).unwrap());
+    let msg = mock_ibc_packet_ack(CHANNEL, &proposal_envelope(1), ack).unwrap();
+    let sequence = msg.original_packet.sequence;
+    let resp = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_completed_callback(&resp.messages[0].msg, 1, ProposalStatus::Executed);
+
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 1).unwrap();
+    assert_eq!(packet.status, PacketStatus::Executed);
+    assert_eq!(packet.sequence, Some(sequence));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(2),
+    )
+    .unwrap();
+
+    let ack =
+        IbcAcknowledgement::new(to_binary(&IbcAckResult::Error("failed".to_string())).unwrap());
+    let msg = mock_ibc_packet_ack(CHANNEL, &proposal_envelope(2), ack).unwrap();
+    let resp = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_completed_callback(&resp.messages[0].msg, 2, ProposalStatus::Failed);
+
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 2).unwrap();
+    assert_eq!(
+        packet.status,
+        PacketStatus::Failed {
+            error: "failed".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_proposal_packet_timeout() {
+    let mut deps = instantiate_controller();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ResendProposal { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotTimedOut(1));
+
+    // The Assembly isn't notified so the proposal stays in progress
+    let msg = mock_ibc_packet_timeout(CHANNEL, &proposal_envelope(1)).unwrap();
+    let resp = ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(resp.messages.is_empty());
+
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 1).unwrap();
+    assert_eq!(packet.status, PacketStatus::TimedOut);
+    assert!(packet.sequence.is_some());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random", &[]),
+        ExecuteMsg::ResendProposal { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ResendProposal { proposal_id: 1 },
+    )
+    .unwrap();
+    match &resp.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket {
+            channel_id, data, ..
+        }) => {
+            assert_eq!(channel_id, CHANNEL);
+            assert_eq!(
+                Envelope::<HubToOutpostMsg>::from_packet_data(data).unwrap(),
+                proposal_envelope(1)
+            );
+        }
+        msg => panic!("Unexpected message: {msg:?}"),
+    }
+
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 1).unwrap();
+    assert_eq!(packet.status, PacketStatus::Sent);
+    assert_eq!(packet.sequence, None);
+
+    let ack = IbcAcknowledgement::new(to_binary(&IbcAckResult::Ok(Binary::default())).unwrap());
+    let msg = mock_ibc_packet_ack(CHANNEL, &proposal_envelope(1), ack).unwrap();
+    let resp = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_completed_callback(&resp.messages[0].msg, 1, ProposalStatus::Executed);
+}
+
+#[test]
+fn test_forward_proposal() {
+    let mut deps = instantiate_controller();
+
+    let forward_msg = |hops: Vec<&str>| ExecuteMsg::IbcExecuteProposal {
+        channel_id: CHANNEL.to_string(),
+        proposal_id: 1,
+        messages: vec![],
+        forward: Some(ForwardMetadata {
+            hops: hops.into_iter().map(ToString::to_string).collect(),
+        }),
+    };
+
+    for hops in [vec![], vec![""], vec!["channel-1"; MAX_FORWARD_HOPS + 1]] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ASSEMBLY, &[]),
+            forward_msg(hops),
+        )
+        .unwrap_err();
+    }
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        forward_msg(vec!["channel-1", "channel-2"]),
+    )
+    .unwrap();
+    let envelope = match &resp.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) => {
+            Envelope::<HubToOutpostMsg>::from_packet_data(data).unwrap()
+        }
+        msg => panic!("Unexpected message: {msg:?}"),
+    };
+
+    // Every intermediate chain pops its hop until the envelope reaches its destination
+    let (channel_id, envelope) = envelope.next_hop().unwrap();
+    assert_eq!(channel_id, "channel-1");
+    let (channel_id, envelope) = envelope.next_hop().unwrap();
+    assert_eq!(channel_id, "channel-2");
+    let envelope = envelope.next_hop().unwrap_err();
+    assert_eq!(envelope, proposal_envelope(1));
+
+    // The route is kept so that a timed out packet is resent along it
+    let packet = PROPOSAL_PACKETS.load(&deps.storage, 1).unwrap();
+    assert_eq!(packet.forward.unwrap().hops, vec!["channel-1", "channel-2"]);
+}
+
+#[test]
+fn test_outpost_totals_sync() {
+    let mut deps = instantiate_controller();
+
+    let totals_sync = |epoch: u64, total_voting_power: u128| {
+        mock_ibc_packet_recv(
+            CHANNEL,
+            &Envelope::new(OutpostToHubMsg::TotalsSync {
+                epoch,
+                height: epoch * 10,
+                total_voting_power: Uint128::new(total_voting_power),
+            }),
+        )
+        .unwrap()
+    };
+    let query_totals = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        from_binary::<Option<OutpostTotals>>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OutpostTotals {
+                    channel_id: CHANNEL.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(query_totals(&deps), None);
+
+    // Only registered outposts can sync totals
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), totals_sync(2, 100)).unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Error(ContractError::UnknownOutpostChannel(CHANNEL.to_string()).to_string())
+    );
+    assert_eq!(query_totals(&deps), None);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::SetChannelParams {
+            channel_id: CHANNEL.to_string(),
+            params: Some(ChannelParams {
+                timeout: 120,
+                max_messages: None,
+                relayer_fee: vec![],
+            }),
+        },
+    )
+    .unwrap();
+
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), totals_sync(2, 100)).unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Ok(Binary::default())
+    );
+    assert_eq!(
+        query_totals(&deps),
+        Some(OutpostTotals {
+            epoch: 2,
+            height: 20,
+            total_voting_power: Uint128::new(100),
+        })
+    );
+
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), totals_sync(1, 50)).unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Error(
+            ContractError::OutdatedTotals {
+                epoch: 1,
+                last_epoch: 2
+            }
+            .to_string()
+        )
+    );
+    assert_eq!(query_totals(&deps).unwrap().epoch, 2);
+
+    // Proposal packets are only sent by the controller
+    let msg = mock_ibc_packet_recv(CHANNEL, &proposal_envelope(1)).unwrap();
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(matches!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Error(_)
+    ));
+}
+
+#[test]
+fn test_channel_params() {
+    let mut deps = instantiate_controller();
+
+    let params = ChannelParams {
+        timeout: 120,
+        max_messages: Some(1),
+        relayer_fee: coins(100, "uluna"),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::SetChannelParams {
+            channel_id: CHANNEL.to_string(),
+            params: Some(params.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::SetChannelParams {
+            channel_id: CHANNEL.to_string(),
+            params: Some(ChannelParams {
+                max_messages: Some(0),
+                ..params.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMaxMessages {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::SetChannelParams {
+            channel_id: CHANNEL.to_string(),
+            params: Some(params.clone()),
+        },
+    )
+    .unwrap();
+
+    let resp: Option<ChannelParams> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ChannelParams {
+                channel_id: CHANNEL.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(resp, Some(params));
+
+    let messages = vec![
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(1, "uluna"),
+        });
+        2
+    ];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::IbcExecuteProposal {
+            channel_id: CHANNEL.to_string(),
+            proposal_id: 1,
+            messages,
+            forward: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TooManyMessages {
+            channel_id: CHANNEL.to_string(),
+            max: 1
+        }
+    );
+
+    let env = mock_env();
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap();
+    match &resp.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket { timeout, .. }) => assert_eq!(
+            timeout,
+            &IbcTimeout::with_timestamp(env.block.time.plus_seconds(120))
+        ),
+        msg => panic!("Unexpected message: {msg:?}"),
+    }
+
+    // The relayer fee isn't paid while the contract can't cover it
+    let ack = IbcAcknowledgement::new(to_binary(&IbcAckResult::Ok(Binary::default())).unwrap());
+    let msg = mock_ibc_packet_ack(CHANNEL, &proposal_envelope(1), ack.clone()).unwrap();
+    let resp = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(resp.messages.len(), 1);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(2),
+    )
+    .unwrap();
+
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(100, "uluna"));
+    let msg = mock_ibc_packet_ack(CHANNEL, &proposal_envelope(2), ack).unwrap();
+    let relayer = msg.relayer.to_string();
+    let resp = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(
+        resp.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: relayer,
+            amount: coins(100, "uluna"),
+        })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ASSEMBLY, &[]),
+        ExecuteMsg::SetChannelParams {
+            channel_id: CHANNEL.to_string(),
+            params: None,
+        },
+    )
+    .unwrap();
+
+    let resp: Option<ChannelParams> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ChannelParams {
+                channel_id: CHANNEL.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(resp, None);
+}
+
+#[test]
+fn test_query_proposal_state() {
+    let mut deps = instantiate_controller();
+
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ProposalState { proposal_id: 1 },
+    )
+    .unwrap_err();
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ASSEMBLY, &[]),
+        execute_proposal_msg(1),
+    )
+    .unwrap();
+
+    let state: ProposalPacket = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProposalState { proposal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        state,
+        ProposalPacket {
+            channel_id: CHANNEL.to_string(),
+            sequence: None,
+            sent_at: env.block.time.seconds(),
+            status: PacketStatus::Sent,
+            forward: None,
+        }
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let mut deps = instantiate_controller();
+
+    // There are no released versions to migrate from yet
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(err, ContractError::MigrationError {});
+
+    // Other contracts can't be migrated to the controller
+    set_contract_version(deps.as_mut().storage, "astro-assembly", "1.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(err, ContractError::MigrationError {});
+}
+
+fn instantiate_controller() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        InstantiateMsg {
+            owner: OWNER.to_string(),
+            assembly: ASSEMBLY.to_string(),
+            timeout: 0,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTimeout {});
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        InstantiateMsg {
+            owner: OWNER.to_string(),
+            assembly: ASSEMBLY.to_string(),
+            timeout: 3600,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn execute_proposal_msg(proposal_id: u64) -> ExecuteMsg {
+    ExecuteMsg::IbcExecuteProposal {
+        channel_id: CHANNEL.to_string(),
+        proposal_id,
+        messages: vec![],
+        forward: None,
+    }
+}
+
+fn proposal_envelope(proposal_id: u64) -> Envelope<HubToOutpostMsg> {
+    Envelope::new(HubToOutpostMsg::ExecuteProposal {
+        proposal_id,
+        messages: vec![],
+    })
+}
+
+fn assert_completed_callback(msg: &CosmosMsg, proposal_id: u64, status: ProposalStatus) {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, ASSEMBLY);
+            assert_eq!(
+                from_binary::<AssemblyExecuteMsg>(msg).unwrap(),
+                AssemblyExecuteMsg::IBCProposalCompleted {
+                    proposal_id,
+                    status
+                }
+            );
+        }
+        _ => panic!("Unexpected message: {msg:?}"),
+    }
+}
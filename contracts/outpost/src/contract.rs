@@ -0,0 +1,414 @@
+use astroport_governance::astroport::common::{
+    claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport_governance::ibc_controller::{IbcAckResult, TIMEOUT_LIMITS};
+use astroport_governance::interchain::{Envelope, OutpostToHubMsg};
+use astroport_governance::migration::{load_migratable_version, migration_response};
+use astroport_governance::outpost::{
+    Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SyncInfo, SyncStatus,
+    EPOCH_LIMITS,
+};
+use astroport_governance::voting_escrow::VotingPowerResponse;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, IbcMsg, IbcTimeout, MessageInfo,
+    Reply, Response, StdError, StdResult, SubMsgResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::error::ContractError;
+use crate::state::{CONFIG, LAST_SYNC, OWNERSHIP_PROPOSAL, STAKED, TOTAL_STAKED};
+
+// Version info for contract migration.
+const CONTRACT_NAME: &str = "astroport-outpost";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Reply ID of the submessage executing proposal messages
+pub const EXECUTE_PROPOSAL_REPLY_ID: u64 = 1;
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_epoch_length(msg.epoch_length)?;
+    validate_timeout(msg.timeout)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(&msg.owner)?,
+        xastro_token: deps.api.addr_validate(&msg.xastro_token)?,
+        hub_channel: msg.hub_channel,
+        epoch_length: msg.epoch_length,
+        timeout: msg.timeout,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    TOTAL_STAKED.save(deps.storage, &Uint128::zero(), env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", config.owner))
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::Receive(msg)** Parse incoming messages coming from the xASTRO token contract.
+///
+/// * **ExecuteMsg::Unstake { amount }** Withdraw staked xASTRO.
+///
+/// * **ExecuteMsg::SyncTotals {}** Send the total staked xASTRO to the hub.
+///
+/// * **ExecuteMsg::ExecuteProposal { proposal_id, messages }** Executes the messages of a
+/// proposal sent by the hub.
+///
+/// * **ExecuteMsg::UpdateConfig { hub_channel, epoch_length, timeout }** Updates contract parameters.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change
+/// contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Unstake { amount } => unstake(deps, env, info, amount),
+        ExecuteMsg::SyncTotals {} => sync_totals(deps, env),
+        ExecuteMsg::ExecuteProposal {
+            proposal_id,
+            messages,
+        } => execute_proposal(env, info, proposal_id, messages),
+        ExecuteMsg::UpdateConfig {
+            hub_channel,
+            epoch_length,
+            timeout,
+        } => update_config(deps, info, hub_channel, epoch_length, timeout),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        }
+    }
+}
+
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+///
+/// * **cw20_msg** CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.xastro_token {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Stake {} => {
+            if cw20_msg.amount.is_zero() {
+                return Err(ContractError::ZeroAmount {});
+            }
+
+            let user = deps.api.addr_validate(&cw20_msg.sender)?;
+            let height = env.block.height;
+
+            let staked = STAKED.may_load(deps.storage, &user)?.unwrap_or_default();
+            STAKED.save(
+                deps.storage,
+                &user,
+                &staked.checked_add(cw20_msg.amount)?,
+                height,
+            )?;
+
+            let total = TOTAL_STAKED.load(deps.storage)?;
+            TOTAL_STAKED.save(deps.storage, &total.checked_add(cw20_msg.amount)?, height)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "stake")
+                .add_attribute("user", user)
+                .add_attribute("amount", cw20_msg.amount))
+        }
+    }
+}
+
+/// Withdraws staked xASTRO.
+///
+/// * **amount** amount of xASTRO to withdraw.
+fn unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let height = env.block.height;
+
+    let staked = STAKED
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    STAKED.save(
+        deps.storage,
+        &info.sender,
+        &staked.checked_sub(amount)?,
+        height,
+    )?;
+
+    let total = TOTAL_STAKED.load(deps.storage)?;
+    TOTAL_STAKED.save(deps.storage, &total.checked_sub(amount)?, height)?;
+
+    let transfer = WasmMsg::Execute {
+        contract_addr: config.xastro_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("action", "unstake")
+        .add_attribute("user", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Sends the total staked xASTRO to the hub. Totals can be synced once per epoch unless the
+/// previous sync of the same epoch failed.
+fn sync_totals(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let hub_channel = config
+        .hub_channel
+        .ok_or(ContractError::HubChannelNotSet {})?;
+
+    let epoch = env.block.time.seconds() / config.epoch_length;
+    if let Some(last_sync) = LAST_SYNC.may_load(deps.storage)? {
+        if last_sync.epoch >= epoch && last_sync.status != SyncStatus::Failed {
+            return Err(ContractError::AlreadySynced(last_sync.epoch));
+        }
+    }
+
+    let sync = SyncInfo {
+        epoch,
+        height: env.block.height,
+        total_voting_power: TOTAL_STAKED.load(deps.storage)?,
+        status: SyncStatus::Sent,
+    };
+    LAST_SYNC.save(deps.storage, &sync)?;
+
+    let packet = IbcMsg::SendPacket {
+        channel_id: hub_channel,
+        data: Envelope::new(OutpostToHubMsg::TotalsSync {
+            epoch: sync.epoch,
+            height: sync.height,
+            total_voting_power: sync.total_voting_power,
+        })
+        .to_packet_data()?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(config.timeout)),
+    };
+
+    Ok(Response::new()
+        .add_message(packet)
+        .add_attribute("action", "sync_totals")
+        .add_attribute("epoch", epoch.to_string())
+        .add_attribute("total_voting_power", sync.total_voting_power))
+}
+
+/// Executes the messages of a proposal received from the hub. Dispatched by the contract itself
+/// as a single submessage, so the messages either all succeed or are all reverted.
+///
+/// * **proposal_id** proposal ID in the hub Assembly.
+///
+/// * **messages** messages to execute.
+fn execute_proposal(
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    messages: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Turns a failed proposal execution into an error acknowledgement for the hub.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg {
+        Reply {
+            id: EXECUTE_PROPOSAL_REPLY_ID,
+            result: SubMsgResult::Err(error),
+        } => Ok(Response::new()
+            .set_data(to_binary(&IbcAckResult::Error(error))?)
+            .add_attribute("action", "execute_proposal_failed")),
+        _ => Err(ContractError::InvalidReplyId(msg.id)),
+    }
+}
+
+/// Updates contract parameters.
+///
+/// * **hub_channel** new IBC channel to the hub.
+///
+/// * **epoch_length** new sync epoch length in seconds.
+///
+/// * **timeout** new IBC packet timeout in seconds.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    hub_channel: Option<String>,
+    epoch_length: Option<u64>,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(hub_channel) = hub_channel {
+        config.hub_channel = Some(hub_channel);
+    }
+
+    if let Some(epoch_length) = epoch_length {
+        validate_epoch_length(epoch_length)?;
+        config.epoch_length = epoch_length;
+    }
+
+    if let Some(timeout) = timeout {
+        validate_timeout(timeout)?;
+        config.timeout = timeout;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn validate_epoch_length(epoch_length: u64) -> Result<(), ContractError> {
+    if !EPOCH_LIMITS.contains(&epoch_length) {
+        return Err(ContractError::InvalidEpochLength {});
+    }
+
+    Ok(())
+}
+
+fn validate_timeout(timeout: u64) -> Result<(), ContractError> {
+    if !TIMEOUT_LIMITS.contains(&timeout) {
+        return Err(ContractError::InvalidTimeout {});
+    }
+
+    Ok(())
+}
+
+/// Exposes all the queries available in the contract.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::UserVotingPower { user }** Returns a user's current voting power.
+///
+/// * **QueryMsg::UserVotingPowerAt { user, height }** Returns a user's voting power at the
+/// given block height.
+///
+/// * **QueryMsg::TotalVotingPower {}** Returns the current total voting power.
+///
+/// * **QueryMsg::TotalVotingPowerAt { height }** Returns the total voting power at the given
+/// block height.
+///
+/// * **QueryMsg::LastSync {}** Returns the last sync of totals with the hub.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::UserVotingPower { user } => {
+            let user = deps.api.addr_validate(&user)?;
+            let voting_power = STAKED.may_load(deps.storage, &user)?.unwrap_or_default();
+            to_binary(&VotingPowerResponse { voting_power })
+        }
+        QueryMsg::UserVotingPowerAt { user, height } => {
+            let user = deps.api.addr_validate(&user)?;
+            let voting_power = STAKED
+                .may_load_at_height(deps.storage, &user, height)?
+                .unwrap_or_default();
+            to_binary(&VotingPowerResponse { voting_power })
+        }
+        QueryMsg::TotalVotingPower {} => to_binary(&VotingPowerResponse {
+            voting_power: TOTAL_STAKED.load(deps.storage)?,
+        }),
+        QueryMsg::TotalVotingPowerAt { height } => to_binary(&VotingPowerResponse {
+            voting_power: TOTAL_STAKED
+                .may_load_at_height(deps.storage, height)?
+                .unwrap_or_default(),
+        }),
+        QueryMsg::LastSync {} => to_binary(&LAST_SYNC.may_load(deps.storage)?),
+    }
+}
+
+/// Manages contract migration. The stored cw2 contract name and version must be migratable.
+/// There are no released versions to migrate from yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = load_migratable_version(deps.storage, CONTRACT_NAME, &[])?
+        .ok_or(ContractError::MigrationError {})?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(migration_response(
+        &contract_version,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+    ))
+}
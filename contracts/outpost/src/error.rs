@@ -0,0 +1,63 @@
+use astroport_governance::errors::CommonError;
+use astroport_governance::ibc_controller::TIMEOUT_LIMITS;
+use astroport_governance::interchain::IBC_APP_VERSION;
+use astroport_governance::outpost::EPOCH_LIMITS;
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// This enum describes outpost contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error(
+        "Timeout must be within limits: {} <= timeout <= {}",
+        TIMEOUT_LIMITS.start(),
+        TIMEOUT_LIMITS.end()
+    )]
+    InvalidTimeout {},
+
+    #[error(
+        "Epoch length must be within limits: {} <= epoch_length <= {}",
+        EPOCH_LIMITS.start(),
+        EPOCH_LIMITS.end()
+    )]
+    InvalidEpochLength {},
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Hub channel is not set")]
+    HubChannelNotSet {},
+
+    #[error("Packets are only accepted from the hub channel. Got {0}")]
+    InvalidHubChannel(String),
+
+    #[error("Unexpected packet message")]
+    UnexpectedPacket {},
+
+    #[error("Invalid reply id {0}")]
+    InvalidReplyId(u64),
+
+    #[error("Totals were already synced for epoch {0}")]
+    AlreadySynced(u64),
+
+    #[error("Only unordered channels are supported")]
+    InvalidChannelOrder {},
+
+    #[error("Invalid IBC version {0}. Expected {expected}", expected = IBC_APP_VERSION)]
+    InvalidIbcVersion(String),
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
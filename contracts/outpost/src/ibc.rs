@@ -0,0 +1,242 @@
+use astroport_governance::ibc_controller::IbcAckResult;
+use astroport_governance::interchain::{
+    Envelope, HubToOutpostMsg, OutpostToHubMsg, IBC_APP_VERSION,
+};
+use astroport_governance::outpost::{ExecuteMsg, SyncStatus};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcMsg,
+    IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, IbcTimeout, Never, StdResult, Storage, SubMsg, WasmMsg,
+};
+
+use crate::contract::EXECUTE_PROPOSAL_REPLY_ID;
+use crate::error::ContractError;
+use crate::state::{CONFIG, LAST_SYNC};
+
+/// Checks the channel order and version before a channel is opened.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(
+        msg.channel().order.clone(),
+        &msg.channel().version,
+        msg.counterparty_version(),
+    )?;
+
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: IBC_APP_VERSION.to_string(),
+    }))
+}
+
+/// Checks the channel order and version once the handshake is finished.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    validate_channel(
+        channel.order.clone(),
+        &channel.version,
+        msg.counterparty_version(),
+    )?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_connect")
+        .add_attribute("channel_id", &channel.endpoint.channel_id))
+}
+
+/// Allows channels to be closed.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_close")
+        .add_attribute("channel_id", &msg.channel().endpoint.channel_id))
+}
+
+/// Executes proposals sent by the hub. Errors are returned to the hub as failed acknowledgements
+/// instead of failing the relayer transaction.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    do_packet_receive(deps, env, msg).or_else(|err| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(ack_fail(err.to_string()))
+            .add_attribute("action", "ibc_packet_receive"))
+    })
+}
+
+/// Accepts proposals from the hub channel only. The proposal messages are executed in a
+/// submessage, so a failure is reported to the hub by [`crate::contract::reply`] replacing the ack.
+/// Packets with forward metadata are relayed over their next hop instead of being executed.
+fn do_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let hub_channel = config
+        .hub_channel
+        .ok_or(ContractError::HubChannelNotSet {})?;
+    if msg.packet.dest.channel_id != hub_channel {
+        return Err(ContractError::InvalidHubChannel(msg.packet.dest.channel_id));
+    }
+
+    let envelope = match Envelope::<HubToOutpostMsg>::from_packet_data(&msg.packet.data)?.next_hop()
+    {
+        Ok((channel_id, envelope)) => {
+            return Ok(IbcReceiveResponse::new()
+                .set_ack(to_binary(&IbcAckResult::Ok(Binary::default()))?)
+                .add_message(IbcMsg::SendPacket {
+                    channel_id: channel_id.clone(),
+                    data: envelope.to_packet_data()?,
+                    timeout: IbcTimeout::with_timestamp(
+                        env.block.time.plus_seconds(config.timeout),
+                    ),
+                })
+                .add_attribute("action", "ibc_packet_forward")
+                .add_attribute("channel_id", channel_id));
+        }
+        Err(envelope) => envelope,
+    };
+
+    match envelope.msg {
+        HubToOutpostMsg::ExecuteProposal {
+            proposal_id,
+            messages,
+        } => {
+            let mut response = IbcReceiveResponse::new()
+                .set_ack(to_binary(&IbcAckResult::Ok(Binary::default()))?)
+                .add_attribute("action", "ibc_packet_receive")
+                .add_attribute("proposal_id", proposal_id.to_string());
+
+            if !messages.is_empty() {
+                response = response.add_submessage(SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: env.contract.address.to_string(),
+                        msg: to_binary(&ExecuteMsg::ExecuteProposal {
+                            proposal_id,
+                            messages,
+                        })?,
+                        funds: vec![],
+                    },
+                    EXECUTE_PROPOSAL_REPLY_ID,
+                ));
+            }
+
+            Ok(response)
+        }
+        HubToOutpostMsg::ProposalBroadcast { .. } => Err(ContractError::UnexpectedPacket {}),
+    }
+}
+
+/// Records whether the hub accepted the synced totals. Acknowledgements of forwarded proposals are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let response = IbcBasicResponse::new().add_attribute("action", "ibc_packet_ack");
+    let epoch = match epoch_from_packet(&msg.original_packet)? {
+        Some(epoch) => epoch,
+        None => return Ok(response),
+    };
+
+    let status = match from_binary(&msg.acknowledgement.data)? {
+        IbcAckResult::Ok(_) => SyncStatus::Acknowledged,
+        IbcAckResult::Error(_) => SyncStatus::Failed,
+    };
+
+    update_sync_status(deps.storage, epoch, status)?;
+
+    Ok(response.add_attribute("epoch", epoch.to_string()))
+}
+
+/// Marks the sync as failed so the epoch can be synced again. Timeouts of forwarded proposals are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let response = IbcBasicResponse::new().add_attribute("action", "ibc_packet_timeout");
+    let epoch = match epoch_from_packet(msg.packet())? {
+        Some(epoch) => epoch,
+        None => return Ok(response),
+    };
+
+    update_sync_status(deps.storage, epoch, SyncStatus::Failed)?;
+
+    Ok(response.add_attribute("epoch", epoch.to_string()))
+}
+
+/// Checks that the channel is unordered and that both sides use [`IBC_APP_VERSION`].
+fn validate_channel(
+    order: IbcOrder,
+    version: &str,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidChannelOrder {});
+    }
+
+    if version != IBC_APP_VERSION {
+        return Err(ContractError::InvalidIbcVersion(version.to_string()));
+    }
+
+    if let Some(counterparty_version) = counterparty_version {
+        if counterparty_version != IBC_APP_VERSION {
+            return Err(ContractError::InvalidIbcVersion(
+                counterparty_version.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts the epoch from the data of a sync packet sent by this contract.
+/// Returns `None` for proposal packets this contract forwarded.
+fn epoch_from_packet(packet: &IbcPacket) -> Result<Option<u64>, ContractError> {
+    if Envelope::<HubToOutpostMsg>::from_packet_data(&packet.data).is_ok() {
+        return Ok(None);
+    }
+
+    match Envelope::<OutpostToHubMsg>::from_packet_data(&packet.data)?.msg {
+        OutpostToHubMsg::TotalsSync { epoch, .. } => Ok(Some(epoch)),
+        _ => Err(ContractError::UnexpectedPacket {}),
+    }
+}
+
+/// Builds an error acknowledgement.
+fn ack_fail(error: String) -> Binary {
+    // Serializing an enum holding a string can't fail
+    to_binary(&IbcAckResult::Error(error)).unwrap()
+}
+
+/// Updates the status of the last sync. Outcomes of older syncs are ignored.
+fn update_sync_status(storage: &mut dyn Storage, epoch: u64, status: SyncStatus) -> StdResult<()> {
+    if let Some(mut last_sync) = LAST_SYNC.may_load(storage)? {
+        if last_sync.epoch == epoch {
+            last_sync.status = status;
+            LAST_SYNC.save(storage, &last_sync)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use astroport_governance::astroport::common::OwnershipProposal;
+use astroport_governance::outpost::{Config, SyncInfo};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+
+/// Stores the contract config
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Stores the xASTRO staked by every user with block height checkpoints
+pub const STAKED: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "staked",
+    "staked__checkpoints",
+    "staked__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Stores the total staked xASTRO with block height checkpoints
+pub const TOTAL_STAKED: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_staked",
+    "total_staked__checkpoints",
+    "total_staked__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Stores the last sync of totals with the hub
+pub const LAST_SYNC: Item<SyncInfo> = Item::new("last_sync");
@@ -0,0 +1,416 @@
+use astroport_governance::ibc_controller::IbcAckResult;
+use astroport_governance::interchain::{
+    Envelope, ForwardMetadata, HubToOutpostMsg, OutpostToHubMsg,
+};
+use astroport_governance::outpost::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SyncInfo, SyncStatus,
+};
+use astroport_governance::voting_escrow::VotingPowerResponse;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_ibc_packet_ack, mock_ibc_packet_recv,
+    mock_ibc_packet_timeout, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, BankMsg, Binary, CosmosMsg, Env, IbcAcknowledgement, IbcMsg,
+    OwnedDeps, Reply, ReplyOn, SubMsgResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use outpost::contract::{execute, instantiate, migrate, query, reply, EXECUTE_PROPOSAL_REPLY_ID};
+use outpost::error::ContractError;
+use outpost::ibc::{ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout};
+
+const OWNER: &str = "owner";
+const XASTRO: &str = "xastro";
+const HUB_CHANNEL: &str = "channel-0";
+const EPOCH_LENGTH: u64 = 86400;
+
+#[test]
+fn test_voting_power_checkpoints() {
+    let mut deps = instantiate_outpost();
+    let env = mock_env();
+    let height = env.block.height;
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        stake_msg("user", 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XASTRO, &[]),
+        stake_msg("user", 100),
+    )
+    .unwrap();
+
+    let mut env = env_at(height + 10, 0);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XASTRO, &[]),
+        stake_msg("user2", 50),
+    )
+    .unwrap();
+
+    env.block.height += 10;
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        ExecuteMsg::Unstake {
+            amount: Uint128::new(101),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Overflow(_)));
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        ExecuteMsg::Unstake {
+            amount: Uint128::new(40),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        resp.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: XASTRO.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "user".to_string(),
+                amount: Uint128::new(40),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Checkpoints are taken at the start of every block
+    let cases = [
+        (height, 0, 0),
+        (height + 1, 100, 100),
+        (height + 11, 100, 150),
+        (height + 21, 60, 110),
+    ];
+    for (height, user_power, total_power) in cases {
+        assert_eq!(
+            query_power(
+                &deps,
+                QueryMsg::UserVotingPowerAt {
+                    user: "user".to_string(),
+                    height,
+                },
+            ),
+            user_power
+        );
+        assert_eq!(
+            query_power(&deps, QueryMsg::TotalVotingPowerAt { height }),
+            total_power
+        );
+    }
+
+    assert_eq!(
+        query_power(
+            &deps,
+            QueryMsg::UserVotingPower {
+                user: "user2".to_string(),
+            },
+        ),
+        50
+    );
+    assert_eq!(query_power(&deps, QueryMsg::TotalVotingPower {}), 110);
+}
+
+#[test]
+fn test_sync_totals() {
+    let mut deps = instantiate_outpost();
+    let env = mock_env();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(XASTRO, &[]),
+        stake_msg("user", 100),
+    )
+    .unwrap();
+
+    let env = env_at(env.block.height + 1, 0);
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        ExecuteMsg::SyncTotals {},
+    )
+    .unwrap();
+    let epoch = env.block.time.seconds() / EPOCH_LENGTH;
+    let sync_msg = OutpostToHubMsg::TotalsSync {
+        epoch,
+        height: env.block.height,
+        total_voting_power: Uint128::new(100),
+    };
+    match &resp.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket {
+            channel_id, data, ..
+        }) => {
+            assert_eq!(channel_id, HUB_CHANNEL);
+            assert_eq!(
+                Envelope::<OutpostToHubMsg>::from_packet_data(data)
+                    .unwrap()
+                    .msg,
+                sync_msg
+            );
+        }
+        msg => panic!("Unexpected message: {msg:?}"),
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        ExecuteMsg::SyncTotals {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadySynced(epoch));
+
+    // A timed out sync can be retried within the same epoch
+    let msg = mock_ibc_packet_timeout(HUB_CHANNEL, &Envelope::new(sync_msg.clone())).unwrap();
+    ibc_packet_timeout(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(query_last_sync(&deps).status, SyncStatus::Failed);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        ExecuteMsg::SyncTotals {},
+    )
+    .unwrap();
+
+    let ack = IbcAcknowledgement::new(to_binary(&IbcAckResult::Ok(Binary::default())).unwrap());
+    let msg = mock_ibc_packet_ack(HUB_CHANNEL, &Envelope::new(sync_msg), ack).unwrap();
+    ibc_packet_ack(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        query_last_sync(&deps),
+        SyncInfo {
+            epoch,
+            height: env.block.height,
+            total_voting_power: Uint128::new(100),
+            status: SyncStatus::Acknowledged,
+        }
+    );
+
+    let env = env_at(env.block.height + 1, EPOCH_LENGTH);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("random", &[]),
+        ExecuteMsg::SyncTotals {},
+    )
+    .unwrap();
+    assert_eq!(query_last_sync(&deps).epoch, epoch + 1);
+}
+
+#[test]
+fn test_execute_proposal() {
+    let mut deps = instantiate_outpost();
+
+    let messages: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: "receiver".to_string(),
+        amount: coins(100, "uluna"),
+    }
+    .into()];
+    let packet = Envelope::new(HubToOutpostMsg::ExecuteProposal {
+        proposal_id: 1,
+        messages: messages.clone(),
+    });
+
+    let msg = mock_ibc_packet_recv("channel-1", &packet).unwrap();
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(resp.messages.is_empty());
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Error(ContractError::InvalidHubChannel("channel-1".to_string()).to_string())
+    );
+
+    let msg = mock_ibc_packet_recv(HUB_CHANNEL, &packet).unwrap();
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Ok(Binary::default())
+    );
+    assert_eq!(resp.messages[0].id, EXECUTE_PROPOSAL_REPLY_ID);
+    assert_eq!(resp.messages[0].reply_on, ReplyOn::Error);
+    let execute_msg = match &resp.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, MOCK_CONTRACT_ADDR);
+            from_binary::<ExecuteMsg>(msg).unwrap()
+        }
+        msg => panic!("Unexpected message: {msg:?}"),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        execute_msg,
+    )
+    .unwrap();
+    assert_eq!(resp.messages[0].msg, messages[0]);
+
+    // A failed execution replaces the acknowledgement with an error
+    let resp = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: EXECUTE_PROPOSAL_REPLY_ID,
+            result: SubMsgResult::Err("insufficient funds".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.data.unwrap()).unwrap(),
+        IbcAckResult::Error("insufficient funds".to_string())
+    );
+}
+
+#[test]
+fn test_forward_proposal() {
+    let mut deps = instantiate_outpost();
+
+    let proposal = Envelope::new(HubToOutpostMsg::ExecuteProposal {
+        proposal_id: 1,
+        messages: vec![BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: coins(100, "uluna"),
+        }
+        .into()],
+    });
+    let packet = proposal.clone().with_forward(Some(ForwardMetadata {
+        hops: vec!["channel-5".to_string(), "channel-9".to_string()],
+    }));
+
+    // The outpost relays the packet over its hop instead of executing it
+    let msg = mock_ibc_packet_recv(HUB_CHANNEL, &packet).unwrap();
+    let resp = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(
+        from_binary::<IbcAckResult>(&resp.acknowledgement).unwrap(),
+        IbcAckResult::Ok(Binary::default())
+    );
+    assert_eq!(resp.messages.len(), 1);
+    let forwarded = match &resp.messages[0].msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket {
+            channel_id, data, ..
+        }) => {
+            assert_eq!(channel_id, "channel-5");
+            Envelope::<HubToOutpostMsg>::from_packet_data(data).unwrap()
+        }
+        msg => panic!("Unexpected message: {msg:?}"),
+    };
+    assert_eq!(
+        forwarded,
+        proposal.clone().with_forward(Some(ForwardMetadata {
+            hops: vec!["channel-9".to_string()],
+        }))
+    );
+
+    // Acknowledgements and timeouts of forwarded packets don't affect syncs
+    let ack = IbcAcknowledgement::new(to_binary(&IbcAckResult::Ok(Binary::default())).unwrap());
+    let msg = mock_ibc_packet_ack("channel-5", &forwarded, ack).unwrap();
+    ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+    let msg = mock_ibc_packet_timeout("channel-5", &forwarded).unwrap();
+    ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+
+    // The last chain on the route executes the proposal
+    let (_, last) = forwarded.next_hop().unwrap();
+    assert_eq!(last, proposal);
+}
+
+#[test]
+fn test_migrate() {
+    let mut deps = instantiate_outpost();
+
+    // There are no released versions to migrate from yet
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(err, ContractError::MigrationError {});
+
+    // Other contracts can't be migrated to the outpost
+    set_contract_version(deps.as_mut().storage, "ibc-controller", "1.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(err, ContractError::MigrationError {});
+}
+
+fn instantiate_outpost() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        owner: OWNER.to_string(),
+        xastro_token: XASTRO.to_string(),
+        hub_channel: Some(HUB_CHANNEL.to_string()),
+        epoch_length: 60,
+        timeout: 3600,
+    };
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidEpochLength {});
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        InstantiateMsg {
+            epoch_length: EPOCH_LENGTH,
+            ..msg
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn env_at(height: u64, seconds_passed: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env.block.time = env.block.time.plus_seconds(seconds_passed);
+    env
+}
+
+fn stake_msg(user: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: user.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Stake {}).unwrap(),
+    })
+}
+
+fn query_power(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, msg: QueryMsg) -> u128 {
+    let resp: VotingPowerResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    resp.voting_power.u128()
+}
+
+fn query_last_sync(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> SyncInfo {
+    let resp: Option<SyncInfo> =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LastSync {}).unwrap()).unwrap();
+    resp.unwrap()
+}
@@ -0,0 +1,572 @@
+use astroport_governance::astroport::common::{
+    claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport_governance::pagination::paginate_map;
+use astroport_governance::treasury::{
+    Budget, Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Stream,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::error::ContractError;
+use crate::state::{
+    BUDGETS, BUDGET_COUNT, COMMITTED, CONFIG, OWNERSHIP_PROPOSAL, STREAMS, STREAM_COUNT,
+};
+
+// Version info for contract migration.
+const CONTRACT_NAME: &str = "astroport-treasury";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = deps.api.addr_validate(&msg.owner)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: owner.clone(),
+        },
+    )?;
+    BUDGET_COUNT.save(deps.storage, &0)?;
+    STREAM_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", owner))
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::Spend { asset, recipient }** Sends treasury funds to a recipient.
+///
+/// * **ExecuteMsg::CreateBudget { .. }** Creates a budget stream.
+///
+/// * **ExecuteMsg::CancelBudget { budget_id }** Stops a budget stream.
+///
+/// * **ExecuteMsg::ClaimBudget { budget_id }** Claims the unlocked amount of a budget.
+///
+/// * **ExecuteMsg::CreateStream { .. }** Creates a continuous payment stream.
+///
+/// * **ExecuteMsg::CancelStream { stream_id }** Stops a payment stream.
+///
+/// * **ExecuteMsg::WithdrawStream { stream_id, amount }** Withdraws from a payment stream.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change
+/// contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Spend { asset, recipient } => spend(deps, env, info, asset, recipient),
+        ExecuteMsg::CreateBudget {
+            recipient,
+            asset_info,
+            amount_per_period,
+            period,
+            start_time,
+            end_time,
+        } => create_budget(
+            deps,
+            env,
+            info,
+            recipient,
+            asset_info,
+            amount_per_period,
+            period,
+            start_time,
+            end_time,
+        ),
+        ExecuteMsg::CancelBudget { budget_id } => cancel_budget(deps, env, info, budget_id),
+        ExecuteMsg::ClaimBudget { budget_id } => claim_budget(deps, env, info, budget_id),
+        ExecuteMsg::CreateStream {
+            recipient,
+            asset_info,
+            amount,
+            start_time,
+            end_time,
+        } => create_stream(
+            deps, env, info, recipient, asset_info, amount, start_time, end_time,
+        ),
+        ExecuteMsg::CancelStream { stream_id } => cancel_stream(deps, env, info, stream_id),
+        ExecuteMsg::WithdrawStream { stream_id, amount } => {
+            withdraw_stream(deps, env, info, stream_id, amount)
+        }
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        }
+    }
+}
+
+/// Sends treasury funds to a recipient. Funds still owed to budget and stream recipients can't be
+/// spent.
+///
+/// * **asset** asset and amount to send.
+///
+/// * **recipient** address that receives the funds.
+fn spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: Asset,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if asset.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let balance = query_balance(deps.as_ref(), &env, &asset.info)?;
+    let committed = COMMITTED
+        .may_load(deps.storage, asset.info.to_string())?
+        .unwrap_or_default();
+    let spendable = balance.saturating_sub(committed);
+    if asset.amount > spendable {
+        return Err(ContractError::InsufficientSpendable { spendable });
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&asset, &recipient)?)
+        .add_attribute("action", "spend")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("recipient", recipient))
+}
+
+/// Creates a budget which unlocks `amount_per_period` at the end of every period.
+///
+/// * **recipient** address that can claim the budget.
+///
+/// * **asset_info** asset paid from the treasury.
+///
+/// * **period** period length in seconds.
+///
+/// * **start_time** timestamp at which the first period starts. Defaults to the current block time.
+///
+/// * **end_time** timestamp after which no more periods are unlocked.
+#[allow(clippy::too_many_arguments)]
+fn create_budget(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    asset_info: AssetInfo,
+    amount_per_period: Uint128,
+    period: u64,
+    start_time: Option<u64>,
+    end_time: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount_per_period.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    if period == 0 {
+        return Err(ContractError::ZeroPeriod {});
+    }
+
+    let start_time = start_time.unwrap_or_else(|| env.block.time.seconds());
+    if end_time <= start_time {
+        return Err(ContractError::InvalidEndTime {});
+    }
+
+    asset_info.check(deps.api)?;
+
+    let budget_id = BUDGET_COUNT.update::<_, StdError>(deps.storage, |count| Ok(count + 1))?;
+    let budget = Budget {
+        budget_id,
+        recipient: deps.api.addr_validate(&recipient)?,
+        asset_info,
+        amount_per_period,
+        period,
+        start_time,
+        end_time,
+        claimed: Uint128::zero(),
+    };
+    BUDGETS.save(deps.storage, budget_id, &budget)?;
+    commit(
+        deps.storage,
+        &budget.asset_info,
+        budget.unlocked(budget.end_time)?,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_budget")
+        .add_attribute("budget_id", budget_id.to_string())
+        .add_attribute("recipient", budget.recipient))
+}
+
+/// Stops a budget. Periods that already ended stay claimable.
+///
+/// * **budget_id** budget identifier.
+fn cancel_budget(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    budget_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut budget = BUDGETS.load(deps.storage, budget_id)?;
+    let total = budget.unlocked(budget.end_time)?;
+    budget.end_time = budget.end_time.min(env.block.time.seconds());
+    BUDGETS.save(deps.storage, budget_id, &budget)?;
+
+    // Periods that will never end are not owed to the recipient anymore
+    release(
+        deps.storage,
+        &budget.asset_info,
+        total - budget.unlocked(budget.end_time)?,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_budget")
+        .add_attribute("budget_id", budget_id.to_string()))
+}
+
+/// Sends the unlocked amount of a budget to its recipient.
+///
+/// * **budget_id** budget identifier.
+fn claim_budget(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    budget_id: u64,
+) -> Result<Response, ContractError> {
+    let mut budget = BUDGETS.load(deps.storage, budget_id)?;
+    if info.sender != budget.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = budget.claimable(env.block.time.seconds())?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim(budget_id));
+    }
+
+    budget.claimed += amount;
+    BUDGETS.save(deps.storage, budget_id, &budget)?;
+    release(deps.storage, &budget.asset_info, amount)?;
+
+    let asset = Asset {
+        info: budget.asset_info,
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&asset, &budget.recipient)?)
+        .add_attribute("action", "claim_budget")
+        .add_attribute("budget_id", budget_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Creates a stream which pays `amount` linearly between `start_time` and `end_time`.
+///
+/// * **recipient** address that can withdraw from the stream.
+///
+/// * **asset_info** asset paid from the treasury.
+///
+/// * **start_time** timestamp at which the stream starts. Defaults to the current block time.
+///
+/// * **end_time** timestamp at which the whole amount is streamed.
+#[allow(clippy::too_many_arguments)]
+fn create_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    asset_info: AssetInfo,
+    amount: Uint128,
+    start_time: Option<u64>,
+    end_time: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let start_time = start_time.unwrap_or_else(|| env.block.time.seconds());
+    if end_time <= start_time {
+        return Err(ContractError::InvalidEndTime {});
+    }
+
+    asset_info.check(deps.api)?;
+
+    let stream_id = STREAM_COUNT.update::<_, StdError>(deps.storage, |count| Ok(count + 1))?;
+    let stream = Stream {
+        stream_id,
+        recipient: deps.api.addr_validate(&recipient)?,
+        asset_info,
+        amount,
+        start_time,
+        end_time,
+        withdrawn: Uint128::zero(),
+        cancelled_at: None,
+    };
+    STREAMS.save(deps.storage, stream_id, &stream)?;
+    commit(deps.storage, &stream.asset_info, stream.amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_stream")
+        .add_attribute("stream_id", stream_id.to_string())
+        .add_attribute("recipient", stream.recipient))
+}
+
+/// Stops a stream. The amount streamed before cancellation stays withdrawable.
+///
+/// * **stream_id** stream identifier.
+fn cancel_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut stream = STREAMS.load(deps.storage, stream_id)?;
+    if stream.cancelled_at.is_some() {
+        return Err(ContractError::StreamCancelled(stream_id));
+    }
+
+    stream.cancelled_at = Some(env.block.time.seconds());
+    STREAMS.save(deps.storage, stream_id, &stream)?;
+
+    // The amount that will never be streamed is not owed to the recipient anymore
+    let streamed = stream.streamed(stream.end_time);
+    release(deps.storage, &stream.asset_info, stream.amount - streamed)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_stream")
+        .add_attribute("stream_id", stream_id.to_string()))
+}
+
+/// Sends streamed funds to the stream recipient.
+///
+/// * **stream_id** stream identifier.
+///
+/// * **amount** amount to withdraw. Defaults to everything available.
+fn withdraw_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut stream = STREAMS.load(deps.storage, stream_id)?;
+    if info.sender != stream.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let withdrawable = stream.withdrawable(env.block.time.seconds());
+    let amount = amount.unwrap_or(withdrawable);
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    if amount > withdrawable {
+        return Err(ContractError::InsufficientWithdrawable {
+            stream_id,
+            withdrawable,
+        });
+    }
+
+    stream.withdrawn += amount;
+    STREAMS.save(deps.storage, stream_id, &stream)?;
+    release(deps.storage, &stream.asset_info, amount)?;
+
+    let asset = Asset {
+        info: stream.asset_info,
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&asset, &stream.recipient)?)
+        .add_attribute("action", "withdraw_stream")
+        .add_attribute("stream_id", stream_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Increases the amount of an asset owed to budget and stream recipients.
+fn commit(storage: &mut dyn Storage, asset_info: &AssetInfo, amount: Uint128) -> StdResult<()> {
+    COMMITTED.update::<_, StdError>(storage, asset_info.to_string(), |committed| {
+        Ok(committed.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Decreases the amount of an asset owed to budget and stream recipients.
+fn release(storage: &mut dyn Storage, asset_info: &AssetInfo, amount: Uint128) -> StdResult<()> {
+    COMMITTED.update::<_, StdError>(storage, asset_info.to_string(), |committed| {
+        Ok(committed.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Returns the treasury balance of the specified asset.
+fn query_balance(deps: Deps, env: &Env, asset_info: &AssetInfo) -> StdResult<Uint128> {
+    match asset_info {
+        AssetInfo::Token { contract_addr } => {
+            let resp: BalanceResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(resp.balance)
+        }
+        AssetInfo::NativeToken { denom } => Ok(deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount),
+    }
+}
+
+/// Builds a message which transfers the specified asset to the recipient.
+fn transfer_msg(asset: &Asset, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let msg = match &asset.info {
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }),
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(asset.amount.u128(), denom),
+        }),
+    };
+
+    Ok(msg)
+}
+
+/// Exposes all the queries available in the contract.
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::Budget { budget_id }** Returns a budget.
+///
+/// * **QueryMsg::Budgets { start_after, limit }** Returns a page of budgets.
+///
+/// * **QueryMsg::Claimable { budget_id }** Returns the amount the budget recipient can claim.
+///
+/// * **QueryMsg::Stream { stream_id }** Returns a stream.
+///
+/// * **QueryMsg::Streams { start_after, limit }** Returns a page of streams.
+///
+/// * **QueryMsg::Withdrawable { stream_id }** Returns the amount the stream recipient can withdraw.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Budget { budget_id } => to_binary(&BUDGETS.load(deps.storage, budget_id)?),
+        QueryMsg::Budgets { start_after, limit } => {
+            let budgets =
+                paginate_map(&BUDGETS, deps.storage, start_after, limit, Order::Ascending)?
+                    .into_iter()
+                    .map(|(_, budget)| budget)
+                    .collect::<Vec<_>>();
+            to_binary(&budgets)
+        }
+        QueryMsg::Claimable { budget_id } => to_binary(
+            &BUDGETS
+                .load(deps.storage, budget_id)?
+                .claimable(env.block.time.seconds())?,
+        ),
+        QueryMsg::Stream { stream_id } => to_binary(&STREAMS.load(deps.storage, stream_id)?),
+        QueryMsg::Streams { start_after, limit } => {
+            let streams =
+                paginate_map(&STREAMS, deps.storage, start_after, limit, Order::Ascending)?
+                    .into_iter()
+                    .map(|(_, stream)| stream)
+                    .collect::<Vec<_>>();
+            to_binary(&streams)
+        }
+        QueryMsg::Withdrawable { stream_id } => to_binary(
+            &STREAMS
+                .load(deps.storage, stream_id)?
+                .withdrawable(env.block.time.seconds()),
+        ),
+    }
+}
+
+/// Manages contract migration.
+/// There are no released versions to migrate from yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Err(ContractError::MigrationError {})
+}
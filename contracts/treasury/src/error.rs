@@ -0,0 +1,39 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+/// This enum describes treasury contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Budget period must be greater than zero")]
+    ZeroPeriod {},
+
+    #[error("Budget end time must be after its start time")]
+    InvalidEndTime {},
+
+    #[error("Nothing to claim from budget {0}")]
+    NothingToClaim(u64),
+
+    #[error("Stream {0} was already cancelled")]
+    StreamCancelled(u64),
+
+    #[error("Stream {stream_id} only has {withdrawable} available to withdraw")]
+    InsufficientWithdrawable {
+        stream_id: u64,
+        withdrawable: Uint128,
+    },
+
+    #[error("Only {spendable} is available to spend, the rest is owed to budgets and streams")]
+    InsufficientSpendable { spendable: Uint128 },
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
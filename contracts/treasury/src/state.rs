@@ -0,0 +1,25 @@
+use astroport_governance::astroport::common::OwnershipProposal;
+use astroport_governance::treasury::{Budget, Config, Stream};
+use cosmwasm_std::Uint128;
+use cw_storage_plus::{Item, Map};
+
+/// Stores the contract config
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// Number of created budgets
+pub const BUDGET_COUNT: Item<u64> = Item::new("budget_count");
+
+/// Budget streams keyed by budget ID
+pub const BUDGETS: Map<u64, Budget> = Map::new("budgets");
+
+/// Number of created streams
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+
+/// Payment streams keyed by stream ID
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+
+/// Amounts still owed to budget and stream recipients keyed by asset
+pub const COMMITTED: Map<String, Uint128> = Map::new("committed");
@@ -0,0 +1,486 @@
+use astroport_governance::astroport::asset::{Asset, AssetInfo};
+use astroport_governance::treasury::{Budget, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{coins, from_binary, BankMsg, CosmosMsg, Env, OwnedDeps, Timestamp, Uint128};
+use treasury::contract::{execute, instantiate, query};
+use treasury::error::ContractError;
+
+const OWNER: &str = "assembly";
+const RECIPIENT: &str = "grantee";
+const DENOM: &str = "uluna";
+const PERIOD: u64 = 30 * 86400;
+
+#[test]
+fn test_spend() {
+    let mut deps = instantiate_treasury();
+
+    let msg = ExecuteMsg::Spend {
+        asset: Asset {
+            info: native_asset_info(),
+            amount: Uint128::new(100),
+        },
+        recipient: RECIPIENT.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(RECIPIENT, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSpendable {
+            spendable: Uint128::zero()
+        }
+    );
+
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(100, DENOM));
+    let resp = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(100));
+}
+
+#[test]
+fn test_spend_owed_funds() {
+    let mut deps = instantiate_treasury();
+    let start_time = mock_env().block.time.seconds();
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(5000, DENOM));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CreateBudget {
+            recipient: RECIPIENT.to_string(),
+            asset_info: native_asset_info(),
+            amount_per_period: Uint128::new(1000),
+            period: PERIOD,
+            start_time: None,
+            end_time: start_time + 3 * PERIOD,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CreateStream {
+            recipient: RECIPIENT.to_string(),
+            asset_info: native_asset_info(),
+            amount: Uint128::new(1000),
+            start_time: None,
+            end_time: start_time + 1000,
+        },
+    )
+    .unwrap();
+
+    let spend = |amount: u128| ExecuteMsg::Spend {
+        asset: Asset {
+            info: native_asset_info(),
+            amount: Uint128::new(amount),
+        },
+        recipient: RECIPIENT.to_string(),
+    };
+
+    // 3000 is owed to the budget and 1000 to the stream
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        spend(1001),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSpendable {
+            spendable: Uint128::new(1000)
+        }
+    );
+
+    // Funds that will never be streamed can be spent after the cancellation
+    let env = env_at(start_time + 500);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelStream { stream_id: 1 },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        spend(1501),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSpendable {
+            spendable: Uint128::new(1500)
+        }
+    );
+
+    // Withdrawn funds are not owed anymore
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::WithdrawStream {
+            stream_id: 1,
+            amount: None,
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(4500, DENOM));
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        spend(1501),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSpendable {
+            spendable: Uint128::new(1500)
+        }
+    );
+
+    // Periods that end after the cancellation are not owed anymore
+    let env = env_at(start_time + PERIOD + 1);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelBudget { budget_id: 1 },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        spend(3501),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientSpendable {
+            spendable: Uint128::new(3500)
+        }
+    );
+
+    let resp = execute(deps.as_mut(), env, mock_info(OWNER, &[]), spend(3500)).unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(3500));
+}
+
+#[test]
+fn test_budget_stream() {
+    let mut deps = instantiate_treasury();
+    let env = mock_env();
+    let start_time = env.block.time.seconds();
+
+    let create_msg = ExecuteMsg::CreateBudget {
+        recipient: RECIPIENT.to_string(),
+        asset_info: native_asset_info(),
+        amount_per_period: Uint128::new(1000),
+        period: PERIOD,
+        start_time: None,
+        end_time: start_time + 3 * PERIOD + PERIOD / 2,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        create_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        create_msg,
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::ClaimBudget { budget_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim(1));
+
+    let env = env_at(start_time + PERIOD + 1);
+    assert_eq!(query_claimable(&deps, &env), 1000);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        ExecuteMsg::ClaimBudget { budget_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::ClaimBudget { budget_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(1000));
+    assert_eq!(query_claimable(&deps, &env), 0);
+
+    // The partial period before the end time is never unlocked
+    let env = env_at(start_time + 10 * PERIOD);
+    assert_eq!(query_claimable(&deps, &env), 2000);
+
+    let resp = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::ClaimBudget { budget_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(2000));
+
+    let budget: Budget =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Budget { budget_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(budget.claimed.u128(), 3000);
+}
+
+#[test]
+fn test_cancel_budget() {
+    let mut deps = instantiate_treasury();
+    let start_time = mock_env().block.time.seconds();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CreateBudget {
+            recipient: RECIPIENT.to_string(),
+            asset_info: native_asset_info(),
+            amount_per_period: Uint128::new(1000),
+            period: PERIOD,
+            start_time: Some(start_time),
+            end_time: start_time + 12 * PERIOD,
+        },
+    )
+    .unwrap();
+
+    let env = env_at(start_time + 2 * PERIOD + PERIOD / 2);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::CancelBudget { budget_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelBudget { budget_id: 1 },
+    )
+    .unwrap();
+
+    // Periods that ended before the cancellation stay claimable
+    let env = env_at(start_time + 12 * PERIOD);
+    assert_eq!(query_claimable(&deps, &env), 2000);
+}
+
+#[test]
+fn test_payment_stream() {
+    let mut deps = instantiate_treasury();
+    let start_time = mock_env().block.time.seconds();
+
+    let create_msg = ExecuteMsg::CreateStream {
+        recipient: RECIPIENT.to_string(),
+        asset_info: native_asset_info(),
+        amount: Uint128::new(1000),
+        start_time: None,
+        end_time: start_time + 1000,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(RECIPIENT, &[]),
+        create_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), create_msg).unwrap();
+
+    let env = env_at(start_time + 250);
+    assert_eq!(query_withdrawable(&deps, &env), 250);
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::WithdrawStream {
+            stream_id: 1,
+            amount: Some(Uint128::new(100)),
+        },
+    )
+    .unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(100));
+    assert_eq!(query_withdrawable(&deps, &env), 150);
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::WithdrawStream {
+            stream_id: 1,
+            amount: Some(Uint128::new(200)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientWithdrawable {
+            stream_id: 1,
+            withdrawable: Uint128::new(150)
+        }
+    );
+
+    let env = env_at(start_time + 500);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::CancelStream { stream_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelStream { stream_id: 1 },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelStream { stream_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StreamCancelled(1));
+
+    // Streaming stops at the cancellation time
+    let env = env_at(start_time + 900);
+    assert_eq!(query_withdrawable(&deps, &env), 400);
+
+    let resp = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::WithdrawStream {
+            stream_id: 1,
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(resp.messages[0].msg, bank_send(400));
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RECIPIENT, &[]),
+        ExecuteMsg::WithdrawStream {
+            stream_id: 1,
+            amount: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+}
+
+fn instantiate_treasury() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        InstantiateMsg {
+            owner: OWNER.to_string(),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn native_asset_info() -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: DENOM.to_string(),
+    }
+}
+
+fn bank_send(amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: RECIPIENT.to_string(),
+        amount: coins(amount, DENOM),
+    })
+}
+
+fn env_at(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(time);
+    env
+}
+
+fn query_claimable(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env) -> u128 {
+    let claimable: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Claimable { budget_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    claimable.u128()
+}
+
+fn query_withdrawable(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env) -> u128 {
+    let withdrawable: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Withdrawable { stream_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    withdrawable.u128()
+}
@@ -5,8 +5,8 @@ use astroport_governance::astroport::DecimalCheckedOps;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Reply, Response, StdError, StdResult, Storage, SubMsgResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::{
@@ -17,30 +17,37 @@ use cw20_base::contract::{
     execute_update_marketing, execute_upload_logo, query_download_logo, query_marketing_info,
 };
 use cw20_base::state::{MinterData, TokenInfo, LOGO, MARKETING_INFO, TOKEN_INFO};
+use cw_storage_plus::Bound;
 
 use crate::astroport::asset::addr_opt_validate;
 use crate::astroport::common::validate_addresses;
-use astroport_governance::utils::{get_period, get_periods_count, EPOCH_START, WEEK};
+use astroport_governance::utils::{get_period, get_period_start, get_periods_count};
 use astroport_governance::voting_escrow::{
     BlacklistedVotersResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
-    LockInfoResponse, MigrateMsg, QueryMsg, VotingPowerResponse, DEFAULT_LIMIT, MAX_LIMIT,
+    LockInfoResponse, LockedBalance, LockedBalancesResponse, MigrateMsg, QueryMsg, SudoMsg,
+    VotingPowerResponse, DEFAULT_LIMIT, MAX_LIMIT,
 };
 
 use crate::error::ContractError;
 use crate::marketing_validation::{validate_marketing_info, validate_whitelist_links};
 use crate::state::{
-    Config, Lock, Point, BLACKLIST, CONFIG, HISTORY, LAST_SLOPE_CHANGE, LOCKED, OWNERSHIP_PROPOSAL,
+    Config, Lock, Point, BLACKLIST, CHECKPOINT_NONCES, CONFIG, HISTORY, LAST_SLOPE_CHANGE, LOCKED,
+    OWNERSHIP_PROPOSAL, PAUSED,
 };
 use crate::utils::{
     adjust_vp_and_slope, blacklist_check, calc_coefficient, calc_voting_power,
-    cancel_scheduled_slope, fetch_last_checkpoint, fetch_slope_changes, schedule_slope_change,
-    time_limits_check, xastro_token_check,
+    cancel_scheduled_slope, checkpoint_hook_msgs, fetch_last_checkpoint, fetch_slope_changes,
+    generator_controller_hook_msg, schedule_slope_change, time_limits_check, xastro_token_check,
 };
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astro-voting-escrow";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Reply id of checkpoint hook notifications, which are only replied to on error
+pub(crate) const CHECKPOINT_HOOK_REPLY_ID: u64 = 1;
+/// Reply id of generator controller notifications, which are only replied to on error
+pub(crate) const GENERATOR_CONTROLLER_HOOK_REPLY_ID: u64 = 2;
 
 /// Creates a new contract with the specified parameters in [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -71,6 +78,8 @@ pub fn instantiate(
         astro_addr: staking_config.deposit_token_addr,
         xastro_staking_addr: deps.api.addr_validate(&xastro_minter_resp.minter)?,
         logo_urls_whitelist: msg.logo_urls_whitelist.clone(),
+        checkpoint_hooks: vec![],
+        generator_controller: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -225,9 +234,17 @@ pub fn execute(
                 Ok(Response::default().add_attribute("action", "set_logo_urls_whitelist"))
             }
         }
-        ExecuteMsg::UpdateConfig { new_guardian } => {
-            execute_update_config(deps, info, new_guardian)
-        }
+        ExecuteMsg::UpdateConfig {
+            new_guardian,
+            checkpoint_hooks,
+            generator_controller,
+        } => execute_update_config(
+            deps,
+            info,
+            new_guardian,
+            checkpoint_hooks,
+            generator_controller,
+        ),
     }
 }
 
@@ -402,6 +419,9 @@ fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
     xastro_token_check(deps.storage, info.sender)?;
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
     let sender = Addr::unchecked(cw20_msg.sender);
     blacklist_check(deps.storage, &sender)?;
 
@@ -428,7 +448,7 @@ fn receive_cw20(
 ///
 /// * **time** duration of the lock.
 fn create_lock(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     user: Addr,
     amount: Uint128,
@@ -451,9 +471,11 @@ fn create_lock(
         })
     })?;
 
-    checkpoint(deps, env, user, Some(amount), Some(end))?;
+    checkpoint(deps.branch(), env, user.clone(), Some(amount), Some(end))?;
 
-    Ok(Response::default().add_attribute("action", "create_lock"))
+    Ok(Response::default()
+        .add_submessages(checkpoint_hook_msgs(deps.storage, &user, block_period)?)
+        .add_attribute("action", "create_lock"))
 }
 
 /// Deposits an 'amount' of xASTRO tokens into 'user''s lock.
@@ -465,7 +487,7 @@ fn create_lock(
 ///
 /// * **user** user who's lock amount will increase.
 fn deposit_for(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     amount: Uint128,
     user: Addr,
@@ -486,9 +508,12 @@ fn deposit_for(
             _ => Err(ContractError::LockDoesNotExist {}),
         },
     )?;
-    checkpoint(deps, env, user, Some(amount), None)?;
+    let block_period = get_period(env.block.time.seconds())?;
+    checkpoint(deps.branch(), env, user.clone(), Some(amount), None)?;
 
-    Ok(Response::default().add_attribute("action", "deposit_for"))
+    Ok(Response::default()
+        .add_submessages(checkpoint_hook_msgs(deps.storage, &user, block_period)?)
+        .add_attribute("action", "deposit_for"))
 }
 
 /// Withdraws the whole amount of locked xASTRO from a specific user lock.
@@ -520,7 +545,7 @@ fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
         // We need to checkpoint and eliminate the slope influence on a future lock
         HISTORY.save(
             deps.storage,
-            (sender, cur_period),
+            (sender.clone(), cur_period),
             &Point {
                 power: Uint128::zero(),
                 start: cur_period,
@@ -531,6 +556,8 @@ fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
 
         Ok(Response::default()
             .add_message(transfer_msg)
+            .add_submessages(checkpoint_hook_msgs(deps.storage, &sender, cur_period)?)
+            .add_submessages(generator_controller_hook_msg(deps.storage, &sender)?)
             .add_attribute("action", "withdraw"))
     }
 }
@@ -547,7 +574,7 @@ fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
 ///
 /// * **time** increase in lock time applied to the staker's position.
 fn extend_lock_time(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     time: u64,
@@ -562,18 +589,21 @@ fn extend_lock_time(
     // Disable the ability to extend the lock time by less than a week
     time_limits_check(time)?;
 
-    if lock.end <= get_period(env.block.time.seconds())? {
+    let block_period = get_period(env.block.time.seconds())?;
+    if lock.end <= block_period {
         return Err(ContractError::LockExpired {});
     };
 
     // Should not exceed MAX_LOCK_TIME
-    time_limits_check(EPOCH_START + lock.end * WEEK + time - env.block.time.seconds())?;
+    time_limits_check(get_period_start(lock.end) + time - env.block.time.seconds())?;
     lock.end += get_periods_count(time);
     LOCKED.save(deps.storage, user.clone(), &lock, env.block.height)?;
 
-    checkpoint(deps, env, user, None, Some(lock.end))?;
+    checkpoint(deps.branch(), env, user.clone(), None, Some(lock.end))?;
 
-    Ok(Response::default().add_attribute("action", "extend_lock_time"))
+    Ok(Response::default()
+        .add_submessages(checkpoint_hook_msgs(deps.storage, &user, block_period)?)
+        .add_attribute("action", "extend_lock_time"))
 }
 
 /// Update the staker blacklist. Whitelists addresses specified in 'remove_addrs'
@@ -615,6 +645,7 @@ fn update_blacklist(
     let cur_period_key = cur_period;
     let mut reduce_total_vp = Uint128::zero(); // accumulator for decreasing total voting power
     let mut old_slopes = Uint128::zero(); // accumulator for old slopes
+    let mut hook_msgs = vec![];
 
     for addr in append.iter() {
         let last_checkpoint = fetch_last_checkpoint(deps.storage, addr, cur_period_key)?;
@@ -630,6 +661,8 @@ fn update_blacklist(
                     end: cur_period,
                 },
             )?;
+            hook_msgs.extend(checkpoint_hook_msgs(deps.storage, addr, cur_period)?);
+            hook_msgs.extend(generator_controller_hook_msg(deps.storage, addr)?);
 
             let cur_power = calc_voting_power(&point, cur_period);
             // User's contribution is already zero. Skipping them
@@ -666,6 +699,7 @@ fn update_blacklist(
                 Some(amount),
                 Some(end),
             )?;
+            hook_msgs.extend(checkpoint_hook_msgs(deps.storage, addr, cur_period)?);
         }
     }
 
@@ -686,30 +720,101 @@ fn update_blacklist(
         attrs.push(attr("removed_addresses", remove_addrs.join(",")))
     }
 
-    Ok(Response::default().add_attributes(attrs))
+    Ok(Response::default()
+        .add_submessages(hook_msgs)
+        .add_attributes(attrs))
 }
 
-/// Updates contracts' guardian address.
+/// Updates contracts' guardian address, checkpoint hook contracts and the generator controller.
 fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     new_guardian: Option<String>,
+    checkpoint_hooks: Option<Vec<String>>,
+    generator_controller: Option<String>,
 ) -> Result<Response, ContractError> {
-    let mut cfg = CONFIG.load(deps.storage)?;
-
-    if cfg.owner != info.sender {
+    if CONFIG.load(deps.storage)?.owner != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
+    apply_config_update(deps, new_guardian, checkpoint_hooks, generator_controller)
+}
+
+/// Validates and saves the guardian address, checkpoint hook contracts and the generator controller.
+fn apply_config_update(
+    deps: DepsMut,
+    new_guardian: Option<String>,
+    checkpoint_hooks: Option<Vec<String>>,
+    generator_controller: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
     if let Some(new_guardian) = new_guardian {
         cfg.guardian_addr = Some(deps.api.addr_validate(&new_guardian)?);
     }
 
+    if let Some(checkpoint_hooks) = checkpoint_hooks {
+        cfg.checkpoint_hooks = validate_addresses(deps.api, &checkpoint_hooks)?;
+    }
+
+    if let Some(generator_controller) = generator_controller {
+        cfg.generator_controller = Some(deps.api.addr_validate(&generator_controller)?);
+    }
+
     CONFIG.save(deps.storage, &cfg)?;
 
     Ok(Response::default().add_attribute("action", "execute_update_config"))
 }
 
+/// Exposes the privileged messages the chain can send to the contract.
+///
+/// ## Sudo messages
+/// * **SudoMsg::Pause {}** Stops accepting new locks and deposits until the chain unpauses.
+///
+/// * **SudoMsg::Unpause {}** Resumes accepting new locks and deposits.
+///
+/// * **SudoMsg::ForceConfig { new_guardian, checkpoint_hooks, generator_controller }** Updates
+/// the config bypassing the contract owner.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::Pause {} => {
+            PAUSED.save(deps.storage, &true)?;
+
+            Ok(Response::default().add_attribute("action", "sudo_pause"))
+        }
+        SudoMsg::Unpause {} => {
+            if !PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+                return Err(ContractError::NotPaused {});
+            }
+            PAUSED.remove(deps.storage);
+
+            Ok(Response::default().add_attribute("action", "sudo_unpause"))
+        }
+        SudoMsg::ForceConfig {
+            new_guardian,
+            checkpoint_hooks,
+            generator_controller,
+        } => apply_config_update(deps, new_guardian, checkpoint_hooks, generator_controller)
+            .map(|response| response.add_attribute("sudo", "true")),
+    }
+}
+
+/// Handles failed checkpoint hook and generator controller notifications. Their state changes are
+/// reverted while the lock operation which triggered them is kept.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (CHECKPOINT_HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "checkpoint_hook_failed")
+            .add_attribute("error", err)),
+        (GENERATOR_CONTROLLER_HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "generator_controller_hook_failed")
+            .add_attribute("error", err)),
+        _ => Err(StdError::generic_err("Unknown reply ID").into()),
+    }
+}
+
 /// Expose available contract queries.
 ///
 /// ## Queries
@@ -722,6 +827,8 @@ fn execute_update_config(
 /// * **QueryMsg::UserVotingPowerAt { time }** Fetch the user's voting power (vxASTRO balance) at a specified timestamp.
 ///
 /// * **QueryMsg::LockInfo { user }** Fetch a user's lock information.
+///
+/// * **QueryMsg::LockedBalances { start_after, limit }** Fetch active locks ordered by address.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -731,6 +838,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::BlacklistedVoters { start_after, limit } => {
             to_binary(&get_blacklisted_voters(deps, start_after, limit)?)
         }
+        QueryMsg::LockedBalances { start_after, limit } => {
+            to_binary(&get_locked_balances(deps, env, start_after, limit)?)
+        }
         QueryMsg::TotalVotingPower {} => to_binary(&get_total_voting_power(deps, env, None)?),
         QueryMsg::UserVotingPower { user } => {
             to_binary(&get_user_voting_power(deps, env, user, None)?)
@@ -760,6 +870,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 astro_addr: config.astro_addr.to_string(),
                 xastro_staking_addr: config.xastro_staking_addr.to_string(),
                 logo_urls_whitelist: config.logo_urls_whitelist,
+                checkpoint_hooks: config.checkpoint_hooks,
+                generator_controller: config.generator_controller,
             })
         }
         QueryMsg::Balance { address } => to_binary(&get_user_balance(deps, env, address)?),
@@ -829,6 +941,49 @@ pub fn get_blacklisted_voters(
     Ok(black_list[start_index..end_index].to_vec())
 }
 
+/// Returns a list of active locks. Withdrawn and expired locks are skipped.
+///
+/// * **start_after** is an optional field that specifies whether the function should return
+/// a list of locks starting from a specific address onward.
+///
+/// * **limit** max amount of locks to scan.
+fn get_locked_balances(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<LockedBalancesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = addr_opt_validate(deps.api, &start_after)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+
+    let scanned = LOCKED
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let last_scanned = scanned.last().map(|(address, _)| address.clone());
+    let locks = scanned
+        .into_iter()
+        .filter(|(_, lock)| !lock.amount.is_zero() && lock.end > cur_period)
+        .map(|(address, lock)| LockedBalance {
+            address,
+            amount: lock.amount,
+            end: lock.end,
+        })
+        .collect();
+
+    Ok(LockedBalancesResponse {
+        locks,
+        last_scanned,
+    })
+}
+
 /// Return a user's lock information.
 ///
 /// * **user** user for which we return lock information.
@@ -845,6 +1000,9 @@ fn get_user_lock_info(deps: Deps, env: Env, user: String) -> StdResult<LockInfoR
             start: lock.start,
             end: lock.end,
             slope,
+            checkpoint_nonce: CHECKPOINT_NONCES
+                .may_load(deps.storage, addr)?
+                .unwrap_or_default(),
         };
         Ok(resp)
     } else {
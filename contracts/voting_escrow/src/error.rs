@@ -1,3 +1,4 @@
+use astroport_governance::errors::CommonError;
 use cosmwasm_std::StdError;
 use cw20_base::ContractError as cw20baseError;
 use thiserror::Error;
@@ -8,6 +9,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("{0}")]
     Cw20Base(#[from] cw20baseError),
 
@@ -35,6 +39,12 @@ pub enum ContractError {
     #[error("Marketing info validation error: {0}")]
     MarketingInfoValidationError(String),
 
+    #[error("New locks and deposits are paused")]
+    Paused {},
+
+    #[error("The contract isn't paused")]
+    NotPaused {},
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 }
@@ -18,6 +18,12 @@ pub struct Config {
     pub xastro_staking_addr: Addr,
     /// The list of whitelisted logo urls prefixes
     pub logo_urls_whitelist: Vec<String>,
+    /// Contracts notified about every change of stakers' voting power
+    #[serde(default)]
+    pub checkpoint_hooks: Vec<Addr>,
+    /// Generator controller notified when a staker's lock expires or the staker is blacklisted
+    #[serde(default)]
+    pub generator_controller: Option<Addr>,
 }
 
 /// This structure stores points along the checkpoint history for every vxASTRO staker.
@@ -61,6 +67,9 @@ pub const LOCKED: SnapshotMap<Addr, Lock> = SnapshotMap::new(
 /// Total voting power checkpoints are stored using a (contract_addr => period) key
 pub const HISTORY: Map<(Addr, u64), Point> = Map::new("history");
 
+/// Stores the sequence number of the latest checkpoint pushed to checkpoint hook contracts for every staker
+pub const CHECKPOINT_NONCES: Map<Addr, u64> = Map::new("checkpoint_nonces");
+
 /// Scheduled slope changes per period (week)
 pub const SLOPE_CHANGES: Map<u64, Uint128> = Map::new("slope_changes");
 
@@ -72,3 +81,6 @@ pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_pro
 
 /// Contains blacklisted staker addresses
 pub const BLACKLIST: Item<Vec<Addr>> = Item::new("blacklist");
+
+/// Whether new locks and deposits are paused by the chain
+pub const PAUSED: Item<bool> = Item::new("paused");
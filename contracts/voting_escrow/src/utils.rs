@@ -1,10 +1,20 @@
 use crate::error::ContractError;
 use astroport_governance::utils::{get_periods_count, MAX_LOCK_TIME, WEEK};
 
-use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use astroport_governance::generator_controller::ExecuteMsg as ControllerExecuteMsg;
+use astroport_governance::voting_escrow::{
+    CheckpointHookExecuteMsg, CheckpointHookMsg, CHECKPOINT_HOOK_GAS_LIMIT,
+    GENERATOR_CONTROLLER_HOOK_GAS_LIMIT,
+};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Order, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
 use cw_storage_plus::Bound;
 
-use crate::state::{Point, BLACKLIST, CONFIG, HISTORY, LAST_SLOPE_CHANGE, SLOPE_CHANGES};
+use crate::contract::{CHECKPOINT_HOOK_REPLY_ID, GENERATOR_CONTROLLER_HOOK_REPLY_ID};
+use crate::state::{
+    Point, BLACKLIST, CHECKPOINT_NONCES, CONFIG, HISTORY, LAST_SLOPE_CHANGE, SLOPE_CHANGES,
+};
 
 /// Checks that a timestamp is within limits.
 pub(crate) fn time_limits_check(time: u64) -> Result<(), ContractError> {
@@ -139,3 +149,88 @@ pub(crate) fn fetch_slope_changes(
         )
         .collect()
 }
+
+/// Builds messages which push the staker's checkpoint for the specified period to all checkpoint hook contracts.
+/// Every message is limited to [`CHECKPOINT_HOOK_GAS_LIMIT`] gas and a failing hook can't block
+/// the lock operation, its error is handled in [`crate::contract::reply`].
+/// The staker's checkpoint nonce is incremented even if there are no hooks, so a hook contract
+/// can detect checkpoints it missed by comparing nonces with the staker's lock info.
+///
+/// * **addr** staker whose voting power changed.
+///
+/// * **period** period at which the staker's checkpoint was saved.
+pub(crate) fn checkpoint_hook_msgs(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    period: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let nonce = CHECKPOINT_NONCES
+        .may_load(storage, addr.clone())?
+        .unwrap_or_default()
+        + 1;
+    CHECKPOINT_NONCES.save(storage, addr.clone(), &nonce)?;
+
+    let config = CONFIG.load(storage)?;
+    if config.checkpoint_hooks.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let point = HISTORY.load(storage, (addr.clone(), period))?;
+    let msg = to_binary(&CheckpointHookExecuteMsg::CheckpointHook(
+        CheckpointHookMsg {
+            user: addr.to_string(),
+            power: point.power,
+            slope: point.slope,
+            start: point.start,
+            end: point.end,
+            nonce,
+        },
+    ))?;
+
+    Ok(config
+        .checkpoint_hooks
+        .into_iter()
+        .map(|hook| {
+            SubMsg::reply_on_error(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: msg.clone(),
+                    funds: vec![],
+                }),
+                CHECKPOINT_HOOK_REPLY_ID,
+            )
+            .with_gas_limit(CHECKPOINT_HOOK_GAS_LIMIT)
+        })
+        .collect())
+}
+
+/// Builds a message which prunes the staker's gauge votes in the generator controller.
+/// Returns `None` if the generator controller isn't set. The message is limited to
+/// [`GENERATOR_CONTROLLER_HOOK_GAS_LIMIT`] gas and a failing controller can't block withdrawals
+/// or blacklisting, its error is handled in [`crate::contract::reply`].
+///
+/// * **addr** staker whose lock expired or who was blacklisted.
+pub(crate) fn generator_controller_hook_msg(
+    storage: &dyn Storage,
+    addr: &Addr,
+) -> StdResult<Option<SubMsg>> {
+    let config = CONFIG.load(storage)?;
+
+    config
+        .generator_controller
+        .map(|controller| {
+            let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: controller.to_string(),
+                msg: to_binary(&ControllerExecuteMsg::PruneVotesHook {
+                    voter: addr.to_string(),
+                })?,
+                funds: vec![],
+            });
+
+            Ok(
+                SubMsg::reply_on_error(msg, GENERATOR_CONTROLLER_HOOK_REPLY_ID)
+                    .with_gas_limit(GENERATOR_CONTROLLER_HOOK_GAS_LIMIT),
+            )
+        })
+        .transpose()
+}
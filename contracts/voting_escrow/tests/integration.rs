@@ -6,7 +6,7 @@ use voting_escrow::astroport;
 
 use astroport_governance::utils::{get_period, MAX_LOCK_TIME, WEEK};
 use astroport_governance::voting_escrow::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, LockInfoResponse, QueryMsg,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, LockInfoResponse, QueryMsg, SudoMsg,
 };
 
 use crate::test_utils::{mock_app, Helper, MULTIPLIER};
@@ -1036,6 +1036,253 @@ fn total_vp_multiple_slope_subtraction() {
     assert_eq!(total, 0f32);
 }
 
+#[test]
+fn locked_balances() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    for user in ["user1", "user2", "user3"] {
+        helper.mint_xastro(router_ref, user, 100);
+    }
+    helper
+        .create_lock(router_ref, "user1", 2 * WEEK, 10f32)
+        .unwrap();
+    helper
+        .create_lock(router_ref, "user2", 5 * WEEK, 20f32)
+        .unwrap();
+    helper
+        .create_lock(router_ref, "user3", 10 * WEEK, 30f32)
+        .unwrap();
+
+    let res = helper
+        .query_locked_balances(router_ref, None, None)
+        .unwrap();
+    let locks = res.locks;
+    assert_eq!(locks.len(), 3);
+    assert_eq!(res.last_scanned, Some(Addr::unchecked("user3")));
+    for (lock, (user, amount)) in locks
+        .iter()
+        .zip([("user1", 10u64), ("user2", 20), ("user3", 30)])
+    {
+        let lock_info: LockInfoResponse = router_ref
+            .wrap()
+            .query_wasm_smart(
+                helper.voting_instance.clone(),
+                &QueryMsg::LockInfo {
+                    user: user.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(lock.address, Addr::unchecked(user));
+        assert_eq!(lock.amount, Uint128::from(amount * MULTIPLIER));
+        assert_eq!(lock.end, lock_info.end);
+    }
+
+    // Paginate over the locks
+    let page = helper
+        .query_locked_balances(router_ref, None, Some(2))
+        .unwrap();
+    assert_eq!(page.locks, locks[..2]);
+    assert_eq!(page.last_scanned, Some(Addr::unchecked("user2")));
+    let page = helper
+        .query_locked_balances(router_ref, Some("user2".to_string()), Some(2))
+        .unwrap();
+    assert_eq!(page.locks, locks[2..]);
+    let page = helper
+        .query_locked_balances(router_ref, Some("user3".to_string()), Some(2))
+        .unwrap();
+    assert!(page.locks.is_empty());
+    assert_eq!(page.last_scanned, None);
+
+    // Expired locks are skipped even before they are withdrawn
+    router_ref.update_block(|bi| bi.time = bi.time.plus_seconds(3 * WEEK));
+    let locks = helper
+        .query_locked_balances(router_ref, None, None)
+        .unwrap()
+        .locks;
+    let addresses: Vec<_> = locks.iter().map(|lock| lock.address.as_str()).collect();
+    assert_eq!(addresses, vec!["user2", "user3"]);
+
+    // Skipped locks count towards the limit, so a page can be empty while more locks are left
+    let page = helper
+        .query_locked_balances(router_ref, None, Some(1))
+        .unwrap();
+    assert!(page.locks.is_empty());
+    assert_eq!(page.last_scanned, Some(Addr::unchecked("user1")));
+    let page = helper
+        .query_locked_balances(router_ref, page.last_scanned.map(String::from), Some(1))
+        .unwrap();
+    assert_eq!(page.locks, locks[..1]);
+
+    helper.withdraw(router_ref, "user1").unwrap();
+    router_ref.update_block(|bi| bi.time = bi.time.plus_seconds(3 * WEEK));
+    helper.withdraw(router_ref, "user2").unwrap();
+    let locks = helper
+        .query_locked_balances(router_ref, None, None)
+        .unwrap()
+        .locks;
+    let addresses: Vec<_> = locks.iter().map(|lock| lock.address.as_str()).collect();
+    assert_eq!(addresses, vec!["user3"]);
+}
+
+#[test]
+fn sudo_pause_and_force_config() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    helper.mint_xastro(router_ref, "user", 200);
+    helper
+        .create_lock(router_ref, "user", 2 * WEEK, 100f32)
+        .unwrap();
+
+    let err = router_ref
+        .wasm_sudo(helper.voting_instance.clone(), &SudoMsg::Unpause {})
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The contract isn't paused");
+
+    router_ref
+        .wasm_sudo(helper.voting_instance.clone(), &SudoMsg::Pause {})
+        .unwrap();
+
+    // New locks and deposits are rejected while paused
+    let err = helper
+        .extend_lock_amount(router_ref, "user", 50f32)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "New locks and deposits are paused"
+    );
+    helper.mint_xastro(router_ref, "user2", 100);
+    let err = helper
+        .create_lock(router_ref, "user2", 2 * WEEK, 100f32)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "New locks and deposits are paused"
+    );
+
+    // Expired locks can still be withdrawn
+    router_ref.update_block(|bi| bi.time = bi.time.plus_seconds(2 * WEEK));
+    helper.withdraw(router_ref, "user").unwrap();
+    helper.check_xastro_balance(router_ref, "user", 200);
+
+    router_ref
+        .wasm_sudo(helper.voting_instance.clone(), &SudoMsg::Unpause {})
+        .unwrap();
+    helper
+        .create_lock(router_ref, "user", 2 * WEEK, 100f32)
+        .unwrap();
+
+    // The chain can update the config bypassing the owner
+    router_ref
+        .wasm_sudo(
+            helper.voting_instance.clone(),
+            &SudoMsg::ForceConfig {
+                new_guardian: Some("new_guardian".to_string()),
+                checkpoint_hooks: None,
+                generator_controller: None,
+            },
+        )
+        .unwrap();
+    let config: ConfigResponse = router_ref
+        .wrap()
+        .query_wasm_smart(helper.voting_instance.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.guardian_addr, Some(Addr::unchecked("new_guardian")));
+}
+
+#[test]
+fn failing_checkpoint_hook() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner.clone());
+
+    // The ASTRO token doesn't handle checkpoint hooks
+    router_ref
+        .execute_contract(
+            owner,
+            helper.voting_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                checkpoint_hooks: Some(vec![helper.astro_token.to_string()]),
+                generator_controller: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    helper.mint_xastro(router_ref, "user", 100);
+    let resp = helper
+        .create_lock(router_ref, "user", 2 * WEEK, 100f32)
+        .unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value == "checkpoint_hook_failed")));
+    helper.check_xastro_balance(router_ref, "user", 0);
+
+    // The failing hook doesn't block the withdrawal either
+    router_ref.update_block(|bi| bi.time = bi.time.plus_seconds(2 * WEEK));
+    helper.withdraw(router_ref, "user").unwrap();
+    helper.check_xastro_balance(router_ref, "user", 100);
+}
+
+#[test]
+fn failing_generator_controller_hook() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner.clone());
+
+    // The ASTRO token doesn't handle generator controller hooks
+    router_ref
+        .execute_contract(
+            owner.clone(),
+            helper.voting_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                checkpoint_hooks: None,
+                generator_controller: Some(helper.astro_token.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    for user in ["user1", "user2"] {
+        helper.mint_xastro(router_ref, user, 100);
+        helper
+            .create_lock(router_ref, user, 2 * WEEK, 100f32)
+            .unwrap();
+    }
+
+    // Withdrawals aren't blocked by the failing controller
+    router_ref.update_block(|bi| bi.time = bi.time.plus_seconds(2 * WEEK));
+    let resp = helper.withdraw(router_ref, "user1").unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value == "generator_controller_hook_failed")));
+    helper.check_xastro_balance(router_ref, "user1", 100);
+
+    // Neither is blacklisting
+    router_ref
+        .execute_contract(
+            owner,
+            helper.voting_instance.clone(),
+            &ExecuteMsg::UpdateBlacklist {
+                append_addrs: Some(vec!["user2".to_string()]),
+                remove_addrs: None,
+            },
+            &[],
+        )
+        .unwrap();
+}
+
 #[test]
 fn marketing_info() {
     let mut router = mock_app();
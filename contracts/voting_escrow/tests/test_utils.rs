@@ -3,8 +3,8 @@ use astroport::{staking as xastro, token as astro};
 use astroport_governance::escrow_fee_distributor::InstantiateMsg as FeeDistributorInstantiateMsg;
 use astroport_governance::utils::EPOCH_START;
 use astroport_governance::voting_escrow::{
-    BlacklistedVotersResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-    UpdateMarketingInfo, VotingPowerResponse,
+    BlacklistedVotersResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockedBalancesResponse,
+    QueryMsg, UpdateMarketingInfo, VotingPowerResponse,
 };
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{attr, to_binary, Addr, QueryRequest, StdResult, Timestamp, Uint128, WasmQuery};
@@ -92,11 +92,15 @@ impl Helper {
             }))
             .unwrap();
 
-        let voting_contract = Box::new(ContractWrapper::new_with_empty(
-            voting_escrow::contract::execute,
-            voting_escrow::contract::instantiate,
-            voting_escrow::contract::query,
-        ));
+        let voting_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                voting_escrow::contract::execute,
+                voting_escrow::contract::instantiate,
+                voting_escrow::contract::query,
+            )
+            .with_reply_empty(voting_escrow::contract::reply)
+            .with_sudo_empty(voting_escrow::contract::sudo),
+        );
 
         let voting_code_id = router.store_code(voting_contract);
 
@@ -139,6 +143,9 @@ impl Helper {
             voting_escrow_addr: voting_instance.to_string(),
             claim_many_limit: None,
             is_claim_disabled: None,
+            astro_staking: None,
+            claim_expiry_periods: None,
+            max_periods_per_claim: None,
         };
 
         let fee_distributor_instance = router
@@ -467,6 +474,19 @@ impl Helper {
         )
     }
 
+    #[allow(dead_code)]
+    pub fn query_locked_balances(
+        &self,
+        router: &mut App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<LockedBalancesResponse> {
+        router.wrap().query_wasm_smart(
+            self.voting_instance.clone(),
+            &QueryMsg::LockedBalances { start_after, limit },
+        )
+    }
+
     #[allow(dead_code)]
     pub fn check_voters_are_blacklisted(
         &self,
@@ -1,3 +1,4 @@
+use astroport_governance::errors::CommonError;
 use cosmwasm_std::StdError;
 use cw_utils::ParseReplyError;
 use thiserror::Error;
@@ -7,6 +8,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Common(#[from] CommonError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
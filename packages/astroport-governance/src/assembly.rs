@@ -1,7 +1,14 @@
-use crate::assembly::helpers::is_safe_link;
+use crate::assembly::helpers::{validate_proposal_content, ProposalValidationRules};
+use crate::interchain::ForwardMetadata;
+use cosmwasm_schema::serde::de::{self, Deserializer, Visitor};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdError, StdResult, Uint128, Uint64};
-use cw20::Cw20ReceiveMsg;
+#[cfg(feature = "full")]
+use cosmwasm_std::{coins, BankMsg};
+use cosmwasm_std::{
+    to_vec, wasm_execute, Addr, Binary, CosmosMsg, Decimal, StdError, StdResult, Uint128, Uint64,
+};
+#[cfg(feature = "full")]
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use std::fmt::{Display, Formatter, Result};
 use std::str::FromStr;
 
@@ -40,12 +47,35 @@ mod proposal_constants {
 pub use proposal_constants::*;
 
 /// Proposal validation attributes
-const MIN_TITLE_LENGTH: usize = 4;
-const MAX_TITLE_LENGTH: usize = 64;
-const MIN_DESC_LENGTH: usize = 4;
-const MAX_DESC_LENGTH: usize = 1024;
-const MIN_LINK_LENGTH: usize = 12;
-const MAX_LINK_LENGTH: usize = 128;
+pub const MIN_TITLE_LENGTH: usize = 4;
+pub const MAX_TITLE_LENGTH: usize = 64;
+pub const MIN_DESC_LENGTH: usize = 4;
+pub const MAX_DESC_LENGTH: usize = 1024;
+pub const MIN_LINK_LENGTH: usize = 12;
+pub const MAX_LINK_LENGTH: usize = 128;
+pub const MAX_FORUM_THREAD_ID_LENGTH: usize = 64;
+/// The maximum number of options of a multi-choice proposal
+pub const MAX_PROPOSAL_OPTIONS: usize = 10;
+pub const MAX_OPTION_LABEL_LENGTH: usize = 64;
+/// The maximum number of options of a ranked-choice proposal. Keeps the instant runoff cheap
+pub const MAX_RANKED_OPTIONS: usize = 5;
+/// The maximum number of proposals in a bundle. All of them are executed in a single transaction
+pub const MAX_BUNDLE_PROPOSALS: usize = 10;
+/// The maximum number of messages a proposal can execute
+pub const MAX_PROPOSAL_MESSAGES: usize = 16;
+/// The maximum total size in bytes of the JSON encoded proposal messages
+pub const MAX_PROPOSAL_MESSAGES_SIZE: u64 = 65536;
+/// The gas limit of a single proposal status notification
+pub const PROPOSAL_HOOK_GAS_LIMIT: u64 = 300_000;
+/// The maximum number of contracts notified about every vote
+pub const MAX_VOTE_LISTENERS: usize = 5;
+/// The gas limit of a single vote notification
+pub const VOTE_LISTENER_GAS_LIMIT: u64 = 150_000;
+/// The maximum number of addresses excluded from the total voting power
+pub const MAX_EXCLUDED_VOTERS: usize = 10;
+/// The period in seconds an emergency pause lasts. A new pause can only start once the same
+/// period has passed since the previous one ended, so voting can't be blocked indefinitely
+pub const EMERGENCY_PAUSE_PERIOD: u64 = 7 * 86400;
 
 /// Special characters that are allowed in proposal text
 const SAFE_TEXT_CHARS: &str = "!&?#()*+'-./\"";
@@ -72,25 +102,79 @@ pub struct InstantiateMsg {
     /// Proposal required deposit
     pub proposal_required_deposit: Uint128,
     /// Proposal required quorum
-    pub proposal_required_quorum: String,
+    pub proposal_required_quorum: Decimal,
     /// Proposal required threshold
-    pub proposal_required_threshold: String,
+    pub proposal_required_threshold: Decimal,
     /// Whitelisted links
     pub whitelisted_links: Vec<String>,
+    /// The maximum number of messages a proposal can execute
+    pub proposal_max_messages: u32,
+    /// The maximum total size in bytes of the JSON encoded proposal messages
+    pub proposal_max_messages_size: u64,
 }
 
 /// This enum describes all execute functions available in the contract.
+#[cfg(feature = "full")]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Receive a message of type [`Cw20ReceiveMsg`]
     Receive(Cw20ReceiveMsg),
-    /// Cast a vote for an active proposal
+    /// Cast a vote for an active proposal. Multi-choice proposals only accept
+    /// [`ProposalVoteOption::Choice`] and binary proposals only `For` or `Against`
     CastVote {
         /// Proposal identifier
         proposal_id: u64,
         /// Vote option
         vote: ProposalVoteOption,
     },
+    /// Cast a vote signed off-chain by the voter. Lets relayers pay the fees of voters who
+    /// hold no gas tokens. The voter signs sha256 of the JSON encoded [`VoteBySigPayload`].
+    /// Ranked ballots with several preferences can only be cast with [`ExecuteMsg::CastRankedVote`]
+    CastVoteBySig {
+        /// The voter that signed the vote
+        voter: String,
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+        /// 64 byte secp256k1 signature (r || s) followed by a one byte recovery id
+        signature: Binary,
+        /// The next nonce of the voter, see [`QueryMsg::VoteNonce`]
+        nonce: u64,
+    },
+    /// Allow an operator to vote on behalf of the sender. The votes are cast with the sender's
+    /// voting power, which unlike delegation stays with the sender
+    GrantVoteOperator {
+        operator: String,
+        /// Timestamp (in seconds) after which the grant expires. Never expires if not set
+        expires: Option<u64>,
+    },
+    /// Revoke a grant made with [`ExecuteMsg::GrantVoteOperator`]
+    RevokeVoteOperator { operator: String },
+    /// Cast a vote on behalf of a grantor that allowed the sender to vote for it.
+    /// Ranked ballots with several preferences can only be cast with [`ExecuteMsg::CastRankedVote`]
+    CastVoteAsOperator {
+        /// The address the vote is cast for
+        grantor: String,
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+    },
+    /// Cast a vote for an option of an active multi-choice proposal
+    CastMultiChoiceVote {
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Index of the chosen option
+        option: u32,
+    },
+    /// Cast a ranked ballot for an active ranked-choice proposal
+    CastRankedVote {
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Indexes of the preferred options, most preferred first
+        ranking: Vec<u32>,
+    },
     /// Set the status of a proposal that expired
     EndProposal {
         /// Proposal identifier
@@ -108,15 +192,32 @@ pub enum ExecuteMsg {
         /// Proposal identifier
         proposal_id: u64,
     },
-    /// Remove a proposal that was already executed (or failed/expired)
+    /// Execute all proposals of a bundle in a single transaction. Every member must have passed
+    ExecuteBundle {
+        /// Identifiers of all proposals in the bundle
+        proposal_ids: Vec<u64>,
+    },
+    /// Remove a proposal that was already executed (or failed/expired). Votes are removed in batches,
+    /// so the removal is repeated with the same proposal id until it's finished
     RemoveCompletedProposal {
         /// Proposal identifier
         proposal_id: u64,
+        /// The amount of votes and vote merkle tree entries to remove
+        limit: Option<u32>,
     },
     /// Update parameters in the Assembly contract
     /// ## Executor
     /// Only the Assembly contract is allowed to update its own parameters
     UpdateConfig(Box<UpdateConfig>),
+    /// Add and remove links from the link whitelist
+    /// ## Executor
+    /// Only the links curator or the Assembly contract itself can call this method
+    UpdateLinkWhitelist {
+        /// Links to add to the whitelist
+        add: Option<Vec<String>>,
+        /// Links to remove from the whitelist
+        remove: Option<Vec<String>>,
+    },
     /// Update proposal status InProgress -> Executed or Failed.
     /// ## Executor
     /// Only the IBC controller contract is allowed to call this method.
@@ -124,6 +225,85 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         status: ProposalStatus,
     },
+    /// Register a contract which is notified about proposal status changes
+    /// ## Executor
+    /// Only the Assembly contract is allowed to add hooks (through a successful proposal)
+    AddProposalHook { address: String },
+    /// Unregister a proposal hook
+    /// ## Executor
+    /// Only the Assembly contract is allowed to remove hooks (through a successful proposal)
+    RemoveProposalHook { address: String },
+    /// Register a contract which is notified about every vote
+    /// ## Executor
+    /// Only the Assembly contract is allowed to add listeners (through a successful proposal)
+    AddVoteListener { address: String },
+    /// Unregister a vote listener
+    /// ## Executor
+    /// Only the Assembly contract is allowed to remove listeners (through a successful proposal)
+    RemoveVoteListener { address: String },
+    /// Backfill the status, submitter and end block indexes for proposals created before they existed.
+    /// Anyone can call this method until all proposals are processed.
+    Reindex {
+        /// The amount of proposals to process
+        limit: Option<u32>,
+    },
+    /// Migrate the proposals left after a contract migration to the current layout. Other messages
+    /// are rejected until every proposal is migrated.
+    /// Anyone can call this method until all proposals are migrated.
+    MigrateProposals {
+        /// The amount of proposals to migrate
+        limit: Option<u32>,
+    },
+    /// Build the merkle tree over the votes of an ended proposal, first storing the leaves and then
+    /// hashing them level by level. Anyone can call this method until the root is committed.
+    CommitVotes {
+        /// The proposal whose votes are committed
+        proposal_id: u64,
+        /// The amount of leaves or nodes to store
+        limit: Option<u32>,
+    },
+    /// Stop accepting new proposals and votes for [`EMERGENCY_PAUSE_PERIOD`]
+    /// ## Executor
+    /// Only the emergency committee can execute this
+    Pause {},
+    /// Resume accepting new proposals and votes before the pause ends
+    /// ## Executor
+    /// Only the emergency committee or the Assembly contract (through a successful proposal) can execute this
+    Unpause {},
+    /// Reject a passed proposal before it is executed
+    /// ## Executor
+    /// Only the emergency committee can execute this
+    VetoProposal {
+        /// Proposal identifier
+        proposal_id: u64,
+    },
+}
+
+/// This structure describes a migration message.
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Voting Escrow delegator address
+    pub voting_escrow_delegator_addr: Option<String>,
+    /// vxASTRO token address
+    pub vxastro_token_addr: Option<String>,
+    /// Astroport IBC controller contract
+    pub ibc_controller: Option<String>,
+}
+
+/// This enum describes the privileged messages a chain can send to the Assembly.
+#[cw_serde]
+pub enum SudoMsg {
+    /// Stop accepting new proposals and votes
+    Pause {},
+    /// Resume accepting new proposals and votes
+    Unpause {},
+    /// End the voting period of a proposal immediately
+    ForceEndProposal {
+        /// Proposal identifier
+        proposal_id: u64,
+    },
+    /// Update parameters in the Assembly contract bypassing the governance process
+    ForceConfig(Box<UpdateConfig>),
 }
 
 /// Thie enum describes all the queries available in the contract.
@@ -136,8 +316,17 @@ pub enum QueryMsg {
     /// Return the current list of proposals
     #[returns(ProposalListResponse)]
     Proposals {
-        /// Id from which to start querying
+        /// Id from which to start querying (inclusive).
+        /// Deprecated, use `start_after` instead
         start: Option<u64>,
+        /// Id after which to start querying. Takes precedence over `start`
+        start_after: Option<u64>,
+        /// The amount of proposals to return
+        limit: Option<u32>,
+    },
+    /// Return the most recent proposals, newest first
+    #[returns(ProposalListResponse)]
+    LatestProposals {
         /// The amount of proposals to return
         limit: Option<u32>,
     },
@@ -148,23 +337,132 @@ pub enum QueryMsg {
         proposal_id: u64,
         /// Proposal vote option
         vote_option: ProposalVoteOption,
-        /// Id from which to start querying
+        /// Position in the voter list from which to start querying.
+        /// Deprecated, use `start_after` instead
         start: Option<u64>,
+        /// Voter address after which to start querying. Takes precedence over `start`
+        start_after: Option<String>,
         /// The amount of proposals to return
         limit: Option<u32>,
     },
     /// Return information about a specific proposal
     #[returns(Proposal)]
     Proposal { proposal_id: u64 },
+    /// Return the description, link and messages of a specific proposal
+    #[returns(ProposalContent)]
+    ProposalContent { proposal_id: u64 },
     /// Return information about the votes cast on a specific proposal
     #[returns(ProposalVotesResponse)]
     ProposalVotes { proposal_id: u64 },
-    /// Return user voting power for a specific proposal
+    /// Return user voting power for a specific proposal or, if `at` is set, at any point in time
     #[returns(Uint128)]
-    UserVotingPower { user: String, proposal_id: u64 },
-    /// Return total voting power for a specific proposal
+    UserVotingPower {
+        user: String,
+        proposal_id: Option<u64>,
+        /// Takes precedence over `proposal_id`
+        at: Option<VotingPowerPoint>,
+    },
+    /// Return total voting power for a specific proposal or, if `at` is set, at any point in time
     #[returns(Uint128)]
-    TotalVotingPower { proposal_id: u64 },
+    TotalVotingPower {
+        proposal_id: Option<u64>,
+        /// Takes precedence over `proposal_id`
+        at: Option<VotingPowerPoint>,
+    },
+    /// Return ids of active proposals whose voting period has ended and which can be ended
+    #[returns(Vec<u64>)]
+    FinalizableProposals {
+        /// The amount of proposal ids to return
+        limit: Option<u32>,
+    },
+    /// Return the vote option and voting power recorded for a user on a specific proposal
+    #[returns(Option<ProposalVote>)]
+    UserVote { proposal_id: u64, user: String },
+    /// Check that the proposal tallies equal the sum of the recorded votes
+    #[returns(TallyCheckResponse)]
+    CheckTally { proposal_id: u64 },
+    /// Return a merkle proof that the vote of a user is part of the tally committed after the proposal ended
+    #[returns(VoteProofResponse)]
+    VoteProof { proposal_id: u64, voter: String },
+    /// Return proposals with a specific status
+    #[returns(Vec<Proposal>)]
+    ProposalsByStatus {
+        status: ProposalStatus,
+        /// Id after which to start querying
+        start_after: Option<u64>,
+        /// The amount of proposals to return
+        limit: Option<u32>,
+    },
+    /// Return proposals created by a specific submitter
+    #[returns(Vec<Proposal>)]
+    ProposalsBySubmitter {
+        submitter: String,
+        /// Id after which to start querying
+        start_after: Option<u64>,
+        /// The amount of proposals to return
+        limit: Option<u32>,
+    },
+    /// Return the contracts notified about proposal status changes
+    #[returns(Vec<Addr>)]
+    ProposalHooks {},
+    /// Return the contracts notified about every vote
+    #[returns(Vec<Addr>)]
+    VoteListeners {},
+    /// Return the nonce the next signed vote of the voter must use
+    #[returns(u64)]
+    VoteNonce { voter: String },
+    /// Return the operators allowed to vote on behalf of the grantor
+    #[returns(Vec<VoteOperatorGrant>)]
+    VoteOperators { grantor: String },
+    /// Return the number of stored proposals with every status
+    #[returns(ProposalCountsResponse)]
+    ProposalCounts {},
+    /// Return the voting power of every option of a multi-choice proposal
+    #[returns(MultiChoiceTallyResponse)]
+    MultiChoiceTally { proposal_id: u64 },
+    /// Return both rounds of the two-phase proposal the specified proposal belongs to
+    #[returns(TwoPhaseProposalResponse)]
+    TwoPhaseProposal { proposal_id: u64 },
+    /// Return the proposals of the specified bundle
+    #[returns(ProposalBundle)]
+    ProposalBundle { bundle_id: u64 },
+    /// Return the contracts and bank recipients the messages of a proposal touch
+    #[returns(ProposalTargetsResponse)]
+    ProposalTargets { proposal_id: u64 },
+    /// Return a page of raw storage entries from a section of the contract state
+    #[returns(Vec<RawStateEntry>)]
+    ExportState {
+        section: StateSection,
+        /// Raw storage key after which to start querying
+        start_after: Option<Binary>,
+        /// The amount of entries to return
+        limit: Option<u32>,
+    },
+    /// Return the timestamp until which new proposals and votes are paused, if a pause was ever started
+    #[returns(Option<u64>)]
+    PausedUntil {},
+}
+
+/// This enum describes the sections of the contract state which can be exported.
+#[cw_serde]
+pub enum StateSection {
+    /// Proposals keyed by proposal id
+    Proposals,
+    /// Proposal descriptions, links and messages keyed by proposal id
+    ProposalContent,
+    /// Votes keyed by (proposal id, voter)
+    Votes,
+    /// Config versions keyed by the block height at which they were set
+    ConfigHistory,
+}
+
+/// This structure describes a raw storage entry.
+#[cw_serde]
+pub struct RawStateEntry {
+    /// Storage key without the section namespace
+    pub key: Binary,
+    /// JSON encoded value
+    pub value: Binary,
 }
 
 /// This structure stores data for a CW20 hook message.
@@ -178,7 +476,65 @@ pub enum Cw20HookMsg {
         messages: Option<Vec<CosmosMsg>>,
         /// If proposal should be executed on a remote chain this field should specify governance channel
         ibc_channel: Option<String>,
+        /// Route through intermediate chains if the remote chain has no direct channel to the hub.
+        /// Requires `ibc_channel`
+        ibc_forward: Option<ForwardMetadata>,
+        /// Forum discussion thread of the proposal. Only one active proposal can reference a thread
+        forum_thread_id: Option<String>,
+        /// Bundle the proposal joins. Bundled proposals are only executed together, with [`ExecuteMsg::ExecuteBundle`]
+        bundle_id: Option<u64>,
     },
+    /// Submit a multi-choice proposal. The messages of the winning option are executed
+    SubmitMultiChoiceProposal {
+        title: String,
+        description: String,
+        link: Option<String>,
+        /// Between 2 and [`MAX_PROPOSAL_OPTIONS`] labeled options
+        options: Vec<ProposalOption>,
+        /// Whether voters rank the options and the winner is decided by instant runoff.
+        /// Ranked-choice proposals can have up to [`MAX_RANKED_OPTIONS`] options
+        ranked: bool,
+        /// If proposal should be executed on a remote chain this field should specify governance channel
+        ibc_channel: Option<String>,
+        /// Forum discussion thread of the proposal. Only one active proposal can reference a thread
+        forum_thread_id: Option<String>,
+    },
+    /// Submit a two-phase proposal. A signaling round without messages is voted on first and
+    /// the binding round with `messages` opens automatically once it passes
+    SubmitTwoPhaseProposal {
+        title: String,
+        description: String,
+        link: Option<String>,
+        /// Messages of the binding round
+        messages: Option<Vec<CosmosMsg>>,
+        /// If the binding round should be executed on a remote chain this field should specify governance channel
+        ibc_channel: Option<String>,
+        /// Forum discussion thread of the proposal. Only one active proposal can reference a thread
+        forum_thread_id: Option<String>,
+    },
+}
+
+/// Parameters of the time-weighted xASTRO voting power mode. xASTRO an address already held
+/// `holding_blocks` before the snapshot block counts fully, xASTRO acquired since counts with
+/// `min_weight`. Both balances come from the xASTRO balance snapshots.
+#[cw_serde]
+pub struct StakeTimeWeighting {
+    /// Weight of recently acquired xASTRO
+    pub min_weight: Decimal,
+    /// Number of blocks xASTRO must be held for to get its full weight
+    pub holding_blocks: u64,
+}
+
+impl StakeTimeWeighting {
+    /// Returns the weighted xASTRO balance.
+    ///
+    /// * **balance** xASTRO balance at the snapshot block.
+    ///
+    /// * **held_balance** xASTRO balance `holding_blocks` before the snapshot block.
+    pub fn weighted_balance(&self, balance: Uint128, held_balance: Uint128) -> Uint128 {
+        let held = balance.min(held_balance);
+        held + (balance - held) * self.min_weight
+    }
 }
 
 /// This structure stores general parameters for the Assembly contract.
@@ -208,9 +564,35 @@ pub struct Config {
     pub proposal_required_threshold: Decimal,
     /// Whitelisted links
     pub whitelisted_links: Vec<String>,
+    /// The maximum number of messages a proposal can execute
+    pub proposal_max_messages: u32,
+    /// The maximum total size in bytes of the JSON encoded proposal messages
+    pub proposal_max_messages_size: u64,
+    /// Address allowed to update the link whitelist without a proposal
+    pub links_curator: Option<Addr>,
+    /// Addresses (e.g. protocol-owned xASTRO, bridge escrows) which can't vote and whose
+    /// voting power is excluded from the total voting power
+    pub excluded_voters: Vec<Addr>,
+    /// The share of locked builder allocations counted as voting power
+    pub builder_power_coefficient: Decimal,
+    /// Scales xASTRO voting power by how long the tokens have been held. Only voters' xASTRO is
+    /// scaled, the total voting power keeps the full xASTRO supply, so the weighting makes the
+    /// quorum harder to reach
+    pub xastro_time_weighting: Option<StakeTimeWeighting>,
+    /// Deposit required for the signaling round of a two-phase proposal
+    pub signaling_required_deposit: Uint128,
+    /// Emergency committee allowed to pause the Assembly and veto passed proposals
+    pub emergency_committee: Option<Addr>,
 }
 
 impl Config {
+    /// Returns the asset used for proposal deposits.
+    pub fn deposit_asset(&self) -> DepositAsset {
+        DepositAsset::Cw20 {
+            contract_addr: self.xastro_token_addr.clone(),
+        }
+    }
+
     pub fn validate(&self) -> StdResult<()> {
         if self.proposal_required_threshold
             > Decimal::percent(MAX_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE)
@@ -265,6 +647,51 @@ impl Config {
             )));
         }
 
+        if self.proposal_max_messages == 0
+            || self.proposal_max_messages as usize > MAX_PROPOSAL_MESSAGES
+        {
+            return Err(StdError::generic_err(format!(
+                "The maximum number of proposal messages should be between 1 and {MAX_PROPOSAL_MESSAGES}"
+            )));
+        }
+
+        if self.proposal_max_messages_size == 0
+            || self.proposal_max_messages_size > MAX_PROPOSAL_MESSAGES_SIZE
+        {
+            return Err(StdError::generic_err(format!(
+                "The maximum size of proposal messages should be between 1 and {MAX_PROPOSAL_MESSAGES_SIZE} bytes"
+            )));
+        }
+
+        if self.builder_power_coefficient > Decimal::one() {
+            return Err(StdError::generic_err(
+                "The builder power coefficient can't be greater than 1",
+            ));
+        }
+
+        if !DEPOSIT_INTERVAL.contains(&self.signaling_required_deposit.u128())
+            || self.signaling_required_deposit > self.proposal_required_deposit
+        {
+            return Err(StdError::generic_err(format!(
+                "The required signaling deposit cannot be lower than {} or higher than the proposal required deposit",
+                DEPOSIT_INTERVAL.start()
+            )));
+        }
+
+        if let Some(weighting) = &self.xastro_time_weighting {
+            if weighting.min_weight > Decimal::one() || weighting.holding_blocks == 0 {
+                return Err(StdError::generic_err(
+                    "The minimum stake weight can't be greater than 1 and the holding period must be positive",
+                ));
+            }
+        }
+
+        if self.excluded_voters.len() > MAX_EXCLUDED_VOTERS {
+            return Err(StdError::generic_err(format!(
+                "Can't exclude more than {MAX_EXCLUDED_VOTERS} addresses from voting"
+            )));
+        }
+
         if self.voting_escrow_delegator_addr.is_some() && self.vxastro_token_addr.is_none() {
             return Err(StdError::generic_err(
                 "The Voting Escrow contract should be specified to use the Voting Escrow Delegator contract."
@@ -275,6 +702,114 @@ impl Config {
     }
 }
 
+/// This enum describes the asset used for proposal deposits.
+#[cw_serde]
+pub enum DepositAsset {
+    /// A CW20 token such as xASTRO
+    Cw20 { contract_addr: Addr },
+    /// A native token
+    Native { denom: String },
+}
+
+impl Display for DepositAsset {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            DepositAsset::Cw20 { contract_addr } => write!(fmt, "{contract_addr}"),
+            DepositAsset::Native { denom } => write!(fmt, "{denom}"),
+        }
+    }
+}
+
+impl DepositAsset {
+    /// Returns a message transferring the specified amount of the deposit asset to the recipient.
+    #[cfg(feature = "full")]
+    pub fn transfer_msg(
+        &self,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            DepositAsset::Cw20 { contract_addr } => wasm_execute(
+                contract_addr,
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.into(),
+                    amount,
+                },
+                vec![],
+            )?
+            .into(),
+            DepositAsset::Native { denom } => BankMsg::Send {
+                to_address: recipient.into(),
+                amount: coins(amount.u128(), denom),
+            }
+            .into(),
+        })
+    }
+
+    /// Returns a message refunding a proposal deposit to the proposal submitter.
+    #[cfg(feature = "full")]
+    pub fn refund_msg(&self, proposal: &Proposal) -> StdResult<CosmosMsg> {
+        self.transfer_msg(&proposal.submitter, proposal.deposit_amount)
+    }
+}
+
+/// Deserializes an optional [`Uint128`] encoded either as a string or as a number.
+fn deserialize_legacy_amount<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Uint128>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct AmountVisitor;
+
+    impl<'de> Visitor<'de> for AmountVisitor {
+        type Value = Uint128;
+
+        fn expecting(&self, formatter: &mut Formatter) -> Result {
+            formatter.write_str("an amount encoded as a string or as a number")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<Self::Value, E> {
+            Ok(value.into())
+        }
+
+        fn visit_u128<E: de::Error>(self, value: u128) -> std::result::Result<Self::Value, E> {
+            Ok(value.into())
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Self::Value, E> {
+            Uint128::from_str(value).map_err(E::custom)
+        }
+    }
+
+    struct OptionalAmountVisitor;
+
+    impl<'de> Visitor<'de> for OptionalAmountVisitor {
+        type Value = Option<Uint128>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> Result {
+            formatter.write_str("an optional amount")
+        }
+
+        fn visit_none<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> std::result::Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(AmountVisitor).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(OptionalAmountVisitor)
+}
+
 /// This structure stores the params used when updating the main Assembly contract params.
 #[cw_serde]
 pub struct UpdateConfig {
@@ -294,16 +829,42 @@ pub struct UpdateConfig {
     pub proposal_effective_delay: Option<u64>,
     /// Proposal expiration period
     pub proposal_expiration_period: Option<u64>,
-    /// Proposal required deposit
-    pub proposal_required_deposit: Option<u128>,
+    /// Proposal required deposit. A JSON number is accepted as well, so `UpdateConfig` messages
+    /// stored in proposals before the switch to [`Uint128`] can still be executed
+    #[serde(default, deserialize_with = "deserialize_legacy_amount")]
+    pub proposal_required_deposit: Option<Uint128>,
     /// Proposal required quorum
-    pub proposal_required_quorum: Option<String>,
+    pub proposal_required_quorum: Option<Decimal>,
     /// Proposal required threshold
-    pub proposal_required_threshold: Option<String>,
+    pub proposal_required_threshold: Option<Decimal>,
     /// Links to remove from whitelist
     pub whitelist_remove: Option<Vec<String>>,
     /// Links to add to whitelist
     pub whitelist_add: Option<Vec<String>>,
+    /// The maximum number of messages a proposal can execute
+    pub proposal_max_messages: Option<u32>,
+    /// The maximum total size in bytes of the JSON encoded proposal messages
+    pub proposal_max_messages_size: Option<u64>,
+    /// Address allowed to update the link whitelist without a proposal
+    pub links_curator: Option<String>,
+    /// Whether the current links curator should be removed
+    pub remove_links_curator: Option<bool>,
+    /// Addresses to exclude from voting and from the total voting power
+    pub excluded_voters_add: Option<Vec<String>>,
+    /// Addresses to include back into voting
+    pub excluded_voters_remove: Option<Vec<String>>,
+    /// The share of locked builder allocations counted as voting power
+    pub builder_power_coefficient: Option<Decimal>,
+    /// Enables or updates the time-weighted xASTRO voting power mode
+    pub xastro_time_weighting: Option<StakeTimeWeighting>,
+    /// Whether the time-weighted xASTRO voting power mode should be disabled
+    pub disable_xastro_time_weighting: Option<bool>,
+    /// Deposit required for the signaling round of a two-phase proposal
+    pub signaling_required_deposit: Option<Uint128>,
+    /// Emergency committee allowed to pause the Assembly and veto passed proposals
+    pub emergency_committee: Option<String>,
+    /// Whether the current emergency committee should be removed
+    pub remove_emergency_committee: Option<bool>,
 }
 
 /// This structure stores data for a proposal.
@@ -315,9 +876,11 @@ pub struct Proposal {
     pub submitter: Addr,
     /// Status of the proposal
     pub status: ProposalStatus,
-    /// `For` power of proposal
+    /// `For` power of proposal. Also accepted as `for_votes`, the name used by legacy state
+    #[serde(alias = "for_votes")]
     pub for_power: Uint128,
-    /// `Against` power of proposal
+    /// `Against` power of proposal. Also accepted as `against_votes`, the name used by legacy state
+    #[serde(alias = "against_votes")]
     pub against_power: Uint128,
     /// `For` votes for the proposal
     pub for_voters: Vec<Addr>,
@@ -335,72 +898,77 @@ pub struct Proposal {
     pub expiration_block: u64,
     /// Proposal title
     pub title: String,
-    /// Proposal description
-    pub description: String,
-    /// Proposal link
-    pub link: Option<String>,
-    /// Proposal messages
-    pub messages: Option<Vec<CosmosMsg>>,
     /// Amount of xASTRO deposited in order to post the proposal
     pub deposit_amount: Uint128,
     /// IBC channel
     pub ibc_channel: Option<String>,
+    /// Forum discussion thread of the proposal
+    pub forum_thread_id: Option<String>,
 }
 
 impl Proposal {
-    pub fn validate(&self, whitelisted_links: Vec<String>) -> StdResult<()> {
-        // Title validation
-        if self.title.len() < MIN_TITLE_LENGTH {
-            return Err(StdError::generic_err("Title too short!"));
-        }
-        if self.title.len() > MAX_TITLE_LENGTH {
-            return Err(StdError::generic_err("Title too long!"));
-        }
-        if !self.title.chars().all(|c| {
-            c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || SAFE_TEXT_CHARS.contains(c)
-        }) {
-            return Err(StdError::generic_err(
-                "Title is not in alphanumeric format!",
-            ));
-        }
-
-        // Description validation
-        if self.description.len() < MIN_DESC_LENGTH {
-            return Err(StdError::generic_err("Description too short!"));
-        }
-        if self.description.len() > MAX_DESC_LENGTH {
-            return Err(StdError::generic_err("Description too long!"));
-        }
-        if !self.description.chars().all(|c| {
-            c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || SAFE_TEXT_CHARS.contains(c)
-        }) {
-            return Err(StdError::generic_err(
-                "Description is not in alphanumeric format",
-            ));
+    /// Returns the point at which the voting power for this proposal is computed.
+    pub fn voting_power_point(&self) -> VotingPowerPoint {
+        VotingPowerPoint {
+            height: self.start_block,
+            time: self.start_time,
         }
+    }
 
-        // Link validation
-        if let Some(link) = &self.link {
-            if link.len() < MIN_LINK_LENGTH {
-                return Err(StdError::generic_err("Link too short!"));
-            }
-            if link.len() > MAX_LINK_LENGTH {
-                return Err(StdError::generic_err("Link too long!"));
+    /// Validates the proposal title together with its content. The message limits
+    /// keep a proposal executable within the block gas limit.
+    pub fn validate(&self, content: &ProposalContent, config: &Config) -> StdResult<()> {
+        if let Some(messages) = &content.messages {
+            let max_messages = config.proposal_max_messages as usize;
+            if messages.len() > max_messages {
+                return Err(StdError::generic_err(format!(
+                    "A proposal can't have more than {max_messages} messages!"
+                )));
             }
-            if !whitelisted_links.iter().any(|wl| link.starts_with(wl)) {
-                return Err(StdError::generic_err("Link is not whitelisted!"));
+
+            let messages_size = to_vec(messages)?.len() as u64;
+            if messages_size > config.proposal_max_messages_size {
+                return Err(StdError::generic_err(format!(
+                    "Proposal messages can't be larger than {} bytes!",
+                    config.proposal_max_messages_size
+                )));
             }
-            if !is_safe_link(link) {
+        }
+
+        if let Some(forum_thread_id) = &self.forum_thread_id {
+            if forum_thread_id.is_empty()
+                || forum_thread_id.len() > MAX_FORUM_THREAD_ID_LENGTH
+                || !forum_thread_id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
                 return Err(StdError::generic_err(
-                    "Link is not properly formatted or contains unsafe characters!",
+                    "Forum thread id is not properly formatted!",
                 ));
             }
         }
 
-        Ok(())
+        validate_proposal_content(
+            &self.title,
+            &content.description,
+            content.link.as_deref(),
+            &ProposalValidationRules::new(config.whitelisted_links.clone()),
+        )
     }
 }
 
+/// This structure describes the bulky parts of a proposal. They are stored separately from
+/// [`Proposal`] so that votes don't need to load them.
+#[cw_serde]
+pub struct ProposalContent {
+    /// Proposal description
+    pub description: String,
+    /// Proposal link
+    pub link: Option<String>,
+    /// Proposal messages
+    pub messages: Option<Vec<CosmosMsg>>,
+}
+
 /// This enum describes available statuses/states for a Proposal.
 #[cw_serde]
 pub enum ProposalStatus {
@@ -427,6 +995,34 @@ impl Display for ProposalStatus {
     }
 }
 
+impl FromStr for ProposalStatus {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> StdResult<Self> {
+        match s {
+            "active" => Ok(ProposalStatus::Active),
+            "passed" => Ok(ProposalStatus::Passed),
+            "rejected" => Ok(ProposalStatus::Rejected),
+            "in_progress" => Ok(ProposalStatus::InProgress),
+            "failed" => Ok(ProposalStatus::Failed),
+            "executed" => Ok(ProposalStatus::Executed),
+            "expired" => Ok(ProposalStatus::Expired),
+            _ => Err(StdError::parse_err(
+                "ProposalStatus",
+                format!("Unknown proposal status: {s}"),
+            )),
+        }
+    }
+}
+
+impl TryFrom<&str> for ProposalStatus {
+    type Error = StdError;
+
+    fn try_from(s: &str) -> StdResult<Self> {
+        s.parse()
+    }
+}
+
 /// This structure describes a proposal vote.
 #[cw_serde]
 pub struct ProposalVote {
@@ -441,6 +1037,9 @@ pub struct ProposalVote {
 pub enum ProposalVoteOption {
     For,
     Against,
+    /// Index of the chosen option of a multi-choice proposal. Ranked ballots are recorded
+    /// with their first preference
+    Choice(u32),
 }
 
 impl Display for ProposalVoteOption {
@@ -448,21 +1047,246 @@ impl Display for ProposalVoteOption {
         match self {
             ProposalVoteOption::For {} => fmt.write_str("for"),
             ProposalVoteOption::Against {} => fmt.write_str("against"),
+            ProposalVoteOption::Choice(option) => write!(fmt, "choice_{option}"),
+        }
+    }
+}
+
+impl FromStr for ProposalVoteOption {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> StdResult<Self> {
+        match s {
+            "for" => Ok(ProposalVoteOption::For),
+            "against" => Ok(ProposalVoteOption::Against),
+            _ => match s.strip_prefix("choice_").map(str::parse) {
+                Some(Ok(option)) => Ok(ProposalVoteOption::Choice(option)),
+                _ => Err(StdError::parse_err(
+                    "ProposalVoteOption",
+                    format!("Unknown vote option: {s}"),
+                )),
+            },
         }
     }
 }
 
+impl TryFrom<&str> for ProposalVoteOption {
+    type Error = StdError;
+
+    fn try_from(s: &str) -> StdResult<Self> {
+        s.parse()
+    }
+}
+
 /// This structure describes a proposal vote response.
 #[cw_serde]
 pub struct ProposalVotesResponse {
     /// Proposal identifier
     pub proposal_id: u64,
     /// Total amount of `for` votes for a proposal
+    #[serde(alias = "for_votes")]
     pub for_power: Uint128,
     /// Total amount of `against` votes for a proposal.
+    #[serde(alias = "against_votes")]
     pub against_power: Uint128,
 }
 
+/// This enum describes the notifications sent to registered proposal hooks.
+#[cw_serde]
+pub enum ProposalHookMsg {
+    /// A proposal was submitted (`active`), ended (`passed` or `rejected`) or executed
+    /// (`in_progress`, `executed` or `failed`)
+    ProposalStatusChanged {
+        proposal_id: u64,
+        status: ProposalStatus,
+    },
+}
+
+/// The execute message a proposal hook contract must accept.
+#[cw_serde]
+enum ProposalHookExecuteMsg {
+    ProposalHook(ProposalHookMsg),
+}
+
+impl ProposalHookMsg {
+    /// Serializes the notification as `{"proposal_hook": ...}` and wraps it into a message to `contract_addr`.
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(wasm_execute(
+            contract_addr,
+            &ProposalHookExecuteMsg::ProposalHook(self),
+            vec![],
+        )?
+        .into())
+    }
+}
+
+/// This enum describes the notifications sent to registered vote listeners.
+#[cw_serde]
+pub enum VoteListenerMsg {
+    /// A vote was cast on a proposal. Votes on multi-choice proposals carry
+    /// [`ProposalVoteOption::Choice`] with the first preference of the voter
+    VoteCast {
+        proposal_id: u64,
+        voter: Addr,
+        vote: ProposalVoteOption,
+        voting_power: Uint128,
+    },
+}
+
+/// The execute message a vote listener contract must accept.
+#[cw_serde]
+enum VoteListenerExecuteMsg {
+    VoteHook(VoteListenerMsg),
+}
+
+impl VoteListenerMsg {
+    /// Serializes the notification as `{"vote_hook": ...}` and wraps it into a message to `contract_addr`.
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(wasm_execute(
+            contract_addr,
+            &VoteListenerExecuteMsg::VoteHook(self),
+            vec![],
+        )?
+        .into())
+    }
+}
+
+/// This structure describes the number of stored proposals with every status.
+/// Removed proposals aren't counted.
+#[cw_serde]
+pub struct ProposalCountsResponse {
+    pub active: u64,
+    /// Passed proposals awaiting execution
+    pub passed: u64,
+    pub rejected: u64,
+    /// Proposals being executed on a remote chain
+    pub in_progress: u64,
+    pub failed: u64,
+    pub executed: u64,
+    pub expired: u64,
+}
+
+/// An option of a multi-choice proposal
+#[cw_serde]
+pub struct ProposalOption {
+    /// Option label
+    pub label: String,
+    /// Messages executed if the option wins
+    pub messages: Option<Vec<CosmosMsg>>,
+}
+
+/// The point at which voting power is computed. Voting power for a proposal is computed
+/// at its start block and start time.
+#[cw_serde]
+pub struct VotingPowerPoint {
+    /// xASTRO and builder allocations are taken from this block
+    pub height: u64,
+    /// vxASTRO is taken from one week before this timestamp (in seconds)
+    pub time: u64,
+}
+
+/// An operator allowed to vote on behalf of a grantor
+#[cw_serde]
+pub struct VoteOperatorGrant {
+    pub operator: Addr,
+    /// Timestamp (in seconds) after which the grant expires
+    pub expires: Option<u64>,
+}
+
+/// The voting power an option of a multi-choice proposal received
+#[cw_serde]
+pub struct OptionTally {
+    /// Option label
+    pub label: String,
+    /// Voting power cast for the option
+    pub power: Uint128,
+}
+
+/// This structure describes the tally of a multi-choice proposal.
+#[cw_serde]
+pub struct MultiChoiceTallyResponse {
+    /// Tally of every option, in the submitted order
+    pub options: Vec<OptionTally>,
+    /// Index of the winning option once the proposal passed
+    pub winning_option: Option<u32>,
+}
+
+/// Links the rounds of a two-phase proposal. The parent id is the id of the signaling round.
+#[cw_serde]
+pub struct TwoPhaseProposalResponse {
+    /// Id of the two-phase proposal
+    pub parent_id: u64,
+    /// Signaling round
+    pub signaling: Proposal,
+    /// Binding round. Opened once the signaling round passes
+    pub binding: Option<Proposal>,
+}
+
+/// The addresses a proposal's messages touch, sorted and deduplicated. Multi-choice proposals
+/// include the messages of every option and two-phase proposals those of a pending binding round.
+#[cw_serde]
+pub struct ProposalTargetsResponse {
+    /// Contracts that are executed, migrated or have their admin changed
+    pub contracts: Vec<String>,
+    /// Recipients of bank transfers
+    pub bank_recipients: Vec<String>,
+}
+
+/// Interdependent proposals that are either all executed or none is.
+#[cw_serde]
+pub struct ProposalBundle {
+    /// The submitter of every proposal in the bundle
+    pub submitter: Addr,
+    /// Bundled proposals in ascending order
+    pub proposal_ids: Vec<u64>,
+}
+
+/// Merkle membership proof of a single vote. Leaves are sha256(0x00 || voter || option || power)
+/// with a one byte option (0 for `For`, 1 for `Against`) and a 16 byte big-endian power. Inner
+/// nodes are sha256(0x01 || min(a, b) || max(a, b)) and an unpaired node is carried up as is.
+#[cw_serde]
+pub struct VoteProofResponse {
+    /// Merkle root over all votes of the proposal
+    pub root: Binary,
+    /// The vote of the user
+    pub vote: ProposalVote,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<Binary>,
+}
+
+/// The payload a voter signs to cast a vote with [`ExecuteMsg::CastVoteBySig`]. The chain id
+/// and the Assembly address keep a signature from being replayed elsewhere.
+#[cw_serde]
+pub struct VoteBySigPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub voter: String,
+    pub proposal_id: u64,
+    pub vote: ProposalVoteOption,
+    pub nonce: u64,
+}
+
+/// This structure describes the result of a proposal tally check.
+#[cw_serde]
+pub struct TallyCheckResponse {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// `For` power stored in the proposal
+    pub for_power: Uint128,
+    /// `Against` power stored in the proposal
+    pub against_power: Uint128,
+    /// Sum of the recorded `for` votes
+    pub recorded_for_power: Uint128,
+    /// Sum of the recorded `against` votes
+    pub recorded_against_power: Uint128,
+    /// Option tallies stored for a multi-choice proposal, empty for binary proposals
+    pub option_powers: Vec<Uint128>,
+    /// Sums of the recorded votes for every option of a multi-choice proposal
+    pub recorded_option_powers: Vec<Uint128>,
+    /// Whether the proposal tallies equal the sums of the recorded votes
+    pub is_consistent: bool,
+}
+
 /// This structure describes a proposal list response.
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -473,16 +1297,170 @@ pub struct ProposalListResponse {
 }
 
 pub mod helpers {
+    use cosmwasm_schema::cw_serde;
+    #[cfg(feature = "full")]
+    use cosmwasm_std::{to_binary, wasm_execute, CosmosMsg, Uint128};
     use cosmwasm_std::{StdError, StdResult};
+    #[cfg(feature = "full")]
+    use cw20::Cw20ExecuteMsg;
+
+    #[cfg(feature = "full")]
+    use super::{Cw20HookMsg, ForwardMetadata};
+    use super::{
+        MAX_DESC_LENGTH, MAX_LINK_LENGTH, MAX_TITLE_LENGTH, MIN_DESC_LENGTH, MIN_LINK_LENGTH,
+        MIN_TITLE_LENGTH, SAFE_TEXT_CHARS,
+    };
 
     const SAFE_LINK_CHARS: &str = "-_:/?#@!$&()*+,;=.~[]'%";
 
+    /// This structure describes the rules a proposal title, description and link must follow.
+    #[cw_serde]
+    pub struct ProposalValidationRules {
+        /// Minimum title length
+        pub min_title_length: usize,
+        /// Maximum title length
+        pub max_title_length: usize,
+        /// Minimum description length
+        pub min_description_length: usize,
+        /// Maximum description length
+        pub max_description_length: usize,
+        /// Minimum link length
+        pub min_link_length: usize,
+        /// Maximum link length
+        pub max_link_length: usize,
+        /// Prefixes one of which a proposal link must start with
+        pub whitelisted_links: Vec<String>,
+    }
+
+    impl ProposalValidationRules {
+        /// Returns the default Assembly rules with the specified link whitelist
+        pub fn new(whitelisted_links: Vec<String>) -> Self {
+            Self {
+                min_title_length: MIN_TITLE_LENGTH,
+                max_title_length: MAX_TITLE_LENGTH,
+                min_description_length: MIN_DESC_LENGTH,
+                max_description_length: MAX_DESC_LENGTH,
+                min_link_length: MIN_LINK_LENGTH,
+                max_link_length: MAX_LINK_LENGTH,
+                whitelisted_links,
+            }
+        }
+    }
+
+    /// Checks if the text consists of alphanumeric characters, whitespaces and allowed special characters.
+    fn is_safe_text(text: &str) -> bool {
+        text.chars().all(|c| {
+            c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || SAFE_TEXT_CHARS.contains(c)
+        })
+    }
+
+    /// Validates the title, description and link of a proposal against the specified rules.
+    pub fn validate_proposal_content(
+        title: &str,
+        description: &str,
+        link: Option<&str>,
+        rules: &ProposalValidationRules,
+    ) -> StdResult<()> {
+        // Title validation
+        if title.len() < rules.min_title_length {
+            return Err(StdError::generic_err("Title too short!"));
+        }
+        if title.len() > rules.max_title_length {
+            return Err(StdError::generic_err("Title too long!"));
+        }
+        if !is_safe_text(title) {
+            return Err(StdError::generic_err(
+                "Title is not in alphanumeric format!",
+            ));
+        }
+
+        // Description validation
+        if description.len() < rules.min_description_length {
+            return Err(StdError::generic_err("Description too short!"));
+        }
+        if description.len() > rules.max_description_length {
+            return Err(StdError::generic_err("Description too long!"));
+        }
+        if !is_safe_text(description) {
+            return Err(StdError::generic_err(
+                "Description is not in alphanumeric format",
+            ));
+        }
+
+        // Link validation
+        if let Some(link) = link {
+            if link.len() < rules.min_link_length {
+                return Err(StdError::generic_err("Link too short!"));
+            }
+            if link.len() > rules.max_link_length {
+                return Err(StdError::generic_err("Link too long!"));
+            }
+            if !rules
+                .whitelisted_links
+                .iter()
+                .any(|wl| link.starts_with(wl))
+            {
+                return Err(StdError::generic_err("Link is not whitelisted!"));
+            }
+            if !is_safe_link(link) {
+                return Err(StdError::generic_err(
+                    "Link is not properly formatted or contains unsafe characters!",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Checks if the link is valid. Returns a boolean value.
     pub fn is_safe_link(link: &str) -> bool {
         link.chars()
             .all(|c| c.is_ascii_alphanumeric() || SAFE_LINK_CHARS.contains(c))
     }
 
+    /// Builds a message that sends the xASTRO deposit to the Assembly and submits a new proposal.
+    ///
+    /// * **xastro_addr** xASTRO token address.
+    ///
+    /// * **assembly_addr** Assembly contract address.
+    ///
+    /// * **deposit** amount of xASTRO to deposit. Should be at least the Assembly's required deposit.
+    #[cfg(feature = "full")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_submit_proposal_msg(
+        xastro_addr: impl Into<String>,
+        assembly_addr: impl Into<String>,
+        deposit: Uint128,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        link: Option<String>,
+        messages: Option<Vec<CosmosMsg>>,
+        ibc_channel: Option<String>,
+        ibc_forward: Option<ForwardMetadata>,
+        forum_thread_id: Option<String>,
+        bundle_id: Option<u64>,
+    ) -> StdResult<CosmosMsg> {
+        Ok(wasm_execute(
+            xastro_addr,
+            &Cw20ExecuteMsg::Send {
+                contract: assembly_addr.into(),
+                amount: deposit,
+                msg: to_binary(&Cw20HookMsg::SubmitProposal {
+                    title: title.into(),
+                    description: description.into(),
+                    link,
+                    messages,
+                    ibc_channel,
+                    ibc_forward,
+                    forum_thread_id,
+                    bundle_id,
+                })?,
+            },
+            vec![],
+        )?
+        .into())
+    }
+
     /// Validating the list of links. Returns an error if a list has an invalid link.
     pub fn validate_links(links: &[String]) -> StdResult<()> {
         for link in links {
@@ -0,0 +1,110 @@
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// The maximum amount of different reward tokens that can be attached to a pool at a specific period
+pub const MAX_BRIBE_ASSETS: usize = 10;
+
+/// This structure describes the basic settings for creating a contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Contract owner, manages the reward tokens that can be attached to pools
+    pub owner: String,
+    /// Generator controller contract address
+    pub generator_controller: String,
+    /// Reward tokens that can be attached to pools
+    pub allowed_assets: Vec<AssetInfo>,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
+    Receive(Cw20ReceiveMsg),
+    /// DepositBribe attaches native tokens sent along with the message to a pool at a specific period
+    DepositBribe { pool: String, period: u64 },
+    /// Claim sends rewards attached to a pool at a specific period to a voter pro-rata to their applied vote weight.
+    /// Every reward token is claimed separately, so a token which can't be transferred doesn't block the others
+    Claim {
+        pool: String,
+        period: u64,
+        /// Reward tokens to claim. All unclaimed ones if not set
+        assets: Option<Vec<AssetInfo>>,
+    },
+    /// Reclaim returns rewards the sender attached to a pool at a specific period if nobody voted for the pool
+    Reclaim { pool: String, period: u64 },
+    /// UpdateAllowedAssets adds or removes reward tokens that can be attached to pools.
+    /// Rewards already attached with a removed token can still be claimed
+    UpdateAllowedAssets {
+        add: Option<Vec<AssetInfo>>,
+        remove: Option<Vec<AssetInfo>>,
+    },
+    /// ProposeNewOwner creates a proposal to change contract ownership
+    ProposeNewOwner { new_owner: String, expires_in: u64 },
+    /// DropOwnershipProposal removes a proposal to change contract ownership
+    DropOwnershipProposal {},
+    /// ClaimOwnership claims contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes custom hooks for a CW20.
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// DepositBribe attaches received CW20 tokens to a pool at a specific period
+    DepositBribe { pool: String, period: u64 },
+}
+
+/// This structure describes the query messages available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Config returns the contract configuration
+    #[returns(ConfigResponse)]
+    Config {},
+    /// AllowedAssets returns reward tokens that can be attached to pools
+    #[returns(Vec<AssetInfo>)]
+    AllowedAssets {},
+    /// Bribes returns all rewards attached to a pool at a specific period
+    #[returns(Vec<BribeResponse>)]
+    Bribes { pool: String, period: u64 },
+    /// Claimable returns rewards a voter can claim for a pool at a specific period
+    #[returns(Vec<Asset>)]
+    Claimable {
+        user: String,
+        pool: String,
+        period: u64,
+    },
+    /// Deposits returns rewards a depositor attached to a pool at a specific period and hasn't reclaimed yet
+    #[returns(Vec<Asset>)]
+    Deposits {
+        depositor: String,
+        pool: String,
+        period: u64,
+    },
+}
+
+/// This structure describes the parameters returned when querying for the contract configuration.
+#[cw_serde]
+pub struct ConfigResponse {
+    /// Contract owner
+    pub owner: Addr,
+    /// Generator controller contract address
+    pub generator_controller: Addr,
+}
+
+/// This structure describes a reward attached to a pool at a specific period.
+#[cw_serde]
+pub struct BribeResponse {
+    /// The reward token
+    pub asset_info: AssetInfo,
+    /// Total amount of deposited reward tokens
+    pub amount: Uint128,
+    /// Amount of reward tokens already claimed by voters
+    pub claimed: Uint128,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -1,15 +1,43 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdError, Uint128};
+use cosmwasm_std::{Addr, Decimal, StdError, Uint128};
+
+/// The maximum validity period of a receiver change proposal
+pub const MAX_RECEIVER_PROPOSAL_TTL: u64 = 14 * 86400; // 14 days
+
+/// The gas limit of a single allocation hook notification
+pub const ALLOCATION_HOOK_GAS_LIMIT: u64 = 300_000;
 
 /// This structure stores general parameters for the builder unlock contract.
 #[cw_serde]
 pub struct Config {
-    /// Account that can create new unlock schedules
-    pub owner: Addr,
     /// Address of ASTRO token
     pub astro_token: Addr,
     /// Max ASTRO tokens to allocate
     pub max_allocations_amount: Uint128,
+    /// Notice period in seconds between queueing and applying an allocation decrease
+    #[serde(default)]
+    pub decrease_notice_period: u64,
+    /// ASTRO staking contract where locked ASTRO can be staked
+    #[serde(default)]
+    pub astro_staking: Option<Addr>,
+    /// Account that receives the staking yield
+    #[serde(default)]
+    pub yield_recipient: Option<Addr>,
+    /// Max ASTRO tokens allocated to a single account across all its allocations
+    #[serde(default)]
+    pub max_allocation_per_account: Option<Uint128>,
+    /// Max number of accounts that can receive allocations
+    #[serde(default)]
+    pub max_total_accounts: Option<u64>,
+    /// vxASTRO contract used to lock withdrawn ASTRO
+    #[serde(default)]
+    pub voting_escrow: Option<Addr>,
+    /// Whether withdrawals, receiver changes and allocation changes are suspended
+    #[serde(default)]
+    pub paused: bool,
+    /// Contracts notified about every created, increased, decreased or transferred allocation
+    #[serde(default)]
+    pub allocation_hooks: Vec<Addr>,
 }
 
 /// This structure stores the total and the remaining amount of ASTRO to be unlocked by all accounts.
@@ -34,6 +62,36 @@ pub struct Schedule {
     pub cliff: u64,
     /// Time after the cliff during which the remaining tokens linearly unlock
     pub duration: u64,
+    /// Interval between periodic unlocks (in seconds). If set, tokens unlock in discrete tranches
+    /// every period instead of linearly
+    pub unlock_period: Option<u64>,
+    /// Portion of the allocation which is unlocked at the start time. The rest of the allocation unlocks
+    /// according to the schedule
+    pub percent_at_start: Option<Decimal>,
+}
+
+impl Schedule {
+    /// Validates the periodic unlock settings and the portion unlocked at the start time.
+    pub fn validate(&self, account: &str) -> Result<(), StdError> {
+        if let Some(percent_at_start) = self.percent_at_start {
+            if percent_at_start > Decimal::one() {
+                return Err(StdError::generic_err(format!(
+                    "The percent unlocked at start must not exceed 100%: {percent_at_start}. Account: {account}"
+                )));
+            }
+        }
+
+        if let Some(unlock_period) = self.unlock_period {
+            if unlock_period == 0 || unlock_period > self.duration {
+                return Err(StdError::generic_err(format!(
+                    "The unlock period must be greater than 0 and not exceed the duration: {} <= {}. Account: {}",
+                    unlock_period, self.duration, account
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// This structure stores the parameters used to describe an ASTRO allocation.
@@ -57,6 +115,8 @@ impl AllocationParams {
             )));
         };
 
+        self.unlock_schedule.validate(account)?;
+
         if self.amount.is_zero() {
             return Err(StdError::generic_err(format!(
                 "Amount must not be zero. Account: {account}"
@@ -98,6 +158,19 @@ impl AllocationParams {
             )));
         }
 
+        if new_schedule.percent_at_start.unwrap_or_default()
+            > self.unlock_schedule.percent_at_start.unwrap_or_default()
+        {
+            return Err(StdError::generic_err(format!(
+                "The new percent unlocked at start should be less than or equal to the old one: {} <= {}. Account error: {}",
+                new_schedule.percent_at_start.unwrap_or_default(),
+                self.unlock_schedule.percent_at_start.unwrap_or_default(),
+                account
+            )));
+        }
+
+        new_schedule.validate(account)?;
+
         self.unlock_schedule = new_schedule;
         Ok(())
     }
@@ -122,13 +195,65 @@ impl AllocationStatus {
     }
 }
 
+/// This structure stores the record of a revoked allocation.
+#[cw_serde]
+pub struct Revocation {
+    /// Timestamp at which the allocation was revoked
+    pub revoked_at: u64,
+    /// Amount of unvested ASTRO returned to the unallocated tokens
+    pub unvested_amount: Uint128,
+}
+
+/// This structure stores an allocation decrease waiting for its notice period to pass.
+#[cw_serde]
+pub struct PendingDecrease {
+    /// Amount of ASTRO to remove from the allocation
+    pub amount: Uint128,
+    /// Timestamp after which the decrease can be applied
+    pub effective_at: u64,
+}
+
+/// This enum describes the types of allocation changes pushed to allocation hook contracts.
+#[cw_serde]
+pub enum AllocationChange {
+    /// A new allocation was created
+    Create,
+    /// The allocation amount was increased
+    Increase,
+    /// The allocation amount was decreased or the allocation was revoked
+    Decrease,
+    /// The allocation (or a part of it) was transferred from **prev_account**
+    Transfer { prev_account: String },
+}
+
+/// This structure describes an allocation change pushed to allocation hook contracts.
+#[cw_serde]
+pub struct AllocationHookMsg {
+    /// Type of the change
+    pub change: AllocationChange,
+    /// Account holding the allocation
+    pub account: String,
+    /// ID of the allocation
+    pub allocation_id: u64,
+    /// Allocation amount after the change
+    pub amount: Uint128,
+}
+
+/// This enum describes messages sent to allocation hook contracts.
+#[cw_serde]
+pub enum AllocationHookExecuteMsg {
+    /// AllocationHook notifies a contract about an allocation change
+    AllocationHook(AllocationHookMsg),
+}
+
 pub mod msg {
     use crate::builder_unlock::Schedule;
     use cosmwasm_schema::{cw_serde, QueryResponses};
-    use cosmwasm_std::Uint128;
+    use cosmwasm_std::{Addr, Uint128};
     use cw20::Cw20ReceiveMsg;
+    use cw_ownable::{Action, Ownership};
 
-    use super::{AllocationParams, AllocationStatus, Config};
+    use super::{AllocationParams, AllocationStatus, Config, PendingDecrease, Revocation};
 
     /// This structure holds the initial parameters used to instantiate the contract.
     #[cw_serde]
@@ -139,6 +264,8 @@ pub mod msg {
         pub astro_token: String,
         /// Max ASTRO tokens to allocate
         pub max_allocations_amount: Uint128,
+        /// Notice period in seconds between queueing and applying an allocation decrease
+        pub decrease_notice_period: Option<u64>,
     }
 
     /// This enum describes all the execute functions available in the contract.
@@ -147,47 +274,167 @@ pub mod msg {
         /// Receive is an implementation for the CW20 receive msg
         Receive(Cw20ReceiveMsg),
         /// Withdraw claims withdrawable ASTRO
-        Withdraw {},
+        Withdraw {
+            /// Address that receives the withdrawn ASTRO (the sender by default)
+            recipient: Option<String>,
+        },
+        /// WithdrawAndLock claims withdrawable ASTRO, stakes it in the ASTRO staking contract and
+        /// deposits the received xASTRO into the sender's vxASTRO lock
+        WithdrawAndLock {
+            /// Minimum time in seconds the sender's vxASTRO lock must still last (the lock must
+            /// not be expired by default)
+            lock_time: Option<u64>,
+        },
         /// ProposeNewReceiver allows a user to change the receiver address for their ASTRO allocation
-        ProposeNewReceiver { new_receiver: String },
+        ProposeNewReceiver {
+            new_receiver: String,
+            /// The validity period of the proposal in seconds
+            expires_in: u64,
+        },
         /// DropNewReceiver allows a user to remove the previously proposed new receiver for their ASTRO allocation
         DropNewReceiver {},
         /// ClaimReceiver allows newly proposed receivers to claim ASTRO allocations ownership
         ClaimReceiver { prev_receiver: String },
+        /// ProposeSplitReceivers allows a user to split an ASTRO allocation among several new receivers.
+        /// Amounts must sum up to the allocation amount
+        ProposeSplitReceivers {
+            /// New receivers along with the amounts they receive
+            receivers: Vec<(String, Uint128)>,
+            /// Allocation ID (the first allocation of the sender by default)
+            allocation_id: Option<u64>,
+        },
+        /// DropSplitReceivers allows a user to remove the unclaimed part of a proposed allocation split
+        DropSplitReceivers {
+            /// Allocation ID (the first allocation of the sender by default)
+            allocation_id: Option<u64>,
+        },
+        /// ClaimSplitReceiver allows a proposed split receiver to claim their part of an ASTRO allocation
+        ClaimSplitReceiver {
+            prev_receiver: String,
+            /// Allocation ID (the first allocation of the previous receiver by default)
+            allocation_id: Option<u64>,
+        },
         /// Increase the ASTRO allocation of a receiver
-        IncreaseAllocation { receiver: String, amount: Uint128 },
+        IncreaseAllocation {
+            receiver: String,
+            amount: Uint128,
+            /// Allocation ID (the first allocation of the receiver by default)
+            allocation_id: Option<u64>,
+        },
         /// Decrease the ASTRO allocation of a receiver
-        DecreaseAllocation { receiver: String, amount: Uint128 },
+        DecreaseAllocation {
+            receiver: String,
+            amount: Uint128,
+            /// Allocation ID (the first allocation of the receiver by default)
+            allocation_id: Option<u64>,
+        },
+        /// Delegate the voting power of locked allocations to another account.
+        /// Delegating to the sender's own address removes the delegation
+        DelegateVotingPower { delegate: String },
+        /// Apply a queued allocation decrease once its notice period has passed (only accessible to the owner)
+        ApplyDecreaseAllocation {
+            receiver: String,
+            /// Allocation ID (the first allocation of the receiver by default)
+            allocation_id: Option<u64>,
+        },
+        /// Cancel a queued allocation decrease (only accessible to the owner)
+        CancelDecreaseAllocation {
+            receiver: String,
+            /// Allocation ID (the first allocation of the receiver by default)
+            allocation_id: Option<u64>,
+        },
+        /// Revoke the allocation of an account returning unvested ASTRO to the unallocated tokens (only accessible to the owner)
+        Revoke {
+            account: String,
+            /// Allocation ID (the first allocation of the account by default)
+            allocation_id: Option<u64>,
+        },
+        /// Block withdrawals and receiver claims for an account pending a governance decision (only accessible to the owner)
+        FreezeAccount { account: String },
+        /// Unblock withdrawals and receiver claims for a frozen account (only accessible to the owner)
+        UnfreezeAccount { account: String },
+        /// Suspend withdrawals, receiver changes and allocation changes (only accessible to the owner)
+        Pause {},
+        /// Resume withdrawals, receiver changes and allocation changes (only accessible to the owner)
+        Unpause {},
         /// Transfer unallocated tokens (only accessible to the owner)
         TransferUnallocated {
             amount: Uint128,
             recipient: Option<String>,
         },
-        /// Propose a new owner for the contract
+        /// Propose a new owner for the contract. Kept for backward compatibility, equivalent to
+        /// [`Action::TransferOwnership`] with an expiry in **expires_in** seconds
         ProposeNewOwner { new_owner: String, expires_in: u64 },
         /// Remove the ownership transfer proposal
         DropOwnershipProposal {},
-        /// Claim contract ownership
+        /// Claim contract ownership. Kept for backward compatibility, equivalent to
+        /// [`Action::AcceptOwnership`]
         ClaimOwnership {},
+        /// Update contract ownership in the cw-ownable format. Ownership can't be renounced
+        UpdateOwnership(Action),
         /// Update parameters in the contract configuration
-        UpdateConfig { new_max_allocations_amount: Uint128 },
+        UpdateConfig {
+            new_max_allocations_amount: Uint128,
+            /// New notice period in seconds for allocation decreases
+            new_decrease_notice_period: Option<u64>,
+            /// New max ASTRO tokens allocated to a single account
+            new_max_allocation_per_account: Option<Uint128>,
+            /// New max number of accounts that can receive allocations
+            new_max_total_accounts: Option<u64>,
+            /// New vxASTRO contract used to lock withdrawn ASTRO
+            new_voting_escrow: Option<String>,
+            /// New contracts notified about allocation changes
+            new_allocation_hooks: Option<Vec<String>>,
+        },
+        /// Set the ASTRO staking contract and the staking yield recipient. The staking contract
+        /// can't be changed while ASTRO is staked
+        UpdateStakingConfig {
+            astro_staking: Option<String>,
+            yield_recipient: Option<String>,
+        },
+        /// Stake locked ASTRO in the staking contract (only accessible to the owner).
+        /// Staked ASTRO is unstaked automatically when it is needed for withdrawals
+        StakeAstro { amount: Uint128 },
+        /// Unstake ASTRO from the staking contract (only accessible to the owner). All staked ASTRO
+        /// is unstaked by default
+        UnstakeAstro { amount: Option<Uint128> },
+        /// Send xASTRO worth the staking yield to the yield recipient
+        ClaimStakingYield {},
         /// Update a schedule of allocation for specified accounts
         UpdateUnlockSchedules {
             new_unlock_schedules: Vec<(String, Schedule)>,
+            /// ID of the allocations to update (the first allocation of every account by default)
+            allocation_id: Option<u64>,
+        },
+        /// Migrate the allocations left after a contract migration to the current layout. Other
+        /// messages are rejected until every allocation is migrated. Anyone can call this
+        MigrateAllocations {
+            /// The amount of accounts to migrate
+            limit: Option<u32>,
         },
     }
 
     /// This enum describes receive msg templates.
     #[cw_serde]
     pub enum ReceiveMsg {
-        /// CreateAllocations creates new ASTRO allocations
+        /// CreateAllocations creates new ASTRO allocations. An account which already has allocations
+        /// receives a new allocation with its own unlock schedule
         CreateAllocations {
             allocations: Vec<(String, AllocationParams)>,
         },
         /// Increase the ASTRO allocation for a receiver
-        IncreaseAllocation { user: String, amount: Uint128 },
+        IncreaseAllocation {
+            user: String,
+            amount: Uint128,
+            /// Allocation ID (the first allocation of the receiver by default)
+            allocation_id: Option<u64>,
+        },
     }
 
+    /// This structure describes a migration message.
+    #[cw_serde]
+    pub struct MigrateMsg {}
+
     /// Thie enum describes all the queries available in the contract.
     #[cw_serde]
     #[derive(QueryResponses)]
@@ -195,24 +442,44 @@ pub mod msg {
         /// Config returns the configuration for this contract
         #[returns(Config)]
         Config {},
+        /// StakingState returns the amount of staked ASTRO and the accrued staking yield
+        #[returns(StakingStateResponse)]
+        StakingState {},
+        /// Solvency compares the ASTRO actually held by the contract against its internal accounting
+        #[returns(SolvencyResponse)]
+        Solvency {},
+        /// Ownership returns the contract owner and the pending ownership transfer in the cw-ownable format
+        #[returns(Ownership<Addr>)]
+        Ownership {},
         /// State returns the state of this contract
         #[returns(StateResponse)]
         State {},
-        /// Allocation returns the parameters and current status of an allocation
+        /// Allocation returns the parameters and current status of all allocations of an account
+        /// aggregated into a single allocation
         #[returns(AllocationResponse)]
         Allocation {
             /// Account whose allocation status we query
             account: String,
         },
-        /// Allocations returns a vector that contains builder unlock allocations by specified
+        /// AccountAllocations returns the parameters and current status of every allocation of an account
+        #[returns(Vec<(u64, AllocationResponse)>)]
+        AccountAllocations {
+            /// Account whose allocations we query
+            account: String,
+        },
+        /// Allocations returns a vector that contains aggregated builder unlock allocations by specified
         /// parameters
         #[returns(Vec<(String, AllocationParams)>)]
         Allocations {
             start_after: Option<String>,
             limit: Option<u32>,
+            /// Return only the accounts whose allocations match the filter
+            filter: Option<AllocationsFilter>,
+            /// Sorting order of the returned allocations (by account by default)
+            order_by: Option<AllocationsOrder>,
         },
         #[returns(Uint128)]
-        /// UnlockedTokens returns the unlocked tokens from an allocation
+        /// UnlockedTokens returns the unlocked tokens from all allocations of an account
         UnlockedTokens {
             /// Account whose amount of unlocked ASTRO we query for
             account: String,
@@ -225,10 +492,127 @@ pub mod msg {
             /// Timestamp used to simulate how much ASTRO the account can withdraw
             timestamp: Option<u64>,
         },
+        /// UnlockedTokensBatch returns the unlocked tokens from all allocations of multiple accounts
+        #[returns(Vec<(String, Uint128)>)]
+        UnlockedTokensBatch {
+            /// Accounts whose amount of unlocked ASTRO we query for
+            accounts: Vec<String>,
+        },
+        /// SimulateWithdrawBatch simulates how many ASTRO will be released for multiple accounts
+        #[returns(Vec<(String, SimulateWithdrawResponse)>)]
+        SimulateWithdrawBatch {
+            /// Accounts for which we simulate a withdrawal
+            accounts: Vec<String>,
+            /// Timestamp used to simulate how much ASTRO the accounts can withdraw
+            timestamp: Option<u64>,
+        },
+        /// TotalUnlockedAt returns the amount of unlocked and still locked ASTRO across all allocations at a timestamp
+        #[returns(TotalUnlockedResponse)]
+        TotalUnlockedAt {
+            /// Timestamp at which the unlocked amount is computed
+            timestamp: u64,
+        },
+        /// SplitProposal returns unclaimed parts of a proposed allocation split
+        #[returns(Option<Vec<(Addr, Uint128)>>)]
+        SplitProposal {
+            /// Account whose allocation split we query
+            account: String,
+            /// Allocation ID (the first allocation of the account by default)
+            allocation_id: Option<u64>,
+        },
+        /// ReceiverProposal returns the pending receiver change proposal of an account
+        #[returns(Option<ReceiverProposalResponse>)]
+        ReceiverProposal {
+            /// Account whose receiver change proposal we query
+            account: String,
+        },
+        /// IsFrozen returns whether withdrawals for an account are frozen
+        #[returns(bool)]
+        IsFrozen {
+            /// Account whose freeze status we query
+            account: String,
+        },
+        /// ValidateScheduleUpdates dry-runs all validations of
+        /// [`ExecuteMsg::UpdateUnlockSchedules`] and returns a result for every account
+        #[returns(Vec<ScheduleUpdateValidation>)]
+        ValidateScheduleUpdates {
+            new_unlock_schedules: Vec<(String, Schedule)>,
+            /// ID of the allocations to update (the first allocation of every account by default)
+            allocation_id: Option<u64>,
+        },
+        /// Revocation returns the revocation record of an allocation if it was revoked
+        #[returns(Option<Revocation>)]
+        Revocation {
+            /// Account whose revocation record we query
+            account: String,
+            /// Allocation ID (the first allocation of the account by default)
+            allocation_id: Option<u64>,
+        },
+        /// AllocationAtHeight returns the aggregated allocation of an account at a specific block height
+        #[returns(AllocationResponse)]
+        AllocationAtHeight { account: String, height: u64 },
+        /// VotingPower returns the voting power of an account's locked allocations along with
+        /// the voting power delegated to the account
+        #[returns(Uint128)]
+        VotingPower {
+            account: String,
+            /// Block height at which the voting power is queried (the current state by default)
+            height: Option<u64>,
+        },
+        /// VotingDelegate returns the account to which an account delegated its voting power
+        #[returns(Option<Addr>)]
+        VotingDelegate { account: String },
+        /// PendingDecrease returns the queued decrease of an allocation if there is one
+        #[returns(Option<PendingDecrease>)]
+        PendingDecrease {
+            /// Account whose queued decrease we query
+            account: String,
+            /// Allocation ID (the first allocation of the account by default)
+            allocation_id: Option<u64>,
+        },
     }
 
     pub type ConfigResponse = Config;
 
+    /// This enum describes the filters available in the Allocations query.
+    #[cw_serde]
+    pub enum AllocationsFilter {
+        /// Accounts which still have locked ASTRO
+        Active,
+        /// Accounts whose allocations are fully unlocked
+        FullyUnlocked,
+        /// Accounts with at least one revoked allocation
+        Revoked,
+    }
+
+    /// This enum describes the sorting orders available in the Allocations query.
+    #[cw_serde]
+    pub enum AllocationsOrder {
+        /// Ascending order of account addresses
+        Account,
+        /// Descending order of the remaining (not yet withdrawn) ASTRO amount
+        RemainingAmount,
+    }
+
+    /// This structure describes a pending receiver change proposal.
+    #[cw_serde]
+    pub struct ReceiverProposalResponse {
+        /// The proposed receiver
+        pub proposed_receiver: Addr,
+        /// Timestamp after which the proposal can no longer be claimed. Proposals created
+        /// before proposal expiry was introduced never expire
+        pub expires_at: Option<u64>,
+    }
+
+    /// This structure describes the result of a dry-run unlock schedule update for an account.
+    #[cw_serde]
+    pub struct ScheduleUpdateValidation {
+        /// Account whose unlock schedule is updated
+        pub account: String,
+        /// The error the update would fail with. `None` if the update is valid
+        pub error: Option<String>,
+    }
+
     /// This structure stores the parameters used to return the response when querying for an allocation data.
     #[cw_serde]
     pub struct AllocationResponse {
@@ -245,6 +629,48 @@ pub mod msg {
         pub astro_to_withdraw: Uint128,
     }
 
+    /// This structure stores the parameters used to return the protocol-wide unlock status at a timestamp.
+    #[cw_serde]
+    pub struct TotalUnlockedResponse {
+        /// Amount of ASTRO unlocked across all allocations (including already withdrawn ASTRO)
+        pub unlocked_amount: Uint128,
+        /// Amount of ASTRO still locked across all allocations
+        pub locked_amount: Uint128,
+    }
+
+    /// This structure describes the staking position of the contract.
+    #[cw_serde]
+    pub struct StakingStateResponse {
+        /// Amount of ASTRO staked in the staking contract
+        pub staked_astro: Uint128,
+        /// Amount of xASTRO held by the contract
+        pub xastro_amount: Uint128,
+        /// Amount of ASTRO the held xASTRO is worth
+        pub astro_value: Uint128,
+        /// Staking yield which can be claimed by the yield recipient
+        pub pending_yield: Uint128,
+    }
+
+    /// This structure describes the response returned when querying for the contract solvency.
+    #[cw_serde]
+    pub struct SolvencyResponse {
+        /// ASTRO held by the contract, including the principal staked in the staking contract
+        pub astro_balance: Uint128,
+        /// ASTRO withdrawn from all allocations so far
+        pub total_withdrawn: Uint128,
+        /// ASTRO the contract should hold according to deposits and withdrawals
+        /// (`total_astro_deposited - total_withdrawn`)
+        pub expected_balance: Uint128,
+        /// ASTRO the contract owes according to its state (`remaining_astro_tokens + unallocated_tokens`)
+        pub owed_balance: Uint128,
+        /// ASTRO held above the expected balance
+        pub surplus: Uint128,
+        /// ASTRO missing to cover the expected balance
+        pub deficit: Uint128,
+        /// ASTRO by which the expected and the owed balances differ
+        pub accounting_drift: Uint128,
+    }
+
     /// This structure stores parameters used to return the response when querying for the contract state.
     #[cw_serde]
     pub struct StateResponse {
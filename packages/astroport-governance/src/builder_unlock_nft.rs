@@ -0,0 +1,93 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128};
+
+use crate::builder_unlock::msg::AllocationResponse;
+
+/// This structure stores the main parameters for the builder unlock NFT wrapper contract.
+#[cw_serde]
+pub struct Config {
+    /// Address that's allowed to change contract parameters (the Assembly)
+    pub owner: Addr,
+    /// Builder unlock contract address
+    pub builder_unlock: Addr,
+    /// ASTRO token address
+    pub astro_token: Addr,
+    /// Whether governance sanctioned secondary transfers of wrapped allocations
+    pub transfers_enabled: bool,
+}
+
+/// This structure holds the initial parameters used to instantiate the contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The contract owner address
+    pub owner: String,
+    /// Builder unlock contract address
+    pub builder_unlock: String,
+    /// NFT collection name
+    pub name: String,
+    /// NFT collection symbol
+    pub symbol: String,
+}
+
+/// This enum describes all the execute functions available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Wrap an allocation into an NFT. The sender must have proposed a split of the allocation
+    /// in the builder unlock contract with this contract as one of the receivers
+    Wrap {
+        /// ID of the sender's allocation in the builder unlock contract (the first allocation by default)
+        allocation_id: Option<u64>,
+    },
+    /// Withdraw unlocked ASTRO of a wrapped allocation. Only the NFT owner can execute this method
+    Withdraw { token_id: String },
+    /// Transfer a wrapped allocation to another account. Only allowed when transfers are enabled
+    TransferNft { recipient: String, token_id: String },
+    /// Send a wrapped allocation to a contract and trigger an action on the receiving contract.
+    /// Only allowed when transfers are enabled
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Update contract parameters
+    UpdateConfig {
+        /// Enable or disable secondary transfers of wrapped allocations
+        transfers_enabled: Option<bool>,
+    },
+    /// Propose a new owner for the contract
+    ProposeNewOwner { new_owner: String, expires_in: u64 },
+    /// Remove the ownership transfer proposal
+    DropOwnershipProposal {},
+    /// Claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This enum describes all the queries available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Config returns the contract configuration
+    #[returns(Config)]
+    Config {},
+    /// Allocation returns the parameters and current status of a wrapped allocation
+    #[returns(AllocationResponse)]
+    Allocation { token_id: String },
+    /// Claimable returns the amount of ASTRO already withdrawn from the builder unlock contract
+    /// and not yet paid out to the owner of a wrapped allocation
+    #[returns(Uint128)]
+    Claimable { token_id: String },
+    /// OwnerOf returns the owner of a wrapped allocation
+    #[returns(Addr)]
+    OwnerOf { token_id: String },
+    /// Tokens returns the IDs of wrapped allocations owned by an account
+    #[returns(Vec<String>)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// This structure describes a migration message.
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,122 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+
+/// This structure holds the parameters used for creating an emergency committee contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Committee members. Only the Assembly can change them afterwards
+    pub members: Vec<String>,
+    /// Number of member approvals required to execute an action
+    pub threshold: u32,
+    /// Period in seconds during which a proposed action can be approved and executed
+    pub voting_period: u64,
+    /// The Generator Controller contract address
+    pub generator_controller: String,
+    /// The Assembly contract address
+    pub assembly: String,
+}
+
+/// This structure stores the main parameters for the emergency committee contract.
+#[cw_serde]
+pub struct Config {
+    /// Committee members
+    pub members: Vec<Addr>,
+    /// Number of member approvals required to execute an action
+    pub threshold: u32,
+    /// Period in seconds during which a proposed action can be approved and executed
+    pub voting_period: u64,
+    /// The Generator Controller contract address
+    pub generator_controller: Addr,
+    /// The Assembly contract address
+    pub assembly: Addr,
+}
+
+/// This enum describes the only actions the committee is allowed to execute.
+#[cw_serde]
+pub enum EmergencyAction {
+    /// Freeze pools tuning in the Generator Controller
+    PauseTuning {},
+    /// Stop accepting new proposals and votes in the Assembly
+    PauseAssembly {},
+    /// Resume accepting new proposals and votes in the Assembly
+    UnpauseAssembly {},
+    /// Reject a passed Assembly proposal before it is executed
+    VetoProposal { proposal_id: u64 },
+}
+
+/// This enum describes all execute functions available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Propose an emergency action. The proposer approves it automatically
+    /// ## Executor
+    /// Only committee members can execute this
+    Propose { action: EmergencyAction },
+    /// Approve a proposed action
+    /// ## Executor
+    /// Only committee members can execute this
+    Approve { proposal_id: u64 },
+    /// Execute an action once it has enough approvals. Only approvals of current members count
+    /// ## Executor
+    /// Only committee members can execute this
+    Execute { proposal_id: u64 },
+    /// Rotate committee members and update the threshold
+    /// ## Executor
+    /// Only the Assembly contract (through a successful proposal) can execute this
+    UpdateMembers {
+        /// Members to add
+        add: Vec<String>,
+        /// Members to remove
+        remove: Vec<String>,
+        /// The new number of member approvals required to execute an action
+        threshold: Option<u32>,
+    },
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This enum describes all the queries available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the contract configuration
+    #[returns(Config)]
+    Config {},
+    /// Return a proposed action
+    #[returns(CommitteeProposal)]
+    Proposal { proposal_id: u64 },
+    /// Return a page of proposed actions
+    #[returns(Vec<CommitteeProposal>)]
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// This enum describes the state of a proposed action.
+#[cw_serde]
+pub enum CommitteeProposalStatus {
+    /// The action is collecting approvals
+    Open,
+    /// The action was executed
+    Executed,
+}
+
+/// This structure describes an emergency action proposed by a committee member.
+#[cw_serde]
+pub struct CommitteeProposal {
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// The proposed action
+    pub action: EmergencyAction,
+    /// Member who proposed the action
+    pub proposer: Addr,
+    /// Members who approved the action
+    pub approvals: Vec<Addr>,
+    /// Timestamp after which the action can't be approved or executed
+    pub expires_at: u64,
+    /// Proposal status
+    pub status: CommitteeProposalStatus,
+}
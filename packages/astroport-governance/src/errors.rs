@@ -0,0 +1,45 @@
+use cosmwasm_std::StdError;
+use std::fmt::Display;
+use thiserror::Error;
+
+/// This enum describes errors shared by the governance contracts. Every contract error type
+/// wraps it, so the same failure has the same shape in each contract.
+#[derive(Error, Debug, PartialEq)]
+pub enum CommonError {
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The contract is paused")]
+    Paused {},
+
+    #[error("Invalid {name}: {value}. It must be between {min} and {max}")]
+    InvalidParameter {
+        name: String,
+        value: String,
+        min: String,
+        max: String,
+    },
+}
+
+impl CommonError {
+    /// Returns an [`CommonError::InvalidParameter`] error for a value that is out of the `min..=max` bounds.
+    pub fn invalid_parameter(
+        name: impl Into<String>,
+        value: impl Display,
+        min: impl Display,
+        max: impl Display,
+    ) -> Self {
+        CommonError::InvalidParameter {
+            name: name.into(),
+            value: value.to_string(),
+            min: min.to_string(),
+            max: max.to_string(),
+        }
+    }
+}
+
+impl From<CommonError> for StdError {
+    fn from(err: CommonError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
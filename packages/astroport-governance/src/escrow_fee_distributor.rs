@@ -1,7 +1,11 @@
+use crate::voting_escrow::CheckpointHookMsg;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use cw20::Cw20ReceiveMsg;
 
+/// The maximum amount of different native denoms that can be distributed in a single period
+pub const MAX_NATIVE_DENOMS: usize = 10;
+
 /// This structure describes the basic settings for creating a contract.
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -15,6 +19,12 @@ pub struct InstantiateMsg {
     pub claim_many_limit: Option<u64>,
     /// Whether reward claiming is disabled
     pub is_claim_disabled: Option<bool>,
+    /// ASTRO staking contract address used to convert claimed ASTRO into xASTRO
+    pub astro_staking: Option<String>,
+    /// Amount of periods after which unclaimed rewards can be recycled
+    pub claim_expiry_periods: Option<u64>,
+    /// Max amount of periods processed in a single claim
+    pub max_periods_per_claim: Option<u64>,
 }
 
 /// This structure describes the execute messages available in the contract.
@@ -33,9 +43,13 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
     /// Claim claims staking rewards for a single staker and sends them to the specified recipient
     Claim {
+        /// Address that receives claimed rewards. Defaults to the sender
         recipient: Option<String>,
+        /// Max amount of periods to claim rewards for in a single call
         max_periods: Option<u64>,
     },
+    /// ClaimAndLock claims staking rewards for the sender, stakes them for xASTRO and deposits it in the sender's vxASTRO lock
+    ClaimAndLock {},
     /// ClaimMany claims staking rewards for multiple addresses in a single call
     ClaimMany { receivers: Vec<String> },
     /// UpdateConfig updates the contract configuration
@@ -44,7 +58,22 @@ pub enum ExecuteMsg {
         claim_many_limit: Option<u64>,
         /// Whether reward claiming is disabled
         is_claim_disabled: Option<bool>,
+        /// ASTRO staking contract used to stake claimed rewards in ClaimAndLock
+        astro_staking: Option<String>,
+        /// Amount of periods after which unclaimed rewards can be recycled
+        claim_expiry_periods: Option<u64>,
+        /// Max amount of periods processed in a single claim
+        max_periods_per_claim: Option<u64>,
+        /// Native denoms accepted by ReceiveTokens. Replaces the current list
+        native_denoms: Option<Vec<String>>,
     },
+    /// RecycleExpired moves expired unclaimed rewards to the current period distribution
+    RecycleExpired {},
+    /// CheckpointHook receives stakers' voting power checkpoints from the voting escrow contract
+    CheckpointHook(CheckpointHookMsg),
+    /// ReceiveTokens adds native coins sent along with the message to the current period distribution.
+    /// Only denoms allowed by the owner are accepted
+    ReceiveTokens {},
     /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
     Receive(Cw20ReceiveMsg),
 }
@@ -65,6 +94,18 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u64>,
     },
+    /// SimulateClaim returns the reward amount a staker would receive by claiming now along with the per week breakdown
+    #[returns(SimulateClaimResponse)]
+    SimulateClaim {
+        user: String,
+        max_periods: Option<u64>,
+    },
+    /// PeriodStats returns deposited and claimed rewards along with the total vxASTRO supply for every funded period
+    #[returns(Vec<PeriodStatsResponse>)]
+    PeriodStats {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
 }
 
 /// This structure describes the parameters returned when querying for the contract configuration.
@@ -80,6 +121,40 @@ pub struct ConfigResponse {
     pub claim_many_limit: u64,
     /// Wthether reward claiming is disabled
     pub is_claim_disabled: bool,
+    /// ASTRO staking contract address
+    pub astro_staking: Option<Addr>,
+    /// Amount of periods after which unclaimed rewards can be recycled
+    pub claim_expiry_periods: Option<u64>,
+    /// Max amount of periods processed in a single claim
+    pub max_periods_per_claim: u64,
+    /// Native denoms accepted as rewards
+    pub native_denoms: Vec<String>,
+}
+
+/// This structure describes the response returned when simulating a reward claim.
+#[cw_serde]
+pub struct SimulateClaimResponse {
+    /// Total amount of ASTRO that can be claimed
+    pub amount: Uint128,
+    /// ASTRO rewards for every claimed period
+    pub rewards_per_period: Vec<(u64, Uint128)>,
+    /// Native coins that can be claimed
+    pub native_amount: Vec<Coin>,
+    /// Amount of claimable periods left after this claim
+    pub remaining_periods: u64,
+}
+
+/// This structure describes reward distribution statistics for a specific period.
+#[cw_serde]
+pub struct PeriodStatsResponse {
+    /// Period number
+    pub period: u64,
+    /// Total amount of ASTRO deposited for the period
+    pub deposited: Uint128,
+    /// Total amount of ASTRO claimed for the period
+    pub claimed: Uint128,
+    /// Total vxASTRO supply used to calculate rewards for the period
+    pub total_voting_power: Uint128,
 }
 
 /// This structure describes a migration message.
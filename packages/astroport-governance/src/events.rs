@@ -0,0 +1,70 @@
+use cosmwasm_std::{attr, Addr, Attribute, Uint128};
+
+use crate::assembly::{ProposalStatus, ProposalVoteOption};
+
+/// The name of the executed action
+pub const ACTION: &str = "action";
+/// Proposal identifier
+pub const PROPOSAL_ID: &str = "proposal_id";
+/// The address of the proposal submitter
+pub const SUBMITTER: &str = "submitter";
+/// The block at which proposal voting ends
+pub const PROPOSAL_END_HEIGHT: &str = "proposal_end_height";
+/// The status of a proposal after voting ended
+pub const PROPOSAL_RESULT: &str = "proposal_result";
+/// The address of the voter
+pub const VOTER: &str = "voter";
+/// The selected vote option
+pub const VOTE: &str = "vote";
+/// The voting power used to vote
+pub const VOTING_POWER: &str = "voting_power";
+
+/// Returns the `action` attribute.
+pub fn action(action: &str) -> Attribute {
+    attr(ACTION, action)
+}
+
+/// Returns the `action` and `proposal_id` attributes of an action executed on a proposal.
+pub fn proposal_attrs(action_name: &str, proposal_id: u64) -> Vec<Attribute> {
+    vec![
+        action(action_name),
+        attr(PROPOSAL_ID, proposal_id.to_string()),
+    ]
+}
+
+/// Returns the attributes of a newly submitted proposal.
+pub fn submit_proposal_attrs(
+    submitter: &Addr,
+    proposal_id: u64,
+    end_height: u64,
+) -> Vec<Attribute> {
+    vec![
+        action("submit_proposal"),
+        attr(SUBMITTER, submitter),
+        attr(PROPOSAL_ID, proposal_id.to_string()),
+        attr(PROPOSAL_END_HEIGHT, end_height.to_string()),
+    ]
+}
+
+/// Returns the attributes of a vote cast on a proposal.
+pub fn cast_vote_attrs(
+    proposal_id: u64,
+    voter: &Addr,
+    vote: &ProposalVoteOption,
+    voting_power: Uint128,
+) -> Vec<Attribute> {
+    let mut attrs = proposal_attrs("cast_vote", proposal_id);
+    attrs.extend([
+        attr(VOTER, voter),
+        attr(VOTE, vote.to_string()),
+        attr(VOTING_POWER, voting_power),
+    ]);
+    attrs
+}
+
+/// Returns the attributes of a proposal whose voting period ended.
+pub fn end_proposal_attrs(proposal_id: u64, result: &ProposalStatus) -> Vec<Attribute> {
+    let mut attrs = proposal_attrs("end_proposal", proposal_id);
+    attrs.push(attr(PROPOSAL_RESULT, result.to_string()));
+    attrs
+}
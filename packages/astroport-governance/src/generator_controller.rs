@@ -27,7 +27,13 @@ pub enum ExecuteMsg {
     /// Removes all votes applied by blacklisted voters
     KickBlacklistedVoters { blacklisted_voters: Vec<String> },
     /// Vote allows a vxASTRO holder to cast votes on which generators should get ASTRO emissions in the next epoch
-    Vote { votes: Vec<(String, u16)> },
+    Vote {
+        votes: Vec<(String, u16)>,
+        /// Whether votes should be refreshed with the user's latest vxASTRO voting power until changed
+        persist: Option<bool>,
+    },
+    /// RefreshVotes re-applies persistent votes of the specified users using their latest vxASTRO voting power
+    RefreshVotes { voters: Vec<String> },
     /// TunePools transforms the latest vote distribution into alloc_points which are then applied to ASTRO generators
     TunePools {},
     UpdateConfig {
@@ -39,6 +45,8 @@ pub enum ExecuteMsg {
         main_pool_min_alloc: Option<Decimal>,
         /// Should the main pool be removed or not? If the variable is omitted then the pool will be kept.
         remove_main_pool: Option<bool>,
+        /// Emergency committee address which is allowed to pause pools tuning
+        emergency_committee: Option<String>,
     },
     /// ChangePoolsLimit changes the max amount of pools that can be voted at once to receive ASTRO emissions
     ChangePoolsLimit { limit: u64 },
@@ -58,15 +66,40 @@ pub enum ExecuteMsg {
         add: Option<Vec<String>>,
         remove: Option<Vec<String>>,
     },
+    /// DelegateGaugeVotes allows a vxASTRO holder to delegate their emission voting power to a strategist.
+    /// This delegation is independent from Assembly vote delegation
+    DelegateGaugeVotes { strategist: String },
+    /// MigratePoolVotes moves all votes and the whitelist entry from a replaced LP token to a new one
+    MigratePoolVotes { from_lp: String, to_lp: String },
+    /// PauseTuning freezes pools tuning while still allowing vote changes.
+    /// Can only be called by the owner (Assembly) or the emergency committee
+    PauseTuning {},
+    /// UnpauseTuning resumes pools tuning. Can only be called by the owner (Assembly)
+    UnpauseTuning {},
+    /// UndelegateGaugeVotes revokes the strategist's right to vote on behalf of the vxASTRO holder
+    UndelegateGaugeVotes {},
+    /// VoteOnBehalf allows a strategist to cast votes using a delegator's vxASTRO voting power
+    VoteOnBehalf {
+        voter: String,
+        votes: Vec<(String, u16)>,
+        persist: Option<bool>,
+    },
+    /// PruneVotesHook removes all votes of a vxASTRO holder whose lock expired or who was blacklisted.
+    /// Can only be called by the voting escrow contract
+    PruneVotesHook { voter: String },
 }
 
 /// This structure describes the query messages available in the contract.
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    /// UserInfo returns information about a voter and the generators they voted for
+    /// UserInfo returns information about a voter and the generators they voted for.
+    /// The voting power is decayed by the user's slope to the current period
     #[returns(UserInfoResponse)]
     UserInfo { user: String },
+    /// AvailableVotePower returns the user's vxASTRO voting power along with allocated and remaining basic points
+    #[returns(AvailableVotePowerResponse)]
+    AvailableVotePower { user: String },
     /// TuneInfo returns information about the latest generators that were voted to receive ASTRO emissions
     #[returns(GaugeInfoResponse)]
     TuneInfo {},
@@ -79,6 +112,36 @@ pub enum QueryMsg {
     /// PoolInfo returns the voting power allocated to a specific pool (generator) at a specific period
     #[returns(VotedPoolInfoResponse)]
     PoolInfoAtPeriod { pool_addr: String, period: u64 },
+    /// SimulateTune returns the allocation points pools would receive if tuning was executed now
+    #[returns(GaugeInfoResponse)]
+    SimulateTune {},
+    /// GaugeDelegation returns the strategist a vxASTRO holder delegated their emission voting power to
+    #[returns(GaugeDelegationResponse)]
+    GaugeDelegation { user: String },
+    /// UserVoteAtPeriod returns the user's contribution to a specific pool's voting power at a specific period
+    #[returns(VotedPoolInfoResponse)]
+    UserVoteAtPeriod {
+        user: String,
+        pool_addr: String,
+        period: u64,
+    },
+    /// IsTuningPaused returns whether pools tuning is paused
+    #[returns(bool)]
+    IsTuningPaused {},
+    /// PoolVotesAtPeriod returns the voting power persisted for a specific pool (generator) at a specific period.
+    /// Unlike PoolInfoAtPeriod it doesn't recalculate missing periods and returns None instead
+    #[returns(Option<VotedPoolInfoResponse>)]
+    PoolVotesAtPeriod { pool: String, period: u64 },
+    /// PoolPeriods returns the list of periods which have persisted voting power for a specific pool (generator)
+    #[returns(Vec<u64>)]
+    PoolPeriods {
+        pool: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// ResolvedPool returns the actual LP token address for a given pool following LP token replacements
+    #[returns(Addr)]
+    ResolvedPool { pool_addr: String },
 }
 
 /// This structure describes a migration message.
@@ -107,6 +170,28 @@ pub struct ConfigResponse {
     pub main_pool_min_alloc: Decimal,
     /// The list of pools which are eligible to receive votes
     pub whitelisted_pools: Vec<Addr>,
+    /// Emergency committee address which is allowed to pause pools tuning
+    pub emergency_committee: Option<Addr>,
+}
+
+/// This structure describes the response used to return a user's unallocated voting power.
+#[cw_serde]
+pub struct AvailableVotePowerResponse {
+    /// The user's current vxASTRO voting power
+    pub voting_power: Uint128,
+    /// Basic points allocated by the user's active votes
+    pub allocated_bps: u16,
+    /// Basic points which can still be allocated
+    pub remaining_bps: u16,
+}
+
+/// This structure describes the response used to return a user's gauge vote delegation.
+#[cw_serde]
+pub struct GaugeDelegationResponse {
+    /// The vxASTRO holder
+    pub user: Addr,
+    /// The address allowed to vote for pools on behalf of the user
+    pub strategist: Option<Addr>,
 }
 
 /// This structure describes the response used to return voting information for a specific pool (generator).
@@ -135,7 +220,7 @@ pub struct GaugeInfoResponse {
 pub struct UserInfoResponse {
     /// Last timestamp when the user voted
     pub vote_ts: u64,
-    /// The user's vxASTRO voting power
+    /// The user's vxASTRO voting power applied to votes, decayed to the current period
     pub voting_power: Uint128,
     /// The slope at which the user's voting power decays
     pub slope: Uint128,
@@ -143,4 +228,6 @@ pub struct UserInfoResponse {
     pub lock_end: u64,
     /// The vote distribution for all the generators/pools the staker picked
     pub votes: Vec<(Addr, u16)>,
+    /// Whether votes are refreshed with the user's latest vxASTRO voting power until changed
+    pub persist: bool,
 }
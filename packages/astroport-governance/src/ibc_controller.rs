@@ -0,0 +1,165 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Uint128};
+use std::ops::RangeInclusive;
+
+use crate::interchain::ForwardMetadata;
+
+/// The allowed range of IBC packet timeouts in seconds (from 1 minute to 1 week)
+pub const TIMEOUT_LIMITS: RangeInclusive<u64> = 60..=7 * 86400;
+
+/// This structure holds the parameters used for creating an IBC controller contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Assembly contract address
+    pub assembly: String,
+    /// IBC packet timeout in seconds
+    pub timeout: u64,
+}
+
+/// This structure stores the main parameters for the IBC controller contract.
+#[cw_serde]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Assembly contract address
+    pub assembly: Addr,
+    /// Default IBC packet timeout in seconds for channels without their own parameters
+    pub timeout: u64,
+}
+
+/// This enum describes all execute functions available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Send the messages of a passed proposal to a remote chain
+    /// ## Executor
+    /// Only the Assembly contract can execute this.
+    IbcExecuteProposal {
+        /// IBC channel to the remote chain
+        channel_id: String,
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Messages to execute on the remote chain
+        messages: Vec<CosmosMsg>,
+        /// Route through intermediate chains if the remote chain has no direct channel to the hub
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        forward: Option<ForwardMetadata>,
+    },
+    /// Send the messages of a proposal again after its packet timed out
+    /// ## Executor
+    /// Only the owner or the Assembly contract can execute this.
+    ResendProposal {
+        /// Proposal identifier
+        proposal_id: u64,
+    },
+    /// Set IBC parameters for a channel or remove them to fall back to the defaults
+    /// ## Executor
+    /// Only the Assembly contract can execute this.
+    SetChannelParams {
+        /// IBC channel to the remote chain
+        channel_id: String,
+        /// The new channel parameters
+        params: Option<ChannelParams>,
+    },
+    /// Update contract parameters
+    /// ## Executor
+    /// Only the owner can execute this.
+    UpdateConfig {
+        /// The new Assembly contract address
+        new_assembly: Option<String>,
+        /// The new IBC packet timeout in seconds
+        new_timeout: Option<u64>,
+    },
+    /// Propose a new owner for the contract
+    ProposeNewOwner { new_owner: String, expires_in: u64 },
+    /// Remove the ownership transfer proposal
+    DropOwnershipProposal {},
+    /// Claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This enum describes all the queries available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the contract configuration
+    #[returns(Config)]
+    Config {},
+    /// Return the state of the packet sent for the specified proposal
+    #[returns(ProposalPacket)]
+    ProposalState { proposal_id: u64 },
+    /// Return the IBC parameters set for a channel
+    #[returns(Option<ChannelParams>)]
+    ChannelParams { channel_id: String },
+    /// Return the last totals synced by the outpost on the other end of a channel. The totals are
+    /// informational only
+    #[returns(Option<OutpostTotals>)]
+    OutpostTotals { channel_id: String },
+}
+
+/// This structure describes IBC parameters specific to a channel.
+#[cw_serde]
+pub struct ChannelParams {
+    /// IBC packet timeout in seconds
+    pub timeout: u64,
+    /// The maximum number of messages a proposal packet can carry
+    pub max_messages: Option<u32>,
+    /// Fee paid from the contract balance to the relayer of a proposal acknowledgement
+    pub relayer_fee: Vec<Coin>,
+}
+
+/// This enum describes the lifecycle of a proposal packet.
+#[cw_serde]
+pub enum PacketStatus {
+    /// The packet was sent and is waiting for an acknowledgement
+    Sent,
+    /// The remote chain executed the proposal messages
+    Executed,
+    /// The remote chain failed to execute the proposal messages
+    Failed { error: String },
+    /// The packet wasn't relayed before its timeout. The proposal can be resent
+    TimedOut,
+}
+
+/// This structure describes a proposal packet sent to a remote chain.
+#[cw_serde]
+pub struct ProposalPacket {
+    /// IBC channel the packet was sent to
+    pub channel_id: String,
+    /// Packet sequence. It is known once the packet is acknowledged or timed out
+    pub sequence: Option<u64>,
+    /// Timestamp at which the packet was sent
+    pub sent_at: u64,
+    /// Packet status
+    pub status: PacketStatus,
+    /// Route through intermediate chains
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward: Option<ForwardMetadata>,
+}
+
+/// This structure describes the totals an outpost synced to the hub.
+/// The totals are informational only. They are not counted in Assembly proposal tallies.
+#[cw_serde]
+pub struct OutpostTotals {
+    /// Epoch number on the outpost
+    pub epoch: u64,
+    /// Outpost block height the total was taken at
+    pub height: u64,
+    /// Total xASTRO staked on the outpost
+    pub total_voting_power: Uint128,
+}
+
+/// This enum describes the acknowledgement a remote chain returns for a proposal packet.
+#[cw_serde]
+pub enum IbcAckResult {
+    /// The messages were executed
+    Ok(Binary),
+    /// The messages failed with the specified error
+    Error(String),
+}
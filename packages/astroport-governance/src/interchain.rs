@@ -0,0 +1,174 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_slice, to_binary, Binary, CosmosMsg, StdError, StdResult, Uint128};
+
+use crate::assembly::ProposalVoteOption;
+
+/// The packet version this package produces
+pub const PACKET_VERSION: u32 = 1;
+
+/// The oldest packet version this package can still read
+pub const MIN_SUPPORTED_PACKET_VERSION: u32 = 1;
+
+/// IBC channel version used by the hub and the outposts
+pub const IBC_APP_VERSION: &str = "astroport-governance-1";
+
+/// The maximum number of intermediate chains a packet can be forwarded through
+pub const MAX_FORWARD_HOPS: usize = 4;
+
+/// Returns the packet version both sides of a channel understand given the counterparty's
+/// latest supported version.
+pub fn negotiate_version(counterparty_version: u32) -> StdResult<u32> {
+    if counterparty_version < MIN_SUPPORTED_PACKET_VERSION {
+        return Err(StdError::generic_err(format!(
+            "Packet version {counterparty_version} is not supported. Minimum supported version is {MIN_SUPPORTED_PACKET_VERSION}"
+        )));
+    }
+
+    Ok(counterparty_version.min(PACKET_VERSION))
+}
+
+/// This structure wraps every interchain governance message together with its packet version.
+#[cw_serde]
+pub struct Envelope<T> {
+    /// Packet version used to serialize the message
+    pub version: u32,
+    /// The wrapped message
+    pub msg: T,
+    /// Route through intermediate chains. Packets without it are handled by the receiving chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward: Option<ForwardMetadata>,
+}
+
+/// This structure describes a packet-forward style route for chains without a direct channel to the hub.
+#[cw_serde]
+pub struct ForwardMetadata {
+    /// Channels the packet is forwarded over on every intermediate chain, in order
+    pub hops: Vec<String>,
+}
+
+impl ForwardMetadata {
+    /// Checks that the route has between 1 and [`MAX_FORWARD_HOPS`] non-empty hops
+    pub fn validate(&self) -> StdResult<()> {
+        if self.hops.is_empty() || self.hops.len() > MAX_FORWARD_HOPS {
+            return Err(StdError::generic_err(format!(
+                "Forward route must have between 1 and {MAX_FORWARD_HOPS} hops"
+            )));
+        }
+
+        if self.hops.iter().any(|hop| hop.is_empty()) {
+            return Err(StdError::generic_err("Forward hop channel can't be empty"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Envelope<T> {
+    /// Wraps the message using the current packet version
+    pub fn new(msg: T) -> Self {
+        Self {
+            version: PACKET_VERSION,
+            msg,
+            forward: None,
+        }
+    }
+
+    /// Sets the route the packet takes through intermediate chains
+    pub fn with_forward(mut self, forward: Option<ForwardMetadata>) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    /// Used by intermediate chains. Returns the channel the envelope must be forwarded over
+    /// together with the envelope to send, or gives the envelope back if it reached its destination.
+    pub fn next_hop(mut self) -> Result<(String, Self), Self> {
+        match self.forward.take() {
+            Some(mut forward) if !forward.hops.is_empty() => {
+                let channel_id = forward.hops.remove(0);
+                if !forward.hops.is_empty() {
+                    self.forward = Some(forward);
+                }
+                Ok((channel_id, self))
+            }
+            _ => Err(self),
+        }
+    }
+
+    /// Serializes the envelope into IBC packet data
+    pub fn to_packet_data(&self) -> StdResult<Binary> {
+        to_binary(self)
+    }
+
+    /// Deserializes IBC packet data into an envelope and checks that its version is supported
+    pub fn from_packet_data(data: &[u8]) -> StdResult<Self> {
+        let envelope: Self = from_slice(data)?;
+
+        if !(MIN_SUPPORTED_PACKET_VERSION..=PACKET_VERSION).contains(&envelope.version) {
+            return Err(StdError::generic_err(format!(
+                "Unsupported packet version {}",
+                envelope.version
+            )));
+        }
+
+        Ok(envelope)
+    }
+}
+
+/// This enum describes messages sent from the hub Assembly to the outposts.
+#[cw_serde]
+pub enum HubToOutpostMsg {
+    /// Announce a new proposal so that outpost stakers can vote on it
+    ProposalBroadcast {
+        /// Proposal ID in the hub Assembly
+        proposal_id: u64,
+        /// Proposal title
+        title: String,
+        /// Timestamp at which voting power snapshots are taken
+        start_time: u64,
+        /// Block height on the hub at which voting ends
+        end_block: u64,
+    },
+    /// Execute the messages of a passed proposal on the outpost chain
+    ExecuteProposal {
+        /// Proposal ID in the hub Assembly
+        proposal_id: u64,
+        /// Messages to execute
+        messages: Vec<CosmosMsg>,
+    },
+}
+
+/// This enum describes messages sent from the outposts to the hub Assembly.
+#[cw_serde]
+pub enum OutpostToHubMsg {
+    /// Relay a vote cast on an outpost
+    VoteRelay {
+        /// Proposal ID in the hub Assembly
+        proposal_id: u64,
+        /// Voter address on the outpost chain
+        voter: String,
+        /// Voted option
+        vote_option: ProposalVoteOption,
+        /// Voting power of the voter at the proposal start time
+        voting_power: Uint128,
+    },
+    /// Report the result of executing proposal messages on the outpost chain
+    ExecutionResult {
+        /// Proposal ID in the hub Assembly
+        proposal_id: u64,
+        /// Whether the messages were executed successfully
+        success: bool,
+        /// Error message if the execution failed
+        error: Option<String>,
+    },
+    /// Sync the total xASTRO staked on an outpost for the given epoch
+    TotalsSync {
+        /// Epoch number on the outpost
+        epoch: u64,
+        /// Outpost block height the total was taken at
+        height: u64,
+        /// Total xASTRO staked on the outpost
+        total_voting_power: Uint128,
+    },
+}
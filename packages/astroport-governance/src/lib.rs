@@ -1,12 +1,37 @@
 pub mod assembly;
+#[cfg(feature = "full")]
+pub mod bribes;
+#[cfg(feature = "full")]
 pub mod builder_unlock;
+#[cfg(feature = "full")]
+pub mod builder_unlock_nft;
+pub mod emergency_committee;
+pub mod errors;
+#[cfg(feature = "full")]
 pub mod escrow_fee_distributor;
+pub mod events;
 pub mod generator_controller;
+pub mod ibc_controller;
+pub mod interchain;
+#[cfg(feature = "full")]
+pub mod migration;
+#[cfg(feature = "mock-querier")]
+pub mod mock_querier;
 pub mod nft;
+#[cfg(feature = "full")]
+pub mod outpost;
+#[cfg(feature = "full")]
+pub mod pagination;
+#[cfg(feature = "full")]
+pub mod queriers;
+#[cfg(feature = "full")]
+pub mod treasury;
 pub mod utils;
+#[cfg(feature = "full")]
 pub mod voting_escrow;
 pub mod voting_escrow_delegation;
 
+#[cfg(feature = "full")]
 pub use astroport;
 
 // Default pagination constants
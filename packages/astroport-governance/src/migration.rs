@@ -0,0 +1,78 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Order, Response, StdResult, Storage};
+use cw2::{get_contract_version, ContractVersion};
+use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
+
+/// Loads the stored contract version. Returns `None` if the stored contract name is not
+/// `contract_name` or the stored version is not one of `supported_versions`.
+pub fn load_migratable_version(
+    storage: &dyn Storage,
+    contract_name: &str,
+    supported_versions: &[&str],
+) -> StdResult<Option<ContractVersion>> {
+    let version = get_contract_version(storage)?;
+
+    if version.contract == contract_name && supported_versions.contains(&version.version.as_str()) {
+        Ok(Some(version))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Re-serializes entries of a legacy [`Map`] into a new layout.
+///
+/// * **legacy** map with the old storage layout. Use a `Map` with the old namespace and value type.
+///
+/// * **start_after** key after which to continue. Needed when the new layout keeps the legacy
+/// namespace, so that already migrated entries are skipped.
+///
+/// * **limit** max amount of entries to process. All entries are processed if not specified.
+///
+/// * **migrate** function that stores an entry in the new layout. It should remove the entry from
+/// the legacy map if the new layout uses a different namespace, so that the next batch
+/// starts from the remaining entries.
+///
+/// Returns the amount of processed entries.
+pub fn migrate_map<'a, K, T, F>(
+    storage: &mut dyn Storage,
+    legacy: &Map<'a, K, T>,
+    start_after: Option<K>,
+    limit: Option<usize>,
+    mut migrate: F,
+) -> StdResult<usize>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    T: Serialize + DeserializeOwned,
+    F: FnMut(&mut dyn Storage, K::Output, T) -> StdResult<()>,
+{
+    let entries = legacy
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(usize::MAX))
+        .collect::<StdResult<Vec<_>>>()?;
+    let migrated = entries.len();
+
+    for (key, value) in entries {
+        migrate(storage, key, value)?;
+    }
+
+    Ok(migrated)
+}
+
+/// Returns the response of a successful migration.
+pub fn migration_response(
+    previous: &ContractVersion,
+    contract_name: &str,
+    contract_version: &str,
+) -> Response {
+    Response::new()
+        .add_attribute("previous_contract_name", &previous.contract)
+        .add_attribute("previous_contract_version", &previous.version)
+        .add_attribute("new_contract_name", contract_name)
+        .add_attribute("new_contract_version", contract_version)
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Binary, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, StdError, StdResult, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::BalanceResponse;
+
+use crate::astroport::xastro_token::QueryMsg as XAstroQueryMsg;
+use crate::builder_unlock::msg::{
+    AllocationResponse, QueryMsg as BuilderUnlockQueryMsg, StateResponse,
+};
+use crate::voting_escrow::{QueryMsg as VotingEscrowQueryMsg, VotingPowerResponse};
+
+/// Address of the mocked xASTRO token
+pub const XASTRO_ADDR: &str = "xastro";
+/// Address of the mocked builder unlock contract
+pub const BUILDER_UNLOCK_ADDR: &str = "builder_unlock";
+/// Address of the mocked vxASTRO contract
+pub const VXASTRO_ADDR: &str = "vxastro";
+
+/// Returns mock dependencies that answer the governance queries with a [`GovernanceMockQuerier`].
+pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, GovernanceMockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: GovernanceMockQuerier::default(),
+        custom_query_type: PhantomData,
+    }
+}
+
+/// This structure simulates the xASTRO, builder unlock and vxASTRO contracts located at
+/// [`XASTRO_ADDR`], [`BUILDER_UNLOCK_ADDR`] and [`VXASTRO_ADDR`].
+/// Historical queries ignore the requested block or time and return the current values,
+/// except for xASTRO balances set in `xastro_balances_at`.
+/// All other requests are forwarded to the base [`MockQuerier`].
+pub struct GovernanceMockQuerier {
+    pub base: MockQuerier<Empty>,
+    /// xASTRO balance of every account
+    pub xastro_balances: HashMap<String, Uint128>,
+    /// xASTRO total supply
+    pub xastro_total_supply: Uint128,
+    /// Builder unlock allocation of every account
+    pub builder_allocations: HashMap<String, AllocationResponse>,
+    /// Builder unlock voting power of every account
+    pub builder_voting_powers: HashMap<String, Uint128>,
+    /// Builder unlock contract state
+    pub builder_state: StateResponse,
+    /// vxASTRO voting power of every account
+    pub vxastro_voting_powers: HashMap<String, Uint128>,
+    /// Amount of xASTRO locked in vxASTRO by every account
+    pub vxastro_deposits: HashMap<String, Uint128>,
+    /// vxASTRO total voting power
+    pub vxastro_total_voting_power: Uint128,
+    /// xASTRO balances of accounts at specific blocks
+    pub xastro_balances_at: HashMap<(String, u64), Uint128>,
+}
+
+impl Default for GovernanceMockQuerier {
+    fn default() -> Self {
+        Self {
+            base: MockQuerier::new(&[]),
+            xastro_balances: HashMap::new(),
+            xastro_total_supply: Uint128::zero(),
+            builder_allocations: HashMap::new(),
+            builder_voting_powers: HashMap::new(),
+            builder_state: StateResponse {
+                total_astro_deposited: Uint128::zero(),
+                remaining_astro_tokens: Uint128::zero(),
+                unallocated_astro_tokens: Uint128::zero(),
+            },
+            vxastro_voting_powers: HashMap::new(),
+            vxastro_deposits: HashMap::new(),
+            vxastro_total_voting_power: Uint128::zero(),
+            xastro_balances_at: HashMap::new(),
+        }
+    }
+}
+
+impl Querier for GovernanceMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {e}"),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl GovernanceMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                let result = match contract_addr.as_str() {
+                    XASTRO_ADDR => self.query_xastro(msg),
+                    BUILDER_UNLOCK_ADDR => self.query_builder_unlock(msg),
+                    VXASTRO_ADDR => self.query_vxastro(msg),
+                    _ => return self.base.handle_query(request),
+                };
+                SystemResult::Ok(result.into())
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn query_xastro(&self, msg: &Binary) -> StdResult<Binary> {
+        match from_binary(msg)? {
+            XAstroQueryMsg::BalanceAt { address, block } => to_binary(&BalanceResponse {
+                balance: self
+                    .xastro_balances_at
+                    .get(&(address.clone(), block))
+                    .copied()
+                    .unwrap_or_else(|| amount_of(&self.xastro_balances, &address)),
+            }),
+            XAstroQueryMsg::TotalSupplyAt { .. } => to_binary(&self.xastro_total_supply),
+            _ => Err(StdError::generic_err("Unsupported xASTRO query")),
+        }
+    }
+
+    fn query_builder_unlock(&self, msg: &Binary) -> StdResult<Binary> {
+        match from_binary(msg)? {
+            BuilderUnlockQueryMsg::Allocation { account } => {
+                to_binary(&self.builder_allocations.get(&account).cloned().unwrap_or(
+                    AllocationResponse {
+                        params: Default::default(),
+                        status: Default::default(),
+                    },
+                ))
+            }
+            BuilderUnlockQueryMsg::State {} => to_binary(&self.builder_state),
+            BuilderUnlockQueryMsg::VotingPower { account, .. } => {
+                to_binary(&amount_of(&self.builder_voting_powers, &account))
+            }
+            _ => Err(StdError::generic_err("Unsupported builder unlock query")),
+        }
+    }
+
+    fn query_vxastro(&self, msg: &Binary) -> StdResult<Binary> {
+        match from_binary(msg)? {
+            VotingEscrowQueryMsg::UserVotingPower { user }
+            | VotingEscrowQueryMsg::UserVotingPowerAt { user, .. }
+            | VotingEscrowQueryMsg::UserVotingPowerAtPeriod { user, .. } => {
+                to_binary(&VotingPowerResponse {
+                    voting_power: amount_of(&self.vxastro_voting_powers, &user),
+                })
+            }
+            VotingEscrowQueryMsg::TotalVotingPower {}
+            | VotingEscrowQueryMsg::TotalVotingPowerAt { .. }
+            | VotingEscrowQueryMsg::TotalVotingPowerAtPeriod { .. } => {
+                to_binary(&VotingPowerResponse {
+                    voting_power: self.vxastro_total_voting_power,
+                })
+            }
+            VotingEscrowQueryMsg::UserDepositAtHeight { user, .. } => {
+                to_binary(&amount_of(&self.vxastro_deposits, &user))
+            }
+            _ => Err(StdError::generic_err("Unsupported vxASTRO query")),
+        }
+    }
+}
+
+fn amount_of(amounts: &HashMap<String, Uint128>, account: &str) -> Uint128 {
+    amounts.get(account).copied().unwrap_or_default()
+}
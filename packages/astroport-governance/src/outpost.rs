@@ -0,0 +1,136 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, CosmosMsg, Uint128};
+use cw20::Cw20ReceiveMsg;
+use std::ops::RangeInclusive;
+
+use crate::voting_escrow::VotingPowerResponse;
+
+/// The allowed range of sync epoch lengths in seconds (from 1 hour to 2 weeks)
+pub const EPOCH_LIMITS: RangeInclusive<u64> = 3600..=14 * 86400;
+
+/// This structure holds the parameters used for creating an outpost contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The xASTRO token address on the outpost chain
+    pub xastro_token: String,
+    /// IBC channel to the hub
+    pub hub_channel: Option<String>,
+    /// Length of a sync epoch in seconds
+    pub epoch_length: u64,
+    /// IBC packet timeout in seconds
+    pub timeout: u64,
+}
+
+/// This structure stores the main parameters for the outpost contract.
+#[cw_serde]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The xASTRO token address on the outpost chain
+    pub xastro_token: Addr,
+    /// IBC channel to the hub
+    pub hub_channel: Option<String>,
+    /// Length of a sync epoch in seconds
+    pub epoch_length: u64,
+    /// IBC packet timeout in seconds
+    pub timeout: u64,
+}
+
+/// This enum describes all execute functions available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Receive xASTRO from the outpost staking contract
+    Receive(Cw20ReceiveMsg),
+    /// Withdraw staked xASTRO
+    Unstake { amount: Uint128 },
+    /// Send the total staked xASTRO to the hub. Can be done once per epoch by anyone
+    SyncTotals {},
+    /// Execute the messages of a proposal sent by the hub
+    /// ## Executor
+    /// Only the contract itself can execute this when it receives a proposal packet.
+    ExecuteProposal {
+        /// Proposal ID in the hub Assembly
+        proposal_id: u64,
+        /// Messages to execute
+        messages: Vec<CosmosMsg>,
+    },
+    /// Update contract parameters
+    /// ## Executor
+    /// Only the owner can execute this.
+    UpdateConfig {
+        /// The new IBC channel to the hub
+        hub_channel: Option<String>,
+        /// The new sync epoch length in seconds
+        epoch_length: Option<u64>,
+        /// The new IBC packet timeout in seconds
+        timeout: Option<u64>,
+    },
+    /// Propose a new owner for the contract
+    ProposeNewOwner { new_owner: String, expires_in: u64 },
+    /// Remove the ownership transfer proposal
+    DropOwnershipProposal {},
+    /// Claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This structure describes a CW20 hook message.
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Stake xASTRO to get voting power in the hub Assembly
+    Stake {},
+}
+
+/// This enum describes all the queries available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the contract configuration
+    #[returns(Config)]
+    Config {},
+    /// Return a user's current voting power
+    #[returns(VotingPowerResponse)]
+    UserVotingPower { user: String },
+    /// Return a user's voting power at the given block height
+    #[returns(VotingPowerResponse)]
+    UserVotingPowerAt { user: String, height: u64 },
+    /// Return the current total voting power
+    #[returns(VotingPowerResponse)]
+    TotalVotingPower {},
+    /// Return the total voting power at the given block height
+    #[returns(VotingPowerResponse)]
+    TotalVotingPowerAt { height: u64 },
+    /// Return the last sync of totals with the hub
+    #[returns(Option<SyncInfo>)]
+    LastSync {},
+}
+
+/// This enum describes the state of a sync with the hub.
+#[cw_serde]
+pub enum SyncStatus {
+    /// The sync packet was sent and is waiting for an acknowledgement
+    Sent,
+    /// The hub acknowledged the sync
+    Acknowledged,
+    /// The hub rejected the sync or the packet timed out. The epoch can be synced again
+    Failed,
+}
+
+/// This structure describes a sync of totals with the hub.
+#[cw_serde]
+pub struct SyncInfo {
+    /// Epoch number
+    pub epoch: u64,
+    /// Block height the total was taken at
+    pub height: u64,
+    /// Total staked xASTRO sent to the hub
+    pub total_voting_power: Uint128,
+    /// Sync status
+    pub status: SyncStatus,
+}
@@ -0,0 +1,35 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
+
+use crate::{DEFAULT_LIMIT, MAX_LIMIT};
+
+/// Returns a page of entries from a [`Map`].
+///
+/// * **start_after** key after which to start reading. The key itself is never returned,
+/// regardless of the order.
+///
+/// * **limit** max amount of entries to return. Defaults to [`DEFAULT_LIMIT`] and is capped at [`MAX_LIMIT`].
+///
+/// * **order** the order in which entries are returned.
+pub fn paginate_map<'a, K, T>(
+    map: &Map<'a, K, T>,
+    storage: &dyn Storage,
+    start_after: Option<K>,
+    limit: Option<u32>,
+    order: Order,
+) -> StdResult<Vec<(K::Output, T)>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    T: Serialize + DeserializeOwned,
+{
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Bound::exclusive);
+    let (min, max) = match order {
+        Order::Ascending => (start_after, None),
+        Order::Descending => (None, start_after),
+    };
+
+    map.range(storage, min, max, order).take(limit).collect()
+}
@@ -0,0 +1,78 @@
+use cosmwasm_std::{QuerierWrapper, StdResult, Uint128};
+
+use crate::assembly::{Proposal, ProposalVotesResponse, QueryMsg as AssemblyQueryMsg};
+use crate::builder_unlock::msg::{AllocationResponse, QueryMsg as BuilderUnlockQueryMsg};
+use crate::voting_escrow::{
+    LockInfoResponse, QueryMsg as VotingEscrowQueryMsg, VotingPowerResponse,
+};
+
+/// Queries a proposal from the Assembly contract.
+///
+/// * **proposal_id** proposal for which we return the details.
+pub fn query_proposal(
+    querier: &QuerierWrapper,
+    assembly_addr: impl Into<String>,
+    proposal_id: u64,
+) -> StdResult<Proposal> {
+    querier.query_wasm_smart(assembly_addr, &AssemblyQueryMsg::Proposal { proposal_id })
+}
+
+/// Queries the votes cast on a proposal from the Assembly contract.
+///
+/// * **proposal_id** proposal for which we return the votes.
+pub fn query_proposal_votes(
+    querier: &QuerierWrapper,
+    assembly_addr: impl Into<String>,
+    proposal_id: u64,
+) -> StdResult<ProposalVotesResponse> {
+    querier.query_wasm_smart(
+        assembly_addr,
+        &AssemblyQueryMsg::ProposalVotes { proposal_id },
+    )
+}
+
+/// Queries the current vxASTRO voting power of a user from the voting escrow contract.
+///
+/// * **user** staker for which we return the voting power.
+pub fn query_user_voting_power(
+    querier: &QuerierWrapper,
+    escrow_addr: impl Into<String>,
+    user: impl Into<String>,
+) -> StdResult<Uint128> {
+    let vp: VotingPowerResponse = querier.query_wasm_smart(
+        escrow_addr,
+        &VotingEscrowQueryMsg::UserVotingPower { user: user.into() },
+    )?;
+
+    Ok(vp.voting_power)
+}
+
+/// Queries the lock position of a user from the voting escrow contract.
+///
+/// * **user** staker for which we return the lock position.
+pub fn query_lock_info(
+    querier: &QuerierWrapper,
+    escrow_addr: impl Into<String>,
+    user: impl Into<String>,
+) -> StdResult<LockInfoResponse> {
+    querier.query_wasm_smart(
+        escrow_addr,
+        &VotingEscrowQueryMsg::LockInfo { user: user.into() },
+    )
+}
+
+/// Queries the aggregated allocation of an account from the builder unlock contract.
+///
+/// * **account** account for which we return the allocation.
+pub fn query_allocation(
+    querier: &QuerierWrapper,
+    builder_unlock_addr: impl Into<String>,
+    account: impl Into<String>,
+) -> StdResult<AllocationResponse> {
+    querier.query_wasm_smart(
+        builder_unlock_addr,
+        &BuilderUnlockQueryMsg::Allocation {
+            account: account.into(),
+        },
+    )
+}
@@ -0,0 +1,194 @@
+use crate::astroport::asset::{Asset, AssetInfo};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, StdResult, Uint128};
+
+/// This structure holds the parameters used for creating a treasury contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Address that controls the treasury funds. Normally the Assembly contract
+    pub owner: String,
+}
+
+/// This structure stores the main parameters for the treasury contract.
+#[cw_serde]
+pub struct Config {
+    /// Address that controls the treasury funds
+    pub owner: Addr,
+}
+
+/// This enum describes all execute functions available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Send treasury funds to a recipient. Funds owed to budgets and streams can't be spent
+    /// ## Executor
+    /// Only the owner can execute this.
+    Spend { asset: Asset, recipient: String },
+    /// Create a budget stream paying a fixed amount every period until the end time
+    /// ## Executor
+    /// Only the owner can execute this.
+    CreateBudget {
+        /// Address that can claim the budget
+        recipient: String,
+        /// Asset paid from the treasury
+        asset_info: AssetInfo,
+        /// Amount unlocked at the end of every period
+        amount_per_period: Uint128,
+        /// Period length in seconds
+        period: u64,
+        /// Timestamp at which the first period starts. Defaults to the current block time
+        start_time: Option<u64>,
+        /// Timestamp after which no more periods are unlocked
+        end_time: u64,
+    },
+    /// Stop a budget. Periods that already ended stay claimable
+    /// ## Executor
+    /// Only the owner can execute this.
+    CancelBudget { budget_id: u64 },
+    /// Claim the unlocked amount of a budget
+    /// ## Executor
+    /// Only the budget recipient can execute this.
+    ClaimBudget { budget_id: u64 },
+    /// Create a stream paying the amount continuously every second between the start and end time
+    /// ## Executor
+    /// Only the owner can execute this.
+    CreateStream {
+        /// Address that can withdraw from the stream
+        recipient: String,
+        /// Asset paid from the treasury
+        asset_info: AssetInfo,
+        /// Total amount paid over the stream duration
+        amount: Uint128,
+        /// Timestamp at which the stream starts. Defaults to the current block time
+        start_time: Option<u64>,
+        /// Timestamp at which the whole amount is streamed
+        end_time: u64,
+    },
+    /// Stop a stream. The amount streamed before cancellation stays withdrawable
+    /// ## Executor
+    /// Only the owner can execute this.
+    CancelStream { stream_id: u64 },
+    /// Withdraw from a stream. Withdraws everything available if the amount isn't specified
+    /// ## Executor
+    /// Only the stream recipient can execute this.
+    WithdrawStream {
+        stream_id: u64,
+        amount: Option<Uint128>,
+    },
+    /// Propose a new owner for the contract
+    ProposeNewOwner { new_owner: String, expires_in: u64 },
+    /// Remove the ownership transfer proposal
+    DropOwnershipProposal {},
+    /// Claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This enum describes all the queries available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the contract configuration
+    #[returns(Config)]
+    Config {},
+    /// Return a budget
+    #[returns(Budget)]
+    Budget { budget_id: u64 },
+    /// Return a page of budgets
+    #[returns(Vec<Budget>)]
+    Budgets {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Return the amount a budget recipient can claim right now
+    #[returns(Uint128)]
+    Claimable { budget_id: u64 },
+    /// Return a stream
+    #[returns(Stream)]
+    Stream { stream_id: u64 },
+    /// Return a page of streams
+    #[returns(Vec<Stream>)]
+    Streams {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Return the amount a stream recipient can withdraw right now
+    #[returns(Uint128)]
+    Withdrawable { stream_id: u64 },
+}
+
+/// This structure describes a budget stream.
+#[cw_serde]
+pub struct Budget {
+    /// Budget identifier
+    pub budget_id: u64,
+    /// Address that can claim the budget
+    pub recipient: Addr,
+    /// Asset paid from the treasury
+    pub asset_info: AssetInfo,
+    /// Amount unlocked at the end of every period
+    pub amount_per_period: Uint128,
+    /// Period length in seconds
+    pub period: u64,
+    /// Timestamp at which the first period starts
+    pub start_time: u64,
+    /// Timestamp after which no more periods are unlocked
+    pub end_time: u64,
+    /// Amount already claimed
+    pub claimed: Uint128,
+}
+
+impl Budget {
+    /// Returns the amount unlocked by the periods that ended before the specified time.
+    pub fn unlocked(&self, time: u64) -> StdResult<Uint128> {
+        let periods = time.min(self.end_time).saturating_sub(self.start_time) / self.period;
+        Ok(self.amount_per_period.checked_mul(periods.into())?)
+    }
+
+    /// Returns the amount the recipient can claim at the specified time.
+    pub fn claimable(&self, time: u64) -> StdResult<Uint128> {
+        Ok(self.unlocked(time)?.saturating_sub(self.claimed))
+    }
+}
+
+/// This structure describes a continuous payment stream.
+#[cw_serde]
+pub struct Stream {
+    /// Stream identifier
+    pub stream_id: u64,
+    /// Address that can withdraw from the stream
+    pub recipient: Addr,
+    /// Asset paid from the treasury
+    pub asset_info: AssetInfo,
+    /// Total amount paid over the stream duration
+    pub amount: Uint128,
+    /// Timestamp at which the stream starts
+    pub start_time: u64,
+    /// Timestamp at which the whole amount is streamed
+    pub end_time: u64,
+    /// Amount already withdrawn
+    pub withdrawn: Uint128,
+    /// Timestamp at which the stream was cancelled
+    pub cancelled_at: Option<u64>,
+}
+
+impl Stream {
+    /// Returns the amount streamed up to the specified time.
+    pub fn streamed(&self, time: u64) -> Uint128 {
+        let stop_time = self.cancelled_at.map_or(self.end_time, |cancelled_at| {
+            cancelled_at.min(self.end_time)
+        });
+        let elapsed = time.min(stop_time).saturating_sub(self.start_time);
+
+        self.amount
+            .multiply_ratio(elapsed, self.end_time - self.start_time)
+    }
+
+    /// Returns the amount the recipient can withdraw at the specified time.
+    pub fn withdrawable(&self, time: u64) -> Uint128 {
+        self.streamed(time).saturating_sub(self.withdrawn)
+    }
+}
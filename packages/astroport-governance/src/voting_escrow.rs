@@ -1,5 +1,6 @@
 use crate::voting_escrow::QueryMsg::{
-    LockInfo, TotalVotingPower, TotalVotingPowerAt, UserVotingPower, UserVotingPowerAt,
+    LockInfo, TotalVotingPower, TotalVotingPowerAt, TotalVotingPowerAtPeriod, UserVotingPower,
+    UserVotingPowerAt,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Decimal, QuerierWrapper, StdResult, Uint128};
@@ -18,6 +19,12 @@ pub const DEFAULT_LIMIT: u32 = 10;
 
 pub const DEFAULT_PERIODS_LIMIT: u64 = 20;
 
+/// The gas limit of a single checkpoint hook notification
+pub const CHECKPOINT_HOOK_GAS_LIMIT: u64 = 200_000;
+
+/// The gas limit of a generator controller notification
+pub const GENERATOR_CONTROLLER_HOOK_GAS_LIMIT: u64 = 500_000;
+
 /// This structure stores marketing information for vxASTRO.
 #[cw_serde]
 pub struct UpdateMarketingInfo {
@@ -79,7 +86,13 @@ pub enum ExecuteMsg {
     /// Upload a logo for vxASTRO
     UploadLogo(Logo),
     /// Update config
-    UpdateConfig { new_guardian: Option<String> },
+    UpdateConfig {
+        new_guardian: Option<String>,
+        /// Contracts notified about every change of stakers' voting power
+        checkpoint_hooks: Option<Vec<String>>,
+        /// Generator controller which prunes gauge votes of expired and blacklisted stakers
+        generator_controller: Option<String>,
+    },
     /// Set whitelisted logo urls
     SetLogoUrlsWhitelist { whitelist: Vec<String> },
 }
@@ -95,6 +108,58 @@ pub enum Cw20HookMsg {
     ExtendLockAmount {},
 }
 
+/// This enum describes the privileged messages a chain can send to the vxASTRO contract.
+#[cw_serde]
+pub enum SudoMsg {
+    /// Stop accepting new locks and deposits
+    Pause {},
+    /// Resume accepting new locks and deposits
+    Unpause {},
+    /// Update config bypassing the contract owner
+    ForceConfig {
+        new_guardian: Option<String>,
+        /// Contracts notified about every change of stakers' voting power
+        checkpoint_hooks: Option<Vec<String>>,
+        /// Generator controller which prunes gauge votes of expired and blacklisted stakers
+        generator_controller: Option<String>,
+    },
+}
+
+/// This structure describes a staker's voting power checkpoint pushed to checkpoint hook contracts.
+#[cw_serde]
+pub struct CheckpointHookMsg {
+    /// The staker whose voting power changed
+    pub user: String,
+    /// The staker's voting power at the `start` period
+    pub power: Uint128,
+    /// Weekly voting power decay
+    pub slope: Uint128,
+    /// The period when the checkpoint was created
+    pub start: u64,
+    /// The period when the staker's lock expires
+    pub end: u64,
+    /// Sequence number of the checkpoint. It is incremented on every change of the staker's voting power
+    pub nonce: u64,
+}
+
+impl CheckpointHookMsg {
+    /// Calculates the staker's voting power at the specified period.
+    pub fn voting_power_at(&self, period: u64) -> Uint128 {
+        let shift = self
+            .slope
+            .checked_mul(Uint128::from(period.saturating_sub(self.start)))
+            .unwrap_or_default();
+        self.power.saturating_sub(shift)
+    }
+}
+
+/// This enum describes messages sent to checkpoint hook contracts.
+#[cw_serde]
+pub enum CheckpointHookExecuteMsg {
+    /// CheckpointHook notifies a contract about the staker's latest voting power checkpoint
+    CheckpointHook(CheckpointHookMsg),
+}
+
 /// This enum describes voters status.
 #[cw_serde]
 pub enum BlacklistedVotersResponse {
@@ -128,6 +193,13 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Return active locks ordered by address. Up to `limit` locks are scanned, so a page may
+    /// hold fewer active locks than the limit
+    #[returns(LockedBalancesResponse)]
+    LockedBalances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Return the user's vxASTRO balance
     #[returns(BalanceResponse)]
     Balance { address: String },
@@ -189,6 +261,29 @@ pub struct LockInfoResponse {
     pub end: u64,
     /// Slope at which a staker's vxASTRO balance decreases over time
     pub slope: Uint128,
+    /// Sequence number of the staker's latest checkpoint pushed to checkpoint hook contracts
+    pub checkpoint_nonce: u64,
+}
+
+/// This structure describes an active lock returned by [`QueryMsg::LockedBalances`].
+#[cw_serde]
+pub struct LockedBalance {
+    /// The lock owner
+    pub address: Addr,
+    /// The amount of xASTRO locked in the position
+    pub amount: Uint128,
+    /// End time for the vxASTRO position decay
+    pub end: u64,
+}
+
+/// This structure describes the response of [`QueryMsg::LockedBalances`].
+#[cw_serde]
+pub struct LockedBalancesResponse {
+    /// Active locks among the scanned ones
+    pub locks: Vec<LockedBalance>,
+    /// The address of the last scanned lock, used as `start_after` of the next page.
+    /// `None` once there are no more locks to scan
+    pub last_scanned: Option<Addr>,
 }
 
 /// This structure stores the parameters returned when querying for a contract's configuration.
@@ -206,6 +301,10 @@ pub struct ConfigResponse {
     pub xastro_staking_addr: String,
     /// The list of whitelisted logo urls prefixes
     pub logo_urls_whitelist: Vec<String>,
+    /// Contracts notified about every change of stakers' voting power
+    pub checkpoint_hooks: Vec<Addr>,
+    /// Generator controller notified when a staker's lock expires or the staker is blacklisted
+    pub generator_controller: Option<Addr>,
 }
 
 /// This structure describes a Migration message.
@@ -273,6 +372,20 @@ pub fn get_total_voting_power_at(
     Ok(vp.voting_power)
 }
 
+/// Queries total voting power from the voting escrow contract by period.
+///
+/// * **period** period at which we fetch the total voting power.
+pub fn get_total_voting_power_at_by_period(
+    querier: &QuerierWrapper,
+    escrow_addr: impl Into<String>,
+    period: u64,
+) -> StdResult<Uint128> {
+    let vp: VotingPowerResponse =
+        querier.query_wasm_smart(escrow_addr, &TotalVotingPowerAtPeriod { period })?;
+
+    Ok(vp.voting_power)
+}
+
 /// Queries user's lockup information from the voting escrow contract.
 ///
 /// * **user** staker for which we return lock position information.
@@ -1,4 +1,4 @@
-use crate::voting_escrow_delegation::QueryMsg::AdjustedBalance;
+use crate::voting_escrow_delegation::QueryMsg::{AdjustedBalance, DelegatedVotingPower};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
 
@@ -13,6 +13,7 @@ pub struct Config {
     pub voting_escrow_addr: Addr,
 }
 
+/// This structure describes a delegation of voting power minted as an NFT.
 #[cw_serde]
 pub struct Token {
     /// The amount of voting power to be delegated
@@ -25,6 +26,7 @@ pub struct Token {
     pub expire_period: u64,
 }
 
+/// This structure describes the parameters used for creating a contract.
 #[cw_serde]
 pub struct InstantiateMsg {
     /// The contract owner address
@@ -35,21 +37,30 @@ pub struct InstantiateMsg {
     pub voting_escrow_addr: String,
 }
 
+/// This enum describes all execute functions available in the contract.
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Delegate a share of the sender's voting power to the recipient and mint an NFT for it
     CreateDelegation {
         /// The share of voting power (in bps) that will be delegated to the recipient
         bps: u16,
+        /// The timestamp at which the delegation expires
         expire_time: u64,
+        /// The NFT identifier
         token_id: String,
+        /// The address that receives the delegated voting power
         recipient: String,
     },
+    /// Extend the expiration time and/or increase the share of an existing delegation
     ExtendDelegation {
         /// The share of voting power (in bps) that will be delegated to the recipient
         bps: u16,
+        /// The timestamp at which the delegation expires
         expire_time: u64,
+        /// The NFT identifier
         token_id: String,
     },
+    /// Update contract parameters
     UpdateConfig {
         /// vxASTRO contract address
         new_voting_escrow: Option<String>,
@@ -62,16 +73,20 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
 }
 
+/// This enum describes all the queries available in the contract.
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    /// Return the contract configuration
     #[returns(Config)]
     Config {},
+    /// Return the account's voting power adjusted by the voting power it delegated and received
     #[returns(Uint128)]
     AdjustedBalance {
         account: String,
         timestamp: Option<u64>,
     },
+    /// Return the amount of voting power the account delegated to others
     #[returns(Uint128)]
     DelegatedVotingPower {
         account: String,
@@ -91,3 +106,16 @@ pub fn get_adjusted_balance(
         &AdjustedBalance { account, timestamp },
     )
 }
+
+/// Queries the amount of voting power the user delegated to others.
+pub fn get_delegated_voting_power(
+    querier: &QuerierWrapper,
+    escrow_delegation_addr: String,
+    account: String,
+    timestamp: Option<u64>,
+) -> StdResult<Uint128> {
+    querier.query_wasm_smart(
+        escrow_delegation_addr,
+        &DelegatedVotingPower { account, timestamp },
+    )
+}
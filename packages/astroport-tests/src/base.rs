@@ -139,11 +139,14 @@ impl BaseAstroportTestPackage {
     }
 
     fn init_voting_escrow(&mut self, router: &mut App, owner: Addr) {
-        let voting_contract = Box::new(ContractWrapper::new_with_empty(
-            voting_escrow::contract::execute,
-            voting_escrow::contract::instantiate,
-            voting_escrow::contract::query,
-        ));
+        let voting_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                voting_escrow::contract::execute,
+                voting_escrow::contract::instantiate,
+                voting_escrow::contract::query,
+            )
+            .with_reply_empty(voting_escrow::contract::reply),
+        );
 
         let voting_code_id = router.store_code(voting_contract);
 
@@ -173,11 +176,14 @@ impl BaseAstroportTestPackage {
     }
 
     pub fn init_escrow_fee_distributor(&mut self, router: &mut App, owner: Addr) {
-        let escrow_fee_distributor_contract = Box::new(ContractWrapper::new_with_empty(
-            astroport_escrow_fee_distributor::contract::execute,
-            astroport_escrow_fee_distributor::contract::instantiate,
-            astroport_escrow_fee_distributor::contract::query,
-        ));
+        let escrow_fee_distributor_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                astroport_escrow_fee_distributor::contract::execute,
+                astroport_escrow_fee_distributor::contract::instantiate,
+                astroport_escrow_fee_distributor::contract::query,
+            )
+            .with_reply_empty(astroport_escrow_fee_distributor::contract::reply),
+        );
 
         let escrow_fee_distributor_code_id = router.store_code(escrow_fee_distributor_contract);
 
@@ -187,6 +193,9 @@ impl BaseAstroportTestPackage {
             voting_escrow_addr: self.voting_escrow.clone().unwrap().address.to_string(),
             claim_many_limit: None,
             is_claim_disabled: None,
+            astro_staking: Some(self.staking.clone().unwrap().address.to_string()),
+            claim_expiry_periods: None,
+            max_periods_per_claim: None,
         };
 
         let escrow_fee_distributor_instance = router
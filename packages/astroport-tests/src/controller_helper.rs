@@ -3,7 +3,10 @@ use anyhow::Result as AnyResult;
 use astroport::asset::{AssetInfo, PairInfo};
 use astroport::factory::{PairConfig, PairType};
 
-use astroport_governance::generator_controller::{ConfigResponse, ExecuteMsg, QueryMsg};
+use astroport_governance::generator_controller::{
+    AvailableVotePowerResponse, ConfigResponse, ExecuteMsg, GaugeDelegationResponse,
+    GaugeInfoResponse, QueryMsg,
+};
 use cosmwasm_std::{Addr, Decimal, StdResult};
 use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 use generator_controller::state::{UserInfo, VotedPoolInfo};
@@ -229,11 +232,89 @@ impl ControllerHelper {
                 .into_iter()
                 .map(|(pool, apoints)| (pool.into(), apoints))
                 .collect(),
+            persist: None,
         };
 
         router.execute_contract(Addr::unchecked(user), self.controller.clone(), &msg, &[])
     }
 
+    pub fn persistent_vote(
+        &self,
+        router: &mut App,
+        user: &str,
+        votes: Vec<(impl Into<String>, u16)>,
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::Vote {
+            votes: votes
+                .into_iter()
+                .map(|(pool, apoints)| (pool.into(), apoints))
+                .collect(),
+            persist: Some(true),
+        };
+
+        router.execute_contract(Addr::unchecked(user), self.controller.clone(), &msg, &[])
+    }
+
+    pub fn refresh_votes(&self, router: &mut App, voters: Vec<&str>) -> AnyResult<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked("anyone"),
+            self.controller.clone(),
+            &ExecuteMsg::RefreshVotes {
+                voters: voters.into_iter().map(String::from).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn vote_on_behalf(
+        &self,
+        router: &mut App,
+        strategist: &str,
+        voter: &str,
+        votes: Vec<(impl Into<String>, u16)>,
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::VoteOnBehalf {
+            voter: voter.to_string(),
+            votes: votes
+                .into_iter()
+                .map(|(pool, apoints)| (pool.into(), apoints))
+                .collect(),
+            persist: None,
+        };
+
+        router.execute_contract(
+            Addr::unchecked(strategist),
+            self.controller.clone(),
+            &msg,
+            &[],
+        )
+    }
+
+    pub fn delegate_gauge_votes(
+        &self,
+        router: &mut App,
+        user: &str,
+        strategist: &str,
+    ) -> AnyResult<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.controller.clone(),
+            &ExecuteMsg::DelegateGaugeVotes {
+                strategist: strategist.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn undelegate_gauge_votes(&self, router: &mut App, user: &str) -> AnyResult<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.controller.clone(),
+            &ExecuteMsg::UndelegateGaugeVotes {},
+            &[],
+        )
+    }
+
     pub fn tune(&self, router: &mut App) -> AnyResult<AppResponse> {
         router.execute_contract(
             Addr::unchecked("anyone"),
@@ -271,6 +352,7 @@ impl ControllerHelper {
                 main_pool: None,
                 main_pool_min_alloc: None,
                 remove_main_pool: None,
+                emergency_committee: None,
             },
             &[],
         )
@@ -293,6 +375,7 @@ impl ControllerHelper {
                 main_pool: main_pool.map(|p| p.to_string()),
                 main_pool_min_alloc,
                 remove_main_pool,
+                emergency_committee: None,
             },
             &[],
         )
@@ -351,6 +434,38 @@ impl ControllerHelper {
             .wrap()
             .query_wasm_smart(self.controller.clone(), &QueryMsg::Config {})
     }
+
+    pub fn query_available_vote_power(
+        &self,
+        router: &mut App,
+        user: &str,
+    ) -> StdResult<AvailableVotePowerResponse> {
+        router.wrap().query_wasm_smart(
+            self.controller.clone(),
+            &QueryMsg::AvailableVotePower {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn query_gauge_delegation(
+        &self,
+        router: &mut App,
+        user: &str,
+    ) -> StdResult<GaugeDelegationResponse> {
+        router.wrap().query_wasm_smart(
+            self.controller.clone(),
+            &QueryMsg::GaugeDelegation {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn query_simulate_tune(&self, router: &mut App) -> StdResult<GaugeInfoResponse> {
+        router
+            .wrap()
+            .query_wasm_smart(self.controller.clone(), &QueryMsg::SimulateTune {})
+    }
 }
 
 fn store_whitelist_code(app: &mut App) -> u64 {
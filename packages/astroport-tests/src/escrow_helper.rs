@@ -87,11 +87,14 @@ impl EscrowHelper {
             }))
             .unwrap();
 
-        let voting_contract = Box::new(ContractWrapper::new_with_empty(
-            voting_escrow::contract::execute,
-            voting_escrow::contract::instantiate,
-            voting_escrow::contract::query,
-        ));
+        let voting_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                voting_escrow::contract::execute,
+                voting_escrow::contract::instantiate,
+                voting_escrow::contract::query,
+            )
+            .with_reply_empty(voting_escrow::contract::reply),
+        );
 
         let voting_code_id = router.store_code(voting_contract);
 